@@ -20,6 +20,12 @@ physac-rs is a conversion of Physac into the Rust programming language. Amy Wild
 //!
 //! physac-rs is a Rust-native translation of [Physac][] with built-in support for [raylib-rs][]. Raylib is not *required* however, and can be disabled by disabling the `raylib` feature flag in your `Cargo.toml` file.
 //!
+//! The whole simulation is written in safe Rust (`unsafe` code is forbidden in the crate) and doesn't wrap the C library, so no C toolchain is needed to build it for any target.
+//!
+//! Without `raylib` nothing links against a graphics stack, so the physics core can run on a headless server or inside unit tests. Disabling the default features as well removes the background physics thread, leaving you to call [`Physac::run_physics_step()`] from your own loop.
+//!
+//! That is also how the crate builds for `wasm32-unknown-unknown`, which has no threads and no clock. In the browser, pass the frame time of each `requestAnimationFrame` callback to [`Physac::update()`] instead, since [`Physac::run_physics_step()`] has no time to measure there.
+//!
 //! See the examples directory for usage examples converted 1:1 from the original C implementation of Physac.
 //!
 //! While this library tries to mirror the C API, some changes have been made in order to improve soundness, and to shorten the names of certain methods where they are implied by the name of the type they are implemented for.
@@ -38,6 +44,8 @@ physac-rs is a conversion of Physac into the Rust programming language. Amy Wild
 //!
 //! - The fixed-capacity arrays that store `PhysicsBody`s and `PhysicsManifold`s have been replaced with growable `Vec`s, whose initial capacities are set in [`init_physics`] with the [`PhysacBuilder::max_bodies()`] and [`PhysacBuilder::max_manifolds()`] chain methods respectively.
 //!
+//! - [`PhysicsShape`] is `Clone` but not `Copy` (which it was in earlier versions of this crate), because compound shapes, edge chains and heightfields own their fixtures, vertices and heights. Call `clone()` where a shape used to be copied, such as `let shape = body.shape.clone();`.
+//!
 //! - Manually closing physics is not necessary, because it will automatically close when [`PhysacHandle`] goes out of scope or otherwise drops (such as with [`std::mem::drop`] or during unwinding). If the `phys_thread` feature flag is enabled, the physics thread will also finish & join when this happens.
 //!
 //!   The physics thread can also finish if any unrecoverable errors occur on that thread, such as running out of IDs or a resource being poisoned (i.e. another thread panicks while mutably borrowing a physics body or [`Physac`]).
@@ -106,12 +114,22 @@ physac-rs is a conversion of Physac into the Rust programming language. Amy Wild
 //!
 //! # Features
 //!
-//! | Feature Flag  | Description                                                                                                                                                                                                                               | Default                               |
-//! |:-------------:|:------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------|:-------------------------------------:|
-//! | `raylib`      | Use raylib-rs in the library. If disabled, data types defined on raylib are defined internally in the library and input management and drawing functions must be provided by the user (check library implementation for further details). | Disabled                              |
-//! | `sync`        | The library will use [`std::sync`] instead of [`std::rc`] and [`std::cell`].                                                                                                                                                              | Enabled transitively by `phys_thread` |
-//! | `phys_thread` | If disabled, the library won't use `std::thread` and user must create a secondary thread to call [`Physac::run_physics_step()`], or call it in the main thread if `sync` is disabled. <br/> Requres and automatically enables `sync`.     | Enabled                               |
-//! | `debug`       | Traces log messages when creating and destroying physics bodies and detects errors in physics calculations and reference exceptions; it is useful for debug purposes.                                                                     | Disabled                              |
+//! | Feature Flag    | Description                                                                                                                                                                                                                               | Default                               |
+//! |:---------------:|:------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------|:-------------------------------------:|
+//! | `raylib`        | Use raylib-rs in the library. If disabled, data types defined on raylib are defined internally in the library and input management and drawing functions must be provided by the user (check library implementation for further details). | Disabled                              |
+//! | `sync`          | The library will use [`std::sync`] instead of [`std::rc`] and [`std::cell`].                                                                                                                                                              | Enabled transitively by `phys_thread` |
+//! | `phys_thread`   | If disabled, the library won't use `std::thread` and user must create a secondary thread to call [`Physac::run_physics_step()`], or call it in the main thread if `sync` is disabled. <br/> Requres and automatically enables `sync`.     | Enabled                               |
+//! | `parallel`      | Solves independent islands of touching or connected physics bodies at the same time on a `rayon` thread pool. <br/> Requires and automatically enables `sync`.                                                                            | Disabled                              |
+//! | `serde`         | Implements `Serialize` and `Deserialize` for physics bodies, shapes and [`WorldState`], so that save games can persist physics. <br/> With `raylib`, raylib's own serde feature must be enabled too.                                      | Disabled                              |
+//! | `debug`         | Traces log messages when creating and destroying physics bodies and detects errors in physics calculations and reference exceptions; it is useful for debug purposes.                                                                     | Disabled                              |
+//! | `trace`         | Enters `tracing` spans (at the trace level) around each phase of a step, such as the broadphase, narrowphase and solver, so that profilers like Tracy show where the time goes.                                                           | Disabled                              |
+//! | `inspector`     | Adds an `egui` panel for listing physics bodies and live-editing the selected one while the simulation runs.                                                                                                                              | Disabled                              |
+//! | `glam`          | Converts between `Vector2` and `glam::Vec2` (`glam::DVec2` with `f64`), which every method taking a `Vector2` also accepts. <br/> With `raylib`, raylib's own glam feature must be enabled instead.                                       | Disabled                              |
+//! | `nalgebra`      | Converts between `Vector2` and `nalgebra::Vector2<Real>`, which every method taking a `Vector2` also accepts. <br/> Has no effect with `raylib`.                                                                                           | Disabled                              |
+//! | `bevy`          | Adds a Bevy plugin that steps the world in `FixedUpdate`, moves the `Transform` of entities with a `PhysacBody` and sends collision events as messages. <br/> Requires and automatically enables `sync`.                                  | Disabled                              |
+//! | `f64`           | Simulates with `f64` instead of `f32` (see `Real`), for long running simulations where `f32` rounding errors add up. <br/> Cannot be used with `raylib`, whose vectors are always `f32`.                                                  | Disabled                              |
+//! | `deterministic` | Computes sines, cosines and arctangents with `libm`, so the same inputs give the same results on x86 and ARM (such as for lockstep multiplayer). <br/> Results only match without `parallel`, and between builds with the same features.  | Disabled                              |
+//! | `simd`          | SIMD bounds prefilter: compares the bounds of four physics bodies at once with `wide` in the default broadphase. Body storage and integration are not vectorized.                                                                         | Disabled                              |
 //!
 //! # Thread Safety
 //!
@@ -144,6 +162,10 @@ physac-rs is a conversion of Physac into the Rust programming language. Amy Wild
 //!
 //! ---
 //!
+//! With `sync`, [`PhysacHandle::share()`] returns a [`SharedPhysacHandle`], which can be cloned and sent to other threads (such as a dedicated game logic thread stepping physics with [`Physac::update()`]) and borrowed mutably from any of them.
+//!
+//! ---
+//!
 //! **Important:** Remember not to **borrow** physics bodies, nor [`Physac`], across multiple frames. Storing [`Strong`] and [`Weak`] references across frames is fine, just not `Read/WriteGuard`s.
 //!
 //! When the physics thread needs to borrow a physics body, it will block (wait its turn) until no thread is borrowing it exclusively. The physics thread will mutably borrow *every* physics body being simulated at multiple points during each physics step.
@@ -210,9 +232,24 @@ physac-rs is a conversion of Physac into the Rust programming language. Amy Wild
 //! }
 //! ```
 //!
+//! # Determinism
+//!
+//! Given the same starting world and the same changes made between the same steps, the simulation always gives bit-identical results
+//! on the same platform and build. Nothing in a step depends on the clock, the order bodies are visited in is the order they were created in,
+//! and the broadphase and `parallel` solving give the same results as testing every pair one at a time.
+//!
+//! Stepping with the physics thread or [`Physac::run_physics_step()`] runs as many steps as fit into the real time that has passed, which
+//! is different every run. For lockstep multiplayer or replays, disable `phys_thread` and step with [`Physac::update()`] using the same
+//! frame times on every machine. Randomness (such as fragment sizes with [`ShatterOptions::seed`]) only ever comes from seeds, which
+//! can be drawn from a world seed set with [`PhysacBuilder::seed()`].
+//!
+//! Across platforms, results only match with the `deterministic` feature, without `parallel`, and between builds that enable the same features
+//! (such as `f64`, which changes every calculation).
+//!
 //! [Physac]: https://github.com/victorfisac/Physac
 //! [raylib-rs]: https://github.com/raylib-rs/raylib-rs
 
+#![forbid(unsafe_code)]
 #![deny(
     clippy::missing_safety_doc,
 )]
@@ -245,24 +282,51 @@ pub mod prelude {
     pub use crate::*;
 }
 
+/// The scalar type used throughout the simulation, [`f64`] with the `f64` feature and [`f32`] otherwise
+#[cfg(not(feature = "f64"))]
+pub type Real = f32;
+/// The scalar type used throughout the simulation, [`f64`] with the `f64` feature and [`f32`] otherwise
+#[cfg(feature = "f64")]
+pub type Real = f64;
+
+#[cfg(not(feature = "f64"))]
+use std::f32::consts as real_consts;
+#[cfg(feature = "f64")]
+use std::f64::consts as real_consts;
+
+/// Four [`Real`]s tested at once by the `simd` feature
+#[cfg(all(feature = "simd", not(feature = "f64")))]
+type RealX4 = wide::f32x4;
+#[cfg(all(feature = "simd", feature = "f64"))]
+type RealX4 = wide::f64x4;
+
+#[cfg(all(feature = "f64", feature = "raylib"))]
+compile_error!("raylib vectors are always `f32`, so the `f64` feature cannot be used together with the `raylib` feature");
+
 #[cfg(feature = "raylib")]
 pub use raylib::prelude::Vector2;
 
 #[cfg(not(feature = "raylib"))]
 /// A 2D direction with magnitude.
 #[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Vector2 {
     /// Horizontal
-    pub x: f32,
+    pub x: Real,
     /// Vertical
-    pub y: f32,
+    pub y: Real,
 }
 
-use std::{num::NonZeroUsize, time::Instant};
+use std::{collections::HashMap, num::NonZeroUsize, ops::Range, path::Path, time::Duration};
+
+#[cfg(not(all(target_family = "wasm", target_os = "unknown")))]
+use std::time::Instant;
+
+#[cfg(all(feature = "phys_thread", target_family = "wasm", target_os = "unknown"))]
+compile_error!("threads cannot be spawned on wasm32-unknown-unknown; disable the default `phys_thread` feature and call `Physac::update` from the browser's frame loop");
 
 #[cfg(feature = "phys_thread")]
 use std::{
-    time::Duration,
     sync::atomic::{AtomicBool, Ordering::{Relaxed, Release}},
     thread,
 };
@@ -271,6 +335,12 @@ use std::{
 #[cfg(feature = "sync")]
 use std::sync::{Arc, RwLock};
 
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
 //----------------------------------------------------------------------------------
 // Defines and Macros
 //----------------------------------------------------------------------------------
@@ -285,6 +355,33 @@ macro_rules! debug_print {
     };
 }
 
+/// Enters a `tracing` span with the name until the end of the enclosing block, if the `trace` feature is enabled
+macro_rules! trace_span {
+    ($name:literal) => {
+        #[cfg(feature = "trace")]
+        let _span = tracing::trace_span!($name).entered();
+    };
+}
+
+/// Stands in for [`std::time::Instant`] on `wasm32-unknown-unknown`, where there is no clock and `Instant::now` panics
+///
+/// Time stands still here, so [`Physac::run_physics_step`] never runs a step and [`Physac::update`] has to be given the frame time instead
+#[cfg(all(target_family = "wasm", target_os = "unknown"))]
+#[derive(Debug, Clone, Copy)]
+struct Instant;
+
+#[cfg(all(target_family = "wasm", target_os = "unknown"))]
+impl Instant {
+    const fn now() -> Self {
+        Self
+    }
+
+    #[allow(clippy::unused_self, reason = "mirrors the signature of `std::time::Instant::elapsed`")]
+    const fn elapsed(self) -> Duration {
+        Duration::ZERO
+    }
+}
+
 /// Adds 1 to `index`, wrapping around to 0 if the next index would be out of bounds
 #[inline(always)]
 #[must_use]
@@ -297,15 +394,16 @@ pub const fn next_idx(index: usize, len: usize) -> usize {
 //----------------------------------------------------------------------------------
 /// Mat2 type (used for polygon shape rotation matrix)
 #[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Mat2 {
     /// Row 0, column 0
-    pub m00: f32,
+    pub m00: Real,
     /// Row 0, column 1
-    pub m01: f32,
+    pub m01: Real,
     /// Row 1, column 0
-    pub m10: f32,
+    pub m10: Real,
     /// Row 1, column 1
-    pub m11: f32,
+    pub m11: Real,
 }
 impl Mat2 {
     /// Identity matrix constant
@@ -320,8 +418,8 @@ impl Mat2 {
 
     /// Creates a matrix 2x2 from a given radians value
     #[must_use]
-    pub fn radians(radians: f32) -> Mat2 {
-        let (s, c) = radians.sin_cos();
+    pub fn radians(radians: Real) -> Mat2 {
+        let (s, c) = math_sin_cos(radians);
 
         Mat2 {
             m00: c, m01: -s,
@@ -330,8 +428,8 @@ impl Mat2 {
     }
 
     /// Set values from radians to a created matrix 2x2
-    pub fn set(&mut self, radians: f32) {
-        let (sin, cos) = radians.sin_cos();
+    pub fn set(&mut self, radians: Real) {
+        let (sin, cos) = math_sin_cos(radians);
 
         self.m00 = cos; self.m01 = -sin;
         self.m10 = sin; self.m11 = cos;
@@ -352,24 +450,42 @@ impl Mat2 {
     /// Multiplies a vector by a matrix 2x2
     #[inline(always)]
     #[must_use]
-    pub fn multiply_vector2(&self, vector: Vector2) -> Vector2 {
+    pub fn multiply_vector2(&self, vector: impl Into<Vector2>) -> Vector2 {
+        let vector = vector.into();
         Vector2 {
             x: self.m00*vector.x + self.m01*vector.y,
             y: self.m10*vector.x + self.m11*vector.y,
         }
     }
+
+    /// Multiplies two matrices 2x2
+    #[inline(always)]
+    #[must_use]
+    pub fn multiply(&self, other: &Mat2) -> Mat2 {
+        Mat2 {
+            m00: self.m00*other.m00 + self.m01*other.m10,
+            m01: self.m00*other.m01 + self.m01*other.m11,
+            m10: self.m10*other.m00 + self.m11*other.m10,
+            m11: self.m10*other.m01 + self.m11*other.m11,
+        }
+    }
 }
 
 /// The positions
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[must_use]
 pub struct PolygonData<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize> {
     /// Current used vertex and normals count
     vertex_count: usize,
     /// Polygon vertex positions vectors
+    #[cfg_attr(feature = "serde", serde(with = "serde_array"))]
     positions: [Vector2; MAX_VERTICES],
     /// Polygon vertex normals vectors
+    #[cfg_attr(feature = "serde", serde(with = "serde_array"))]
     normals: [Vector2; MAX_VERTICES],
+    /// Radius the corners are rounded by (the polygon is grown outwards by this much)
+    radius: Real,
 }
 impl<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize> PolygonData<MAX_VERTICES, CIRCLE_VERTICES> {
     /// Construct a new [`PolygonData`] with no vertices.
@@ -378,6 +494,7 @@ impl<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize> PolygonData<MAX_VE
             vertex_count: 0,
             positions: [const { Vector2::zero() }; MAX_VERTICES],
             normals: [const { Vector2::zero() }; MAX_VERTICES],
+            radius: 0.0,
         }
     }
 
@@ -455,6 +572,7 @@ impl<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize> PolygonData<MAX_VE
                     vertex_count,
                     positions,
                     normals,
+                    radius: 0.0,
                 }
             })
     }
@@ -485,7 +603,9 @@ impl<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize> PolygonData<MAX_VE
     /// ]);
     /// ```
     #[must_use]
-    pub fn push(&mut self, position: Vector2, normal: Vector2) -> Option<()> {
+    pub fn push(&mut self, position: impl Into<Vector2>, normal: impl Into<Vector2>) -> Option<()> {
+        let position = position.into();
+        let normal = normal.into();
         if self.vertex_count < MAX_VERTICES {
             self.positions[self.vertex_count] = position;
             self.normals[self.vertex_count] = normal;
@@ -564,7 +684,9 @@ impl<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize> PolygonData<MAX_VE
     /// ]);
     /// ```
     #[must_use]
-    pub fn insert(&mut self, index: usize, position: Vector2, normal: Vector2) -> Option<()> {
+    pub fn insert(&mut self, index: usize, position: impl Into<Vector2>, normal: impl Into<Vector2>) -> Option<()> {
+        let position = position.into();
+        let normal = normal.into();
         if self.vertex_count < MAX_VERTICES {
             if index < self.vertex_count {
                 self.positions.copy_within(index..self.vertex_count, index + 1);
@@ -654,6 +776,238 @@ impl<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize> PolygonData<MAX_VE
     pub fn normals_mut(&mut self) -> &mut [Vector2] {
         &mut self.normals[..self.vertex_count]
     }
+
+    /// Radius the corners of the polygon are rounded by
+    #[must_use]
+    pub fn corner_radius(&self) -> Real {
+        self.radius
+    }
+
+    /// Round the corners of the polygon by `radius`
+    ///
+    /// The rounded shape is the polygon grown outwards by `radius` in every direction,
+    /// so the positions describe the inner core of the shape rather than its outline.
+    /// Rounded polygons slide smoothly over seams between neighboring bodies, where sharp corners would catch
+    ///
+    /// Negative radii are treated as 0
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use physac::prelude::*;
+    /// let square = PolygonData::<24, 24>::from_convex_vertices(&[
+    ///     Vector2 { x: 0.0, y: 0.0 },
+    ///     Vector2 { x: 4.0, y: 0.0 },
+    ///     Vector2 { x: 4.0, y: 4.0 },
+    ///     Vector2 { x: 0.0, y: 4.0 },
+    /// ]).unwrap();
+    ///
+    /// let rounded = square.with_corner_radius(1.0);
+    ///
+    /// assert_eq!(rounded.corner_radius(), 1.0);
+    /// assert_eq!(rounded.positions(), square.positions());
+    /// ```
+    pub fn with_corner_radius(mut self, radius: Real) -> Self {
+        self.radius = radius.max(0.0);
+        self
+    }
+
+    /// Returns the number of vertices on the outline of the polygon, including the rounded corners
+    fn outline_len(&self) -> usize {
+        if self.radius > 0.0 {
+            self.vertex_count*self.corner_vertices()
+        } else {
+            self.vertex_count
+        }
+    }
+
+    /// Returns the number of vertices used to draw each rounded corner
+    fn corner_vertices(&self) -> usize {
+        (CIRCLE_VERTICES/self.vertex_count.max(1)).max(2)
+    }
+
+    /// Returns the position of a vertex on the outline of the polygon, including the rounded corners
+    ///
+    /// Returns [`None`] if `vertex` index is out of bounds
+    fn outline_vertex(&self, vertex: usize) -> Option<Vector2> {
+        if self.radius <= 0.0 {
+            return self.positions().get(vertex).copied();
+        }
+
+        (vertex < self.outline_len()).then(|| {
+            // Each corner is an arc around the corner vertex, from the normal of the face before it to the normal of the face after it
+            let corner_vertices = self.corner_vertices();
+            let (corner, step) = (vertex/corner_vertices, vertex%corner_vertices);
+            let normal_before = self.normals[(corner + self.vertex_count - 1)%self.vertex_count];
+            let normal_after = self.normals[corner];
+
+            let sweep = math_atan2(math_cross_vector2(normal_before, normal_after), normal_before.dot(normal_after));
+            let angle = sweep*step as Real/(corner_vertices - 1) as Real;
+            self.positions[corner] + Mat2::radians(angle).multiply_vector2(normal_before)*self.radius
+        })
+    }
+
+    /// Construct a new [`PolygonData`] from the vertices of a convex polygon, calculating the face normals
+    ///
+    /// The vertices may be given in either winding order, and are stored in the winding order used by Physac
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`PolygonError`] if there are too few or too many vertices, if the polygon has no area or repeats a vertex, or if the polygon is not convex
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use physac::prelude::*;
+    /// let triangle = PolygonData::<24, 24>::from_convex_vertices(&[
+    ///     Vector2 { x: 0.0, y: 0.0 },
+    ///     Vector2 { x: 0.0, y: 4.0 },
+    ///     Vector2 { x: 3.0, y: 0.0 },
+    /// ]).unwrap();
+    ///
+    /// assert_eq!(triangle.len(), 3);
+    ///
+    /// let arrow = PolygonData::<24, 24>::from_convex_vertices(&[
+    ///     Vector2 { x: 0.0, y: 0.0 },
+    ///     Vector2 { x: 4.0, y: 2.0 },
+    ///     Vector2 { x: 0.0, y: 4.0 },
+    ///     Vector2 { x: 1.0, y: 2.0 },
+    /// ]);
+    ///
+    /// assert_eq!(arrow, Err(PolygonError::NotConvex));
+    /// ```
+    pub fn from_convex_vertices(vertices: &[Vector2]) -> Result<Self, PolygonError> {
+        let vertex_count = vertices.len();
+
+        if vertex_count < 3 {
+            return Err(PolygonError::TooFewVertices);
+        }
+        if vertex_count > MAX_VERTICES {
+            return Err(PolygonError::TooManyVertices);
+        }
+
+        let mut data = PolygonData {
+            vertex_count,
+            ..Default::default()
+        };
+        data.positions[..vertex_count].copy_from_slice(vertices);
+
+        // Repeated vertices would leave a face without a normal
+        for i in 0..vertex_count {
+            if dist_sqr(data.positions[i], data.positions[next_idx(i, vertex_count)]) <= Real::EPSILON {
+                return Err(PolygonError::Degenerate);
+            }
+        }
+
+        // Physac polygons have a positive signed area, so reverse the opposite winding
+        let area: Real = (0..vertex_count)
+            .map(|i| math_cross_vector2(data.positions[i], data.positions[next_idx(i, vertex_count)]))
+            .sum::<Real>()/2.0;
+
+        if area.abs() <= Real::EPSILON {
+            return Err(PolygonError::Degenerate);
+        }
+        if area < 0.0 {
+            data.positions[..vertex_count].reverse();
+        }
+
+        // Every corner must turn the same way, and all of them together must only turn around once
+        let mut total_turn = 0.0;
+        for i in 0..vertex_count {
+            let edge1 = data.positions[next_idx(i, vertex_count)] - data.positions[i];
+            let edge2 = data.positions[next_idx(next_idx(i, vertex_count), vertex_count)] - data.positions[next_idx(i, vertex_count)];
+            let cross = math_cross_vector2(edge1, edge2);
+
+            if cross < -Real::EPSILON*edge1.length_sqr().max(edge2.length_sqr()) {
+                return Err(PolygonError::NotConvex);
+            }
+            total_turn += real_to_f64(math_atan2(cross, edge1.dot(edge2)));
+        }
+        if (total_turn - 2.0*PI).abs() > 1e-3 {
+            return Err(PolygonError::NotConvex);
+        }
+
+        data.calculate_normals();
+
+        Ok(data)
+    }
+
+    /// Decompose a simple (possibly concave) polygon into convex [`PolygonData`]s, calculating the face normals of each
+    ///
+    /// The polygon is triangulated by ear clipping, and then neighboring pieces are merged back together
+    /// for as long as the merged piece stays convex and fits in `MAX_VERTICES`
+    ///
+    /// The vertices may be given in either winding order, and the pieces keep the same positions as the vertices
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`PolygonError`] if there are too few vertices, if the polygon has no area or repeats a vertex, or if the edges of the polygon cross each other
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use physac::prelude::*;
+    /// let l_shape = PolygonData::<24, 24>::from_concave_vertices(&[
+    ///     Vector2 { x: 0.0, y: 0.0 },
+    ///     Vector2 { x: 2.0, y: 0.0 },
+    ///     Vector2 { x: 2.0, y: 1.0 },
+    ///     Vector2 { x: 1.0, y: 1.0 },
+    ///     Vector2 { x: 1.0, y: 3.0 },
+    ///     Vector2 { x: 0.0, y: 3.0 },
+    /// ]).unwrap();
+    ///
+    /// assert_eq!(l_shape.len(), 2);
+    ///
+    /// let bowtie = PolygonData::<24, 24>::from_concave_vertices(&[
+    ///     Vector2 { x: 0.0, y: 0.0 },
+    ///     Vector2 { x: 4.0, y: 4.0 },
+    ///     Vector2 { x: 4.0, y: 0.0 },
+    ///     Vector2 { x: 0.0, y: 2.0 },
+    /// ]);
+    ///
+    /// assert_eq!(bowtie, Err(PolygonError::NotSimple));
+    /// ```
+    pub fn from_concave_vertices(vertices: &[Vector2]) -> Result<Vec<Self>, PolygonError> {
+        let vertex_count = vertices.len();
+
+        if vertex_count < 3 {
+            return Err(PolygonError::TooFewVertices);
+        }
+
+        for i in 0..vertex_count {
+            if dist_sqr(vertices[i], vertices[next_idx(i, vertex_count)]) <= Real::EPSILON {
+                return Err(PolygonError::Degenerate);
+            }
+        }
+
+        let area: Real = (0..vertex_count)
+            .map(|i| math_cross_vector2(vertices[i], vertices[next_idx(i, vertex_count)]))
+            .sum::<Real>()/2.0;
+
+        if area.abs() <= Real::EPSILON {
+            return Err(PolygonError::Degenerate);
+        }
+
+        // Work in the winding order used by Physac
+        let mut positions = vertices.to_vec();
+        if area < 0.0 {
+            positions.reverse();
+        }
+
+        if !is_simple_polygon(&positions) {
+            return Err(PolygonError::NotSimple);
+        }
+
+        let mut pieces = ear_clip(&positions).ok_or(PolygonError::NotSimple)?;
+        merge_convex_pieces(&positions, &mut pieces, MAX_VERTICES);
+
+        pieces.iter()
+            .map(|piece| {
+                let piece_positions: Vec<Vector2> = piece.iter().map(|&i| positions[i]).collect();
+                Self::from_convex_vertices(&piece_positions)
+            })
+            .collect()
+    }
 }
 impl<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize> Default for PolygonData<MAX_VERTICES, CIRCLE_VERTICES> {
     fn default() -> Self {
@@ -661,8 +1015,80 @@ impl<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize> Default for Polygo
     }
 }
 
-/// The shape of a physics body; either a Circle or a Polygon
-#[derive(Debug, Clone, Copy)]
+/// An error that occurs when a physics body can't be added to [`Physac`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PhysacError {
+    /// The body builder was never given a shape
+    MissingShape,
+    /// The vertices or heights given for the shape of the body don't make a valid shape
+    InvalidPolygon(PolygonError),
+    /// A compound body was given no fixtures
+    EmptyCompound,
+    /// Every one of the `capacity` physics body slots (see [`PhysacBuilder::max_bodies`]) is in use
+    ///
+    /// Destroy physics bodies that are no longer needed, raise the capacity, or use [`PhysacBuilder::growable_storage`]
+    CapacityExceeded {
+        /// How many physics bodies the world has room for
+        capacity: usize,
+    },
+}
+impl std::fmt::Display for PhysacError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingShape => write!(f, "the body builder was never given a shape"),
+            Self::InvalidPolygon(error) => write!(f, "invalid shape: {error}"),
+            Self::EmptyCompound => write!(f, "a compound body needs at least one fixture"),
+            Self::CapacityExceeded { capacity } => write!(f, "all {capacity} physics body slots are in use"),
+        }
+    }
+}
+impl std::error::Error for PhysacError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::InvalidPolygon(error) => Some(error),
+            Self::MissingShape | Self::EmptyCompound | Self::CapacityExceeded { .. } => None,
+        }
+    }
+}
+impl From<PolygonError> for PhysacError {
+    fn from(error: PolygonError) -> Self {
+        Self::InvalidPolygon(error)
+    }
+}
+
+/// An error that occurs when constructing a polygon from user-provided vertices
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PolygonError {
+    /// A polygon needs at least 3 vertices (or 2 for a chain or heightfield)
+    TooFewVertices,
+    /// The polygon has more than `MAX_VERTICES` vertices
+    TooManyVertices,
+    /// The polygon has no area, or repeats a vertex
+    Degenerate,
+    /// The polygon is not convex
+    NotConvex,
+    /// The edges of the polygon cross each other
+    NotSimple,
+}
+impl std::fmt::Display for PolygonError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::TooFewVertices => write!(f, "a polygon needs at least 3 vertices (or 2 for a chain or heightfield)"),
+            Self::TooManyVertices => write!(f, "the polygon has more vertices than MAX_VERTICES"),
+            Self::Degenerate => write!(f, "the polygon has no area or repeats a vertex"),
+            Self::NotConvex => write!(f, "the polygon is not convex"),
+            Self::NotSimple => write!(f, "the edges of the polygon cross each other"),
+        }
+    }
+}
+impl std::error::Error for PolygonError {}
+
+/// The shape of a physics body: a circle, polygon, capsule, ellipse, or compound of several shapes,
+/// or a one-sided edge chain or heightfield for static terrain
+///
+/// Shapes are [`Clone`] but not [`Copy`], since some of them own their data
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum PhysicsShape<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize> {
     /// A circle - simulated as an impossibly smooth and round circle,
     /// approximated with `CIRCLE_VERTICES` when calling [`PhysicsBodyData::get_physics_shape_vertex()`]
@@ -670,23 +1096,79 @@ pub enum PhysicsShape<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize> {
     /// Rotation is meaningless
     Circle {
         /// Circle shape radius
-        radius: f32,
+        radius: Real,
     },
-    /// A polygon - uses `MAX_VERTICES`
+    /// A polygon - uses `MAX_VERTICES`, and may have rounded corners (see [`PolygonData::with_corner_radius`])
     Polygon {
         /// Polygon shape vertices position and normals data (just used for polygon shapes)
         vertex_data: PolygonData<MAX_VERTICES, CIRCLE_VERTICES>,
         /// Vertices transform matrix 2x2
         transform: Mat2,
     },
+    /// A capsule - a vertical segment swept by a circle, with smooth rounded ends,
+    /// approximated with `CIRCLE_VERTICES` when calling [`PhysicsBodyData::get_physics_shape_vertex()`]
+    ///
+    /// The segment lies along the body's local `y` axis, and rotates with the body
+    Capsule {
+        /// Radius of the rounded ends (and half of the capsule width)
+        radius: Real,
+        /// Total height of the capsule, from the tip of one end to the tip of the other
+        height: Real,
+    },
+    /// A compound - several shapes rigidly attached to the same body
+    ///
+    /// Each fixture is collided separately, and the vertices of every fixture are returned one after another
+    /// when calling [`PhysicsBodyData::get_physics_shape_vertex()`]
+    Compound {
+        /// The shapes making up the body, relative to the body
+        fixtures: Vec<PhysicsFixture<MAX_VERTICES, CIRCLE_VERTICES>>,
+    },
+    /// An edge chain - a one-sided outline of connected segments, meant for static terrain
+    ///
+    /// Only the side the face normals point to collides (the right side when walking from one vertex to the next, like the faces of a polygon).
+    /// The neighboring segments of each segment are taken into account, so that bodies sliding across a joint don't catch on it
+    ///
+    /// If the last vertex is the same as the first, the chain is closed into a loop
+    Chain {
+        /// Chain vertex positions relative to the body
+        vertices: Vec<Vector2>,
+    },
+    /// An ellipse - approximated with `CIRCLE_VERTICES` when calling [`PhysicsBodyData::get_physics_shape_vertex()`]
+    ///
+    /// The radii lie along the body's local `x` and `y` axes, and rotate with the body. Construct it with [`PhysicsShape::ellipse`],
+    /// which builds the core from the radii
+    Ellipse {
+        /// Radius along the local `x` axis
+        radius_x: Real,
+        /// Radius along the local `y` axis
+        radius_y: Real,
+        /// Rounded convex core that collides in place of the exact ellipse
+        core: PolygonData<CIRCLE_VERTICES, CIRCLE_VERTICES>,
+    },
+    /// A heightfield - a one-sided ground surface sampled at evenly spaced points, meant for static terrain
+    ///
+    /// Sample `i` is at `(i*cell_width, heights[i])` relative to the body, and only the top (towards negative `y`) collides.
+    /// Only the cells underneath a body are checked for collision, so a heightfield can be very long
+    Heightfield {
+        /// Surface height at each sample
+        heights: Vec<Real>,
+        /// Horizontal distance between samples
+        cell_width: Real,
+    },
 }
-pub use PhysicsShape::{Circle as PHYSICS_CIRCLE, Polygon as PHYSICS_POLYGON};
+pub use PhysicsShape::{Circle as PHYSICS_CIRCLE, Polygon as PHYSICS_POLYGON, Capsule as PHYSICS_CAPSULE, Compound as PHYSICS_COMPOUND, Chain as PHYSICS_CHAIN, Ellipse as PHYSICS_ELLIPSE, Heightfield as PHYSICS_HEIGHTFIELD};
 impl<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize> PhysicsShape<MAX_VERTICES, CIRCLE_VERTICES> {
     /// Construct a circle with 0 radius
     #[must_use]
     pub const fn new() -> Self {
         PHYSICS_CIRCLE { radius: 0.0 }
     }
+
+    /// Construct an ellipse, along with the rounded core it collides with
+    #[must_use]
+    pub fn ellipse(radius_x: Real, radius_y: Real) -> Self {
+        PHYSICS_ELLIPSE { radius_x, radius_y, core: ellipse_core(radius_x, radius_y) }
+    }
 }
 impl<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize> Default for PhysicsShape<MAX_VERTICES, CIRCLE_VERTICES> {
     fn default() -> Self {
@@ -694,2308 +1176,14882 @@ impl<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize> Default for Physic
     }
 }
 
-/// A physics body
-///
-/// Create using [`Physac::create_physics_body_circle`], [`Physac::create_physics_body_rectangle`], or [`Physac::create_physics_body_polygon`]
+/// Returns the half length of the inner segment of a capsule with the given radius and total height
+#[inline]
+fn capsule_half_length(radius: Real, height: Real) -> Real {
+    (height*0.5 - radius).max(0.0)
+}
+
+/// A shape attached to a [`PHYSICS_COMPOUND`] body
 ///
-/// Destroy (remove from the simulation) using [`Physac::destroy_physics_body`] or [`Physac::destroy_physics_bodies`]
-#[derive(Debug)]
-#[allow(
-    clippy::struct_excessive_bools,
-    reason = "these are flags, not states",
-)]
-pub struct PhysicsBodyData<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize> {
-    /// Reference unique identifier
-    pub id: u32,
-    /// Enabled dynamics state (collisions are calculated anyway)
-    pub enabled: bool,
-    /// Physics body shape pivot
-    pub position: Vector2,
-    /// Current linear velocity applied to position
-    pub velocity: Vector2,
-    /// Current linear force (reset to 0 every step)
-    pub force: Vector2,
-    /// Current angular velocity applied to orient
-    pub angular_velocity: f32,
-    /// Current angular force (reset to 0 every step)
-    pub torque: f32,
-    /// Rotation in radians
-    pub orient: f32,
-    /// Moment of inertia
-    pub inertia: f32,
-    /// Inverse value of inertia
-    pub inverse_inertia: f32,
-    /// Physics body mass
-    pub mass: f32,
-    /// Inverse value of mass
-    pub inverse_mass: f32,
-    /// Friction when the body has not movement (0 to 1)
-    pub static_friction: f32,
-    /// Friction when the body has movement (0 to 1)
-    pub dynamic_friction: f32,
-    /// Restitution coefficient of the body (0 to 1)
-    pub restitution: f32,
-    /// Apply gravity force to dynamics
-    pub use_gravity: bool,
-    /// Physics grounded on other body state
-    pub is_grounded: bool,
-    /// Physics rotation constraint
-    pub freeze_orient: bool,
-    /// Physics body shape information (type, radius, vertices, normals)
+/// The `transform` of a polygon fixture is ignored; fixtures are rotated by the orientation of their body plus their own `rotation`
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PhysicsFixture<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize> {
+    /// Fixture shape
     pub shape: PhysicsShape<MAX_VERTICES, CIRCLE_VERTICES>,
-    /// The body exists in Physac
-    is_simulating: bool,
+    /// Position of the fixture relative to the body position, before the body is rotated
+    pub offset: Vector2,
+    /// Rotation of the fixture relative to the body orientation (in radians)
+    pub rotation: Real,
 }
-impl<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize> PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES> {
-    /// Construct a new physics body with default values
-    const fn new() -> Self {
+impl<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize> PhysicsFixture<MAX_VERTICES, CIRCLE_VERTICES> {
+    /// Construct a new fixture with no offset or rotation
+    #[must_use]
+    pub const fn new(shape: PhysicsShape<MAX_VERTICES, CIRCLE_VERTICES>) -> Self {
         Self {
-            id: 0,
-            enabled: false,
-            position: Vector2::zero(),
-            velocity: Vector2::zero(),
-            force: Vector2::zero(),
-            angular_velocity: 0.0,
-            torque: 0.0,
-            orient: 0.0,
-            inertia: 0.0,
-            inverse_inertia: 0.0,
-            mass: 0.0,
-            inverse_mass: 0.0,
-            static_friction: 0.0,
-            dynamic_friction: 0.0,
-            restitution: 0.0,
-            use_gravity: false,
-            is_grounded: false,
-            freeze_orient: false,
-            shape: PhysicsShape::new(),
-            is_simulating: true,
+            shape,
+            offset: Vector2 { x: 0.0, y: 0.0 },
+            rotation: 0.0,
         }
     }
-}
-impl<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize> Default for PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES> {
-    fn default() -> Self {
-        Self::new()
+
+    /// Construct a circle fixture with no offset or rotation
+    #[must_use]
+    pub const fn circle(radius: Real) -> Self {
+        Self::new(PHYSICS_CIRCLE { radius })
     }
-}
 
-/// A physics collision
-#[derive(Debug, Clone)]
-struct PhysicsManifoldData<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize> {
-    /// Reference unique identifier
-    pub id: u32,
-    /// Manifold first physics body reference
-    pub body_a: Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>,
-    /// Manifold second physics body reference
-    pub body_b: Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>,
-    /// Depth of penetration from collision
-    pub penetration: f32,
-    /// Normal direction vector from 'a' to 'b'
-    pub normal: Vector2,
-    /// Points of contact during collision
-    pub contacts: [Vector2; 2],
-    /// Current collision number of contacts
-    pub contacts_count: u32,
-    /// Mixed restitution during collision
-    pub restitution: f32,
-    /// Mixed dynamic friction during collision
-    pub dynamic_friction: f32,
-    /// Mixed static friction during collision
-    pub static_friction: f32,
+    /// Construct a rectangle fixture centered on its offset, with no offset or rotation
+    #[must_use]
+    pub fn rectangle(width: Real, height: Real) -> Self {
+        Self::polygon(PolygonData::create_rectangle_polygon(Vector2::zero(), Vector2 { x: width, y: height }))
+    }
+
+    /// Construct a rectangle fixture with rounded corners centered on its offset, with no offset or rotation
+    ///
+    /// `corner_radius` is limited to just under half of the smaller side
+    #[must_use]
+    pub fn rounded_rectangle(width: Real, height: Real, corner_radius: Real) -> Self {
+        Self::polygon(PolygonData::create_rounded_rectangle_polygon(Vector2 { x: width, y: height }, corner_radius))
+    }
+
+    /// Construct a capsule fixture with no offset or rotation
+    ///
+    /// `height` is the total height of the capsule, including both rounded ends, and is treated as at least `2*radius`
+    #[must_use]
+    pub fn capsule(radius: Real, height: Real) -> Self {
+        Self::new(PHYSICS_CAPSULE { radius, height: height.max(2.0*radius) })
+    }
+
+    /// Construct an ellipse fixture with no offset or rotation
+    #[must_use]
+    pub fn ellipse(radius_x: Real, radius_y: Real) -> Self {
+        Self::new(PhysicsShape::ellipse(radius_x, radius_y))
+    }
+
+    /// Construct a polygon fixture from vertices relative to the fixture offset, with no offset or rotation
+    #[must_use]
+    pub const fn polygon(vertex_data: PolygonData<MAX_VERTICES, CIRCLE_VERTICES>) -> Self {
+        Self::new(PHYSICS_POLYGON { vertex_data, transform: Mat2::identity() })
+    }
 }
-impl<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize> PhysicsManifoldData<MAX_VERTICES, CIRCLE_VERTICES> {
-    const fn new(body_a: Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>, body_b: Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>) -> Self {
+
+/// Options for how [`Physac::shatter_with`] breaks a body into fragments
+///
+/// The default options break a polygon into one triangular fragment per face, at rest
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct ShatterOptions {
+    /// Number of fragments to break the body into, or [`None`] for one fragment per polygon face like the original Physac
+    ///
+    /// Fewer than 3 fragments are raised to 3, since the fragments fan out around the impact point
+    pub fragment_count: Option<usize>,
+    /// Fragments with a smaller area than this are discarded instead of created
+    pub min_fragment_area: Real,
+    /// Fragments start with the velocity of the point of the body they were broken from, instead of at rest
+    pub inherit_velocity: bool,
+    /// Seed for randomly varying the size of the fragments, or [`None`] for evenly sized fragments
+    ///
+    /// The same seed always breaks the same body the same way. Only used along with `fragment_count`
+    pub seed: Option<u64>,
+}
+impl ShatterOptions {
+    /// Construct options that break a body into `fragment_count` fragments
+    #[must_use]
+    pub const fn fragments(fragment_count: usize) -> Self {
         Self {
-            id: 0,
-            body_a,
-            body_b,
-            penetration: 0.0,
-            normal: Vector2::zero(),
-            contacts: [Vector2::zero(); 2],
-            contacts_count: 0,
-            restitution: 0.0,
-            dynamic_friction: 0.0,
-            static_friction: 0.0,
+            fragment_count: Some(fragment_count),
+            min_fragment_area: 0.0,
+            inherit_velocity: false,
+            seed: None,
         }
     }
-}
-
-/// A helper module for thread safety
-pub mod phys_rc {
-    #[cfg(feature = "sync")]
-    use std::sync::{self, Arc, RwLock, RwLockReadGuard, RwLockWriteGuard};
 
-    #[cfg(not(feature = "sync"))]
-    use std::{rc::{self, Rc}, cell::{RefCell, Ref, RefMut}};
+    /// Choose the number of fragments based on the area of the body, with one fragment per `area_per_fragment`
+    ///
+    /// Larger bodies break into more fragments, so that the fragments of every body have roughly the same size
+    #[must_use]
+    #[allow(
+        clippy::cast_sign_loss,
+        reason = "the fragment count is at least 3",
+    )]
+    pub fn fragments_by_area(area: Real, area_per_fragment: Real) -> Self {
+        Self::fragments((area/area_per_fragment).round().max(3.0) as usize)
+    }
+}
 
-    #[cfg(    feature = "sync" )] type PhysacHandleReadGuardImpl<'a, T> = RwLockReadGuard<'a, T>;
-    #[cfg(not(feature = "sync"))] type PhysacHandleReadGuardImpl<'a, T> = &'a T;
+/// How the push of [`Physac::apply_explosion`] weakens with the distance from its center
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ExplosionFalloff {
+    /// Every body in the radius is pushed equally hard
+    Constant,
+    /// The push weakens evenly, down to nothing at the edge of the radius
+    #[default]
+    Linear,
+    /// The push weakens quickly near the center and slowly near the edge of the radius
+    Quadratic,
+}
+impl ExplosionFalloff {
+    /// Returns how much of the full push reaches a body `distance` away from the center
+    fn factor(self, distance: Real, radius: Real) -> Real {
+        let remaining = if radius > 0.0 { (1.0 - distance/radius).clamp(0.0, 1.0) } else { 0.0 };
+        match self {
+            Self::Constant => 1.0,
+            Self::Linear => remaining,
+            Self::Quadratic => remaining*remaining,
+        }
+    }
+}
 
-    #[cfg(    feature = "sync" )] type PhysacHandleWriteGuardImpl<'a, T> = RwLockWriteGuard<'a, T>;
-    #[cfg(not(feature = "sync"))] type PhysacHandleWriteGuardImpl<'a, T> = &'a mut T;
+/// The first body a shape touches while being swept through the world with [`Physac::shape_cast`]
+#[derive(Debug, Clone)]
+pub struct ShapeCastHit<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize> {
+    /// Body that was hit
+    pub body: Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>,
+    /// Fraction of the translation the shape moved before touching the body (0 to 1)
+    pub fraction: Real,
+    /// Point on the surface of the body where the shape touched it, in world space
+    pub point: Vector2,
+    /// Surface normal of the body at the hit point, pointing towards the shape
+    pub normal: Vector2,
+}
 
-    #[cfg(    feature = "sync" )] type PhysacReadGuardImpl<'a, T> = RwLockReadGuard<'a, T>;
-    #[cfg(not(feature = "sync"))] type PhysacReadGuardImpl<'a, T> = Ref<'a, T>;
+/// A kinematic capsule that is moved through the world with shape casts, sliding along walls and walking up slopes and steps,
+/// like the player of a platformer
+///
+/// The capsule is a kinematic body in the world, so dynamic bodies bump into it,
+/// but only [`CharacterController::move_and_slide`] moves it. Gravity and jumping are up to the velocity it is given
+///
+/// # Examples
+///
+/// ```
+/// # use physac::prelude::*;
+/// let mut ph = init_physics::<24, 24>().build();
+/// let mut ph = ph.borrow_mut();
+///
+/// ph.body()
+///     .rectangle(1000.0, 20.0)
+///     .at(Vector2 { x: 500.0, y: 400.0 })
+///     .fixed(true)
+///     .build();
+///
+/// let mut player = CharacterController::new(&mut ph, Vector2 { x: 100.0, y: 300.0 }, 10.0, 40.0);
+/// let mut velocity = Vector2 { x: 0.0, y: 0.0 };
+///
+/// // Every frame
+/// for _ in 0..120 {
+///     let dt = 1.0/60.0;
+///     velocity.x = 100.0;
+///     velocity.y += 500.0*dt;
+///     velocity = player.move_and_slide(&ph, velocity, dt);
+/// }
+///
+/// // Standing on the floor, having walked along it
+/// assert!(player.is_grounded());
+/// assert!((player.position().y - 370.0).abs() < 1.0);
+/// assert!(player.position().x > 250.0);
+/// ```
+#[derive(Debug, Clone)]
+pub struct CharacterController<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize> {
+    body: Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>,
+    /// Steepest slope the character can stand on and walk up, in radians (45 degrees by default)
+    pub max_slope: Real,
+    /// Tallest ledge the character walks up onto without jumping (a quarter of its height by default)
+    pub step_height: Real,
+    /// Gap kept between the capsule and the surfaces it moves against, so that its next move doesn't start out touching them
+    pub skin_width: Real,
+    is_grounded: bool,
+    ground_normal: Vector2,
+}
 
-    #[cfg(    feature = "sync" )] type PhysacWriteGuardImpl<'a, T> = RwLockWriteGuard<'a, T>;
-    #[cfg(not(feature = "sync"))] type PhysacWriteGuardImpl<'a, T> = RefMut<'a, T>;
+impl<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize> CharacterController<MAX_VERTICES, CIRCLE_VERTICES> {
+    /// How many surfaces a single move slides along before it stops
+    const MAX_SLIDES: usize = 4;
 
-    /// RAII structure used to release the shared read access of a lock when dropped, implemented as a
-    #[cfg_attr(    feature = "sync",  doc = "[`std::sync::RwLockReadGuard<'a, T>`]")]
-    #[cfg_attr(not(feature = "sync"), doc = "`&T`")]
+    /// Creates a character with a capsule body of `radius` and total `height`, standing upright at `position`
     ///
-    /// This structure is created by the [`crate::PhysacHandle::borrow`] method
-    pub type PhysacHandleReadGuard<'a, T> = PhysacHandleReadGuardImpl<'a, T>;
-
-    /// RAII structure used to release the shared write access of a lock when dropped, implemented as a
-    #[cfg_attr(    feature = "sync",  doc = "[`std::sync::RwLockWriteGuard<'a, T>`]")]
-    #[cfg_attr(not(feature = "sync"), doc = "`&mut T`")]
+    /// # Errors
     ///
-    /// This structure is created by the [`crate::PhysacHandle::borrow_mut`] method
-    pub type PhysacHandleWriteGuard<'a, T> = PhysacHandleWriteGuardImpl<'a, T>;
+    /// Returns [`PhysacError::CapacityExceeded`] if every physics body slot is in use
+    pub fn try_new(ph: &mut Physac<MAX_VERTICES, CIRCLE_VERTICES>, position: impl Into<Vector2>, radius: Real, height: Real) -> Result<Self, PhysacError> {
+        let position = position.into();
+        let body = ph.try_create_physics_body_capsule(position, radius, height, 1.0)?.clone();
+        body.borrowed_mut(|body| {
+            body.enabled = false;
+            body.is_kinematic = true;
+        });
+
+        Ok(Self {
+            body,
+            max_slope: real_consts::FRAC_PI_4,
+            step_height: 0.25*height.max(2.0*radius),
+            skin_width: 0.05*radius,
+            is_grounded: false,
+            ground_normal: Vector2::zero(),
+        })
+    }
 
-    /// RAII structure used to release the shared read access of a lock when dropped, implemented as a
-    #[cfg_attr(    feature = "sync",  doc = "[`std::sync::RwLockReadGuard<'a, T>`]")]
-    #[cfg_attr(not(feature = "sync"), doc = "`std::cell::Ref<'a, T>`")]
+    /// Creates a character with a capsule body of `radius` and total `height`, standing upright at `position`
     ///
-    /// This structure is created by the [`Strong::borrow`] method
-    pub type PhysacReadGuard<'a, T> = PhysacReadGuardImpl<'a, T>;
-
-    /// RAII structure used to release the shared read access of a lock when dropped, implemented as a
-    #[cfg_attr(    feature = "sync",  doc = "[`std::sync::RwLockWriteGuard<'a, T>`]")]
-    #[cfg_attr(not(feature = "sync"), doc = "`std::cell::RefMut<'a, T>`")]
+    /// # Panics
     ///
-    /// This structure is created by the [`Strong::borrow_mut`] method
-    pub type PhysacWriteGuard<'a, T> = PhysacWriteGuardImpl<'a, T>;
+    /// This method may panic if there are no available IDs
+    pub fn new(ph: &mut Physac<MAX_VERTICES, CIRCLE_VERTICES>, position: impl Into<Vector2>, radius: Real, height: Real) -> Self {
+        let position = position.into();
+        Self::try_new(ph, position, radius, height).expect("should have an available ID for the character body")
+    }
 
-    /// A
-    #[cfg_attr(    feature = "sync",  doc = "thread-safe")]
-    #[cfg_attr(not(feature = "sync"), doc = "single-threaded")]
-    /// reference-counting pointer to a
-    #[cfg_attr(    feature = "sync",  doc = "reader-writer lock")]
-    #[cfg_attr(not(feature = "sync"), doc = "mutable memory location with dynamically checked borrow rules")]
-    ///
-    /// See
-    #[cfg_attr(    feature = "sync",  doc = "[`Arc`] and [`RwLock`]")]
-    #[cfg_attr(not(feature = "sync"), doc = "[`Rc`] and [`RefCell`]")]
-    /// for a more information
-    #[derive(Debug)]
-    pub struct Strong<T> {
-        #[cfg(feature = "sync")]
-        inner: Arc<RwLock<T>>,
-        #[cfg(not(feature = "sync"))]
-        inner: Rc<RefCell<T>>,
+    /// Returns the capsule body of the character
+    #[must_use]
+    pub const fn body(&self) -> &Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>> {
+        &self.body
     }
-    impl<T> Clone for Strong<T> {
-        fn clone(&self) -> Self {
-            Self { inner: self.inner.clone() }
-        }
+
+    /// Returns the position of the center of the character
+    #[must_use]
+    pub fn position(&self) -> Vector2 {
+        self.body.borrow().position
     }
-    impl<T> Strong<T> {
-        #[must_use]
-        pub(super) fn new(data: T) -> Self {
-            Self {
-                #[cfg(feature = "sync")]
-                inner: Arc::new(RwLock::new(data)),
-                #[cfg(not(feature = "sync"))]
-                inner: Rc::new(RefCell::new(data)),
-            }
-        }
 
-        /// Get a weak reference from a strong one
-        #[must_use]
-        pub fn downgrade(&self) -> Weak<T> {
-            let inner = &self.inner;
-            Weak {
-                #[cfg(feature = "sync")]
-                inner: Arc::downgrade(inner),
-                #[cfg(not(feature = "sync"))]
-                inner: Rc::downgrade(inner),
-            }
-        }
+    /// Returns true if the character was standing on a surface no steeper than [`CharacterController::max_slope`] after its last move
+    #[must_use]
+    pub const fn is_grounded(&self) -> bool {
+        self.is_grounded
+    }
 
-        /// Try to get a temporary reference to the body, returning an error if the resource is poisoned
-        #[cfg_attr(feature = "sync", doc = "\n # Errors\n\n This function will return an error if the RwLock is poisoned. An RwLock is poisoned whenever a writer panics while holding an exclusive lock. The failure will occur immediately after the lock has been acquired. The acquired lock guard will be contained in the returned error.")]
-        #[cfg_attr(feature = "sync", doc = "\n # Deadlocks\n\n The physics thread needs to borrow every body at some point during a tick, so try not store the borrow for longer than you have to (do not store the guard in a struct or at a scope outside of the main loop)")]
-        pub fn read(&self) -> std::sync::LockResult<PhysacReadGuard<'_, T>> {
-            #[cfg(feature = "sync")] {
-                self.inner.read()
-            } #[cfg(not(feature = "sync"))] {
-                Ok(self.inner.borrow())
-            }
-        }
+    /// Returns the normal of the surface the character was standing on after its last move, or [`None`] if it wasn't grounded
+    #[must_use]
+    pub const fn ground_normal(&self) -> Option<Vector2> {
+        if self.is_grounded { Some(self.ground_normal) } else { None }
+    }
 
-        /// Try to get a temporary mutable reference to the body, returning an error if the resource is poisoned
-        #[cfg_attr(feature = "sync", doc = "\n # Errors\n\n This function will return an error if the RwLock is poisoned. An RwLock is poisoned whenever a writer panics while holding an exclusive lock. An error will be returned when the lock is acquired. The acquired lock guard will be contained in the returned error.")]
-        #[cfg_attr(feature = "sync", doc = "\n # Deadlocks\n\n The physics thread needs to borrow every body at some point during a tick, so try not store the borrow for longer than you have to (do not store the guard in a struct or at a scope outside of the main loop)")]
-        pub fn write(&self) -> std::sync::LockResult<PhysacWriteGuard<'_, T>> {
-            #[cfg(feature = "sync")] {
-                self.inner.write()
-            } #[cfg(not(feature = "sync"))] {
-                Ok(self.inner.borrow_mut())
+    /// Moves the character by `velocity*dt`, sliding along the surfaces it runs into and stepping up onto low ledges
+    ///
+    /// Up is against the world gravity (or towards negative y without gravity). Returns the velocity left once the parts
+    /// of it going into the surfaces that were hit are removed, which can be passed back in on the next frame
+    pub fn move_and_slide(&mut self, ph: &Physac<MAX_VERTICES, CIRCLE_VERTICES>, velocity: impl Into<Vector2>, dt: Real) -> Vector2 {
+        let velocity = velocity.into();
+        let mut up = -ph.gravity.acceleration_at(self.body.borrow().center_of_mass());
+        if up.length_sqr() <= Real::EPSILON {
+            up = Vector2 { x: 0.0, y: -1.0 };
+        }
+        math_normalize(&mut up);
+
+        // Copied so that casts don't borrow the character body while looking through the world
+        let character = self.body.borrow().clone();
+        let was_grounded = self.is_grounded;
+        self.is_grounded = false;
+
+        let mut position = character.position;
+        let mut velocity = velocity;
+        let mut remaining = velocity*dt;
+        for _ in 0..Self::MAX_SLIDES {
+            if remaining.length_sqr() <= Real::EPSILON {
+                break;
             }
+
+            let Some(hit) = Self::cast(ph, &character, position, remaining) else {
+                position += remaining;
+                break;
+            };
+            let distance = remaining.length_sqr().sqrt();
+            let travel = (hit.fraction*distance - self.skin_width).max(0.0);
+            position += remaining*(travel/distance);
+            remaining *= 1.0 - travel/distance;
+
+            // Edges of ledges low enough to step onto are walked over like the floor on top of them
+            let ground_normal = if self.is_walkable(hit.normal, up) {
+                Some(hit.normal)
+            } else if was_grounded {
+                self.ledge_top(ph, &character, position, hit.point, up)
+            } else {
+                None
+            };
+
+            let normal = if let Some(ground_normal) = ground_normal {
+                self.is_grounded = true;
+                self.ground_normal = ground_normal;
+
+                if ground_normal != hit.normal && let Some((stepped, moved)) = self.step_up(ph, &character, position, remaining, up) {
+                    position = stepped;
+                    remaining -= moved;
+                    continue;
+                }
+                ground_normal
+            } else {
+                hit.normal
+            };
+
+            // Slide along the surface with whatever is left, but never gain height on a slope too steep to walk up,
+            // which stops the character like a wall would instead
+            let mut wall_normal = normal - up*normal.dot(up);
+            math_normalize(&mut wall_normal);
+            let slide = |motion: Vector2| {
+                let slid = motion - normal*motion.dot(normal).min(0.0);
+                if ground_normal.is_none() && slid.dot(up) > motion.dot(up).max(0.0) {
+                    motion - wall_normal*motion.dot(wall_normal).min(0.0)
+                } else {
+                    slid
+                }
+            };
+            remaining = slide(remaining);
+            velocity = slide(velocity);
         }
 
-        /// Get a temporary reference to the body
-        #[cfg_attr(feature = "sync", doc = "\n # Deadlocks\n\n The physics thread needs to borrow every body at some point during a tick, so try not store the borrow for longer than you have to (do not store the guard in a struct or at a scope outside of the main loop)")]
-        #[cfg_attr(feature = "sync", doc = "\n # Panics\n\n This method may panic if another thread panicked while mutably borrowing this object")]
-        pub fn borrow(&self) -> PhysacReadGuard<'_, T> {
-            #[cfg(feature = "sync")] {
-                self.inner.read().expect("thread poison recovery is not supported")
-            } #[cfg(not(feature = "sync"))] {
-                self.inner.borrow()
+        // Stay on the ground when walking down slopes and steps, unless moving away from it
+        if !self.is_grounded && velocity.dot(up) <= 0.0 {
+            let reach = if was_grounded { self.step_height } else { 0.0 } + 2.0*self.skin_width;
+            if let Some(hit) = Self::cast(ph, &character, position, -up*reach) {
+                let ground_normal = if self.is_walkable(hit.normal, up) {
+                    Some(hit.normal)
+                } else {
+                    self.ledge_top(ph, &character, position, hit.point, up)
+                };
+
+                if let Some(ground_normal) = ground_normal {
+                    position -= up*(hit.fraction*reach - self.skin_width).max(0.0);
+                    velocity -= ground_normal*velocity.dot(ground_normal).min(0.0);
+                    self.is_grounded = true;
+                    self.ground_normal = ground_normal;
+                }
             }
         }
 
-        /// Get a temporary mutable reference to the body
-        #[cfg_attr(feature = "sync", doc = "\n # Deadlocks\n\n The physics thread needs to borrow every body at some point during a tick, so try not store the borrow for longer than you have to (do not store the guard in a struct or at a scope outside of the main loop)")]
-        #[cfg_attr(feature = "sync", doc = "\n # Panics\n\n This method may panic if another thread panicked while mutably borrowing this object")]
-        pub fn borrow_mut(&self) -> PhysacWriteGuard<'_, T> {
-            #[cfg(feature = "sync")] {
-                self.inner.write().expect("thread poison recovery is not supported")
-            } #[cfg(not(feature = "sync"))] {
-                self.inner.borrow_mut()
-            }
+        self.body.borrow_mut().position = position;
+        velocity
+    }
+
+    /// Returns the normal of the flat top of a ledge the character touches at `point`,
+    /// if it is within [`CharacterController::step_height`] of its feet
+    fn ledge_top(&self, ph: &Physac<MAX_VERTICES, CIRCLE_VERTICES>, character: &PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>, position: Vector2, point: Vector2, up: Vector2) -> Option<Vector2> {
+        let PHYSICS_CAPSULE { height, .. } = character.shape else { return None };
+        let mut inward = point - position;
+        inward -= up*inward.dot(up);
+        if self.step_height <= 0.0 || inward.length_sqr() <= Real::EPSILON {
+            return None;
         }
+        math_normalize(&mut inward);
 
-        /// Apply a closure on a temporary reference to the body
-        #[cfg_attr(feature = "sync", doc = "\n # Deadlocks\n\n The physics thread needs to borrow every body at some point during a tick, so try not store the borrow for longer than you have to (do not store the guard in a struct or at a scope outside of the main loop)")]
-        #[cfg_attr(feature = "sync", doc = "\n # Panics\n\n This method may panic if another thread panicked while mutably borrowing this object")]
-        pub fn borrowed<U, F>(&self, f: F) -> U
-        where
-            F: FnOnce(&T) -> U
-        {
-            #[cfg(feature = "sync")] {
-                f(&*self.inner.read().expect("thread poison recovery is not supported"))
-            } #[cfg(not(feature = "sync"))] {
-                f(&*self.inner.borrow())
-            }
+        // Look down onto the ledge just past the edge, from as high as the character can step
+        let feet = position - up*(0.5*height);
+        let probe_start = point + inward*(2.0*self.skin_width) + up*(self.step_height - (point - feet).dot(up));
+        let probe = PHYSICS_CIRCLE { radius: 0.5*self.skin_width };
+        let top = ph.shape_cast_filtered(&probe, probe_start, 0.0, -up*self.step_height, |body, _| {
+            body.id != character.id && body.should_collide(character)
+        })?;
+
+        // Starting out inside means the ledge is taller than a step
+        (top.fraction > 0.0 && self.is_walkable(top.normal, up)).then_some(top.normal)
+    }
+
+    /// Tries to lift the character onto a ledge in the way of the move, returning where it lands and how much of the move it made
+    fn step_up(&self, ph: &Physac<MAX_VERTICES, CIRCLE_VERTICES>, character: &PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>, position: Vector2, remaining: Vector2, up: Vector2) -> Option<(Vector2, Vector2)> {
+        let forward = remaining - up*remaining.dot(up);
+        if forward.length_sqr() <= Real::EPSILON {
+            return None;
         }
 
-        /// Apply a closure on a temporary mutable reference to the body
-        #[cfg_attr(feature = "sync", doc = "\n # Deadlocks\n\n The physics thread needs to borrow every body at some point during a tick, so try not store the borrow for longer than you have to (do not store the guard in a struct or at a scope outside of the main loop)")]
-        #[cfg_attr(feature = "sync", doc = "\n # Panics\n\n This method may panic if another thread panicked while mutably borrowing this object")]
-        pub fn borrowed_mut<U, F>(&self, f: F) -> U
-        where
-            F: FnOnce(&mut T) -> U
-        {
-            #[cfg(feature = "sync")] {
-                f(&mut *self.inner.write().expect("thread poison recovery is not supported"))
-            } #[cfg(not(feature = "sync"))] {
-                f(&mut *self.inner.borrow_mut())
-            }
+        // Go up as far as the ceiling allows, then along, then back down onto the ledge
+        let lift = Self::cast(ph, character, position, up*self.step_height)
+            .map_or(self.step_height, |hit| (hit.fraction*self.step_height - self.skin_width).max(0.0));
+        let raised = position + up*lift;
+
+        let forward_distance = forward.length_sqr().sqrt();
+        let along = Self::cast(ph, character, raised, forward)
+            .map_or(1.0, |hit| (hit.fraction*forward_distance - self.skin_width).max(0.0)/forward_distance);
+        if along <= 0.0 {
+            return None;
         }
+        let advanced = raised + forward*along;
+
+        let drop = lift + self.skin_width;
+        let landed = Self::cast(ph, character, advanced, -up*drop)
+            .map_or(drop, |hit| (hit.fraction*drop - self.skin_width).max(0.0));
+
+        Some((advanced - up*landed, forward*along))
     }
 
-    /// A non-owning reference to the managed allocation
-    ///
-    /// See
-    #[cfg_attr(    feature = "sync",  doc = "[`sync::Weak`] and [`RwLock`]")]
-    #[cfg_attr(not(feature = "sync"), doc = "[`rc::Weak`] and [`RefCell`]")]
-    /// for a more information
-    #[derive(Debug, Clone)]
-    pub struct Weak<T> {
-        #[cfg(feature = "sync")]
-        inner: sync::Weak<RwLock<T>>,
-        #[cfg(not(feature = "sync"))]
-        inner: rc::Weak<RefCell<T>>,
+    /// Sweeps the character capsule from `start`, skipping its own body and bodies it doesn't collide with
+    fn cast(ph: &Physac<MAX_VERTICES, CIRCLE_VERTICES>, character: &PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>, start: Vector2, translation: Vector2) -> Option<ShapeCastHit<MAX_VERTICES, CIRCLE_VERTICES>> {
+        ph.shape_cast_filtered(&character.shape, start, character.orient, translation, |body, normal| {
+            // Surfaces the character is already moving away from don't stop it
+            body.id != character.id && body.should_collide(character) && normal.dot(translation) < 0.0
+        })
     }
-    impl<T> Default for Weak<T> {
-        fn default() -> Self {
-            Self::new()
-        }
+
+    /// Returns true if a surface with this normal is flat enough to stand on
+    fn is_walkable(&self, normal: Vector2, up: Vector2) -> bool {
+        normal.dot(up) >= math_cos(self.max_slope)
     }
-    impl<T> Weak<T> {
-        /// Constructs a weak reference to nothing
-        #[must_use]
-        pub fn new() -> Self {
-            Self {
-                #[cfg(feature = "sync")]
-                inner: sync::Weak::new(),
-                #[cfg(not(feature = "sync"))]
-                inner: rc::Weak::new()
+}
+
+/// Returns the number of vertices of a shape
+fn shape_vertices_count<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize>(shape: &PhysicsShape<MAX_VERTICES, CIRCLE_VERTICES>) -> usize {
+    match shape {
+        PHYSICS_CIRCLE { .. } | PHYSICS_CAPSULE { .. } | PHYSICS_ELLIPSE { .. } => CIRCLE_VERTICES,
+        PHYSICS_POLYGON { vertex_data, .. } => vertex_data.outline_len(),
+        PHYSICS_COMPOUND { fixtures } => fixtures.iter().map(|fixture| shape_vertices_count(&fixture.shape)).sum(),
+        PHYSICS_CHAIN { vertices } => vertices.len(),
+        PHYSICS_HEIGHTFIELD { heights, .. } => heights.len(),
+    }
+}
+
+/// Returns the position of a shape vertex placed at `position` and rotated by `transform`
+///
+/// Returns [`None`] if `vertex` index is out of bounds
+fn shape_vertex<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize>(shape: &PhysicsShape<MAX_VERTICES, CIRCLE_VERTICES>, position: Vector2, transform: Mat2, vertex: usize) -> Option<Vector2> {
+    match *shape {
+        PHYSICS_CIRCLE { radius } => {
+            Some(Vector2 {
+                x: position.x + math_cos(360.0/CIRCLE_VERTICES as Real*vertex as Real*DEG2RAD as Real)*radius,
+                y: position.y + math_sin(360.0/CIRCLE_VERTICES as Real*vertex as Real*DEG2RAD as Real)*radius,
+            })
+        }
+        PHYSICS_POLYGON { ref vertex_data, .. } => {
+            vertex_data.outline_vertex(vertex)
+                .map(|p| position + transform.multiply_vector2(p))
+        }
+        PHYSICS_CAPSULE { radius, height } => {
+            (vertex < CIRCLE_VERTICES).then(|| {
+                // Half of the vertices go around each end, so that the straight sides are kept exactly
+                let half_length = capsule_half_length(radius, height);
+                let first_end = CIRCLE_VERTICES/2;
+                let (center_y, start_angle, i, end_vertices) = if vertex < first_end {
+                    (half_length, 0.0, vertex, first_end)
+                } else {
+                    (-half_length, PI, vertex - first_end, CIRCLE_VERTICES - first_end)
+                };
+                let angle = (start_angle + PI/(end_vertices.max(2) - 1) as f64*i as f64) as Real;
+                let p = Vector2 { x: math_cos(angle)*radius, y: center_y + math_sin(angle)*radius };
+                position + transform.multiply_vector2(p)
+            })
+        }
+        PHYSICS_COMPOUND { ref fixtures } => {
+            let mut vertex = vertex;
+            for fixture in fixtures {
+                let count = shape_vertices_count(&fixture.shape);
+                if vertex < count {
+                    let (fixture_position, fixture_transform) = fixture_placement(fixture, position, transform);
+                    return shape_vertex(&fixture.shape, fixture_position, fixture_transform, vertex);
+                }
+                vertex -= count;
             }
+            None
         }
-
-        #[must_use]
-        pub(super) fn strong_count(&self) -> usize {
-            #[cfg(feature = "sync")]
-            let n = sync::Weak::strong_count(&self.inner);
-            #[cfg(not(feature = "sync"))]
-            let n = rc::Weak::strong_count(&self.inner);
-            n
+        PHYSICS_CHAIN { ref vertices } => {
+            vertices.get(vertex)
+                .map(|&p| position + transform.multiply_vector2(p))
         }
-
-        /// Try to get a strong reference from a weak one
-        ///
-        /// Returns [`None`] if no Strong references exist for the object
-        #[must_use]
-        pub fn upgrade(&self) -> Option<Strong<T>> {
-            self.inner.upgrade().map(|inner| Strong { inner })
+        PHYSICS_HEIGHTFIELD { ref heights, cell_width } => {
+            heights.get(vertex)
+                .map(|&height| position + transform.multiply_vector2(Vector2 { x: vertex as Real*cell_width, y: height }))
         }
-
-        /// Upgrades and borrows the inner value, applying the closure to that
-        ///
-        /// Returns [`None`] if no Strong references exist for the object
-        #[must_use]
-        pub fn borrowed<U, F>(&self, f: F) -> Option<U>
-        where
-            F: FnOnce(&T) -> U
-        {
-            self.upgrade().map(|x| x.borrowed(f))
+        PHYSICS_ELLIPSE { radius_x, radius_y, .. } => {
+            (vertex < CIRCLE_VERTICES).then(|| {
+                let angle = 360.0/CIRCLE_VERTICES as Real*vertex as Real*DEG2RAD as Real;
+                let p = Vector2 { x: math_cos(angle)*radius_x, y: math_sin(angle)*radius_y };
+                position + transform.multiply_vector2(p)
+            })
         }
+    }
+}
 
-        /// Upgrades and borrows the inner value, applying the closure to that
-        ///
-        /// Returns [`None`] if no Strong references exist for the object
-        #[must_use]
-        pub fn borrowed_mut<U, F>(&self, f: F) -> Option<U>
-        where
-            F: FnOnce(&mut T) -> U
-        {
-            self.upgrade().map(|x| x.borrowed_mut(f))
+/// Creates the rounded convex core used for collisions of an ellipse
+///
+/// The core vertices lie on the inner parallel curve of the ellipse, so the rounded outline touches the ellipse at every vertex
+/// and never bulges past it. The rounding radius is half of the smaller radius, limited by the tightest curvature of the ellipse
+fn ellipse_core<const CIRCLE_VERTICES: usize>(radius_x: Real, radius_y: Real) -> PolygonData<CIRCLE_VERTICES, CIRCLE_VERTICES> {
+    let (min_radius, max_radius) = (radius_x.min(radius_y), radius_x.max(radius_y));
+    let radius = if max_radius > 0.0 { (0.5*min_radius).min(min_radius*min_radius/max_radius) } else { 0.0 };
+
+    let mut core = PolygonData::<CIRCLE_VERTICES, CIRCLE_VERTICES> {
+        vertex_count: CIRCLE_VERTICES,
+        ..Default::default()
+    };
+    for (i, p) in core.positions.iter_mut().enumerate() {
+        let angle = 360.0/CIRCLE_VERTICES as Real*i as Real*DEG2RAD as Real;
+        let (sin, cos) = (math_sin(angle), math_cos(angle));
+        let mut normal = Vector2 { x: radius_y*cos, y: radius_x*sin };
+        math_normalize(&mut normal);
+        *p = Vector2 { x: radius_x*cos, y: radius_y*sin } - normal*radius;
+    }
+    core.calculate_normals();
+    core.with_corner_radius(radius)
+}
+
+/// Returns the world position and transform of a fixture attached to a body placed at `position` and rotated by `transform`
+fn fixture_placement<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize>(fixture: &PhysicsFixture<MAX_VERTICES, CIRCLE_VERTICES>, position: Vector2, transform: Mat2) -> (Vector2, Mat2) {
+    (
+        position + transform.multiply_vector2(fixture.offset),
+        transform.multiply(&Mat2::radians(fixture.rotation)),
+    )
+}
+
+/// Calculates the centroid, area and moment of inertia (about the origin, per unit of density) of a shape
+///
+/// Round parts follow the inertia Physac gives circles, `m*r*r`, rather than the `0.5*m*r*r` of a solid disc,
+/// so that every round shape turns as readily as a circle of the same size
+fn shape_mass_data<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize>(shape: &PhysicsShape<MAX_VERTICES, CIRCLE_VERTICES>) -> (Vector2, Real, Real) {
+    match *shape {
+        PHYSICS_CIRCLE { radius } => {
+            let area = (PI*real_to_f64(radius)*real_to_f64(radius)) as Real;
+            (Vector2::zero(), area, area*radius*radius)
         }
-    }
+        PHYSICS_POLYGON { ref vertex_data, .. } => vertex_data.mass_data(),
+        PHYSICS_CAPSULE { radius, height } => {
+            let half_length = capsule_half_length(radius, height);
 
-    use super::PhysicsBodyData;
+            // A rectangle between the centers of both ends plus two half circles
+            let rect_area = 2.0*radius*2.0*half_length;
+            let circle_area = (PI*real_to_f64(radius)*real_to_f64(radius)) as Real;
+            let centroid_offset = (4.0*real_to_f64(radius)/(3.0*PI)) as Real;
 
-    impl<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize> Weak<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>> {
-        /// Try to get a strong reference from a weak one
-        ///
-        /// Returns [`None`] if no Strong references exist for the body, or if the body has been destroyed
-        #[must_use]
-        pub fn sim_upgrade(&self) -> Option<Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>> {
-            self.inner.upgrade()
-                .filter(|body| body.read().is_ok_and(|body| body.is_simulating()))
-                .map(|inner| Strong { inner })
+            let rect_inertia = rect_area*(4.0*radius*radius + 4.0*half_length*half_length)/12.0;
+            let circle_inertia = circle_area*(radius*radius + half_length*half_length + 2.0*half_length*centroid_offset);
+
+            (Vector2::zero(), rect_area + circle_area, rect_inertia + circle_inertia)
+        }
+        PHYSICS_COMPOUND { ref fixtures } => fixtures_mass_data(fixtures),
+        // Chains and heightfields are only outlines, so they have no area
+        PHYSICS_CHAIN { .. } | PHYSICS_HEIGHTFIELD { .. } => (Vector2::zero(), 0.0, 0.0),
+        PHYSICS_ELLIPSE { radius_x, radius_y, .. } => {
+            let area = (PI*real_to_f64(radius_x)*real_to_f64(radius_y)) as Real;
+            (Vector2::zero(), area, area*(radius_x*radius_x + radius_y*radius_y)*0.5)
         }
     }
 }
-pub use self::phys_rc::*;
-
-/***********************************************************************************
-*
-*   PHYSAC IMPLEMENTATION
-*
-************************************************************************************/
-
-//----------------------------------------------------------------------------------
-// Defines and Macros
-//----------------------------------------------------------------------------------
-const PHYSAC_K: f32 = 1.0/3.0;
 
-//----------------------------------------------------------------------------------
-// Global Variables Definition
-//----------------------------------------------------------------------------------
-/// A collection of variables related to simulating physics
-pub struct Physac<const MAX_VERTICES: usize = 24, const CIRCLE_VERTICES: usize = MAX_VERTICES> {
-    #[cfg(feature = "phys_thread")]
-    fixed_time: f64,
-    /// How many times the simulation will iteratively solve each collision every step
-    pub collision_iterations: usize,
-    /// How deep two physics bodies are allowed to overlap
-    pub penetration_allowance: f32,
-    /// How much the engine is allowed to correct physics body positions at a time to push them apart when overlapping
-    pub penetration_correction: f32,
+/// Calculates the combined centroid, area and moment of inertia (about the origin, per unit of density) of several fixtures
+fn fixtures_mass_data<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize>(fixtures: &[PhysicsFixture<MAX_VERTICES, CIRCLE_VERTICES>]) -> (Vector2, Real, Real) {
+    let mut center = Vector2::zero();
+    let mut area = 0.0;
+    let mut inertia = 0.0;
 
-    #[cfg(feature = "phys_thread")]
-    /// Physics thread
-    physics_thread: Option<thread::JoinHandle<()>>,
+    for fixture in fixtures {
+        let (fixture_center, fixture_area, fixture_inertia) = shape_mass_data(&fixture.shape);
+        let (fixture_position, fixture_transform) = fixture_placement(fixture, Vector2::zero(), Mat2::identity());
+        let centroid = fixture_position + fixture_transform.multiply_vector2(fixture_center);
 
-    /// Offset time for MONOTONIC clock
-    base_time: Instant,
-    /// Start time in milliseconds
-    start_time: f64,
-    /// Delta time used for physics steps, in milliseconds
-    delta_time: f64,
-    /// Current time in milliseconds
-    current_time: f64,
+        // Move the fixture inertia from its own origin to its centroid, and then to the body origin
+        area += fixture_area;
+        center += centroid*fixture_area;
+        inertia += fixture_inertia - fixture_area*fixture_center.length_sqr() + fixture_area*centroid.length_sqr();
+    }
 
-    /// Physics time step delta time accumulator
-    accumulator: f64,
-    /// Total physics steps processed
-    steps_count: u32,
-    /// Physics world gravity force
-    gravity_force: Vector2,
+    if area != 0.0 {
+        center *= 1.0/area;
+    }
 
-    /// Physics bodies pointers array
-    bodies: Vec<Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>>,
-    /// Physics bodies pointers array
-    contacts: Vec<PhysicsManifoldData<MAX_VERTICES, CIRCLE_VERTICES>>,
+    (center, area, inertia)
 }
-impl<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize> Drop for Physac<MAX_VERTICES, CIRCLE_VERTICES> {
-    fn drop(&mut self) {
-        #[cfg(feature = "phys_thread")]
-        self.physics_thread
-            .take().expect("[PHYSAC] thread should exist if physics has been initialized")
-            .join().expect("[PHYSAC] physics thread failed to close");
 
-        debug_print!("[PHYSAC] physics module closed successfully");
+/// Adds the world space convex proxies of a shape placed at `position` and rotated by `transform`
+///
+/// If `within` is provided, parts of chains and heightfields outside of it are skipped
+fn push_shape_proxies<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize>(shape: &PhysicsShape<MAX_VERTICES, CIRCLE_VERTICES>, position: Vector2, transform: Mat2, within: Option<Aabb>, proxies: &mut Vec<ConvexProxy>) {
+    match *shape {
+        PHYSICS_CIRCLE { radius } => proxies.push(ConvexProxy {
+            vertices: vec![position],
+            normals: Vec::new(),
+            radius,
+            neighbor_normals: None,
+            part: 0,
+        }),
+        PHYSICS_POLYGON { ref vertex_data, .. } => proxies.push(ConvexProxy {
+            vertices: vertex_data.positions().iter().map(|&p| position + transform.multiply_vector2(p)).collect(),
+            normals: vertex_data.normals().iter().map(|&n| transform.multiply_vector2(n)).collect(),
+            radius: vertex_data.radius,
+            neighbor_normals: None,
+            part: 0,
+        }),
+        PHYSICS_CAPSULE { radius, height } => {
+            let half_length = capsule_half_length(radius, height);
+            if half_length > 0.0 {
+                let axis = transform.multiply_vector2(Vector2 { x: 0.0, y: half_length });
+                let mut normal = Vector2 { x: axis.y, y: -axis.x };
+                math_normalize(&mut normal);
+                proxies.push(ConvexProxy {
+                    vertices: vec![position - axis, position + axis],
+                    normals: vec![normal, -normal],
+                    radius,
+                    neighbor_normals: None,
+                    part: 0,
+                });
+            } else {
+                proxies.push(ConvexProxy {
+                    vertices: vec![position],
+                    normals: Vec::new(),
+                    radius,
+                    neighbor_normals: None,
+                    part: 0,
+                });
+            }
+        }
+        PHYSICS_COMPOUND { ref fixtures } => {
+            for (i, fixture) in fixtures.iter().enumerate() {
+                let (fixture_position, fixture_transform) = fixture_placement(fixture, position, transform);
+                let first = proxies.len();
+                push_shape_proxies(&fixture.shape, fixture_position, fixture_transform, within, proxies);
+
+                for proxy in &mut proxies[first..] {
+                    proxy.part |= (i as u32) << 16;
+                }
+            }
+        }
+        PHYSICS_CHAIN { ref vertices } => {
+            let segment_count = vertices.len().saturating_sub(1);
+            let is_loop = segment_count > 1 && vertices.first() == vertices.last();
+            push_chain_proxies(|i| position + transform.multiply_vector2(vertices[i]), segment_count, is_loop, 0..segment_count, within, proxies);
+        }
+        PHYSICS_HEIGHTFIELD { ref heights, cell_width } => {
+            let segment_count = heights.len().saturating_sub(1);
+
+            // Only the cells below the area of interest need to be checked
+            #[allow(
+                clippy::cast_sign_loss,
+                reason = "the cell indices are clamped to be non-negative",
+            )]
+            let cells = if let Some(within) = within.filter(|_| cell_width > 0.0) {
+                let inverse = transform.transpose();
+                let (min_x, max_x) = within.corners().iter()
+                    .map(|&corner| inverse.multiply_vector2(corner - position).x)
+                    .fold((Real::MAX, Real::MIN), |(min, max), x| (min.min(x), max.max(x)));
+                let first = (min_x/cell_width).floor().clamp(0.0, segment_count as Real) as usize;
+                let last = (max_x/cell_width).ceil().clamp(0.0, segment_count as Real) as usize;
+                first..last
+            } else {
+                0..segment_count
+            };
+
+            push_chain_proxies(|i| position + transform.multiply_vector2(Vector2 { x: i as Real*cell_width, y: heights[i] }), segment_count, false, cells, within, proxies);
+        }
+        PHYSICS_ELLIPSE { ref core, .. } => {
+            proxies.push(ConvexProxy {
+                vertices: core.positions().iter().map(|&p| position + transform.multiply_vector2(p)).collect(),
+                normals: core.normals().iter().map(|&n| transform.multiply_vector2(n)).collect(),
+                radius: core.radius,
+                neighbor_normals: None,
+                part: 0,
+            });
+        }
     }
 }
 
-/// A handle that allows [`Physac`] to be
-#[cfg_attr(    feature = "sync",  doc = "borrowed, locking it so that the current thread isn't racing the physics thread, and")]
-#[cfg_attr(not(feature = "sync"), doc = "accessed,")]
-/// closing physics upon exiting scope or being dropped
+/// Adds the world space one-sided proxies of the chain segments in `segments`, skipping any outside of `within`
 ///
-/// Created with [`PhysacBuilder::build`]
-///
-/// # Examples
-///
-/// ```
-/// # use physac::prelude::*;
-/// let mut ph: PhysacHandle<_> = init_physics::<24, 24>().build();
-///
-/// ph.borrowed_mut(|ph| {
-///     ph.create_physics_body_circle(Vector2 { x: 0.0, y: 0.0 }, 45.0, 10.0);
-/// });
-/// assert_eq!(ph.borrow().get_physics_bodies_count(), 1);
-///
-/// ph.borrowed_mut(|ph| {
-///     let body = ph.get_physics_body(0).clone();
-///     ph.destroy_physics_body(body);
-/// });
-/// assert_eq!(ph.borrow().get_physics_bodies_count(), 0);
-/// ```
-pub struct PhysacHandle<T> {
-    #[cfg(feature = "sync")]
-    phys: Arc<RwLock<T>>,
-    #[cfg(not(feature = "sync"))]
-    phys: T,
+/// `vertex` returns the world position of a chain vertex, and there are `segment_count` segments in the whole chain
+fn push_chain_proxies<F>(vertex: F, segment_count: usize, is_loop: bool, segments: std::ops::Range<usize>, within: Option<Aabb>, proxies: &mut Vec<ConvexProxy>)
+where
+    F: Fn(usize) -> Vector2,
+{
+    let segment_normal = |i: usize| {
+        let face = vertex(i + 1) - vertex(i);
+        let mut normal = Vector2 { x: face.y, y: -face.x };
+        math_normalize(&mut normal);
+        normal
+    };
 
-    /// Physics thread enabled state
-    #[cfg(feature = "phys_thread")]
-    is_physics_thread_enabled: Arc<AtomicBool>,
-}
-impl<T> PhysacHandle<T> {
-    fn new(phys: T) -> Self {
-        #[cfg(feature = "sync")]
-        let phys = Arc::new(RwLock::new(phys));
-        Self {
-            phys,
-            #[cfg(feature = "phys_thread")]
-            is_physics_thread_enabled: Arc::new(AtomicBool::new(false)),
+    // Every segment is one-sided, and remembers its neighbors to avoid catching on the joints
+    for i in segments {
+        let (v1, v2) = (vertex(i), vertex(i + 1));
+        if within.is_some_and(|within| !within.overlaps(&Aabb::from_points([v1, v2]))) {
+            continue;
         }
+
+        let prev = if i > 0 { Some(i - 1) } else if is_loop { Some(segment_count - 1) } else { None };
+        let next = if i + 1 < segment_count { Some(i + 1) } else if is_loop { Some(0) } else { None };
+
+        proxies.push(ConvexProxy {
+            vertices: vec![v1, v2],
+            normals: vec![segment_normal(i)],
+            radius: 0.0,
+            neighbor_normals: Some([prev.map(segment_normal), next.map(segment_normal)]),
+            part: i as u32,
+        });
     }
 }
-impl<T> Drop for PhysacHandle<T> {
-    /// Unitializes physics pointers and exits physics loop thread
-    fn drop(&mut self) {
-        #[cfg(feature = "phys_thread")]
-        // Exit physics loop thread
-        self.is_physics_thread_enabled.store(false, Release);
-    }
+
+/// An axis-aligned bounding box in world space
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Aabb {
+    /// Top left corner
+    min: Vector2,
+    /// Bottom right corner
+    max: Vector2,
 }
-#[cfg(not(feature = "sync"))]
-impl<T> std::ops::Deref for PhysacHandle<T> {
-    type Target = T;
+impl Aabb {
+    /// Returns the smallest box containing every point (or an empty box at the origin if there are none)
+    fn from_points<I: IntoIterator<Item = Vector2>>(points: I) -> Self {
+        points.into_iter()
+            .map(|point| Aabb { min: point, max: point })
+            .reduce(Aabb::union)
+            .unwrap_or(Aabb { min: Vector2::zero(), max: Vector2::zero() })
+    }
+
+    /// Returns the smallest box containing both boxes
+    fn union(self, other: Aabb) -> Self {
+        Aabb {
+            min: Vector2 { x: self.min.x.min(other.min.x), y: self.min.y.min(other.min.y) },
+            max: Vector2 { x: self.max.x.max(other.max.x), y: self.max.y.max(other.max.y) },
+        }
+    }
 
-    fn deref(&self) -> &Self::Target {
-        &self.phys
+    /// Returns the box grown by `amount` on every side
+    fn expanded(self, amount: Real) -> Self {
+        Aabb {
+            min: Vector2 { x: self.min.x - amount, y: self.min.y - amount },
+            max: Vector2 { x: self.max.x + amount, y: self.max.y + amount },
+        }
     }
-}
-#[cfg(not(feature = "sync"))]
-impl<T> std::ops::DerefMut for PhysacHandle<T> {
-    fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut self.phys
+
+    /// Returns true if the boxes touch or overlap
+    fn overlaps(&self, other: &Aabb) -> bool {
+        self.min.x <= other.max.x && other.min.x <= self.max.x &&
+        self.min.y <= other.max.y && other.min.y <= self.max.y
+    }
+
+    /// Returns the four corners of the box
+    fn corners(&self) -> [Vector2; 4] {
+        [
+            self.min,
+            Vector2 { x: self.max.x, y: self.min.y },
+            self.max,
+            Vector2 { x: self.min.x, y: self.max.y },
+        ]
+    }
+
+    /// Returns true if `other` is entirely inside the box
+    fn contains(&self, other: &Aabb) -> bool {
+        self.min.x <= other.min.x && self.min.y <= other.min.y &&
+        other.max.x <= self.max.x && other.max.y <= self.max.y
+    }
+
+    /// Returns the distance around the box, used to judge how much space it wastes
+    fn perimeter(&self) -> Real {
+        2.0*((self.max.x - self.min.x) + (self.max.y - self.min.y))
     }
 }
 
-//----------------------------------------------------------------------------------
-// Module Functions Definition
-//----------------------------------------------------------------------------------
-/// A helper for initializing [`Physac`] with custom parameters
-///
-/// Created with [`init_physics()`]
-#[must_use]
-pub struct PhysacBuilder<const MAX_VERTICES: usize = 24, const CIRCLE_VERTICES: usize = MAX_VERTICES> {
-    circle_vertices: usize,
-    fixed_time: f64,
-    collision_iterations: usize,
-    penetration_allowance: f32,
-    penetration_correction: f32,
-    gravity_force: Vector2,
-    max_bodies: usize,
-    max_manifolds: usize,
-    is_max_manifolds_overridden: bool,
+/// Returns every pair of boxes that touch or overlap in ascending order
+#[cfg(not(feature = "simd"))]
+fn overlapping_pairs(bounds: &[Aabb]) -> Vec<(usize, usize)> {
+    (0..bounds.len())
+        .flat_map(|i| ((i + 1)..bounds.len()).filter(move |&j| bounds[i].overlaps(&bounds[j])).map(move |j| (i, j)))
+        .collect()
 }
 
-/// Initializes physics values, pointers and creates physics loop thread
-pub fn init_physics<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize>() -> PhysacBuilder<MAX_VERTICES, CIRCLE_VERTICES> {
-    PhysacBuilder {
-        circle_vertices: MAX_VERTICES,
-        fixed_time: 1.0/60.0,
-        collision_iterations: 20,
-        penetration_allowance: 0.05,
-        penetration_correction: 0.4,
-        gravity_force: Vector2 { x: 0.0, y: 9.81 },
-        max_bodies: 64,
-        max_manifolds: 4096,
-        is_max_manifolds_overridden: false,
+/// Returns every pair of boxes that touch or overlap in ascending order, testing each box against four others at a time
+///
+/// The boxes are split into one array per coordinate first, so that four neighbouring boxes load into a register straight from
+/// each array. This only prefilters the pairs for the narrowphase: body storage and integration are unchanged
+#[cfg(feature = "simd")]
+fn overlapping_pairs_x4(bounds: &[Aabb]) -> Vec<(usize, usize)> {
+    let column = |coordinate: fn(&Aabb) -> Real| bounds.iter().map(coordinate).collect::<Vec<_>>();
+    let (min_x, min_y) = (column(|b| b.min.x), column(|b| b.min.y));
+    let (max_x, max_y) = (column(|b| b.max.x), column(|b| b.max.y));
+
+    let mut pairs = Vec::new();
+    for (i, a) in bounds.iter().enumerate() {
+        let mut j = i + 1;
+        while j + 4 <= bounds.len() {
+            let lanes = |column: &[Real]| RealX4::from(*column[j..].first_chunk().expect("the loop leaves at least four boxes"));
+            let overlaps = lanes(&min_x).simd_le(RealX4::splat(a.max.x)) & RealX4::splat(a.min.x).simd_le(lanes(&max_x)) &
+                lanes(&min_y).simd_le(RealX4::splat(a.max.y)) & RealX4::splat(a.min.y).simd_le(lanes(&max_y));
+            let mask = overlaps.to_bitmask();
+            pairs.extend((0..4).filter(|lane| mask & (1 << lane) != 0).map(|lane| (i, j + lane)));
+            j += 4;
+        }
+        pairs.extend((j..bounds.len()).filter(|&k| a.overlaps(&bounds[k])).map(|k| (i, k)));
     }
+    pairs
 }
 
-impl<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize> PhysacBuilder<MAX_VERTICES, CIRCLE_VERTICES> {
-    /// Set the number of vertices used to approximate [`PhysicsShape::Circle`]s with [`PhysicsBodyData::get_physics_shape_vertex`]
-    pub fn circle_vertices(&mut self, n: NonZeroUsize) -> &mut Self {
-        self.circle_vertices = n.get();
-        self
-    }
-    /// Set the fixed time
-    pub fn fixed_time(&mut self, value: f64) -> &mut Self {
-        self.fixed_time = value;
-        self
-    }
-    /// Set the number of collision iterations
-    pub fn collision_iterations(&mut self, n: usize) -> &mut Self {
-        self.collision_iterations = n;
-        self
-    }
-    /// Set the penetration allowance amount
-    pub fn penetration_allowance(&mut self, amount: f32) -> &mut Self {
-        self.penetration_allowance = amount;
-        self
-    }
-    /// Set the penetration correction amount
-    pub fn penetration_correction(&mut self, amount: f32) -> &mut Self {
-        self.penetration_correction = amount;
-        self
+/// A node of a [`DynamicTree`]
+#[derive(Debug, Clone)]
+struct TreeNode<T> {
+    /// Box around every leaf below the node
+    bounds: Aabb,
+    /// Node this one is a child of, or [`None`] for the root
+    parent: Option<usize>,
+    /// Children of a branch, or [`None`] for a leaf
+    children: Option<[usize; 2]>,
+    /// Item stored in a leaf
+    item: Option<T>,
+    /// Number of levels below the node (0 for leaves)
+    height: u32,
+}
+
+/// A balanced bounding volume hierarchy of boxes that can be moved around, used to find the items whose boxes overlap a region
+#[derive(Debug, Clone)]
+struct DynamicTree<T> {
+    nodes: Vec<TreeNode<T>>,
+    /// Nodes that were removed and can be reused
+    free_nodes: Vec<usize>,
+    root: Option<usize>,
+}
+impl<T> DynamicTree<T> {
+    const fn new() -> Self {
+        Self { nodes: Vec::new(), free_nodes: Vec::new(), root: None }
     }
-    /// Set the direction and strength of gravity in units per second per second
-    pub fn gravity_force(&mut self, x: f32, y: f32) -> &mut Self {
-        self.gravity_force.x = x;
-        self.gravity_force.y = y;
-        self
+
+    /// Adds an item with a box around it, returning the leaf it is stored in
+    fn insert(&mut self, bounds: Aabb, item: T) -> usize {
+        let leaf = self.allocate(TreeNode { bounds, parent: None, children: None, item: Some(item), height: 0 });
+        self.insert_leaf(leaf);
+        leaf
     }
-    /// Set the direction and strength of gravity in units per second per second with a [`Vector2`]
-    pub fn gravity_force_v(&mut self, v: Vector2) -> &mut Self {
-        self.gravity_force = v;
-        self
+
+    /// Removes a leaf, returning its item
+    fn remove(&mut self, leaf: usize) -> Option<T> {
+        self.remove_leaf(leaf);
+        self.free_nodes.push(leaf);
+        self.nodes[leaf].item.take()
     }
-    /// Set the horizontal strength of gravity in units per second per second
-    pub fn gravity_force_x(&mut self, x: f32) -> &mut Self {
-        self.gravity_force.x = x;
-        self
+
+    /// Moves a leaf to a new box
+    fn move_leaf(&mut self, leaf: usize, bounds: Aabb) {
+        self.remove_leaf(leaf);
+        self.nodes[leaf].bounds = bounds;
+        self.insert_leaf(leaf);
     }
-    /// Set the vertical strength of gravity in units per second per second
-    pub fn gravity_force_y(&mut self, y: f32) -> &mut Self {
-        self.gravity_force.y = y;
-        self
+
+    /// Calls `f` with every leaf whose box overlaps `region`, along with its item
+    fn query<'a>(&'a self, region: &Aabb, mut f: impl FnMut(usize, &'a T)) {
+        let mut stack: Vec<usize> = self.root.into_iter().collect();
+        while let Some(index) = stack.pop() {
+            let node = &self.nodes[index];
+            if !node.bounds.overlaps(region) {
+                continue;
+            }
+
+            match (node.children, &node.item) {
+                (Some(children), _) => stack.extend(children),
+                (None, Some(item)) => f(index, item),
+                (None, None) => {}
+            }
+        }
     }
-    /// Set the initial capacity for physics bodies
-    ///
-    /// Automatically sets `max_manifolds` to `n`<sup>2</sup>,
-    /// if it hasn't been set with [`Self::max_manifolds`] yet
-    pub fn max_bodies(&mut self, n: usize) -> &mut Self {
-        self.max_bodies = n;
-        if !self.is_max_manifolds_overridden {
-            self.max_manifolds = n*n;
+
+    fn allocate(&mut self, node: TreeNode<T>) -> usize {
+        if let Some(index) = self.free_nodes.pop() {
+            self.nodes[index] = node;
+            index
+        } else {
+            self.nodes.push(node);
+            self.nodes.len() - 1
         }
-        self
     }
-    /// Set the initial capacity for physics body collisions each step
-    pub fn max_manifolds(&mut self, n: usize) -> &mut Self {
-        self.max_manifolds = n;
-        self.is_max_manifolds_overridden = true;
-        self
+
+    /// Replaces `old_child` with `new_child` under `parent`, or at the root if there is no parent
+    fn replace_child(&mut self, parent: Option<usize>, old_child: usize, new_child: usize) {
+        match parent {
+            Some(parent) => {
+                let children = self.nodes[parent].children.as_mut().expect("parent should be a branch");
+                for child in children.iter_mut().filter(|child| **child == old_child) {
+                    *child = new_child;
+                }
+            }
+            None => self.root = Some(new_child),
+        }
     }
-    /// Construct [`Physac`] with the chosen configuration and put it inside a [`PhysacHandle`]
-    pub fn build(&mut self) -> PhysacHandle<Physac<MAX_VERTICES, CIRCLE_VERTICES>> {
-        let mut phys = Physac {
-            #[cfg(feature = "phys_thread")]
-            fixed_time: self.fixed_time,
-            collision_iterations: self.collision_iterations,
-            penetration_allowance: self.penetration_allowance,
-            penetration_correction: self.penetration_correction,
-            #[cfg(feature = "phys_thread")]
-            physics_thread: None,
-            base_time: Instant::now(),
-            start_time: 0.0,
-            delta_time: self.fixed_time/10.0 * 1000.0,
-            current_time: 0.0,
-            accumulator: 0.0,
-            steps_count: 0,
-            gravity_force: self.gravity_force,
-            bodies: Vec::with_capacity(self.max_bodies),
-            contacts: Vec::with_capacity(self.max_manifolds),
+
+    fn insert_leaf(&mut self, leaf: usize) {
+        let Some(root) = self.root else {
+            self.nodes[leaf].parent = None;
+            self.root = Some(leaf);
+            return;
         };
 
-        // Initialize high resolution timer
-        phys.init_timer();
+        // Descend towards the sibling that makes the tree grow the least
+        let leaf_bounds = self.nodes[leaf].bounds;
+        let mut sibling = root;
+        while let Some(children) = self.nodes[sibling].children {
+            let perimeter = self.nodes[sibling].bounds.perimeter();
+            let combined_perimeter = self.nodes[sibling].bounds.union(leaf_bounds).perimeter();
+
+            // Cost of pairing the leaf with this node, and the growth every node below it has to make up for
+            let cost = 2.0*combined_perimeter;
+            let inherited_cost = 2.0*(combined_perimeter - perimeter);
+
+            let child_costs = children.map(|child| {
+                let node = &self.nodes[child];
+                let grown_perimeter = node.bounds.union(leaf_bounds).perimeter();
+                if node.children.is_some() {
+                    grown_perimeter - node.bounds.perimeter() + inherited_cost
+                } else {
+                    grown_perimeter + inherited_cost
+                }
+            });
 
-        #[allow(unused_mut, reason = "used mutably if phys_thread is active")]
-        let mut ph = PhysacHandle::new(phys);
+            if cost < child_costs[0] && cost < child_costs[1] {
+                break;
+            }
 
-        #[cfg(feature = "phys_thread")] {
-            // NOTE: if defined, user will need to create a thread for PhysicsThread function manually
-            // Create physics thread using POSIXS thread libraries
-            let phys_clone = ph.phys.clone();
-            let is_physics_thread_enabled = ph.is_physics_thread_enabled.clone();
-            ph.borrowed_mut(|ph| ph.physics_thread = Some(thread::spawn(move || physics_loop(phys_clone, is_physics_thread_enabled))));
+            sibling = if child_costs[0] < child_costs[1] { children[0] } else { children[1] };
         }
 
-        debug_print!("[PHYSAC] physics module initialized successfully");
+        let old_parent = self.nodes[sibling].parent;
+        let new_parent = self.allocate(TreeNode {
+            bounds: leaf_bounds.union(self.nodes[sibling].bounds),
+            parent: old_parent,
+            children: Some([sibling, leaf]),
+            item: None,
+            height: self.nodes[sibling].height + 1,
+        });
+        self.nodes[sibling].parent = Some(new_parent);
+        self.nodes[leaf].parent = Some(new_parent);
+        self.replace_child(old_parent, sibling, new_parent);
 
-        ph
+        self.refit(Some(new_parent));
     }
-}
 
-impl<T> PhysacHandle<T> {
-    #[cfg(feature = "phys_thread")]
-    /// Returns true if physics thread is currently enabled
-    #[must_use]
-    pub fn is_physics_enabled(&self) -> bool {
-        self.is_physics_thread_enabled.load(Relaxed)
-    }
+    fn remove_leaf(&mut self, leaf: usize) {
+        let Some(parent) = self.nodes[leaf].parent else {
+            self.root = None;
+            return;
+        };
 
-    /// Borrow Physac from any other threads for the duration of the closure
-    #[cfg_attr(feature = "sync", doc = "\n # Panics\n\n This method may panic if another thread panicked while mutably borrowing Physac")]
-    pub fn borrowed<U, F>(&self, f: F) -> U
-    where
-        F: FnOnce(&T) -> U
-    {
-        let phys = &self.phys;
-        #[cfg(feature = "sync")]
-        let phys = phys.read().expect("thread poison recovery is not supported");
-        f(&*phys)
+        // The sibling takes the place of the parent
+        let [child_a, child_b] = self.nodes[parent].children.expect("parent should be a branch");
+        let sibling = if child_a == leaf { child_b } else { child_a };
+        let grandparent = self.nodes[parent].parent;
+        self.nodes[sibling].parent = grandparent;
+        self.replace_child(grandparent, parent, sibling);
+        self.nodes[parent].children = None;
+        self.free_nodes.push(parent);
+
+        self.refit(grandparent);
+    }
+
+    /// Rebalances and updates the boxes and heights of `index` and every node above it
+    fn refit(&mut self, mut index: Option<usize>) {
+        while let Some(current) = index {
+            let current = self.balance(current);
+            let [child_a, child_b] = self.nodes[current].children.expect("ancestors should be branches");
+            self.nodes[current].height = 1 + self.nodes[child_a].height.max(self.nodes[child_b].height);
+            self.nodes[current].bounds = self.nodes[child_a].bounds.union(self.nodes[child_b].bounds);
+            index = self.nodes[current].parent;
+        }
     }
 
-    /// Borrow Physac mutably from any other threads for the duration of the closure
-    #[cfg_attr(feature = "sync", doc = "\n # Panics\n\n This method may panic if another thread panicked while mutably borrowing Physac")]
-    pub fn borrowed_mut<U, F>(&mut self, f: F) -> U
-    where
-        F: FnOnce(&mut T) -> U
-    {
-        let phys = &mut self.phys;
-        #[cfg(feature = "sync")]
-        let mut phys = phys.write().expect("thread poison recovery is not supported");
-        f(&mut *phys)
-    }
+    /// Rotates the tree around a branch if one of its children is more than one level taller than the other,
+    /// returning the node that took its place
+    fn balance(&mut self, index: usize) -> usize {
+        let Some([child_a, child_b]) = self.nodes[index].children else {
+            return index;
+        };
 
-    /// Borrow Physac from any other threads until the guard goes out of scope
-    #[cfg_attr(feature = "sync", doc = "\n # Panics\n\n This method may panic if another thread panicked while mutably borrowing Physac")]
-    pub fn borrow(&self) -> PhysacHandleReadGuard<'_, T> {
-        let phys = &self.phys;
-        #[cfg(feature = "sync")]
-        let phys = phys.read().expect("thread poison recovery is not supported");
-        phys
+        let (height_a, height_b) = (self.nodes[child_a].height, self.nodes[child_b].height);
+        if height_b > height_a + 1 {
+            self.rotate(index, child_b, child_a)
+        } else if height_a > height_b + 1 {
+            self.rotate(index, child_a, child_b)
+        } else {
+            index
+        }
     }
 
-    /// Borrow Physac mutably from any other threads until the guard goes out of scope
-    #[cfg_attr(feature = "sync", doc = "\n # Panics\n\n This method may panic if another thread panicked while mutably borrowing Physac")]
-    pub fn borrow_mut(&mut self) -> PhysacHandleWriteGuard<'_, T> {
-        let phys = &mut self.phys;
-        #[cfg(feature = "sync")]
-        let phys = phys.write().expect("thread poison recovery is not supported");
-        phys
-    }
-}
+    /// Lifts `tall` into the place of its parent `index`, which becomes a child of `tall` along with `short` and the shorter child of `tall`
+    fn rotate(&mut self, index: usize, tall: usize, short: usize) -> usize {
+        let [grandchild_a, grandchild_b] = self.nodes[tall].children.expect("a taller child should be a branch");
+        let (kept, given) = if self.nodes[grandchild_a].height > self.nodes[grandchild_b].height {
+            (grandchild_a, grandchild_b)
+        } else {
+            (grandchild_b, grandchild_a)
+        };
 
-impl<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize> Physac<MAX_VERTICES, CIRCLE_VERTICES> {
-    /// Sets physics global gravity force
-    pub fn set_physics_gravity(&mut self, x: f32, y: f32) {
-        self.gravity_force.x = x;
-        self.gravity_force.y = y;
-    }
+        let parent = self.nodes[index].parent;
+        self.nodes[tall].parent = parent;
+        self.replace_child(parent, index, tall);
 
-    /// Creates a new physics body with the provided shape and generic parameters
-    ///
-    /// Returns [`None`] if there are no available IDs
-    pub fn try_create_physics_body(&mut self, pos: Vector2, shape: PhysicsShape<MAX_VERTICES, CIRCLE_VERTICES>, inertia: f32, mass: f32) -> Option<&Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>> {
-        if let Some(new_id) = self.find_available_body_index() {
-            // Initialize new body with generic values
-            let new_body = PhysicsBodyData {
-                id: new_id,
-                enabled: true,
-                position: pos,
-                velocity: Vector2::zero(),
-                force: Vector2::zero(),
-                angular_velocity: 0.0,
-                torque: 0.0,
-                orient: 0.0,
-                inertia,
-                inverse_inertia: if inertia != 0.0 { 1.0/inertia } else { 0.0 },
-                shape,
-                mass,
-                inverse_mass: if mass != 0.0 { 1.0/mass } else { 0.0 },
-                static_friction: 0.4,
-                dynamic_friction: 0.2,
-                restitution: 0.0,
-                use_gravity: true,
-                is_grounded: false,
-                freeze_orient: false,
-                is_simulating: true,
-            };
+        self.nodes[tall].children = Some([index, kept]);
+        self.nodes[index].parent = Some(tall);
+        self.nodes[index].children = Some([short, given]);
+        self.nodes[given].parent = Some(index);
 
-            // Add new body to bodies pointers array and update bodies count
-            self.bodies.push(Strong::new(new_body));
+        self.nodes[index].bounds = self.nodes[short].bounds.union(self.nodes[given].bounds);
+        self.nodes[index].height = 1 + self.nodes[short].height.max(self.nodes[given].height);
+        self.nodes[tall].bounds = self.nodes[index].bounds.union(self.nodes[kept].bounds);
+        self.nodes[tall].height = 1 + self.nodes[index].height.max(self.nodes[kept].height);
 
-            debug_print!("[PHYSAC] created physics body id {new_id}");
-            let [.., result] = &self.bodies[..] else { unreachable!("should have at least one element after pushing") };
-            Some(result)
-        } else {
-            debug_print!("[PHYSAC] new physics body creation failed because there isn't any available id to use");
-            None
-        }
+        tall
     }
+}
 
-    /// Creates a new physics body with the provided shape and generic parameters
-    ///
-    /// # Panics
-    ///
-    /// This method may panic if there are no available IDs
-    #[inline]
-    pub fn create_physics_body(&mut self, pos: Vector2, shape: PhysicsShape<MAX_VERTICES, CIRCLE_VERTICES>, inertia: f32, mass: f32) -> &Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>> {
-        self.try_create_physics_body(pos, shape, inertia, mass).unwrap()
+/// Returns the world space bounding box of a shape placed at `position` and rotated by `transform`
+fn shape_bounds<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize>(shape: &PhysicsShape<MAX_VERTICES, CIRCLE_VERTICES>, position: Vector2, transform: Mat2) -> Aabb {
+    match *shape {
+        PHYSICS_CIRCLE { radius } => Aabb { min: position, max: position }.expanded(radius),
+        PHYSICS_POLYGON { ref vertex_data, .. } => {
+            Aabb::from_points(vertex_data.positions().iter().map(|&p| position + transform.multiply_vector2(p)))
+                .expanded(vertex_data.radius)
+        }
+        PHYSICS_CAPSULE { radius, height } => {
+            let axis = transform.multiply_vector2(Vector2 { x: 0.0, y: capsule_half_length(radius, height) });
+            Aabb::from_points([position - axis, position + axis]).expanded(radius)
+        }
+        PHYSICS_COMPOUND { ref fixtures } => {
+            fixtures.iter()
+                .map(|fixture| {
+                    let (fixture_position, fixture_transform) = fixture_placement(fixture, position, transform);
+                    shape_bounds(&fixture.shape, fixture_position, fixture_transform)
+                })
+                .reduce(Aabb::union)
+                .unwrap_or(Aabb { min: position, max: position })
+        }
+        PHYSICS_CHAIN { ref vertices } => {
+            Aabb::from_points(vertices.iter().map(|&p| position + transform.multiply_vector2(p)))
+        }
+        PHYSICS_ELLIPSE { radius_x, radius_y, .. } => {
+            let extent = Vector2 {
+                x: ((radius_x*transform.m00).powi(2) + (radius_y*transform.m01).powi(2)).sqrt(),
+                y: ((radius_x*transform.m10).powi(2) + (radius_y*transform.m11).powi(2)).sqrt(),
+            };
+            Aabb { min: position - extent, max: position + extent }
+        }
+        PHYSICS_HEIGHTFIELD { ref heights, cell_width } => {
+            Aabb::from_points(heights.iter().enumerate().map(|(i, &height)| position + transform.multiply_vector2(Vector2 { x: i as Real*cell_width, y: height })))
+        }
     }
+}
 
-    /// Creates a new circle physics body with generic parameters
-    ///
-    /// Returns [`None`] if there are no available IDs
-    pub fn try_create_physics_body_circle(&mut self, pos: Vector2, radius: f32, density: f32) -> Option<&Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>> {
-        if let Some(new_id) = self.find_available_body_index() {
-            // Initialize new body with generic values
-            let mut new_body = PhysicsBodyData {
-                id: new_id,
-                enabled: true,
-                position: pos,
-                velocity: Vector2::zero(),
-                force: Vector2::zero(),
-                angular_velocity: 0.0,
-                torque: 0.0,
-                orient: 0.0,
-                shape: PHYSICS_CIRCLE { radius },
-
-                mass: (PI*f64::from(radius)*f64::from(radius)*f64::from(density)) as f32,
-                static_friction: 0.4,
-                dynamic_friction: 0.2,
-                restitution: 0.0,
-                use_gravity: true,
-                is_grounded: false,
-                freeze_orient: false,
-                is_simulating: true,
-                ..Default::default()
-            };
-            new_body.inverse_mass = if new_body.mass != 0.0 { 1.0/new_body.mass } else { 0.0 };
-            new_body.inertia = new_body.mass*radius*radius;
-            new_body.inverse_inertia = if new_body.inertia != 0.0 { 1.0/new_body.inertia } else { 0.0 };
+/// Returns a copy of a shape moved by `delta` along its local axes
+fn translated_shape<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize>(shape: &PhysicsShape<MAX_VERTICES, CIRCLE_VERTICES>, delta: Vector2) -> PhysicsShape<MAX_VERTICES, CIRCLE_VERTICES> {
+    match *shape {
+        PHYSICS_POLYGON { mut vertex_data, transform } => {
+            for position in vertex_data.positions_mut() {
+                *position += delta;
+            }
+            PHYSICS_POLYGON { vertex_data, transform }
+        }
+        PHYSICS_COMPOUND { ref fixtures } => PHYSICS_COMPOUND {
+            fixtures: fixtures.iter()
+                .map(|fixture| PhysicsFixture { offset: fixture.offset + delta, ..fixture.clone() })
+                .collect(),
+        },
+        PHYSICS_CHAIN { ref vertices } => PHYSICS_CHAIN { vertices: vertices.iter().map(|&p| p + delta).collect() },
+        // These shapes are always centered on the body, so they need to be a fixture to be moved
+        PHYSICS_CIRCLE { .. } | PHYSICS_CAPSULE { .. } | PHYSICS_ELLIPSE { .. } | PHYSICS_HEIGHTFIELD { .. } => PHYSICS_COMPOUND {
+            fixtures: vec![PhysicsFixture { offset: delta, ..PhysicsFixture::new(shape.clone()) }],
+        },
+    }
+}
 
-            // Add new body to bodies pointers array and update bodies count
-            self.bodies.push(Strong::new(new_body));
+/// Returns a copy of a shape scaled by `factor_x` and `factor_y` along its local axes
+fn scaled_shape<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize>(shape: &PhysicsShape<MAX_VERTICES, CIRCLE_VERTICES>, factor_x: Real, factor_y: Real) -> PhysicsShape<MAX_VERTICES, CIRCLE_VERTICES> {
+    let scale = |p: Vector2| Vector2 { x: p.x*factor_x, y: p.y*factor_y };
 
-            debug_print!("[PHYSAC] created polygon physics body id {new_id}");
-            let [.., result] = &self.bodies[..] else { unreachable!("should have at least one element after pushing") };
-            Some(result)
-        } else {
-            debug_print!("[PHYSAC] new physics body creation failed because there isn't any available id to use");
-            None
+    match *shape {
+        PHYSICS_CIRCLE { radius } => {
+            if (factor_x - factor_y).abs() <= Real::EPSILON*factor_x.max(factor_y) {
+                PHYSICS_CIRCLE { radius: radius*factor_x }
+            } else {
+                PhysicsShape::ellipse(radius*factor_x, radius*factor_y)
+            }
         }
+        PHYSICS_POLYGON { vertex_data, transform } => {
+            let mut vertex_data = vertex_data.with_corner_radius(vertex_data.radius*factor_x.min(factor_y));
+            for position in vertex_data.positions_mut() {
+                *position = scale(*position);
+            }
+            vertex_data.calculate_normals();
+            PHYSICS_POLYGON { vertex_data, transform }
+        }
+        PHYSICS_CAPSULE { radius, height } => {
+            let radius = radius*factor_x;
+            PHYSICS_CAPSULE { radius, height: (height*factor_y).max(2.0*radius) }
+        }
+        PHYSICS_COMPOUND { ref fixtures } => PHYSICS_COMPOUND {
+            fixtures: fixtures.iter()
+                .map(|fixture| PhysicsFixture {
+                    shape: scaled_shape(&fixture.shape, factor_x, factor_y),
+                    offset: scale(fixture.offset),
+                    rotation: fixture.rotation,
+                })
+                .collect(),
+        },
+        PHYSICS_CHAIN { ref vertices } => PHYSICS_CHAIN { vertices: vertices.iter().map(|&p| scale(p)).collect() },
+        PHYSICS_ELLIPSE { radius_x, radius_y, .. } => PhysicsShape::ellipse(radius_x*factor_x, radius_y*factor_y),
+        PHYSICS_HEIGHTFIELD { ref heights, cell_width } => PHYSICS_HEIGHTFIELD {
+            heights: heights.iter().map(|&height| height*factor_y).collect(),
+            cell_width: cell_width*factor_x,
+        },
     }
+}
 
-    /// Creates a new circle physics body with generic parameters
-    ///
-    /// # Panics
+/// Gravity scale of bodies saved before it could be changed
+#[cfg(feature = "serde")]
+const fn default_gravity_scale() -> Real {
+    1.0
+}
+
+/// A physics body
+///
+/// Create using [`Physac::create_physics_body_circle`], [`Physac::create_physics_body_rectangle`], or [`Physac::create_physics_body_polygon`]
+///
+/// Destroy (remove from the simulation) using [`Physac::destroy_physics_body`] or [`Physac::destroy_physics_bodies`]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[allow(
+    clippy::struct_excessive_bools,
+    reason = "these are flags, not states",
+)]
+pub struct PhysicsBodyData<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize> {
+    /// Reference unique identifier
+    pub id: u32,
+    /// Enabled dynamics state (collisions are calculated anyway)
+    pub enabled: bool,
+    /// Physics body shape pivot
+    pub position: Vector2,
+    /// Current linear velocity applied to position
+    pub velocity: Vector2,
+    /// Current linear force (reset to 0 every step)
+    pub force: Vector2,
+    /// Current angular velocity applied to orient
+    pub angular_velocity: Real,
+    /// Current angular force (reset to 0 every step)
+    pub torque: Real,
+    /// Rotation in radians
+    pub orient: Real,
+    /// Moment of inertia
+    pub inertia: Real,
+    /// Inverse value of inertia
+    pub inverse_inertia: Real,
+    /// Physics body mass
+    pub mass: Real,
+    /// Inverse value of mass
+    pub inverse_mass: Real,
+    /// Friction when the body has not movement (0 to 1)
+    pub static_friction: Real,
+    /// Friction when the body has movement (0 to 1)
+    pub dynamic_friction: Real,
+    /// Restitution coefficient of the body (0 to 1)
+    pub restitution: Real,
+    /// Apply gravity force to dynamics
+    pub use_gravity: bool,
+    /// How strongly gravity pulls the body, if it [uses gravity](Self::use_gravity) (1 by default)
     ///
-    /// This method may panic if there are no available IDs
-    #[inline]
-    pub fn create_physics_body_circle(&mut self, pos: Vector2, radius: f32, density: f32) -> &Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>> {
-        self.try_create_physics_body_circle(pos, radius, density).unwrap()
+    /// 0 makes the body float, a negative scale makes it rise, and a scale above 1 makes it heavier
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use physac::prelude::*;
+    /// let mut ph = init_physics::<24, 24>().build();
+    ///
+    /// let balloon = ph.borrow_mut()
+    ///     .create_physics_body_circle(Vector2 { x: 100.0, y: 100.0 }, 10.0, 1.0)
+    ///     .clone();
+    /// balloon.borrow_mut().gravity_scale = -0.5;
+    ///
+    /// for _ in 0..60 {
+    ///     ph.borrow_mut().update(1.0/60.0).unwrap();
+    /// }
+    ///
+    /// assert!(balloon.borrow().position.y < 100.0);
+    /// ```
+    #[cfg_attr(feature = "serde", serde(default = "default_gravity_scale"))]
+    pub gravity_scale: Real,
+    /// How quickly the body slows down by itself, such as bodies sliding over the floor of a top-down game (0 by default)
+    ///
+    /// The velocity is divided by `1 + linear_damping` every second, spread evenly over the steps, so a damping of 1 leaves about
+    /// a third of the speed after a second whatever the time step is
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use physac::prelude::*;
+    /// let mut ph = init_physics::<24, 24>().gravity_force(0.0, 0.0).build();
+    ///
+    /// let puck = ph.borrow_mut()
+    ///     .create_physics_body_circle(Vector2 { x: 100.0, y: 100.0 }, 10.0, 1.0)
+    ///     .clone();
+    /// puck.borrowed_mut(|puck| {
+    ///     puck.velocity = Vector2 { x: 0.2, y: 0.0 };
+    ///     puck.linear_damping = 1.0;
+    /// });
+    ///
+    /// for _ in 0..60 {
+    ///     ph.borrow_mut().update(1.0/60.0).unwrap();
+    /// }
+    ///
+    /// let speed = puck.borrow().velocity.x;
+    /// assert!(speed > 0.06 && speed < 0.09);
+    /// ```
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub linear_damping: Real,
+    /// How quickly the body stops spinning by itself, like [`linear_damping`](Self::linear_damping) (0 by default)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub angular_damping: Real,
+    /// Fastest speed (in pixels per millisecond) the body moves at, if any
+    ///
+    /// The velocity is clamped every step before the body is moved, so a violent collision can't fling it across the world in one step
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use physac::prelude::*;
+    /// let mut ph = init_physics::<24, 24>().gravity_force(0.0, 0.0).build();
+    ///
+    /// let ball = ph.borrow_mut()
+    ///     .create_physics_body_circle(Vector2 { x: 100.0, y: 100.0 }, 10.0, 1.0)
+    ///     .clone();
+    /// ball.borrowed_mut(|ball| {
+    ///     ball.velocity = Vector2 { x: 3.0, y: 4.0 };
+    ///     ball.max_linear_velocity = Some(1.0);
+    /// });
+    ///
+    /// ph.borrow_mut().update(1.0/60.0).unwrap();
+    ///
+    /// assert!((ball.borrow().velocity.length_sqr().sqrt() - 1.0).abs() < 1e-4);
+    /// ```
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub max_linear_velocity: Option<Real>,
+    /// Fastest angular velocity (in radians per millisecond, either way) the body spins at, if any
+    ///
+    /// See [`max_linear_velocity`](Self::max_linear_velocity)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub max_angular_velocity: Option<Real>,
+    /// How the friction of the body is mixed with the friction of bodies it touches, instead of the world's [`Physac::friction_combine`]
+    ///
+    /// If both bodies of a contact choose a mode, the one listed last in [`CombineMode`] is used
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub friction_combine: Option<CombineMode>,
+    /// How the restitution of the body is mixed with the restitution of bodies it touches, instead of the world's [`Physac::restitution_combine`]
+    ///
+    /// See [`friction_combine`](Self::friction_combine)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub restitution_combine: Option<CombineMode>,
+    /// Speed (in pixels per millisecond) the surface of the body moves along itself without the body moving, like a conveyor belt
+    ///
+    /// Friction drags touching bodies along with the surface. Positive speeds move the surface clockwise around the body on screen,
+    /// so bodies on top of it are carried right
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use physac::prelude::*;
+    /// let mut ph = init_physics::<24, 24>().build();
+    ///
+    /// let belt = ph.borrow_mut()
+    ///     .create_physics_body_rectangle(Vector2 { x: 200.0, y: 300.0 }, 400.0, 20.0, 1.0)
+    ///     .clone();
+    /// belt.borrowed_mut(|belt| {
+    ///     belt.enabled = false;
+    ///     belt.surface_velocity = 0.05;
+    /// });
+    /// let parcel = ph.borrow_mut()
+    ///     .create_physics_body_rectangle(Vector2 { x: 100.0, y: 270.0 }, 40.0, 40.0, 1.0)
+    ///     .clone();
+    ///
+    /// for _ in 0..120 {
+    ///     ph.borrow_mut().update(1.0/60.0).unwrap();
+    /// }
+    ///
+    /// // The parcel is carried along while the belt stays in place
+    /// assert!(parcel.borrow().position.x > 105.0);
+    /// assert_eq!(belt.borrow().position.x, 200.0);
+    /// ```
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub surface_velocity: Real,
+    /// Any value the game wants to keep with the body, such as the ID or index of the entity it belongs to (0 by default)
+    ///
+    /// The physics never reads it, so it can map bodies in events and queries back to game objects
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use physac::prelude::*;
+    /// let mut ph = init_physics::<24, 24>().build();
+    ///
+    /// let floor = ph.borrow_mut()
+    ///     .create_physics_body_rectangle(Vector2 { x: 200.0, y: 120.0 }, 400.0, 20.0, 1.0)
+    ///     .clone();
+    /// floor.borrow_mut().enabled = false;
+    ///
+    /// // Entity 42 is the player
+    /// let player = ph.borrow_mut()
+    ///     .create_physics_body_circle(Vector2 { x: 200.0, y: 100.0 }, 10.0, 1.0)
+    ///     .clone();
+    /// player.borrow_mut().user_data = 42;
+    ///
+    /// ph.borrow_mut().update(1.0/60.0).unwrap();
+    ///
+    /// let landed = ph.borrow_mut().drain_collision_events().any(|event| {
+    ///     matches!(event, CollisionEvent::Begin(contact) if [&contact.body_a, &contact.body_b].iter().any(|body| body.borrow().user_data == 42))
+    /// });
+    /// assert!(landed);
+    /// ```
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub user_data: u128,
+    /// Name given with [`PhysicsBodyData::set_name`], or empty if the body has none
+    #[cfg_attr(feature = "serde", serde(default))]
+    name: String,
+    /// Physics grounded on other body state
+    pub is_grounded: bool,
+    /// Physics rotation constraint
+    pub freeze_orient: bool,
+    /// Detects overlapping bodies without colliding with them (see [`Physac::drain_sensor_events`])
+    pub is_sensor: bool,
+    /// Bits of the categories the body belongs to (only the first category by default)
+    pub collision_category: u32,
+    /// Bits of the categories the body collides with (every category by default)
+    ///
+    /// Two bodies only collide if each one's category shares a bit with the other one's mask
+    pub collision_mask: u32,
+    /// Bodies that share a positive group always collide and bodies that share a negative group never do, regardless of their categories and masks
+    ///
+    /// A group of 0 (the default) is no group
+    pub collision_group: i32,
+    /// Lets the body fall asleep once it comes to rest, if sleeping is turned on with [`PhysacBuilder::sleep_steps`]
+    pub can_sleep: bool,
+    /// Moves the body by its velocity every step while it isn't [`enabled`](Self::enabled), like a moving platform
+    ///
+    /// Nothing else can push a kinematic body, and bodies resting on it are carried along by friction
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use physac::prelude::*;
+    /// let mut ph = init_physics::<24, 24>().build();
+    ///
+    /// let platform = ph.borrow_mut()
+    ///     .create_physics_body_rectangle(Vector2 { x: 200.0, y: 300.0 }, 200.0, 20.0, 1.0)
+    ///     .clone();
+    /// platform.borrowed_mut(|platform| {
+    ///     platform.enabled = false;
+    ///     platform.is_kinematic = true;
+    ///     platform.velocity = Vector2 { x: 0.05, y: 0.0 };
+    /// });
+    /// let crate_body = ph.borrow_mut()
+    ///     .create_physics_body_rectangle(Vector2 { x: 200.0, y: 270.0 }, 40.0, 40.0, 1.0)
+    ///     .clone();
+    ///
+    /// for _ in 0..120 {
+    ///     ph.borrow_mut().update(1.0/60.0).unwrap();
+    /// }
+    ///
+    /// // The crate rides along with the platform
+    /// assert!(crate_body.borrow().position.x > 205.0);
+    /// ```
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub is_kinematic: bool,
+    /// Range the solver keeps the orientation within
+    rotation_limits: Option<JointLimits>,
+    /// Skipped by the simulation until something disturbs it
+    is_sleeping: bool,
+    /// How many substeps in a row the body has been moving slower than the sleep thresholds
+    resting_steps: u32,
+    /// Position and orientation of the body before the last step ([`None`] until the body has been stepped)
+    previous_transform: Option<(Vector2, Real)>,
+    /// The impulses carried over from the last step by the contacts of the body no longer fit it, since it was moved or its velocity was set
+    #[cfg_attr(feature = "serde", serde(skip))]
+    has_stale_contacts: bool,
+    /// Position and orientation the last step left the body at, to notice when it is moved by hand ([`None`] until the body has been stepped)
+    #[cfg_attr(feature = "serde", serde(skip))]
+    settled_transform: Option<(Vector2, Real)>,
+    /// The shape of the body was changed since the last step, so the bodies resting on it have to wake up
+    #[cfg_attr(feature = "serde", serde(skip))]
+    has_new_shape: bool,
+    /// Physics body shape information (type, radius, vertices, normals)
+    pub shape: PhysicsShape<MAX_VERTICES, CIRCLE_VERTICES>,
+    /// Offset of the shape from the anchor point, before the body is rotated
+    shape_offset: Vector2,
+    /// The body exists in Physac
+    is_simulating: bool,
+}
+impl<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize> PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES> {
+    /// Construct a new physics body with default values
+    const fn new() -> Self {
+        Self {
+            id: 0,
+            enabled: false,
+            position: Vector2::zero(),
+            velocity: Vector2::zero(),
+            force: Vector2::zero(),
+            angular_velocity: 0.0,
+            torque: 0.0,
+            orient: 0.0,
+            inertia: 0.0,
+            inverse_inertia: 0.0,
+            mass: 0.0,
+            inverse_mass: 0.0,
+            static_friction: 0.0,
+            dynamic_friction: 0.0,
+            restitution: 0.0,
+            use_gravity: false,
+            gravity_scale: 1.0,
+            linear_damping: 0.0,
+            angular_damping: 0.0,
+            max_linear_velocity: None,
+            max_angular_velocity: None,
+            friction_combine: None,
+            restitution_combine: None,
+            surface_velocity: 0.0,
+            user_data: 0,
+            name: String::new(),
+            is_grounded: false,
+            freeze_orient: false,
+            is_sensor: false,
+            collision_category: 1,
+            collision_mask: u32::MAX,
+            collision_group: 0,
+            can_sleep: true,
+            is_kinematic: false,
+            has_stale_contacts: false,
+            settled_transform: None,
+            has_new_shape: false,
+            is_sleeping: false,
+            resting_steps: 0,
+            previous_transform: None,
+            rotation_limits: None,
+            shape: PhysicsShape::new(),
+            shape_offset: Vector2::zero(),
+            is_simulating: true,
+        }
+    }
+}
+impl<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize> Default for PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES> {
+    fn default() -> Self {
+        Self::new()
     }
+}
 
-    /// Creates a new rectangle physics body with generic parameters
+/// A lightweight reference to a physics body, which is checked by the world every time it is used
+///
+/// Unlike a [`Strong`] reference, a handle doesn't keep its body alive, and a handle to a destroyed body
+/// never refers to a new body that reuses the same ID. Handles can be copied, compared and stored anywhere (such as in ECS components)
+///
+/// Get with [`Physac::body_handle`] and look up with [`Physac::get_body`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct BodyHandle {
+    /// ID of the body
+    index: u32,
+    /// How many times the ID had been given to a body when it was given to this one
+    generation: u32,
+}
+impl BodyHandle {
+    /// Returns the ID of the body the handle refers to (see [`PhysicsBodyData::id`])
+    #[must_use]
+    pub const fn index(&self) -> u32 {
+        self.index
+    }
+}
+
+/// Identifies a contact point by the features of the shapes that produced it, so it can be matched between steps
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+struct ContactId {
+    /// Parts of the first and second shape the contact was found between (always 0 for single part shapes)
+    parts: [u32; 2],
+    /// Face of the reference shape the incident face was clipped against
+    reference_face: u32,
+    /// Face of the incident shape the contact lies on
+    incident_face: u32,
+    /// Which end of the incident face the contact came from (0 or 1), or which side plane of the reference face clipped it (2 or 3)
+    feature: u32,
+    /// Whether the reference face belongs to the second shape
+    flip: bool,
+}
+
+/// A physics collision
+#[derive(Debug, Clone)]
+struct PhysicsManifoldData<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize> {
+    /// Reference unique identifier
+    pub id: u32,
+    /// Manifold first physics body reference
+    pub body_a: Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>,
+    /// Manifold second physics body reference
+    pub body_b: Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>,
+    /// Depth of penetration from collision
+    pub penetration: Real,
+    /// Normal direction vector from 'a' to 'b'
+    pub normal: Vector2,
+    /// Points of contact during collision
+    pub contacts: [Vector2; 2],
+    /// Current collision number of contacts
+    pub contacts_count: u32,
+    /// Mixed restitution during collision
+    pub restitution: Real,
+    /// Mixed dynamic friction during collision
+    pub dynamic_friction: Real,
+    /// Mixed static friction during collision
+    pub static_friction: Real,
+    /// Total impulse applied along the normal while solving the collision
+    pub normal_impulse: Real,
+    /// Features that produced each contact point
+    pub contact_ids: [ContactId; 2],
+    /// Impulse accumulated along the normal at each contact point, when warm starting
+    pub normal_impulses: [Real; 2],
+    /// Impulse accumulated along the tangent at each contact point, when warm starting
+    pub tangent_impulses: [Real; 2],
+    /// Velocity each contact point should bounce back with, when warm starting
+    pub velocity_biases: [Real; 2],
+    /// Velocity the surface of the second body moves with relative to the surface of the first, from their surface velocities
+    pub surface_velocity: Vector2,
+}
+impl<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize> PhysicsManifoldData<MAX_VERTICES, CIRCLE_VERTICES> {
+    const fn new(body_a: Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>, body_b: Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>) -> Self {
+        Self {
+            id: 0,
+            body_a,
+            body_b,
+            penetration: 0.0,
+            normal: Vector2::zero(),
+            contacts: [Vector2::zero(); 2],
+            contacts_count: 0,
+            restitution: 0.0,
+            dynamic_friction: 0.0,
+            static_friction: 0.0,
+            normal_impulse: 0.0,
+            contact_ids: [ContactId { parts: [0; 2], reference_face: 0, incident_face: 0, feature: 0, flip: false }; 2],
+            normal_impulses: [0.0; 2],
+            tangent_impulses: [0.0; 2],
+            velocity_biases: [0.0; 2],
+            surface_velocity: Vector2::zero(),
+        }
+    }
+}
+
+/// The constraint a joint enforces between its physics bodies
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum PhysicsJointKind {
+    /// Keeps the anchors of both bodies a fixed distance apart
+    Distance {
+        /// Distance the anchors are held apart
+        length: Real,
+        /// How rigidly the length is enforced (0 to 1, where 1 behaves like a solid rod)
+        stiffness: Real,
+    },
+    /// Keeps the anchors of both bodies from getting further apart than a maximum length, leaving them free while closer
+    Rope {
+        /// Distance the anchors can get apart before the rope is taut
+        max_length: Real,
+    },
+    /// Hangs both bodies from a rope running over two fixed points in the world, so that the length on the first side plus `ratio` times the length on the second side stays constant
+    Pulley {
+        /// Point in the world the rope runs over on the side of the first body
+        ground_anchor_a: Vector2,
+        /// Point in the world the rope runs over on the side of the second body
+        ground_anchor_b: Vector2,
+        /// Total length of the rope, with the second side weighted by the ratio
+        length: Real,
+        /// How much the second side counts towards the length compared to the first
+        ratio: Real,
+    },
+    /// Pulls or pushes the anchors of both bodies towards a rest length like a damped spring
+    Spring {
+        /// Distance the spring relaxes at
+        rest_length: Real,
+        /// Force the spring applies per unit it is stretched or compressed
+        stiffness: Real,
+        /// Force the spring applies per unit of speed it is stretched or compressed at
+        damping: Real,
+    },
+    /// Pins the anchors of both bodies together, letting them rotate around it
+    Revolute {
+        /// Angle of the second body relative to the first when the joint angle is zero
+        reference_angle: Real,
+        /// Range the joint angle is kept within
+        limits: Option<JointLimits>,
+        /// Motor driving the joint angle
+        motor: Option<JointMotor>,
+    },
+    /// Lets the second body slide along an axis of the first without rotating relative to it
+    Prismatic {
+        /// Direction the bodies slide along, relative to the first body before it is rotated
+        local_axis: Vector2,
+        /// Angle of the second body relative to the first that is maintained
+        reference_angle: Real,
+        /// Range the joint translation is kept within
+        limits: Option<JointLimits>,
+        /// Motor driving the joint translation
+        motor: Option<JointMotor>,
+    },
+    /// Locks the position and angle of the second body relative to the first
+    Weld {
+        /// Angle of the second body relative to the first that is maintained
+        reference_angle: Real,
+    },
+    /// Couples the angles of two revolute joints, so that the angle of the first plus `ratio` times the angle of the second stays constant
+    Gear {
+        /// How much the second joint angle counts towards the constant compared to the first
+        ratio: Real,
+        /// Value the joint angles add up to
+        reference: Real,
+    },
+    /// Pulls the anchor of a single body towards a target point in the world, like dragging it with the mouse
+    Target {
+        /// Maximum force used to pull the body
+        max_force: Real,
+        /// How many times per second the body would oscillate around the target if it wasn't damped
+        frequency: Real,
+        /// How much the oscillation is damped (0 to 1, where 1 doesn't overshoot at all)
+        damping_ratio: Real,
+    },
+}
+
+/// An event emitted when a joint breaks because it had to hold more than its [`PhysicsJointData::break_force`] or [`PhysicsJointData::break_torque`]
+///
+/// Collected with [`Physac::drain_joint_events`]
+#[derive(Debug, Clone)]
+pub struct JointBroken<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize> {
+    /// The joint that broke, which has been destroyed
+    pub joint: Strong<PhysicsJointData<MAX_VERTICES, CIRCLE_VERTICES>>,
+    /// Force the joint was holding when it broke
+    pub force: Real,
+    /// Torque the joint was holding when it broke
+    pub torque: Real,
+}
+
+/// An event emitted when a body starts or stops overlapping a sensor body (one with [`PhysicsBodyData::is_sensor`] set)
+///
+/// Collected with [`Physac::drain_sensor_events`]
+#[derive(Debug, Clone)]
+pub enum SensorEvent<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize> {
+    /// A body started overlapping a sensor
+    Begin {
+        /// The sensor body
+        sensor: Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>,
+        /// The body that entered the sensor
+        visitor: Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>,
+    },
+    /// A body stopped overlapping a sensor, either by leaving it or because one of them was destroyed
+    End {
+        /// The sensor body
+        sensor: Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>,
+        /// The body that left the sensor
+        visitor: Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>,
+    },
+}
+
+/// Where and how two bodies touched during a step, as reported by a [`CollisionEvent`]
+#[derive(Debug, Clone)]
+pub struct CollisionContact<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize> {
+    /// First body of the pair
+    pub body_a: Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>,
+    /// Second body of the pair
+    pub body_b: Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>,
+    /// Point where the bodies touch in world space (the middle of the contact points if there are two)
+    pub point: Vector2,
+    /// Direction from the first body to the second
+    pub normal: Vector2,
+    /// Total impulse the solver applied along the normal to push the bodies apart during the step, a measure of how hard they hit
+    pub normal_impulse: Real,
+}
+
+/// An event emitted when two bodies start touching, keep touching, or stop touching
+///
+/// Collected with [`Physac::drain_collision_events`]
+#[derive(Debug, Clone)]
+pub enum CollisionEvent<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize> {
+    /// The bodies touched during a step after not touching during the one before
+    Begin(CollisionContact<MAX_VERTICES, CIRCLE_VERTICES>),
+    /// The bodies touched during a step and the one before
+    Persist(CollisionContact<MAX_VERTICES, CIRCLE_VERTICES>),
+    /// The bodies stopped touching, either by moving apart or because one of them was destroyed
     ///
-    /// Returns [`None`] if there are no available IDs
-    pub fn try_create_physics_body_rectangle(&mut self, pos: Vector2, width: f32, height: f32, density: f32) -> Option<&Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>> {
-        if let Some(new_id) = self.find_available_body_index() {
-            // Initialize new body with generic values
-            let mut vertex_data = PolygonData::create_rectangle_polygon(pos, Vector2 { x: width, y: height });
+    /// The contact is the last one from while they were touching
+    End(CollisionContact<MAX_VERTICES, CIRCLE_VERTICES>),
+}
 
-            // Calculate centroid and moment of inertia
-            let mut center = Vector2 { x: 0.0, y: 0.0 };
-            let mut area = 0.0;
-            let mut inertia = 0.0;
+/// How the friction or restitution of two touching bodies is mixed into the value their contact is solved with
+///
+/// Set for the whole world with [`PhysacBuilder::friction_combine`] and [`PhysacBuilder::restitution_combine`],
+/// or for single bodies with [`PhysicsBodyData::friction_combine`] and [`PhysicsBodyData::restitution_combine`].
+/// When the bodies of a contact choose different modes, the one listed last here is used
+///
+/// # Examples
+///
+/// ```
+/// # use physac::prelude::*;
+/// // A bouncy ball on concrete barely bounces, unless the concrete is a trampoline
+/// assert_eq!(CombineMode::Min.combine(0.9, 0.1), 0.1);
+/// assert_eq!(CombineMode::Max.combine(0.9, 1.0), 1.0);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum CombineMode {
+    /// The square root of the product of both values
+    #[default]
+    GeometricMean,
+    /// Halfway between both values
+    Average,
+    /// The lower of both values
+    Min,
+    /// The product of both values
+    Multiply,
+    /// The higher of both values
+    Max,
+}
+impl CombineMode {
+    /// Mixes the values of two bodies
+    #[must_use]
+    pub fn combine(self, a: Real, b: Real) -> Real {
+        match self {
+            Self::GeometricMean => (a*b).sqrt(),
+            Self::Average => a.midpoint(b),
+            Self::Min => a.min(b),
+            Self::Multiply => a*b,
+            Self::Max => a.max(b),
+        }
+    }
+
+    /// Returns the mode a contact is solved with, given the mode of the world and the modes chosen by both bodies
+    fn resolve(world: Self, a: Option<Self>, b: Option<Self>) -> Self {
+        a.max(b).unwrap_or(world)
+    }
+}
+
+/// The surface and density of a physics body, which can be given to a body with [`PhysicsBodyData::set_material`] or [`BodyBuilder::material`]
+///
+/// The default material is the one new bodies are created with
+///
+/// # Examples
+///
+/// ```
+/// # use physac::prelude::*;
+/// let mut ph = init_physics::<24, 24>().build();
+///
+/// let rubber = Material { restitution: 0.9, ..Default::default() };
+/// let trampoline = Material { restitution: 1.0, restitution_combine: Some(CombineMode::Max), ..Default::default() };
+///
+/// let ball = ph.borrow_mut().body()
+///     .circle(10.0)
+///     .material(&rubber)
+///     .build()
+///     .clone();
+/// let floor = ph.borrow_mut().body()
+///     .rectangle(400.0, 20.0)
+///     .at(Vector2 { x: 200.0, y: 300.0 })
+///     .material(&trampoline)
+///     .fixed(true)
+///     .build()
+///     .clone();
+///
+/// assert_eq!(ball.borrow().restitution, 0.9);
+/// assert_eq!(floor.borrow().material(), trampoline);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Material {
+    /// Friction when the body has not movement (0 to 1)
+    pub static_friction: Real,
+    /// Friction when the body has movement (0 to 1)
+    pub dynamic_friction: Real,
+    /// Restitution coefficient of the body (0 to 1)
+    pub restitution: Real,
+    /// Mass of the body per unit of area
+    pub density: Real,
+    /// How the friction is mixed with the friction of other bodies (see [`PhysicsBodyData::friction_combine`])
+    pub friction_combine: Option<CombineMode>,
+    /// How the restitution is mixed with the restitution of other bodies (see [`PhysicsBodyData::restitution_combine`])
+    pub restitution_combine: Option<CombineMode>,
+}
+impl Default for Material {
+    fn default() -> Self {
+        Self {
+            static_friction: 0.4,
+            dynamic_friction: 0.2,
+            restitution: 0.0,
+            density: 1.0,
+            friction_combine: None,
+            restitution_combine: None,
+        }
+    }
+}
+
+/// A contact between two bodies, as returned by [`Physac::contact_iter`] or passed to the callback set with [`Physac::set_pre_solve`]
+#[derive(Debug, Clone)]
+pub struct ContactInfo<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize> {
+    /// First body of the pair
+    pub body_a: Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>,
+    /// Second body of the pair
+    pub body_b: Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>,
+    /// Direction from the first body to the second
+    pub normal: Vector2,
+    /// How deep the bodies overlap
+    pub penetration: Real,
+    /// Restitution the contact is solved with, mixed from both bodies
+    pub restitution: Real,
+    /// Static friction the contact is solved with, mixed from both bodies
+    pub static_friction: Real,
+    /// Dynamic friction the contact is solved with, mixed from both bodies
+    pub dynamic_friction: Real,
+    contacts: [Vector2; 2],
+    contacts_count: usize,
+}
+impl<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize> From<&PhysicsManifoldData<MAX_VERTICES, CIRCLE_VERTICES>> for ContactInfo<MAX_VERTICES, CIRCLE_VERTICES> {
+    fn from(manifold: &PhysicsManifoldData<MAX_VERTICES, CIRCLE_VERTICES>) -> Self {
+        Self {
+            body_a: manifold.body_a.clone(),
+            body_b: manifold.body_b.clone(),
+            normal: manifold.normal,
+            penetration: manifold.penetration,
+            restitution: manifold.restitution,
+            static_friction: manifold.static_friction,
+            dynamic_friction: manifold.dynamic_friction,
+            contacts: manifold.contacts,
+            contacts_count: manifold.contacts_count as usize,
+        }
+    }
+}
+impl<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize> ContactInfo<MAX_VERTICES, CIRCLE_VERTICES> {
+    /// Returns the points where the bodies touch in world space (one or two)
+    #[must_use]
+    pub fn points(&self) -> &[Vector2] {
+        &self.contacts[..self.contacts_count]
+    }
+}
+
+/// What to do with a contact, as returned by the callback set with [`Physac::set_pre_solve`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ContactResponse {
+    /// Solve the contact as usual
+    Accept,
+    /// Let the bodies pass through each other for this step
+    Cancel,
+    /// Solve the contact with different material properties
+    Modify {
+        /// Restitution to solve the contact with
+        restitution: Real,
+        /// Static friction to solve the contact with
+        static_friction: Real,
+        /// Dynamic friction to solve the contact with
+        dynamic_friction: Real,
+    },
+}
+
+/// A callback that decides what to do with each contact before it is solved
+trait PreSolveCallback<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize>: ThreadSafe {
+    fn call(&mut self, contact: &ContactInfo<MAX_VERTICES, CIRCLE_VERTICES>) -> ContactResponse;
+}
+impl<F, const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize> PreSolveCallback<MAX_VERTICES, CIRCLE_VERTICES> for F
+where
+    F: FnMut(&ContactInfo<MAX_VERTICES, CIRCLE_VERTICES>) -> ContactResponse + ThreadSafe
+{
+    fn call(&mut self, contact: &ContactInfo<MAX_VERTICES, CIRCLE_VERTICES>) -> ContactResponse {
+        self(contact)
+    }
+}
+
+/// A callback that is told about each physics body as it is destroyed
+trait BodyDestroyedCallback<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize>: ThreadSafe {
+    fn call(&mut self, body: &PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>);
+}
+impl<F, const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize> BodyDestroyedCallback<MAX_VERTICES, CIRCLE_VERTICES> for F
+where
+    F: FnMut(&PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>) + ThreadSafe
+{
+    fn call(&mut self, body: &PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>) {
+        self(body);
+    }
+}
+
+/// How gravity pulls on the bodies, particles and fluids of a world (see [`PhysacBuilder::gravity_mode`] and [`Physac::set_gravity_mode`])
+///
+/// Gravity accelerates everything the same however heavy it is, scaled by [`PhysicsBodyData::gravity_scale`].
+/// It is found at the center of mass of each body, so bodies on opposite sides of a planet fall opposite ways
+///
+/// # Examples
+///
+/// ```
+/// # use physac::prelude::*;
+/// // A small planet, with space outside of it
+/// fn planet_gravity(point: Vector2) -> Vector2 {
+///     let offset = Vector2 { x: 400.0, y: 300.0 } - point;
+///     let distance = offset.length_sqr().sqrt();
+///     if distance > 500.0 || distance == 0.0 {
+///         Vector2::zero()
+///     } else {
+///         offset*(9.81/distance)
+///     }
+/// }
+///
+/// let mut ph = init_physics::<24, 24>()
+///     .gravity_mode(GravityMode::Custom(planet_gravity))
+///     .build();
+///
+/// // Spin the level around
+/// ph.borrow_mut().set_gravity_mode(GravityMode::Directional(Vector2 { x: -9.81, y: 0.0 }));
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub enum GravityMode {
+    /// Pulls the same way everywhere, in units per second per second
+    Directional(Vector2),
+    /// Pulls toward `center` from every direction, like a planet, or pushes away from it with a negative strength
+    Radial {
+        /// Point everything falls toward
+        center: Vector2,
+        /// Acceleration toward the center in units per second per second, which is the same at any distance
+        strength: Real,
+    },
+    /// Pulls with the acceleration returned for each point, in units per second per second
+    Custom(fn(Vector2) -> Vector2),
+}
+impl GravityMode {
+    /// Returns the acceleration of gravity at `point`
+    #[must_use]
+    pub fn acceleration_at(&self, point: Vector2) -> Vector2 {
+        match *self {
+            Self::Directional(acceleration) => acceleration,
+            Self::Radial { center, strength } => {
+                let mut direction = center - point;
+                math_normalize(&mut direction);
+                direction*strength
+            },
+            Self::Custom(acceleration_at) => acceleration_at(point),
+        }
+    }
+}
+
+/// The gravity of a [`WorldState`], which leaves out the function of [`GravityMode::Custom`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+enum GravityState {
+    Directional(Vector2),
+    Radial { center: Vector2, strength: Real },
+    /// Custom gravity, which the world keeps its own gravity for when the state is loaded
+    Custom,
+}
+impl From<GravityMode> for GravityState {
+    fn from(mode: GravityMode) -> Self {
+        match mode {
+            GravityMode::Directional(acceleration) => Self::Directional(acceleration),
+            GravityMode::Radial { center, strength } => Self::Radial { center, strength },
+            GravityMode::Custom(_) => Self::Custom,
+        }
+    }
+}
+
+/// What a [`ForceField`] does to the bodies inside of it
+///
+/// Forces are in the same units as [`PhysicsBodyData::add_force`], so heavier bodies are pushed less
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ForceFieldKind {
+    /// Pushes every body the same way, like wind
+    Directional {
+        /// Force applied to each body
+        force: Vector2,
+    },
+    /// Pulls bodies toward `center`, like a magnet, or pushes them away from it with a negative strength
+    Radial {
+        /// Point the bodies are pulled toward
+        center: Vector2,
+        /// Force applied to each body
+        strength: Real,
+    },
+    /// Swirls bodies around `center`, clockwise on screen with a positive strength
+    Vortex {
+        /// Point the bodies swirl around
+        center: Vector2,
+        /// Force applied to each body
+        strength: Real,
+    },
+}
+impl ForceFieldKind {
+    /// Returns the force applied to a body whose center of mass is at `point`
+    fn force_at(&self, point: Vector2) -> Vector2 {
+        match *self {
+            Self::Directional { force } => force,
+            Self::Radial { center, strength } => {
+                let mut direction = center - point;
+                math_normalize(&mut direction);
+                direction*strength
+            },
+            Self::Vortex { center, strength } => {
+                let mut direction = point - center;
+                math_normalize(&mut direction);
+                Vector2 { x: -direction.y, y: direction.x }*strength
+            },
+        }
+    }
+}
+
+/// The area a [`ForceField`] pushes the bodies overlapping it in, or a [`BuoyancyZone`] is filled with fluid
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ForceFieldRegion {
+    /// The box from `min` (top left) to `max` (bottom right), which bodies are in when their bounding boxes overlap it
+    Aabb {
+        /// Top left corner
+        min: Vector2,
+        /// Bottom right corner
+        max: Vector2,
+    },
+    /// A circle, which bodies are in when their shapes overlap it
+    Circle {
+        /// Center of the circle
+        center: Vector2,
+        /// Radius of the circle
+        radius: Real,
+    },
+    /// A convex polygon in world space, which bodies are in when their shapes overlap it (see [`Physac::overlap_polygon`])
+    Polygon {
+        /// Vertices of the polygon, in either winding order
+        vertices: Vec<Vector2>,
+    },
+}
+
+/// A force applied to the bodies in a region every step, such as a wind zone or a magnet
+///
+/// Add to a world with [`Physac::add_force_field`]. Like other forces, force fields don't push sleeping, static or disabled bodies
+///
+/// # Examples
+///
+/// ```
+/// # use physac::prelude::*;
+/// let mut ph = init_physics::<24, 24>().gravity_force(0.0, 0.0).build();
+///
+/// let leaf = ph.borrow_mut()
+///     .create_physics_body_circle(Vector2 { x: 100.0, y: 100.0 }, 5.0, 1.0)
+///     .clone();
+/// let rock = ph.borrow_mut()
+///     .create_physics_body_circle(Vector2 { x: 400.0, y: 100.0 }, 5.0, 1.0)
+///     .clone();
+///
+/// // Wind blows to the right, but only on the left side of the world
+/// ph.borrow_mut().add_force_field(ForceField {
+///     region: Some(ForceFieldRegion::Aabb { min: Vector2 { x: 0.0, y: 0.0 }, max: Vector2 { x: 200.0, y: 200.0 } }),
+///     ..ForceField::new(ForceFieldKind::Directional { force: Vector2 { x: 0.01, y: 0.0 } })
+/// });
+///
+/// for _ in 0..30 {
+///     ph.borrow_mut().update(1.0/60.0).unwrap();
+/// }
+///
+/// assert!(leaf.borrow().position.x > 100.0);
+/// assert_eq!(rock.borrow().position.x, 400.0);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ForceField {
+    /// What the field does to the bodies inside of it
+    pub kind: ForceFieldKind,
+    /// Where the field pushes bodies, or [`None`] for the whole world
+    pub region: Option<ForceFieldRegion>,
+    /// Bits of the categories of the bodies the field pushes (every category by default, see [`PhysicsBodyData::collision_category`])
+    pub mask: u32,
+}
+impl ForceField {
+    /// Creates a force field that pushes every body in the world
+    #[must_use]
+    pub const fn new(kind: ForceFieldKind) -> Self {
+        Self { kind, region: None, mask: u32::MAX }
+    }
+}
+impl ForceFieldRegion {
+    /// Returns the outline of the region as a convex polygon
+    fn outline(&self) -> Vec<Vector2> {
+        const CIRCLE_SEGMENTS: usize = 32;
+
+        match self {
+            Self::Aabb { min, max } => vec![*min, Vector2 { x: max.x, y: min.y }, *max, Vector2 { x: min.x, y: max.y }],
+            Self::Circle { center, radius } => (0..CIRCLE_SEGMENTS)
+                .map(|i| {
+                    let angle = 2.0*PI as Real*i as Real/CIRCLE_SEGMENTS as Real;
+                    *center + Vector2 { x: math_cos(angle), y: math_sin(angle) }*(*radius)
+                })
+                .collect(),
+            Self::Polygon { vertices } => vertices.clone(),
+        }
+    }
+}
+
+/// A region filled with fluid, such as water, which floats the bodies in it and slows them down
+///
+/// Bodies with a lower density than the fluid float, and bodies with a higher density sink.
+/// The push of the fluid and its drag grow with how much of each body is submerged, so floating bodies bob and tilt upright.
+/// Add to a world with [`Physac::add_buoyancy_zone`]
+///
+/// # Examples
+///
+/// ```
+/// # use physac::prelude::*;
+/// let mut ph = init_physics::<24, 24>().build();
+///
+/// // A pool of water 200 units deep
+/// ph.borrow_mut().add_buoyancy_zone(BuoyancyZone::new(
+///     ForceFieldRegion::Aabb { min: Vector2 { x: 0.0, y: 300.0 }, max: Vector2 { x: 400.0, y: 500.0 } },
+///     1.0,
+/// ));
+///
+/// let cork = ph.borrow_mut().body()
+///     .rectangle(40.0, 20.0)
+///     .density(0.5)
+///     .at(Vector2 { x: 100.0, y: 350.0 })
+///     .build()
+///     .clone();
+/// let stone = ph.borrow_mut().body()
+///     .rectangle(20.0, 20.0)
+///     .density(3.0)
+///     .at(Vector2 { x: 300.0, y: 350.0 })
+///     .build()
+///     .clone();
+///
+/// for _ in 0..600 {
+///     ph.borrow_mut().update(1.0/60.0).unwrap();
+/// }
+///
+/// // The cork floats half submerged at the surface, and the stone sinks
+/// assert!((cork.borrow().position.y - 300.0).abs() < 2.0);
+/// assert!(stone.borrow().position.y > 400.0);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct BuoyancyZone {
+    /// Area filled with the fluid, whose top is the surface for regions shaped like a box
+    pub region: ForceFieldRegion,
+    /// Mass of the fluid per unit of area, in the same units as the density of bodies
+    pub density: Real,
+    /// How quickly bodies slow down in the fluid, like [`PhysicsBodyData::linear_damping`] for fully submerged bodies (5 by default)
+    pub linear_drag: Real,
+    /// How quickly bodies stop spinning in the fluid, like [`PhysicsBodyData::angular_damping`] for fully submerged bodies (5 by default)
+    pub angular_drag: Real,
+    /// Velocity (in pixels per millisecond) of the current, which drag pulls submerged bodies along with
+    pub flow: Vector2,
+    /// Bits of the categories of the bodies the fluid floats (every category by default, see [`PhysicsBodyData::collision_category`])
+    pub mask: u32,
+}
+impl BuoyancyZone {
+    /// Creates a region of still fluid with a density
+    #[must_use]
+    pub const fn new(region: ForceFieldRegion, density: Real) -> Self {
+        Self {
+            region,
+            density,
+            linear_drag: 5.0,
+            angular_drag: 5.0,
+            flow: Vector2::zero(),
+            mask: u32::MAX,
+        }
+    }
+}
+
+/// A point simulated with Verlet integration, much lighter than a body, for decorations like cloth and ropes
+///
+/// Particles fall with gravity, are held together by [`ParticleConstraint`]s and are pushed out of the bodies they touch,
+/// so that they can rest on bodies and drape over them. Bodies aren't pushed back by particles.
+/// Add to a world with [`Physac::add_particle`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Particle {
+    /// Position of the particle in the world
+    pub position: Vector2,
+    /// Position of the particle before the last substep, which the particle keeps moving away from (move both to teleport it without changing its velocity)
+    pub previous_position: Vector2,
+    /// Radius of the circle the particle collides with bodies as
+    pub radius: Real,
+    /// Multiplier of the world gravity on the particle (1 by default)
+    pub gravity_scale: Real,
+    /// How quickly the particle slows down, like [`PhysicsBodyData::linear_damping`] (0 by default)
+    pub damping: Real,
+    /// How much of its sliding along a body the particle loses every substep it touches it (0 to 1, 0.5 by default)
+    pub friction: Real,
+    /// Bits of the categories of the bodies the particle collides with (every category by default, see [`PhysicsBodyData::collision_category`])
+    pub mask: u32,
+}
+impl Particle {
+    /// Creates a particle at rest
+    #[must_use]
+    pub const fn new(position: Vector2, radius: Real) -> Self {
+        Self {
+            position,
+            previous_position: position,
+            radius,
+            gravity_scale: 1.0,
+            damping: 0.0,
+            friction: 0.5,
+            mask: u32::MAX,
+        }
+    }
+}
+
+/// A constraint on particles, solved every substep after they move
+///
+/// Add to a world with [`Physac::add_particle_constraint`]. Constraints are removed along with their particles or body
+#[derive(Debug, Clone)]
+pub enum ParticleConstraint<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize> {
+    /// Keeps two particles a distance apart
+    Distance {
+        /// ID of the first particle
+        particle_a: u32,
+        /// ID of the second particle
+        particle_b: u32,
+        /// Distance the particles are held apart
+        length: Real,
+        /// How much of the error in the distance is corrected each iteration (0 to 1, where 1 is rigid)
+        stiffness: Real,
+    },
+    /// Holds a particle at a point in the world
+    Pin {
+        /// ID of the particle
+        particle: u32,
+        /// Point in the world the particle is held at
+        point: Vector2,
+    },
+    /// Holds a particle at a point on a body, carrying it along as the body moves
+    BodyPin {
+        /// ID of the particle
+        particle: u32,
+        /// Body the particle is held to
+        body: Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>,
+        /// Point on the body the particle is held at, relative to the body
+        local_anchor: Vector2,
+    },
+}
+impl<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize> ParticleConstraint<MAX_VERTICES, CIRCLE_VERTICES> {
+    /// Returns the IDs of the particles the constraint is on
+    fn particles(&self) -> impl Iterator<Item = u32> {
+        let (first, second) = match *self {
+            Self::Distance { particle_a, particle_b, .. } => (particle_a, Some(particle_b)),
+            Self::Pin { particle, .. } | Self::BodyPin { particle, .. } => (particle, None),
+        };
+        std::iter::once(first).chain(second)
+    }
+}
+
+/// A grid of particles linked to their neighbors by distance constraints, created with [`Physac::create_cloth`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cloth {
+    /// IDs of the particles, row by row from the top left
+    pub particles: Vec<u32>,
+    /// Number of particles in each row
+    pub columns: usize,
+    /// Number of rows of particles
+    pub rows: usize,
+}
+impl Cloth {
+    /// Returns the ID of the particle in a column and row, counted from the top left
+    ///
+    /// # Panics
+    ///
+    /// This method panics if the column or row is out of bounds
+    #[must_use]
+    pub fn particle(&self, column: usize, row: usize) -> u32 {
+        assert!(column < self.columns && row < self.rows, "cloth particle out of bounds");
+        self.particles[row*self.columns + column]
+    }
+}
+
+/// A particle of a [`Fluid`]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct FluidParticle {
+    /// Position of the particle in the world
+    pub position: Vector2,
+    /// Velocity of the particle, in pixels per millisecond like [`PhysicsBodyData::velocity`]
+    pub velocity: Vector2,
+}
+
+/// A liquid made of particles that push apart when crowded, like water splashing around and pooling in containers (experimental)
+///
+/// Particles find their neighbors through a grid of cells as large as the interaction radius, so a few thousand of them can be simulated.
+/// They flow around bodies without pushing them, so combine with a [`BuoyancyZone`] to float bodies in a pool.
+/// Add to a world with [`Physac::add_fluid`]
+///
+/// # Examples
+///
+/// ```
+/// # use physac::prelude::*;
+/// let mut ph = init_physics::<24, 24>().build();
+///
+/// // A cup to pour the water into
+/// for (x, y, width, height) in [(200.0, 300.0, 200.0, 20.0), (110.0, 240.0, 20.0, 100.0), (290.0, 240.0, 20.0, 100.0)] {
+///     let wall = ph.borrow_mut().create_physics_body_rectangle(Vector2 { x, y }, width, height, 1.0).clone();
+///     wall.borrow_mut().enabled = false;
+/// }
+///
+/// let mut water = Fluid::new(16.0);
+/// water.fill(Vector2 { x: 150.0, y: 100.0 }, Vector2 { x: 250.0, y: 150.0 });
+/// let water = ph.borrow_mut().add_fluid(water);
+///
+/// for _ in 0..60 {
+///     ph.borrow_mut().update(1.0/60.0).unwrap();
+/// }
+///
+/// // The water is held in the cup
+/// let ph = ph.borrow();
+/// assert!(ph.fluid(water).unwrap().particles.iter().all(|particle| particle.position.y < 290.0 && (120.0..280.0).contains(&particle.position.x)));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Fluid {
+    /// Particles of the fluid
+    pub particles: Vec<FluidParticle>,
+    /// Distance particles push each other from, which is also the size of the cells of the neighbor grid
+    pub interaction_radius: Real,
+    /// How crowded the particles are at rest, where the pressure is balanced ([`Fluid::new`] sets it to the density of particles placed by [`Fluid::fill`])
+    pub rest_density: Real,
+    /// How strongly crowded particles push apart, keeping the fluid from being compressed (pixels per second squared)
+    pub stiffness: Real,
+    /// How strongly particles very close to each other push apart, keeping them from clumping (pixels per second squared)
+    pub near_stiffness: Real,
+    /// How quickly neighbors moving towards each other even out their velocities, making the fluid thicker (per second)
+    pub viscosity: Real,
+    /// Radius of the circle particles collide with bodies as
+    pub particle_radius: Real,
+    /// Multiplier of the world gravity on the particles (1 by default)
+    pub gravity_scale: Real,
+    /// Bits of the categories of the bodies the particles collide with (every category by default, see [`PhysicsBodyData::collision_category`])
+    pub mask: u32,
+}
+impl Fluid {
+    /// Creates a fluid without particles whose particles push each other from `interaction_radius` away
+    #[must_use]
+    pub fn new(interaction_radius: Real) -> Self {
+        let mut fluid = Self {
+            particles: Vec::new(),
+            interaction_radius,
+            rest_density: 0.0,
+            stiffness: 100_000.0,
+            near_stiffness: 400_000.0,
+            viscosity: 5.0,
+            particle_radius: 0.25*interaction_radius,
+            gravity_scale: 1.0,
+            mask: u32::MAX,
+        };
+        fluid.rest_density = fluid.grid_density();
+        fluid
+    }
+
+    /// Adds particles at rest on a grid filling the box from `min` to `max`, half of the interaction radius apart
+    pub fn fill(&mut self, min: impl Into<Vector2>, max: impl Into<Vector2>) {
+        let min = min.into();
+        let max = max.into();
+        let spacing = self.fill_spacing();
+        let mut y = min.y + 0.5*spacing;
+        while y <= max.y {
+            let mut x = min.x + 0.5*spacing;
+            while x <= max.x {
+                self.particles.push(FluidParticle { position: Vector2 { x, y }, velocity: Vector2::zero() });
+                x += spacing;
+            }
+            y += spacing;
+        }
+    }
+
+    /// Returns the distance between particles placed by [`Fluid::fill`]
+    fn fill_spacing(&self) -> Real {
+        0.5*self.interaction_radius
+    }
+
+    /// Returns the density of a particle surrounded by others placed by [`Fluid::fill`]
+    fn grid_density(&self) -> Real {
+        let spacing = self.fill_spacing();
+        let mut density = 0.0;
+        for y in -2i8..=2 {
+            for x in -2i8..=2 {
+                let distance = (Real::from(x*x + y*y)).sqrt()*spacing;
+                if (x, y) != (0, 0) && distance < self.interaction_radius {
+                    density += (1.0 - distance/self.interaction_radius).powi(2);
+                }
+            }
+        }
+        density
+    }
+
+    /// Returns every pair of particles closer than the interaction radius (lowest index first), found through a grid of cells as large as the radius
+    fn neighbor_pairs(&self) -> Vec<(usize, usize)> {
+        let radius = self.interaction_radius.max(Real::EPSILON);
+        let cell = |position: Vector2| ((position.x/radius).floor() as i32, (position.y/radius).floor() as i32);
+
+        let mut grid: HashMap<(i32, i32), Vec<usize>> = HashMap::new();
+        for (index, particle) in self.particles.iter().enumerate() {
+            grid.entry(cell(particle.position)).or_default().push(index);
+        }
+
+        let mut pairs = Vec::new();
+        for (i, particle) in self.particles.iter().enumerate() {
+            let (x, y) = cell(particle.position);
+            for neighbor_cell in [(x - 1, y - 1), (x, y - 1), (x + 1, y - 1), (x - 1, y), (x, y), (x + 1, y), (x - 1, y + 1), (x, y + 1), (x + 1, y + 1)] {
+                for &j in grid.get(&neighbor_cell).into_iter().flatten() {
+                    if j > i && dist_sqr(particle.position, self.particles[j].position) < radius*radius {
+                        pairs.push((i, j));
+                    }
+                }
+            }
+        }
+
+        pairs
+    }
+}
+
+/// The range of motion allowed by a joint
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct JointLimits {
+    /// Lowest value allowed (radians for an angle, distance for a translation)
+    pub lower: Real,
+    /// Highest value allowed (radians for an angle, distance for a translation)
+    pub upper: Real,
+}
+
+/// A motor that drives a joint towards a target speed
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct JointMotor {
+    /// Target relative speed, in the same units as [`PhysicsBodyData::angular_velocity`] for an angle
+    /// or [`PhysicsBodyData::velocity`] for a translation
+    pub speed: Real,
+    /// Maximum torque (for an angle) or force (for a translation) the motor can apply to reach its target speed
+    pub max_force: Real,
+}
+
+/// A chain of segment bodies linked end to end by revolute joints, created with [`Physac::create_rope`]
+#[derive(Debug, Clone)]
+pub struct Rope<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize> {
+    /// Segment bodies, from the start of the rope to the end
+    pub segments: Vec<Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>>,
+    /// Revolute joints between neighboring segments, from the start of the rope to the end
+    pub joints: Vec<Strong<PhysicsJointData<MAX_VERTICES, CIRCLE_VERTICES>>>,
+    /// Distance between the joints of a segment
+    segment_length: Real,
+}
+impl<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize> Rope<MAX_VERTICES, CIRCLE_VERTICES> {
+    /// Returns the segment at the start of the rope
+    #[must_use]
+    pub fn start(&self) -> &Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>> {
+        &self.segments[0]
+    }
+
+    /// Returns the segment at the end of the rope
+    #[must_use]
+    pub fn end(&self) -> &Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>> {
+        &self.segments[self.segments.len() - 1]
+    }
+
+    /// Returns the tip of the rope at its start in world space, where it can be attached to another body with a joint
+    #[must_use]
+    pub fn start_anchor(&self) -> Vector2 {
+        self.start().borrow().world_point(Vector2 { x: -0.5*self.segment_length, y: 0.0 })
+    }
+
+    /// Returns the tip of the rope at its end in world space, where it can be attached to another body with a joint
+    #[must_use]
+    pub fn end_anchor(&self) -> Vector2 {
+        self.end().borrow().world_point(Vector2 { x: 0.5*self.segment_length, y: 0.0 })
+    }
+}
+
+/// Sizes of the parts of a ragdoll created with [`Physac::create_ragdoll`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RagdollBones {
+    /// Radius of the head
+    pub head_radius: Real,
+    /// Height of the torso
+    pub torso_length: Real,
+    /// Width of the torso
+    pub torso_width: Real,
+    /// Length of the arms from shoulder to elbow
+    pub upper_arm_length: Real,
+    /// Length of the arms from elbow to hand
+    pub lower_arm_length: Real,
+    /// Length of the legs from hip to knee
+    pub upper_leg_length: Real,
+    /// Length of the legs from knee to foot
+    pub lower_leg_length: Real,
+    /// Width of the arms and legs
+    pub limb_thickness: Real,
+}
+impl RagdollBones {
+    /// Construct bones with human proportions for a ragdoll about `height` tall
+    #[must_use]
+    pub const fn with_height(height: Real) -> Self {
+        Self {
+            head_radius: 0.065*height,
+            torso_length: 0.3*height,
+            torso_width: 0.18*height,
+            upper_arm_length: 0.17*height,
+            lower_arm_length: 0.16*height,
+            upper_leg_length: 0.24*height,
+            lower_leg_length: 0.24*height,
+            limb_thickness: 0.06*height,
+        }
+    }
+}
+
+/// A humanoid set of bodies linked by limited revolute joints, created with [`Physac::create_ragdoll`]
+///
+/// Parts that come in pairs are ordered left (towards negative x) then right
+#[derive(Debug, Clone)]
+pub struct Ragdoll<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize> {
+    /// Circle body of the head
+    pub head: Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>,
+    /// Rectangle body of the torso, which every other part hangs from
+    pub torso: Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>,
+    /// Arms from shoulder to elbow
+    pub upper_arms: [Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>; 2],
+    /// Arms from elbow to hand
+    pub lower_arms: [Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>; 2],
+    /// Legs from hip to knee
+    pub upper_legs: [Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>; 2],
+    /// Legs from knee to foot
+    pub lower_legs: [Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>; 2],
+    /// Joint between the torso and the head
+    pub neck: Strong<PhysicsJointData<MAX_VERTICES, CIRCLE_VERTICES>>,
+    /// Joints between the torso and the upper arms
+    pub shoulders: [Strong<PhysicsJointData<MAX_VERTICES, CIRCLE_VERTICES>>; 2],
+    /// Joints between the upper and lower arms
+    pub elbows: [Strong<PhysicsJointData<MAX_VERTICES, CIRCLE_VERTICES>>; 2],
+    /// Joints between the torso and the upper legs
+    pub hips: [Strong<PhysicsJointData<MAX_VERTICES, CIRCLE_VERTICES>>; 2],
+    /// Joints between the upper and lower legs
+    pub knees: [Strong<PhysicsJointData<MAX_VERTICES, CIRCLE_VERTICES>>; 2],
+}
+impl<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize> Ragdoll<MAX_VERTICES, CIRCLE_VERTICES> {
+    /// Returns an iterator over every body of the ragdoll
+    pub fn bodies(&self) -> impl Iterator<Item = &Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>> {
+        [&self.head, &self.torso].into_iter()
+            .chain(&self.upper_arms)
+            .chain(&self.lower_arms)
+            .chain(&self.upper_legs)
+            .chain(&self.lower_legs)
+    }
+
+    /// Returns an iterator over every joint of the ragdoll
+    pub fn joints(&self) -> impl Iterator<Item = &Strong<PhysicsJointData<MAX_VERTICES, CIRCLE_VERTICES>>> {
+        std::iter::once(&self.neck)
+            .chain(&self.shoulders)
+            .chain(&self.elbows)
+            .chain(&self.hips)
+            .chain(&self.knees)
+    }
+}
+
+/// A squishy ring of point bodies held together by springs and inflated by the pressure inside it, created with [`Physac::create_soft_body`]
+///
+/// The points are small circles that collide with every other body, so the ring squashes against whatever it hits and springs back.
+/// The pressure is kept by the world, see [`Physac::set_soft_body_pressure`]
+#[derive(Debug, Clone)]
+pub struct SoftBody<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize> {
+    /// Point bodies around the ring, in order
+    pub points: Vec<Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>>,
+    /// Spring joints between neighboring points, where spring `i` links point `i` to the next one
+    pub springs: Vec<Strong<PhysicsJointData<MAX_VERTICES, CIRCLE_VERTICES>>>,
+    /// ID the world keeps the pressure of the soft body by
+    id: u32,
+}
+impl<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize> SoftBody<MAX_VERTICES, CIRCLE_VERTICES> {
+    /// Returns the average position of the points
+    #[must_use]
+    pub fn center(&self) -> Vector2 {
+        let sum = self.points.iter().fold(Vector2::zero(), |sum, point| sum + point.borrow().position);
+        sum*(1.0/self.points.len() as Real)
+    }
+
+    /// Returns the area enclosed by the points
+    #[must_use]
+    pub fn area(&self) -> Real {
+        let points: Vec<Vector2> = self.points.iter().map(|point| point.borrow().position).collect();
+        polygon_area_centroid(&points).0.abs()
+    }
+}
+
+/// Pressure pushing out on the ring of points of a soft body, applied by the world every step
+#[derive(Debug, Clone)]
+struct SoftBodyPressure<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize> {
+    id: u32,
+    points: Vec<Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>>,
+    /// Area enclosed by the points when the soft body was created, where the pressure is balanced
+    rest_area: Real,
+    /// Outward force per unit of length on the edges when the area is squashed to half of the rest area
+    pressure: Real,
+}
+
+/// Values calculated by the solver while it works on a joint during a step
+#[derive(Debug, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+struct JointSolverData {
+    /// Anchor of the first body relative to its position, in world space
+    radius_a: Vector2,
+    /// Anchor of the second body relative to its position, in world space
+    radius_b: Vector2,
+    /// Direction the axial constraint acts along
+    axis: Vector2,
+    /// Direction the axial constraint acts along on the second body, when it differs from the first
+    axis_b: Vector2,
+    /// Effective mass along the axis
+    axial_mass: Real,
+    /// Velocity bias used to correct drift along the axis
+    axial_bias: Real,
+    /// Softness of the axial constraint
+    gamma: Real,
+    /// Impulse accumulated along the axis
+    axial_impulse: Real,
+    /// Effective mass of the point constraint
+    point_mass: Mat2,
+    /// Velocity bias used to correct drift between the anchors
+    point_bias: Vector2,
+    /// Impulse accumulated by the point constraint
+    point_impulse: Vector2,
+    /// Effective mass for rotation between the bodies
+    angular_mass: Real,
+    /// Joint angle at the start of the step
+    angle: Real,
+    /// Joint translation along the axis at the start of the step
+    translation: Real,
+    /// Anchor of the second body relative to the first body position, in world space
+    lever_a: Vector2,
+    /// Effective mass perpendicular to the axis
+    perpendicular_mass: Real,
+    /// Velocity bias used to correct drift perpendicular to the axis
+    perpendicular_bias: Real,
+    /// Velocity bias used to correct drift of the relative angle
+    angular_bias: Real,
+    /// Impulse accumulated by the angular constraint
+    angular_impulse: Real,
+    /// Impulse accumulated by the motor
+    motor_impulse: Real,
+    /// Impulse accumulated by the lower limit
+    lower_impulse: Real,
+    /// Impulse accumulated by the upper limit
+    upper_impulse: Real,
+    /// Largest impulse the joint can apply in a step
+    max_impulse: Real,
+    /// Angular velocity of the bodies a gear joint's linked joints are mounted on, weighted by the ratio
+    frame_speed: Real,
+}
+
+/// A constraint between two physics bodies
+///
+/// Create with [`Physac::create_distance_joint`], [`Physac::create_rope_joint`], [`Physac::create_spring_joint`], [`Physac::create_revolute_joint`],
+/// [`Physac::create_prismatic_joint`], [`Physac::create_weld_joint`], [`Physac::create_target_joint`], [`Physac::create_gear_joint`] or [`Physac::create_pulley_joint`]
+///
+/// Destroy (remove from the simulation) using [`Physac::destroy_joint`].
+/// Joints are also destroyed along with either of their bodies
+#[derive(Debug, Clone)]
+pub struct PhysicsJointData<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize> {
+    /// Reference unique identifier
+    pub id: u32,
+    /// First physics body reference
+    body_a: Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>,
+    /// Second physics body reference, or [`None`] if the joint is attached to the world
+    body_b: Option<Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>>,
+    /// Anchor point on the first body, relative to its position before it is rotated
+    pub local_anchor_a: Vector2,
+    /// Anchor point on the second body, relative to its position before it is rotated, or in world space if there is no second body
+    pub local_anchor_b: Vector2,
+    /// Joint type and its parameters
+    pub kind: PhysicsJointKind,
+    /// Force the joint can hold before it breaks and is destroyed, or [`None`] if it can hold any force
+    ///
+    /// Compared against [`PhysicsJointData::reaction_force`], so holding up a body takes its mass times gravity
+    pub break_force: Option<Real>,
+    /// Torque the joint can hold before it breaks and is destroyed, or [`None`] if it can hold any torque
+    pub break_torque: Option<Real>,
+    /// Any value the game wants to keep with the joint, such as the ID of the entity it belongs to (0 by default)
+    ///
+    /// The physics never reads it, so it can map joints in events back to game objects
+    pub user_data: u128,
+    /// Joints whose angles a gear joint couples
+    linked_joints: Option<[Strong<PhysicsJointData<MAX_VERTICES, CIRCLE_VERTICES>>; 2]>,
+    /// Solver values from the last step
+    solver: JointSolverData,
+    /// The joint exists in Physac
+    is_simulating: bool,
+}
+impl<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize> PhysicsJointData<MAX_VERTICES, CIRCLE_VERTICES> {
+    /// Construct a new joint between two bodies with default solver values
+    const fn new(
+        id: u32,
+        body_a: Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>,
+        body_b: Option<Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>>,
+        local_anchor_a: Vector2,
+        local_anchor_b: Vector2,
+        kind: PhysicsJointKind,
+    ) -> Self {
+        Self {
+            id,
+            body_a,
+            body_b,
+            local_anchor_a,
+            local_anchor_b,
+            kind,
+            break_force: None,
+            break_torque: None,
+            user_data: 0,
+            linked_joints: None,
+            solver: JointSolverData {
+                radius_a: Vector2::zero(),
+                radius_b: Vector2::zero(),
+                axis: Vector2::zero(),
+                axis_b: Vector2::zero(),
+                axial_mass: 0.0,
+                axial_bias: 0.0,
+                gamma: 0.0,
+                axial_impulse: 0.0,
+                point_mass: Mat2::identity(),
+                point_bias: Vector2::zero(),
+                point_impulse: Vector2::zero(),
+                angular_mass: 0.0,
+                angle: 0.0,
+                translation: 0.0,
+                lever_a: Vector2::zero(),
+                perpendicular_mass: 0.0,
+                perpendicular_bias: 0.0,
+                angular_bias: 0.0,
+                angular_impulse: 0.0,
+                motor_impulse: 0.0,
+                lower_impulse: 0.0,
+                upper_impulse: 0.0,
+                max_impulse: 0.0,
+                frame_speed: 0.0,
+            },
+            is_simulating: true,
+        }
+    }
+
+    /// Returns true if the joint is still being simulated, returns false if it has been destroyed
+    #[must_use]
+    pub fn is_simulating(&self) -> bool {
+        self.is_simulating
+    }
+
+    /// Returns the first body attached to the joint
+    #[must_use]
+    pub fn body_a(&self) -> &Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>> {
+        &self.body_a
+    }
+
+    /// Returns the second body attached to the joint, or [`None`] if the joint is attached to the world
+    #[must_use]
+    pub fn body_b(&self) -> Option<&Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>> {
+        self.body_b.as_ref()
+    }
+
+    /// Returns the anchor on the first body in world space
+    #[must_use]
+    pub fn anchor_a(&self) -> Vector2 {
+        self.body_a.borrowed(|body| body.world_point(self.local_anchor_a))
+    }
+
+    /// Returns the anchor on the second body in world space, or the point in the world the joint is attached to if there is no second body
+    #[must_use]
+    pub fn anchor_b(&self) -> Vector2 {
+        self.body_b.as_ref().map_or(self.local_anchor_b, |body_b| body_b.borrowed(|body| body.world_point(self.local_anchor_b)))
+    }
+
+    /// Moves the point in the world a joint without a second body (such as a target joint) is attached to
+    ///
+    /// Does nothing for joints between two bodies
+    pub fn set_target(&mut self, target: impl Into<Vector2>) {
+        let target = target.into();
+        if self.body_b.is_none() {
+            self.local_anchor_b = target;
+            self.body_a.borrow_mut().wake_up();
+        }
+    }
+
+    /// Returns the angle of the second body relative to the first, minus the reference angle of a revolute or prismatic joint
+    #[must_use]
+    pub fn joint_angle(&self) -> Real {
+        let reference_angle = match self.kind {
+            PhysicsJointKind::Revolute { reference_angle, .. } |
+            PhysicsJointKind::Prismatic { reference_angle, .. } |
+            PhysicsJointKind::Weld { reference_angle } => reference_angle,
+            _ => 0.0,
+        };
+
+        let orient_b = self.body_b.as_ref().map_or(0.0, |body_b| body_b.borrow().orient);
+        orient_b - self.body_a.borrow().orient - reference_angle
+    }
+
+    /// Returns the linear impulse the solver applied through the joint during the last step
+    fn linear_impulse(&self) -> Vector2 {
+        let solver = &self.solver;
+        match self.kind {
+            PhysicsJointKind::Distance { .. } | PhysicsJointKind::Spring { .. } => solver.axis*solver.axial_impulse,
+            PhysicsJointKind::Rope { .. } => -solver.axis*solver.upper_impulse,
+            PhysicsJointKind::Pulley { ratio, .. } => solver.axis_b*(solver.axial_impulse*ratio),
+            PhysicsJointKind::Prismatic { .. } => solver.axis*(solver.motor_impulse + solver.lower_impulse - solver.upper_impulse) + solver.point_impulse,
+            PhysicsJointKind::Revolute { .. } | PhysicsJointKind::Weld { .. } | PhysicsJointKind::Target { .. } => solver.point_impulse,
+            PhysicsJointKind::Gear { .. } => Vector2::zero(),
+        }
+    }
+
+    /// Returns the angular impulse the solver applied through the joint during the last step
+    fn angular_impulse(&self) -> Real {
+        let solver = &self.solver;
+        match self.kind {
+            PhysicsJointKind::Revolute { .. } => solver.motor_impulse + solver.lower_impulse - solver.upper_impulse,
+            PhysicsJointKind::Prismatic { .. } | PhysicsJointKind::Weld { .. } => solver.angular_impulse,
+            PhysicsJointKind::Gear { ratio, .. } => solver.angular_impulse*ratio,
+            _ => 0.0,
+        }
+    }
+
+    /// Returns the force the joint applied to the second body (or the only body of a target joint) during the last step
+    ///
+    /// `inv_dt` is the inverse of the physics time step in seconds, `1000.0/`[`Physac::get_physics_time_step`], which gives the force
+    /// in the units of mass times gravity. This is the stress on the joint, which is useful for effects like creaking sounds or custom break logic
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use physac::prelude::*;
+    /// let mut ph = init_physics::<24, 24>().build();
+    ///
+    /// let anchor = ph.borrow_mut()
+    ///     .create_physics_body_circle(Vector2 { x: 100.0, y: 50.0 }, 5.0, 1.0)
+    ///     .clone();
+    /// anchor.borrow_mut().enabled = false;
+    ///
+    /// let lamp = ph.borrow_mut()
+    ///     .create_physics_body_circle(Vector2 { x: 100.0, y: 100.0 }, 10.0, 1.0)
+    ///     .clone();
+    ///
+    /// let cord = ph.borrow_mut()
+    ///     .create_revolute_joint(&anchor, &lamp, Vector2 { x: 100.0, y: 50.0 })
+    ///     .clone();
+    ///
+    /// // Let the lamp hang for a second, then weigh it
+    /// for _ in 0..60 {
+    ///     ph.borrow_mut().update(1.0/60.0).unwrap();
+    /// }
+    ///
+    /// let inv_dt = (1000.0/ph.borrow().get_physics_time_step()) as Real;
+    /// let weight = lamp.borrow().mass*9.81;
+    /// let stress = cord.borrow().reaction_force(inv_dt);
+    /// assert!((stress.y + weight).abs() < 0.01*weight);
+    /// ```
+    #[must_use]
+    pub fn reaction_force(&self, inv_dt: Real) -> Vector2 {
+        self.linear_impulse()*inv_dt
+    }
+
+    /// Returns the torque the joint applied to the second body during the last step
+    ///
+    /// `inv_dt` is the inverse of the physics time step in seconds, `1000.0/`[`Physac::get_physics_time_step`]
+    #[must_use]
+    pub fn reaction_torque(&self, inv_dt: Real) -> Real {
+        self.angular_impulse()*inv_dt
+    }
+
+    /// Returns the force and torque the joint had to hold during the last step, if it was more than it can withstand
+    fn overload(&self, inv_dt: Real) -> Option<(Real, Real)> {
+        let force = self.reaction_force(inv_dt).length_sqr().sqrt();
+        let torque = self.reaction_torque(inv_dt).abs();
+
+        let is_overloaded = self.break_force.is_some_and(|break_force| force > break_force) ||
+                            self.break_torque.is_some_and(|break_torque| torque > break_torque);
+        is_overloaded.then_some((force, torque))
+    }
+
+    /// Returns how far the second anchor is from the first along the axis of a prismatic joint,
+    /// the rope length of a pulley joint (with the second side weighted by the ratio),
+    /// or the distance between the anchors for other joints
+    #[must_use]
+    pub fn joint_translation(&self) -> Real {
+        let delta = self.anchor_b() - self.anchor_a();
+        match self.kind {
+            PhysicsJointKind::Prismatic { local_axis, .. } => {
+                let axis = Mat2::radians(self.body_a.borrow().orient).multiply_vector2(local_axis);
+                delta.dot(axis)
+            }
+            PhysicsJointKind::Pulley { ground_anchor_a, ground_anchor_b, ratio, .. } => {
+                (self.anchor_a() - ground_anchor_a).length_sqr().sqrt() + ratio*(self.anchor_b() - ground_anchor_b).length_sqr().sqrt()
+            }
+            _ => delta.length_sqr().sqrt(),
+        }
+    }
+
+    /// Returns the angular velocity of the second body relative to the first
+    #[must_use]
+    pub fn joint_speed(&self) -> Real {
+        let angular_velocity_b = self.body_b.as_ref().map_or(0.0, |body_b| body_b.borrow().angular_velocity);
+        angular_velocity_b - self.body_a.borrow().angular_velocity
+    }
+
+    /// Sets or removes the range of motion of the joint
+    ///
+    /// Does nothing for joints that can't be limited
+    pub fn set_limits(&mut self, new_limits: Option<JointLimits>) {
+        if let PhysicsJointKind::Revolute { limits, .. } | PhysicsJointKind::Prismatic { limits, .. } = &mut self.kind {
+            *limits = new_limits;
+        }
+    }
+
+    /// Sets or removes the motor of the joint
+    ///
+    /// Does nothing for joints that can't have a motor
+    pub fn set_motor(&mut self, new_motor: Option<JointMotor>) {
+        if let PhysicsJointKind::Revolute { motor, .. } | PhysicsJointKind::Prismatic { motor, .. } = &mut self.kind {
+            *motor = new_motor;
+            self.body_a.borrow_mut().wake_up();
+            if let Some(body_b) = &self.body_b {
+                body_b.borrow_mut().wake_up();
+            }
+        }
+    }
+}
+
+/// A helper module for thread safety
+pub mod phys_rc {
+    #[cfg(feature = "sync")]
+    use std::sync::{self, Arc, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+    #[cfg(not(feature = "sync"))]
+    use std::{rc::{self, Rc}, cell::{RefCell, Ref, RefMut}};
+
+    /// Implemented for every type that can be shared with the physics thread
+    #[cfg_attr(    feature = "sync",  doc = "(types that are [`Send`] and [`Sync`])")]
+    #[cfg_attr(not(feature = "sync"), doc = "(all types)")]
+    #[cfg(feature = "sync")]
+    pub trait ThreadSafe: Send + Sync {}
+    #[cfg(feature = "sync")]
+    impl<T: Send + Sync + ?Sized> ThreadSafe for T {}
+
+    /// Implemented for every type that can be shared with the physics thread
+    #[cfg_attr(    feature = "sync",  doc = "(types that are [`Send`] and [`Sync`])")]
+    #[cfg_attr(not(feature = "sync"), doc = "(all types)")]
+    #[cfg(not(feature = "sync"))]
+    pub trait ThreadSafe {}
+    #[cfg(not(feature = "sync"))]
+    impl<T: ?Sized> ThreadSafe for T {}
+
+    #[cfg(    feature = "sync" )] type PhysacHandleReadGuardImpl<'a, T> = RwLockReadGuard<'a, T>;
+    #[cfg(not(feature = "sync"))] type PhysacHandleReadGuardImpl<'a, T> = &'a T;
+
+    #[cfg(    feature = "sync" )] type PhysacHandleWriteGuardImpl<'a, T> = RwLockWriteGuard<'a, T>;
+    #[cfg(not(feature = "sync"))] type PhysacHandleWriteGuardImpl<'a, T> = &'a mut T;
+
+    #[cfg(    feature = "sync" )] type PhysacReadGuardImpl<'a, T> = RwLockReadGuard<'a, T>;
+    #[cfg(not(feature = "sync"))] type PhysacReadGuardImpl<'a, T> = Ref<'a, T>;
+
+    #[cfg(    feature = "sync" )] type PhysacWriteGuardImpl<'a, T> = RwLockWriteGuard<'a, T>;
+    #[cfg(not(feature = "sync"))] type PhysacWriteGuardImpl<'a, T> = RefMut<'a, T>;
+
+    /// RAII structure used to release the shared read access of a lock when dropped, implemented as a
+    #[cfg_attr(    feature = "sync",  doc = "[`std::sync::RwLockReadGuard<'a, T>`]")]
+    #[cfg_attr(not(feature = "sync"), doc = "`&T`")]
+    ///
+    /// This structure is created by the [`crate::PhysacHandle::borrow`] method
+    pub type PhysacHandleReadGuard<'a, T> = PhysacHandleReadGuardImpl<'a, T>;
+
+    /// RAII structure used to release the shared write access of a lock when dropped, implemented as a
+    #[cfg_attr(    feature = "sync",  doc = "[`std::sync::RwLockWriteGuard<'a, T>`]")]
+    #[cfg_attr(not(feature = "sync"), doc = "`&mut T`")]
+    ///
+    /// This structure is created by the [`crate::PhysacHandle::borrow_mut`] method
+    pub type PhysacHandleWriteGuard<'a, T> = PhysacHandleWriteGuardImpl<'a, T>;
+
+    /// RAII structure used to release the shared read access of a lock when dropped, implemented as a
+    #[cfg_attr(    feature = "sync",  doc = "[`std::sync::RwLockReadGuard<'a, T>`]")]
+    #[cfg_attr(not(feature = "sync"), doc = "`std::cell::Ref<'a, T>`")]
+    ///
+    /// This structure is created by the [`Strong::borrow`] method
+    pub type PhysacReadGuard<'a, T> = PhysacReadGuardImpl<'a, T>;
+
+    /// RAII structure used to release the shared read access of a lock when dropped, implemented as a
+    #[cfg_attr(    feature = "sync",  doc = "[`std::sync::RwLockWriteGuard<'a, T>`]")]
+    #[cfg_attr(not(feature = "sync"), doc = "`std::cell::RefMut<'a, T>`")]
+    ///
+    /// This structure is created by the [`Strong::borrow_mut`] method
+    pub type PhysacWriteGuard<'a, T> = PhysacWriteGuardImpl<'a, T>;
+
+    /// A
+    #[cfg_attr(    feature = "sync",  doc = "thread-safe")]
+    #[cfg_attr(not(feature = "sync"), doc = "single-threaded")]
+    /// reference-counting pointer to a
+    #[cfg_attr(    feature = "sync",  doc = "reader-writer lock")]
+    #[cfg_attr(not(feature = "sync"), doc = "mutable memory location with dynamically checked borrow rules")]
+    ///
+    /// See
+    #[cfg_attr(    feature = "sync",  doc = "[`Arc`] and [`RwLock`]")]
+    #[cfg_attr(not(feature = "sync"), doc = "[`Rc`] and [`RefCell`]")]
+    /// for a more information
+    #[derive(Debug)]
+    pub struct Strong<T> {
+        #[cfg(feature = "sync")]
+        inner: Arc<RwLock<T>>,
+        #[cfg(not(feature = "sync"))]
+        inner: Rc<RefCell<T>>,
+    }
+    impl<T> Clone for Strong<T> {
+        fn clone(&self) -> Self {
+            Self { inner: self.inner.clone() }
+        }
+    }
+    impl<T> Strong<T> {
+        #[must_use]
+        pub(super) fn new(data: T) -> Self {
+            Self {
+                #[cfg(feature = "sync")]
+                inner: Arc::new(RwLock::new(data)),
+                #[cfg(not(feature = "sync"))]
+                inner: Rc::new(RefCell::new(data)),
+            }
+        }
+
+        /// Get a weak reference from a strong one
+        #[must_use]
+        pub fn downgrade(&self) -> Weak<T> {
+            let inner = &self.inner;
+            Weak {
+                #[cfg(feature = "sync")]
+                inner: Arc::downgrade(inner),
+                #[cfg(not(feature = "sync"))]
+                inner: Rc::downgrade(inner),
+            }
+        }
+
+        /// Try to get a temporary reference to the body, returning an error if the resource is poisoned
+        #[cfg_attr(feature = "sync", doc = "\n # Errors\n\n This function will return an error if the `RwLock` is poisoned. An `RwLock` is poisoned whenever a writer panics while holding an exclusive lock. The failure will occur immediately after the lock has been acquired. The acquired lock guard will be contained in the returned error.")]
+        #[cfg_attr(feature = "sync", doc = "\n # Deadlocks\n\n The physics thread needs to borrow every body at some point during a tick, so try not store the borrow for longer than you have to (do not store the guard in a struct or at a scope outside of the main loop)")]
+        #[cfg_attr(not(feature = "sync"), doc = "\n # Errors\n\n Without the `sync` feature the body is behind a `RefCell`, so this never returns an error.")]
+        pub fn read(&self) -> std::sync::LockResult<PhysacReadGuard<'_, T>> {
+            #[cfg(feature = "sync")] {
+                self.inner.read()
+            } #[cfg(not(feature = "sync"))] {
+                Ok(self.inner.borrow())
+            }
+        }
+
+        /// Try to get a temporary mutable reference to the body, returning an error if the resource is poisoned
+        #[cfg_attr(feature = "sync", doc = "\n # Errors\n\n This function will return an error if the `RwLock` is poisoned. An `RwLock` is poisoned whenever a writer panics while holding an exclusive lock. An error will be returned when the lock is acquired. The acquired lock guard will be contained in the returned error.")]
+        #[cfg_attr(feature = "sync", doc = "\n # Deadlocks\n\n The physics thread needs to borrow every body at some point during a tick, so try not store the borrow for longer than you have to (do not store the guard in a struct or at a scope outside of the main loop)")]
+        #[cfg_attr(not(feature = "sync"), doc = "\n # Errors\n\n Without the `sync` feature the body is behind a `RefCell`, so this never returns an error.")]
+        pub fn write(&self) -> std::sync::LockResult<PhysacWriteGuard<'_, T>> {
+            #[cfg(feature = "sync")] {
+                self.inner.write()
+            } #[cfg(not(feature = "sync"))] {
+                Ok(self.inner.borrow_mut())
+            }
+        }
+
+        /// Get a temporary reference to the body
+        #[cfg_attr(feature = "sync", doc = "\n # Deadlocks\n\n The physics thread needs to borrow every body at some point during a tick, so try not store the borrow for longer than you have to (do not store the guard in a struct or at a scope outside of the main loop)")]
+        #[cfg_attr(feature = "sync", doc = "\n # Panics\n\n This method may panic if another thread panicked while mutably borrowing this object")]
+        #[cfg_attr(not(feature = "sync"), must_use)]
+        pub fn borrow(&self) -> PhysacReadGuard<'_, T> {
+            #[cfg(feature = "sync")] {
+                self.inner.read().expect("thread poison recovery is not supported")
+            } #[cfg(not(feature = "sync"))] {
+                self.inner.borrow()
+            }
+        }
+
+        /// Get a temporary mutable reference to the body
+        #[cfg_attr(feature = "sync", doc = "\n # Deadlocks\n\n The physics thread needs to borrow every body at some point during a tick, so try not store the borrow for longer than you have to (do not store the guard in a struct or at a scope outside of the main loop)")]
+        #[cfg_attr(feature = "sync", doc = "\n # Panics\n\n This method may panic if another thread panicked while mutably borrowing this object")]
+        #[cfg_attr(not(feature = "sync"), must_use)]
+        pub fn borrow_mut(&self) -> PhysacWriteGuard<'_, T> {
+            #[cfg(feature = "sync")] {
+                self.inner.write().expect("thread poison recovery is not supported")
+            } #[cfg(not(feature = "sync"))] {
+                self.inner.borrow_mut()
+            }
+        }
+
+        /// Apply a closure on a temporary reference to the body
+        #[cfg_attr(feature = "sync", doc = "\n # Deadlocks\n\n The physics thread needs to borrow every body at some point during a tick, so try not store the borrow for longer than you have to (do not store the guard in a struct or at a scope outside of the main loop)")]
+        #[cfg_attr(feature = "sync", doc = "\n # Panics\n\n This method may panic if another thread panicked while mutably borrowing this object")]
+        pub fn borrowed<U, F>(&self, f: F) -> U
+        where
+            F: FnOnce(&T) -> U
+        {
+            #[cfg(feature = "sync")] {
+                f(&*self.inner.read().expect("thread poison recovery is not supported"))
+            } #[cfg(not(feature = "sync"))] {
+                f(&*self.inner.borrow())
+            }
+        }
+
+        /// Apply a closure on a temporary mutable reference to the body
+        #[cfg_attr(feature = "sync", doc = "\n # Deadlocks\n\n The physics thread needs to borrow every body at some point during a tick, so try not store the borrow for longer than you have to (do not store the guard in a struct or at a scope outside of the main loop)")]
+        #[cfg_attr(feature = "sync", doc = "\n # Panics\n\n This method may panic if another thread panicked while mutably borrowing this object")]
+        pub fn borrowed_mut<U, F>(&self, f: F) -> U
+        where
+            F: FnOnce(&mut T) -> U
+        {
+            #[cfg(feature = "sync")] {
+                f(&mut *self.inner.write().expect("thread poison recovery is not supported"))
+            } #[cfg(not(feature = "sync"))] {
+                f(&mut *self.inner.borrow_mut())
+            }
+        }
+    }
+
+    /// A non-owning reference to the managed allocation
+    ///
+    /// See
+    #[cfg_attr(    feature = "sync",  doc = "[`sync::Weak`] and [`RwLock`]")]
+    #[cfg_attr(not(feature = "sync"), doc = "[`rc::Weak`] and [`RefCell`]")]
+    /// for a more information
+    #[derive(Debug, Clone)]
+    pub struct Weak<T> {
+        #[cfg(feature = "sync")]
+        inner: sync::Weak<RwLock<T>>,
+        #[cfg(not(feature = "sync"))]
+        inner: rc::Weak<RefCell<T>>,
+    }
+    impl<T> Default for Weak<T> {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+    impl<T> Weak<T> {
+        /// Constructs a weak reference to nothing
+        #[must_use]
+        pub fn new() -> Self {
+            Self {
+                #[cfg(feature = "sync")]
+                inner: sync::Weak::new(),
+                #[cfg(not(feature = "sync"))]
+                inner: rc::Weak::new()
+            }
+        }
+
+        #[must_use]
+        pub(super) fn strong_count(&self) -> usize {
+            #[cfg(feature = "sync")]
+            let n = sync::Weak::strong_count(&self.inner);
+            #[cfg(not(feature = "sync"))]
+            let n = rc::Weak::strong_count(&self.inner);
+            n
+        }
+
+        /// Try to get a strong reference from a weak one
+        ///
+        /// Returns [`None`] if no Strong references exist for the object
+        #[must_use]
+        pub fn upgrade(&self) -> Option<Strong<T>> {
+            self.inner.upgrade().map(|inner| Strong { inner })
+        }
+
+        /// Upgrades and borrows the inner value, applying the closure to that
+        ///
+        /// Returns [`None`] if no Strong references exist for the object
+        #[must_use]
+        pub fn borrowed<U, F>(&self, f: F) -> Option<U>
+        where
+            F: FnOnce(&T) -> U
+        {
+            self.upgrade().map(|x| x.borrowed(f))
+        }
+
+        /// Upgrades and borrows the inner value, applying the closure to that
+        ///
+        /// Returns [`None`] if no Strong references exist for the object
+        #[must_use]
+        pub fn borrowed_mut<U, F>(&self, f: F) -> Option<U>
+        where
+            F: FnOnce(&mut T) -> U
+        {
+            self.upgrade().map(|x| x.borrowed_mut(f))
+        }
+    }
+
+    use super::PhysicsBodyData;
+
+    impl<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize> Weak<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>> {
+        /// Try to get a strong reference from a weak one
+        ///
+        /// Returns [`None`] if no Strong references exist for the body, or if the body has been destroyed
+        #[must_use]
+        pub fn sim_upgrade(&self) -> Option<Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>> {
+            self.upgrade()
+                .filter(|body| body.read().is_ok_and(|body| body.is_simulating()))
+        }
+    }
+}
+pub use self::phys_rc::*;
+
+/***********************************************************************************
+*
+*   PHYSAC IMPLEMENTATION
+*
+************************************************************************************/
+
+//----------------------------------------------------------------------------------
+// Defines and Macros
+//----------------------------------------------------------------------------------
+const PHYSAC_K: Real = 1.0/3.0;
+/// Fraction of a joint's position error corrected every step
+const JOINT_BAUMGARTE: Real = 0.2;
+/// Most grid cells a body is sorted into before it is tested against every other body instead
+const GRID_MAX_BODY_CELLS: Real = 64.0;
+
+//----------------------------------------------------------------------------------
+// Global Variables Definition
+//----------------------------------------------------------------------------------
+/// Physics bodies that touch each other or are connected by joints, along with their manifolds and joints,
+/// which can be solved without the rest of the world
+///
+/// Static bodies don't pass movement on, so each one is an island of its own
+#[derive(Debug)]
+struct Island<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize> {
+    /// Whether the island is a single static body, which can share its body with other islands
+    #[cfg_attr(not(feature = "parallel"), allow(dead_code))]
+    is_static: bool,
+    bodies: Vec<Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>>,
+    /// Range of the manifolds of the island, which are ordered by island
+    contacts: Range<usize>,
+    joints: Vec<Strong<PhysicsJointData<MAX_VERTICES, CIRCLE_VERTICES>>>,
+}
+
+/// The state of a world at one point in time, which it can be rolled back to with [`Physac::restore`]
+///
+/// Take with [`Physac::snapshot`]
+#[derive(Debug, Clone)]
+pub struct WorldSnapshot<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize> {
+    /// Every body being simulated, along with a copy of its data
+    bodies: Vec<(Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>, PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>)>,
+    /// Every joint being simulated, along with a copy of its data
+    joints: Vec<(Strong<PhysicsJointData<MAX_VERTICES, CIRCLE_VERTICES>>, PhysicsJointData<MAX_VERTICES, CIRCLE_VERTICES>)>,
+    contacts: Vec<PhysicsManifoldData<MAX_VERTICES, CIRCLE_VERTICES>>,
+    contact_impulses: HashMap<(u32, u32, ContactId), (Real, Real)>,
+    body_tree: Option<BodyTree<MAX_VERTICES, CIRCLE_VERTICES>>,
+    sensor_overlaps: Vec<(Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>, Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>)>,
+    touching_pairs: Vec<((u32, u32), CollisionContact<MAX_VERTICES, CIRCLE_VERTICES>)>,
+    ignored_pairs: Vec<[Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>; 2]>,
+    soft_bodies: Vec<SoftBodyPressure<MAX_VERTICES, CIRCLE_VERTICES>>,
+    next_soft_body_id: u32,
+    particles: Vec<(u32, Particle)>,
+    next_particle_id: u32,
+    particle_constraints: Vec<(u32, ParticleConstraint<MAX_VERTICES, CIRCLE_VERTICES>)>,
+    next_particle_constraint_id: u32,
+    fluids: Vec<(u32, Fluid)>,
+    next_fluid_id: u32,
+    body_generations: Vec<u32>,
+    accumulator: f64,
+    steps_count: u32,
+    rng: SplitMix64,
+}
+impl<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize> WorldSnapshot<MAX_VERTICES, CIRCLE_VERTICES> {
+    /// Returns how many steps the world had run when the snapshot was taken
+    #[must_use]
+    pub const fn steps_count(&self) -> u32 {
+        self.steps_count
+    }
+}
+
+/// Version of the [`WorldState`] format written by this version of the crate
+///
+/// States saved with older versions of the format keep loading after the crate is upgraded
+pub const WORLD_STATE_VERSION: u32 = 1;
+
+/// A copy of a whole world that doesn't share anything with it, which can be loaded into any world with [`Physac::load_state`]
+///
+/// Take with [`Physac::save_state`]. With the `serde` feature it can be serialized, so that save games can persist physics
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct WorldState<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize> {
+    /// Version of the format the state was saved with
+    version: u32,
+    gravity: GravityState,
+    /// Delta time used for physics steps, in milliseconds
+    delta_time: f64,
+    time_scale: f64,
+    collision_iterations: usize,
+    position_iterations: usize,
+    substeps: NonZeroUsize,
+    penetration_allowance: Real,
+    penetration_correction: Real,
+    sleep_steps: u32,
+    sleep_velocity: Real,
+    sleep_angular_velocity: Real,
+    warm_starting: bool,
+    friction_combine: CombineMode,
+    restitution_combine: CombineMode,
+    bodies: Vec<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>,
+    joints: Vec<JointState>,
+    /// IDs of the pairs of bodies that never collide with each other
+    ignored_pairs: Vec<[u32; 2]>,
+    /// Warm starting impulses by the IDs of the bodies and the contact, sorted so that saving the same world always gives the same state
+    contact_impulses: Vec<(u32, u32, ContactId, Real, Real)>,
+    accumulator: f64,
+    steps_count: u32,
+    rng: SplitMix64,
+    /// How many times each physics body ID has been given to a body
+    body_generations: Vec<u32>,
+    force_fields: Vec<(u32, ForceField)>,
+    next_force_field_id: u32,
+    buoyancy_zones: Vec<(u32, BuoyancyZone)>,
+    next_buoyancy_zone_id: u32,
+    soft_bodies: Vec<SoftBodyState>,
+    next_soft_body_id: u32,
+    particles: Vec<(u32, Particle)>,
+    next_particle_id: u32,
+    particle_constraints: Vec<(u32, ParticleConstraintState)>,
+    next_particle_constraint_id: u32,
+    fluids: Vec<(u32, Fluid)>,
+    next_fluid_id: u32,
+}
+impl<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize> WorldState<MAX_VERTICES, CIRCLE_VERTICES> {
+    /// Returns the version of the format the state was saved with
+    #[must_use]
+    pub const fn version(&self) -> u32 {
+        self.version
+    }
+
+    /// Returns the saved physics bodies, in the order they were simulated
+    #[must_use]
+    pub fn bodies(&self) -> &[PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>] {
+        &self.bodies
+    }
+
+    /// Encodes the state in a compact binary format, which starts with the bytes `PHYS` (or `PH64` with the `f64` feature) followed by the format version
+    ///
+    /// Numbers are little-endian, so the bytes are the same on every platform. States saved with and without the `f64` feature can't be loaded by each other
+    #[must_use]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        use world_file::Encode;
+
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(&world_file::MAGIC);
+        WORLD_STATE_VERSION.encode(&mut bytes);
+        self.encode(&mut bytes);
+        bytes
+    }
+
+    /// Decodes a state encoded with [`WorldState::to_bytes`], by this or an older version of the crate
+    ///
+    /// # Errors
+    ///
+    /// This method returns an error if the bytes were written by a newer version of the crate, or aren't a valid world state
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, LoadStateError> {
+        use world_file::Encode;
+
+        let mut input = bytes.strip_prefix(&world_file::MAGIC).ok_or(LoadStateError::Malformed)?;
+        let version = u32::decode(&mut input).ok_or(LoadStateError::Malformed)?;
+        if version > WORLD_STATE_VERSION {
+            return Err(LoadStateError::UnsupportedVersion(version));
+        }
+        let mut state = Self::decode(&mut input).ok_or(LoadStateError::Malformed)?;
+        state.version = version;
+        if input.is_empty() { Ok(state) } else { Err(LoadStateError::Malformed) }
+    }
+}
+
+/// A joint in a [`WorldState`], which refers to its bodies and linked joints by their IDs
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+struct JointState {
+    id: u32,
+    body_a: u32,
+    body_b: Option<u32>,
+    local_anchor_a: Vector2,
+    local_anchor_b: Vector2,
+    kind: PhysicsJointKind,
+    break_force: Option<Real>,
+    break_torque: Option<Real>,
+    linked_joints: Option<[u32; 2]>,
+    solver: JointSolverData,
+    user_data: u128,
+}
+
+/// A soft body in a [`WorldState`], which refers to its points by their IDs
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+struct SoftBodyState {
+    id: u32,
+    points: Vec<u32>,
+    rest_area: Real,
+    pressure: Real,
+}
+
+/// A particle constraint in a [`WorldState`], which refers to its body by its ID
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+enum ParticleConstraintState {
+    Distance { particle_a: u32, particle_b: u32, length: Real, stiffness: Real },
+    Pin { particle: u32, point: Vector2 },
+    BodyPin { particle: u32, body: u32, local_anchor: Vector2 },
+}
+
+/// An error that occurs when loading a [`WorldState`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LoadStateError {
+    /// The state was saved by a newer version of the crate, with a format this version doesn't know
+    UnsupportedVersion(u32),
+    /// The state has more physics bodies than the world has room for (see [`PhysacBuilder::growable_storage`])
+    TooManyBodies,
+    /// A joint or ignored pair refers to a physics body ID that isn't in the state
+    MissingBody(u32),
+    /// A gear joint refers to a joint ID that isn't in the state
+    MissingJoint(u32),
+    /// The bytes aren't a world state written by [`WorldState::to_bytes`], or were cut short
+    Malformed,
+}
+impl std::fmt::Display for LoadStateError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnsupportedVersion(version) => write!(f, "world state version {version} is newer than the supported version {WORLD_STATE_VERSION}"),
+            Self::TooManyBodies => write!(f, "the world state has more physics bodies than the world has room for"),
+            Self::MissingBody(id) => write!(f, "physics body id {id} is missing from the world state"),
+            Self::MissingJoint(id) => write!(f, "joint id {id} is missing from the world state"),
+            Self::Malformed => write!(f, "the data is not a valid world state"),
+        }
+    }
+}
+impl std::error::Error for LoadStateError {}
+
+/// A change to a world that can be recorded and replayed, run with [`Physac::execute`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum PhysicsCommand {
+    /// Creates a circle body (see [`Physac::create_physics_body_circle`])
+    CreateCircle {
+        /// Center of the circle
+        position: Vector2,
+        /// Circle radius
+        radius: Real,
+        /// Mass per unit of area
+        density: Real,
+    },
+    /// Creates a rectangle body (see [`Physac::create_physics_body_rectangle`])
+    CreateRectangle {
+        /// Center of the rectangle
+        position: Vector2,
+        /// Rectangle width
+        width: Real,
+        /// Rectangle height
+        height: Real,
+        /// Mass per unit of area
+        density: Real,
+    },
+    /// Creates a regular polygon body (see [`Physac::create_physics_body_polygon`])
+    CreatePolygon {
+        /// Center of the polygon
+        position: Vector2,
+        /// Distance from the center to each vertex
+        radius: Real,
+        /// Number of sides
+        sides: usize,
+        /// Mass per unit of area
+        density: Real,
+    },
+    /// Destroys a body (see [`Physac::destroy_physics_body`])
+    DestroyBody {
+        /// ID of the body
+        id: u32,
+    },
+    /// Shatters a polygon body (see [`Physac::physics_shatter`])
+    Shatter {
+        /// ID of the body
+        id: u32,
+        /// Center of the explosion
+        position: Vector2,
+        /// Force of the explosion
+        force: Real,
+    },
+    /// Adds a force to a body (see [`PhysicsBodyData::add_force`])
+    AddForce {
+        /// ID of the body
+        id: u32,
+        /// Force to add
+        force: Vector2,
+    },
+    /// Adds an angular force to a body (see [`PhysicsBodyData::add_torque`])
+    AddTorque {
+        /// ID of the body
+        id: u32,
+        /// Torque to add
+        torque: Real,
+    },
+    /// Moves a body
+    SetPosition {
+        /// ID of the body
+        id: u32,
+        /// New position
+        position: Vector2,
+    },
+    /// Rotates a body (see [`PhysicsBodyData::set_rotation`])
+    SetRotation {
+        /// ID of the body
+        id: u32,
+        /// New orientation, in radians
+        radians: Real,
+    },
+    /// Sets the velocity of a body
+    SetVelocity {
+        /// ID of the body
+        id: u32,
+        /// New velocity
+        velocity: Vector2,
+    },
+    /// Sets the angular velocity of a body
+    SetAngularVelocity {
+        /// ID of the body
+        id: u32,
+        /// New angular velocity
+        angular_velocity: Real,
+    },
+    /// Turns dynamics of a body on or off (see [`PhysicsBodyData::enabled`])
+    SetEnabled {
+        /// ID of the body
+        id: u32,
+        /// Whether the body is dynamic
+        enabled: bool,
+    },
+    /// Sets the gravity of the world (see [`Physac::set_physics_gravity`])
+    SetGravity {
+        /// New gravity force
+        gravity: Vector2,
+    },
+}
+
+/// The starting state of a world and every command run on it with [`Physac::execute`] while it was being recorded,
+/// which can be replayed with [`Physac::start_replay`]
+///
+/// Record with [`Physac::start_recording`] and [`Physac::stop_recording`]. Only [`PhysicsCommand`]s are recorded,
+/// so changes made by calling methods on the world or its bodies directly are missing from the replay. A checksum of the bodies
+/// is recorded after every step, and a replay that stops matching it fails with [`PhysicsStepError::ReplayDiverged`]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CommandRecording<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize> {
+    /// The world when the recording started
+    start: WorldState<MAX_VERTICES, CIRCLE_VERTICES>,
+    /// Every command, along with the number of steps the world had run when it was executed
+    commands: Vec<(u32, PhysicsCommand)>,
+    /// Number of steps the world had run when the recording stopped
+    end_step: u32,
+    /// Checksum of the bodies after each recorded step
+    checksums: Vec<u64>,
+}
+impl<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize> CommandRecording<MAX_VERTICES, CIRCLE_VERTICES> {
+    /// Returns how many steps were recorded
+    #[must_use]
+    pub const fn steps(&self) -> u32 {
+        self.end_step - self.start.steps_count
+    }
+
+    /// Returns an iterator over the recorded commands, along with how many recorded steps ran before each one
+    #[must_use]
+    pub fn commands(&self) -> impl ExactSizeIterator<Item = (u32, PhysicsCommand)> + '_ {
+        self.commands.iter().map(|&(step, command)| (step - self.start.steps_count, command))
+    }
+}
+
+/// A recording being replayed by a world
+#[derive(Debug)]
+struct Replay<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize> {
+    recording: CommandRecording<MAX_VERTICES, CIRCLE_VERTICES>,
+    /// Index of the next command to run
+    next_command: usize,
+}
+
+/// A collection of variables related to simulating physics
+pub struct Physac<const MAX_VERTICES: usize = 24, const CIRCLE_VERTICES: usize = MAX_VERTICES> {
+    #[cfg(feature = "phys_thread")]
+    fixed_time: f64,
+    /// How many times the simulation will iteratively solve the velocities of each collision and joint every step
+    pub collision_iterations: usize,
+    /// How many times the simulation will push overlapping physics bodies apart every step
+    pub position_iterations: usize,
+    /// How many substeps every step is split into, each simulating an equal part of the time step
+    pub substeps: NonZeroUsize,
+    /// How deep two physics bodies are allowed to overlap
+    pub penetration_allowance: Real,
+    /// How much the engine is allowed to correct physics body positions at a time to push them apart when overlapping
+    pub penetration_correction: Real,
+    /// How many steps in a row a body has to move slower than the sleep thresholds before it falls asleep (0 never lets bodies sleep)
+    pub sleep_steps: u32,
+    /// Speed a body has to stay under to fall asleep, in units per millisecond
+    pub sleep_velocity: Real,
+    /// Angular speed a body has to stay under to fall asleep, in radians per millisecond
+    pub sleep_angular_velocity: Real,
+    /// Whether collisions start solving from the impulses found for the same contact points during the previous step
+    pub warm_starting: bool,
+    /// How the friction of touching bodies is mixed, unless one of them chooses its own [`PhysicsBodyData::friction_combine`]
+    pub friction_combine: CombineMode,
+    /// How the restitution of touching bodies is mixed, unless one of them chooses its own [`PhysicsBodyData::restitution_combine`]
+    pub restitution_combine: CombineMode,
+
+    #[cfg(feature = "phys_thread")]
+    /// Physics thread
+    physics_thread: Option<thread::JoinHandle<()>>,
+
+    /// Offset time for MONOTONIC clock
+    base_time: Instant,
+    /// Start time in milliseconds
+    start_time: f64,
+    /// Delta time used for physics steps, in milliseconds
+    delta_time: f64,
+    /// Current time in milliseconds
+    current_time: f64,
+
+    /// Physics time step delta time accumulator
+    accumulator: f64,
+    /// How fast simulated time passes compared to real time
+    time_scale: f64,
+    /// Whether time has stopped building up for steps
+    is_paused: bool,
+    /// Single steps requested with [`Physac::step_once`] that haven't run yet
+    pending_steps: usize,
+    /// Total physics steps processed
+    steps_count: u32,
+    /// Physics world gravity force
+    gravity: GravityMode,
+    /// How pairs of physics bodies that might be touching are found
+    broadphase: Broadphase,
+    /// Physics bodies sorted into a tree, if the broadphase uses one
+    body_tree: Option<BodyTree<MAX_VERTICES, CIRCLE_VERTICES>>,
+
+    /// Physics bodies pointers array
+    bodies: Vec<Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>>,
+    /// Physics bodies pointers array
+    contacts: Vec<PhysicsManifoldData<MAX_VERTICES, CIRCLE_VERTICES>>,
+    /// Physics joints pointers array
+    joints: Vec<Strong<PhysicsJointData<MAX_VERTICES, CIRCLE_VERTICES>>>,
+    /// Joints that broke since the events were last drained
+    joint_events: Vec<JointBroken<MAX_VERTICES, CIRCLE_VERTICES>>,
+    /// Sensor and visitor bodies that overlapped during the last step
+    sensor_overlaps: Vec<(Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>, Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>)>,
+    /// Sensor overlaps that began or ended since the events were last drained
+    sensor_events: Vec<SensorEvent<MAX_VERTICES, CIRCLE_VERTICES>>,
+    /// Pairs of bodies that touched during the last step, along with their IDs (lowest first)
+    touching_pairs: Vec<((u32, u32), CollisionContact<MAX_VERTICES, CIRCLE_VERTICES>)>,
+    /// Contacts that began, persisted or ended since the events were last drained, along with the IDs of their bodies (lowest first)
+    collision_events: Vec<((u32, u32), CollisionEvent<MAX_VERTICES, CIRCLE_VERTICES>)>,
+    /// Pairs of bodies that never collide with each other
+    ignored_pairs: Vec<[Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>; 2]>,
+    /// Decides what to do with each contact before it is solved
+    pre_solve: Option<Box<dyn PreSolveCallback<MAX_VERTICES, CIRCLE_VERTICES>>>,
+    /// Told about each physics body as it is destroyed
+    on_body_destroyed: Option<Box<dyn BodyDestroyedCallback<MAX_VERTICES, CIRCLE_VERTICES>>>,
+    /// Physics bodies to destroy at the end of the next step
+    deferred_destructions: Vec<Weak<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>>,
+    /// Force fields by ID, in the order they were added
+    force_fields: Vec<(u32, ForceField)>,
+    /// ID given to the next force field
+    next_force_field_id: u32,
+    /// Buoyancy zones by ID, in the order they were added
+    buoyancy_zones: Vec<(u32, BuoyancyZone)>,
+    /// ID given to the next buoyancy zone
+    next_buoyancy_zone_id: u32,
+    /// Pressure of every soft body, in the order they were created
+    soft_bodies: Vec<SoftBodyPressure<MAX_VERTICES, CIRCLE_VERTICES>>,
+    /// ID given to the next soft body
+    next_soft_body_id: u32,
+    /// Particles by ID, in the order they were added
+    particles: Vec<(u32, Particle)>,
+    /// ID given to the next particle
+    next_particle_id: u32,
+    /// Particle constraints by ID, in the order they were added
+    particle_constraints: Vec<(u32, ParticleConstraint<MAX_VERTICES, CIRCLE_VERTICES>)>,
+    /// ID given to the next particle constraint
+    next_particle_constraint_id: u32,
+    /// Fluids by ID, in the order they were added
+    fluids: Vec<(u32, Fluid)>,
+    /// ID given to the next fluid
+    next_fluid_id: u32,
+    /// Normal and tangent impulses of every contact point during the last step, by the IDs of its bodies and its own ID
+    contact_impulses: HashMap<(u32, u32, ContactId), (Real, Real)>,
+    /// Source of the seeds handed out by [`Physac::random_seed`]
+    rng: SplitMix64,
+    /// Recording that commands are added to
+    recording: Option<CommandRecording<MAX_VERTICES, CIRCLE_VERTICES>>,
+    /// Recording whose commands are run as the world reaches their steps
+    replay: Option<Replay<MAX_VERTICES, CIRCLE_VERTICES>>,
+    /// Whether bodies and manifolds can outgrow the capacity they were built with
+    is_storage_growable: bool,
+    /// How many times each physics body ID has been given to a body
+    body_generations: Vec<u32>,
+    /// Counts and timings of the last step
+    stats: StepStats,
+}
+impl<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize> Drop for Physac<MAX_VERTICES, CIRCLE_VERTICES> {
+    fn drop(&mut self) {
+        #[cfg(feature = "phys_thread")]
+        self.physics_thread
+            .take().expect("[PHYSAC] thread should exist if physics has been initialized")
+            .join().expect("[PHYSAC] physics thread failed to close");
+
+        debug_print!("[PHYSAC] physics module closed successfully");
+    }
+}
+
+/// A handle that allows [`Physac`] to be
+#[cfg_attr(    feature = "sync",  doc = "borrowed, locking it so that the current thread isn't racing the physics thread, and")]
+#[cfg_attr(not(feature = "sync"), doc = "accessed,")]
+/// closing physics upon exiting scope or being dropped
+///
+/// Created with [`PhysacBuilder::build`]
+///
+/// # Examples
+///
+/// ```
+/// # use physac::prelude::*;
+/// let mut ph: PhysacHandle<_> = init_physics::<24, 24>().build();
+///
+/// ph.borrowed_mut(|ph| {
+///     ph.create_physics_body_circle(Vector2 { x: 0.0, y: 0.0 }, 45.0, 10.0);
+/// });
+/// assert_eq!(ph.borrow().get_physics_bodies_count(), 1);
+///
+/// ph.borrowed_mut(|ph| {
+///     let body = ph.get_physics_body(0).clone();
+///     ph.destroy_physics_body(body);
+/// });
+/// assert_eq!(ph.borrow().get_physics_bodies_count(), 0);
+/// ```
+pub struct PhysacHandle<T> {
+    #[cfg(feature = "sync")]
+    phys: Arc<RwLock<T>>,
+    #[cfg(not(feature = "sync"))]
+    phys: T,
+
+    /// Physics thread enabled state
+    #[cfg(feature = "phys_thread")]
+    is_physics_thread_enabled: Arc<AtomicBool>,
+}
+impl<T> PhysacHandle<T> {
+    fn new(phys: T) -> Self {
+        #[cfg(feature = "sync")]
+        let phys = Arc::new(RwLock::new(phys));
+        Self {
+            phys,
+            #[cfg(feature = "phys_thread")]
+            is_physics_thread_enabled: Arc::new(AtomicBool::new(false)),
+        }
+    }
+}
+impl<T> Drop for PhysacHandle<T> {
+    /// Unitializes physics pointers and exits physics loop thread
+    fn drop(&mut self) {
+        #[cfg(feature = "phys_thread")]
+        // Exit physics loop thread
+        self.is_physics_thread_enabled.store(false, Release);
+    }
+}
+#[cfg(not(feature = "sync"))]
+impl<T> std::ops::Deref for PhysacHandle<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.phys
+    }
+}
+#[cfg(not(feature = "sync"))]
+impl<T> std::ops::DerefMut for PhysacHandle<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.phys
+    }
+}
+
+/// A handle to the same [`Physac`] as a [`PhysacHandle`], which can be cloned and sent to other threads
+///
+/// Unlike [`PhysacHandle`], it can be borrowed mutably through a shared reference, since the lock keeps threads from racing each other.
+/// Dropping shared handles doesn't close physics; that only happens when the [`PhysacHandle`] is dropped
+///
+/// Created with [`PhysacHandle::share`]
+#[cfg(feature = "sync")]
+pub struct SharedPhysacHandle<T> {
+    phys: Arc<RwLock<T>>,
+}
+#[cfg(feature = "sync")]
+impl<T> Clone for SharedPhysacHandle<T> {
+    fn clone(&self) -> Self {
+        Self { phys: self.phys.clone() }
+    }
+}
+#[cfg(feature = "sync")]
+impl<T> SharedPhysacHandle<T> {
+    /// Borrow Physac from any other threads for the duration of the closure
+    ///
+    /// # Panics
+    ///
+    /// This method may panic if another thread panicked while mutably borrowing Physac
+    pub fn borrowed<U, F>(&self, f: F) -> U
+    where
+        F: FnOnce(&T) -> U
+    {
+        f(&self.borrow())
+    }
+
+    /// Borrow Physac mutably from any other threads for the duration of the closure
+    ///
+    /// # Panics
+    ///
+    /// This method may panic if another thread panicked while mutably borrowing Physac
+    pub fn borrowed_mut<U, F>(&self, f: F) -> U
+    where
+        F: FnOnce(&mut T) -> U
+    {
+        f(&mut self.borrow_mut())
+    }
+
+    /// Borrow Physac from any other threads until the guard goes out of scope
+    ///
+    /// # Panics
+    ///
+    /// This method may panic if another thread panicked while mutably borrowing Physac
+    pub fn borrow(&self) -> PhysacHandleReadGuard<'_, T> {
+        self.phys.read().expect("thread poison recovery is not supported")
+    }
+
+    /// Borrow Physac mutably from any other threads until the guard goes out of scope
+    ///
+    /// # Panics
+    ///
+    /// This method may panic if another thread panicked while mutably borrowing Physac
+    pub fn borrow_mut(&self) -> PhysacHandleWriteGuard<'_, T> {
+        self.phys.write().expect("thread poison recovery is not supported")
+    }
+}
+
+//----------------------------------------------------------------------------------
+// Module Functions Definition
+//----------------------------------------------------------------------------------
+/// A helper for initializing [`Physac`] with custom parameters
+///
+/// Created with [`init_physics()`]
+#[must_use]
+pub struct PhysacBuilder<const MAX_VERTICES: usize = 24, const CIRCLE_VERTICES: usize = MAX_VERTICES> {
+    circle_vertices: usize,
+    fixed_time: f64,
+    collision_iterations: usize,
+    position_iterations: usize,
+    substeps: NonZeroUsize,
+    penetration_allowance: Real,
+    penetration_correction: Real,
+    sleep_steps: u32,
+    sleep_velocity: Real,
+    sleep_angular_velocity: Real,
+    warm_starting: bool,
+    friction_combine: CombineMode,
+    restitution_combine: CombineMode,
+    seed: u64,
+    gravity: GravityMode,
+    max_bodies: usize,
+    max_manifolds: usize,
+    is_max_manifolds_overridden: bool,
+    is_storage_growable: bool,
+    broadphase: Broadphase,
+}
+
+/// How the physics step finds the pairs of physics bodies that might be touching
+///
+/// Set with [`PhysacBuilder::broadphase`]
+///
+/// # Examples
+///
+/// ```
+/// # use physac::prelude::*;
+/// let mut ph = init_physics::<24, 24>()
+///     .broadphase(Broadphase::Tree { margin: 5.0 })
+///     .build();
+///
+/// let floor = ph.borrow_mut()
+///     .create_physics_body_rectangle(Vector2 { x: 500.0, y: 400.0 }, 1000.0, 20.0, 1.0)
+///     .clone();
+/// floor.borrow_mut().enabled = false;
+///
+/// for i in 0..10 {
+///     ph.borrow_mut().create_physics_body_circle(Vector2 { x: 50.0 + 100.0*i as Real, y: 100.0 }, 10.0, 1.0);
+/// }
+///
+/// // Queries only look at the bodies near the region
+/// let found = ph.borrow().query_aabb(Vector2 { x: 0.0, y: 0.0 }, Vector2 { x: 120.0, y: 120.0 });
+/// assert_eq!(found.len(), 1);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Broadphase {
+    /// Tests every pair of bodies whose bounds touch, which is fastest with only a few bodies
+    ///
+    /// With the `simd` feature, the bounds of four bodies are compared at once
+    #[default]
+    AllPairs,
+    /// Sorts bodies into a uniform grid of square cells and only tests bodies that share a cell
+    ///
+    /// Works best when the cells are about the size of a typical body.
+    /// Bodies that would cover a lot of cells (such as large floors) are tested against every other body
+    Grid {
+        /// Width and height of each cell, which has to be positive and finite
+        cell_size: Real,
+    },
+    /// Keeps the bodies in a dynamic bounding volume tree and only tests bodies whose boxes overlap,
+    /// which handles worlds with bodies of very different sizes well
+    ///
+    /// Queries such as [`Physac::query_aabb`] and [`Physac::shape_cast`] also search the tree instead of every body.
+    /// The tree is updated at the start and end of every step, and straight away for bodies moved with
+    /// [`Physac::set_body_position`], [`Physac::set_body_rotation`] or [`Physac::execute`]
+    Tree {
+        /// How far the box of each body in the tree extends past the body, so that it only needs to be moved once the body leaves it
+        ///
+        /// Has to be zero or more, since smaller boxes than the bodies would miss collisions
+        margin: Real,
+    },
+}
+
+/// A [`DynamicTree`] of the physics bodies of a world, along with their indices
+#[derive(Debug, Clone)]
+struct BodyTree<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize> {
+    tree: DynamicTree<(usize, Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>)>,
+    /// The leaf of each body by ID
+    leaves: HashMap<u32, usize>,
+    /// How far the box of each body extends past the body
+    margin: Real,
+}
+impl<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize> BodyTree<MAX_VERTICES, CIRCLE_VERTICES> {
+    fn new(margin: Real) -> Self {
+        Self { tree: DynamicTree::new(), leaves: HashMap::new(), margin }
+    }
+
+    /// Adds new bodies, moves bodies that left their boxes and removes destroyed bodies
+    fn sync(&mut self, bodies: &[Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>]) -> Result<(), PhysicsStepError> {
+        trace_span!("broadphase_sync");
+        let mut ids = Vec::with_capacity(bodies.len());
+        for (index, body) in bodies.iter().enumerate() {
+            let (id, bounds) = {
+                let body = body.read()?;
+                (body.id, body.bounds())
+            };
+            ids.push(id);
+
+            // IDs of destroyed bodies are reused by new ones, which simply take over the leaf
+            if let Some(&leaf) = self.leaves.get(&id) {
+                self.tree.nodes[leaf].item = Some((index, body.clone()));
+                if !self.tree.nodes[leaf].bounds.contains(&bounds) {
+                    self.tree.move_leaf(leaf, bounds.expanded(self.margin));
+                }
+            } else {
+                let leaf = self.tree.insert(bounds.expanded(self.margin), (index, body.clone()));
+                self.leaves.insert(id, leaf);
+            }
+        }
+
+        if self.leaves.len() > ids.len() {
+            // Sorted so that the shape of the tree doesn't depend on the order of the map
+            let mut destroyed_ids: Vec<u32> = self.leaves.keys().copied().filter(|id| !ids.contains(id)).collect();
+            destroyed_ids.sort_unstable();
+            for id in destroyed_ids {
+                if let Some(leaf) = self.leaves.remove(&id) {
+                    self.tree.remove(leaf);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Moves the box of a single body that left it, such as after it was moved by hand
+    fn refit(&mut self, body: &Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>) {
+        let (id, bounds) = {
+            let body = body.borrow();
+            (body.id, body.bounds())
+        };
+        if !self.is_indexed(id) {
+            return;
+        }
+        let leaf = self.leaves[&id];
+        if !self.tree.nodes[leaf].bounds.contains(&bounds) {
+            self.tree.move_leaf(leaf, bounds.expanded(self.margin));
+        }
+    }
+
+    /// Returns the indices of every pair of bodies whose boxes overlap, lowest first
+    fn overlapping_pairs(&self) -> Vec<(usize, usize)> {
+        let mut pairs = Vec::new();
+        for &leaf in self.leaves.values() {
+            let node = &self.tree.nodes[leaf];
+            let Some((index, _)) = &node.item else { continue };
+            self.tree.query(&node.bounds, |other_leaf, &(other_index, _)| {
+                if other_leaf != leaf {
+                    pairs.push(((*index).min(other_index), (*index).max(other_index)));
+                }
+            });
+        }
+        pairs
+    }
+
+    /// Returns true if the body with this ID is in the tree
+    fn is_indexed(&self, id: u32) -> bool {
+        self.leaves.get(&id)
+            .and_then(|&leaf| self.tree.nodes[leaf].item.as_ref())
+            // A destroyed body in the leaf means its ID belongs to a new body now
+            .is_some_and(|(_, body)| body.borrow().is_simulating)
+    }
+
+    /// Returns every body still being simulated whose box overlaps `region`, in the order of the bodies
+    fn query(&self, region: &Aabb) -> Vec<&Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>> {
+        let mut bodies = Vec::new();
+        self.tree.query(region, |_, (index, body)| bodies.push((*index, body)));
+        bodies.retain(|(_, body)| body.borrow().is_simulating);
+        bodies.sort_unstable_by_key(|(index, _)| *index);
+        bodies.into_iter().map(|(_, body)| body).collect()
+    }
+}
+
+/// Initializes physics values, pointers and creates physics loop thread
+///
+/// Every call builds a separate world with its own settings, bodies and (with the `phys_thread` feature) physics thread,
+/// so several worlds can run side by side
+///
+/// # Examples
+///
+/// ```
+/// # use physac::prelude::*;
+/// // A level with regular gravity, and a minigame in space
+/// let mut level = init_physics::<24, 24>().build();
+/// let mut minigame = init_physics::<24, 24>()
+///     .gravity_force(0.0, 0.0)
+///     .fixed_time(1.0/30.0)
+///     .build();
+///
+/// level.borrow_mut().create_physics_body_circle(Vector2 { x: 100.0, y: 100.0 }, 10.0, 1.0);
+/// minigame.borrow_mut().create_physics_body_rectangle(Vector2 { x: 50.0, y: 50.0 }, 20.0, 20.0, 1.0);
+/// minigame.borrow_mut().create_physics_body_rectangle(Vector2 { x: 80.0, y: 50.0 }, 20.0, 20.0, 1.0);
+///
+/// assert_eq!(level.borrow().get_physics_bodies_count(), 1);
+/// assert_eq!(minigame.borrow().get_physics_bodies_count(), 2);
+/// ```
+pub fn init_physics<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize>() -> PhysacBuilder<MAX_VERTICES, CIRCLE_VERTICES> {
+    PhysacBuilder {
+        circle_vertices: MAX_VERTICES,
+        fixed_time: 1.0/60.0,
+        collision_iterations: 20,
+        position_iterations: 1,
+        substeps: NonZeroUsize::MIN,
+        penetration_allowance: 0.05,
+        penetration_correction: 0.4,
+        sleep_steps: 0,
+        sleep_velocity: 0.01,
+        sleep_angular_velocity: 0.0003,
+        warm_starting: false,
+        friction_combine: CombineMode::GeometricMean,
+        restitution_combine: CombineMode::GeometricMean,
+        seed: 0,
+        gravity: GravityMode::Directional(Vector2 { x: 0.0, y: 9.81 }),
+        max_bodies: 64,
+        max_manifolds: 4096,
+        is_max_manifolds_overridden: false,
+        is_storage_growable: false,
+        broadphase: Broadphase::AllPairs,
+    }
+}
+
+/// Initializes physics values, pointers and creates physics loop thread, with storage that grows as bodies are created
+///
+/// Unlike [`init_physics`], the capacity can be chosen at runtime and is only a hint for how much memory to reserve up front
+/// (see [`PhysacBuilder::growable_storage`]), and polygons use the default 24 vertices
+///
+/// # Examples
+///
+/// ```
+/// # use physac::prelude::*;
+/// // Reserve room for the bodies of a level, read from its config
+/// let expected_bodies = 8;
+/// let mut ph = init_physics_dynamic(expected_bodies).build();
+///
+/// for i in 0..100 {
+///     ph.borrow_mut().create_physics_body_circle(Vector2 { x: 10.0*i as Real, y: 100.0 }, 4.0, 1.0);
+/// }
+///
+/// assert_eq!(ph.borrow().get_physics_bodies_count(), 100);
+/// ```
+pub fn init_physics_dynamic(max_hint: usize) -> PhysacBuilder {
+    let mut builder = init_physics();
+    builder
+        .max_bodies(max_hint)
+        .growable_storage(true);
+    builder
+}
+
+impl<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize> PhysacBuilder<MAX_VERTICES, CIRCLE_VERTICES> {
+    /// Set the number of vertices used to approximate [`PhysicsShape::Circle`]s with [`PhysicsBodyData::get_physics_shape_vertex`]
+    pub fn circle_vertices(&mut self, n: NonZeroUsize) -> &mut Self {
+        self.circle_vertices = n.get();
+        self
+    }
+    /// Set the fixed time
+    pub fn fixed_time(&mut self, value: f64) -> &mut Self {
+        self.fixed_time = value;
+        self
+    }
+    /// Set the number of collision iterations, which solve the velocities of collisions and joints
+    pub fn collision_iterations(&mut self, n: usize) -> &mut Self {
+        self.collision_iterations = n;
+        self
+    }
+    /// Set the number of position iterations, which push overlapping physics bodies apart
+    pub fn position_iterations(&mut self, n: usize) -> &mut Self {
+        self.position_iterations = n;
+        self
+    }
+    /// Set the number of velocity (collision) and position iterations together
+    ///
+    /// See [`Physac::set_solver_iterations`]
+    pub fn solver_iterations(&mut self, velocity: usize, position: usize) -> &mut Self {
+        self.collision_iterations = velocity;
+        self.position_iterations = position;
+        self
+    }
+    /// Split every step into `n` substeps, each simulating an equal part of the time step
+    ///
+    /// Fast moving bodies are handled far better by a few substeps than by more iterations,
+    /// since collisions are detected again before bodies have moved as far
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use physac::prelude::*;
+    /// # use std::num::NonZeroUsize;
+    /// // A pinball table, where the ball is much faster than the flippers are thick
+    /// let mut ph = init_physics::<24, 24>()
+    ///     .substeps(NonZeroUsize::new(4).unwrap())
+    ///     .collision_iterations(8)
+    ///     .build();
+    /// ```
+    pub fn substeps(&mut self, n: NonZeroUsize) -> &mut Self {
+        self.substeps = n;
+        self
+    }
+    /// Set the penetration allowance amount
+    pub fn penetration_allowance(&mut self, amount: Real) -> &mut Self {
+        self.penetration_allowance = amount;
+        self
+    }
+    /// Set the penetration correction amount
+    pub fn penetration_correction(&mut self, amount: Real) -> &mut Self {
+        self.penetration_correction = amount;
+        self
+    }
+    /// Let bodies fall asleep after moving slower than the sleep thresholds for `n` steps in a row (0, the default, never lets bodies sleep)
+    ///
+    /// Sleeping bodies are skipped by the simulation until something disturbs them, so piles of bodies at rest cost almost nothing.
+    /// Bodies touching each other or connected by joints only fall asleep together
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use physac::prelude::*;
+    /// let mut ph = init_physics::<24, 24>()
+    ///     .sleep_steps(300)
+    ///     .build();
+    ///
+    /// let crate_body = ph.borrow_mut()
+    ///     .create_physics_body_rectangle(Vector2 { x: 100.0, y: 100.0 }, 20.0, 20.0, 1.0)
+    ///     .clone();
+    ///
+    /// // Keep the player awake all the time
+    /// let player = ph.borrow_mut()
+    ///     .create_physics_body_circle(Vector2 { x: 200.0, y: 100.0 }, 10.0, 1.0)
+    ///     .clone();
+    /// player.borrow_mut().can_sleep = false;
+    ///
+    /// if crate_body.borrow().is_sleeping() {
+    ///     crate_body.borrow_mut().wake_up();
+    /// }
+    /// ```
+    pub fn sleep_steps(&mut self, n: u32) -> &mut Self {
+        self.sleep_steps = n;
+        self
+    }
+    /// Set the speed a body has to stay under to fall asleep, in units per millisecond
+    pub fn sleep_velocity(&mut self, speed: Real) -> &mut Self {
+        self.sleep_velocity = speed;
+        self
+    }
+    /// Set the angular speed a body has to stay under to fall asleep, in radians per millisecond
+    pub fn sleep_angular_velocity(&mut self, speed: Real) -> &mut Self {
+        self.sleep_angular_velocity = speed;
+        self
+    }
+    /// Let collisions start solving from the impulses found for the same contact points during the previous step
+    ///
+    /// The impulses of each contact point are accumulated and clamped instead of being applied one at a time,
+    /// which keeps stacks of bodies from jittering and lets them settle in fewer iterations
+    pub fn warm_starting(&mut self, enabled: bool) -> &mut Self {
+        self.warm_starting = enabled;
+        self
+    }
+    /// Set how the friction of touching bodies is mixed ([`CombineMode::GeometricMean`] by default)
+    pub fn friction_combine(&mut self, mode: CombineMode) -> &mut Self {
+        self.friction_combine = mode;
+        self
+    }
+    /// Set how the restitution of touching bodies is mixed ([`CombineMode::GeometricMean`] by default)
+    pub fn restitution_combine(&mut self, mode: CombineMode) -> &mut Self {
+        self.restitution_combine = mode;
+        self
+    }
+    /// Set the world seed, which decides the seeds handed out by [`Physac::random_seed`]
+    pub fn seed(&mut self, seed: u64) -> &mut Self {
+        self.seed = seed;
+        self
+    }
+    /// Set the direction and strength of gravity in units per second per second
+    pub fn gravity_force(&mut self, x: Real, y: Real) -> &mut Self {
+        self.gravity = GravityMode::Directional(Vector2 { x, y });
+        self
+    }
+    /// Set the direction and strength of gravity in units per second per second with a [`Vector2`]
+    pub fn gravity_force_v(&mut self, v: impl Into<Vector2>) -> &mut Self {
+        let v = v.into();
+        self.gravity = GravityMode::Directional(v);
+        self
+    }
+    /// Set the horizontal strength of gravity in units per second per second
+    ///
+    /// Gravity that isn't [`GravityMode::Directional`] is replaced, with no vertical strength
+    pub fn gravity_force_x(&mut self, x: Real) -> &mut Self {
+        self.directional_gravity().x = x;
+        self
+    }
+    /// Set the vertical strength of gravity in units per second per second
+    ///
+    /// Gravity that isn't [`GravityMode::Directional`] is replaced, with no horizontal strength
+    pub fn gravity_force_y(&mut self, y: Real) -> &mut Self {
+        self.directional_gravity().y = y;
+        self
+    }
+    /// Set how gravity pulls, such as toward a point with [`GravityMode::Radial`]
+    pub fn gravity_mode(&mut self, mode: GravityMode) -> &mut Self {
+        self.gravity = mode;
+        self
+    }
+    /// Returns the acceleration of directional gravity, replacing any other kind of gravity with none
+    fn directional_gravity(&mut self) -> &mut Vector2 {
+        if !matches!(self.gravity, GravityMode::Directional(_)) {
+            self.gravity = GravityMode::Directional(Vector2::zero());
+        }
+        let GravityMode::Directional(acceleration) = &mut self.gravity else { unreachable!("gravity was just made directional") };
+        acceleration
+    }
+    /// Set the initial capacity for physics bodies
+    ///
+    /// Automatically sets `max_manifolds` to `n`<sup>2</sup>,
+    /// if it hasn't been set with [`Self::max_manifolds`] yet
+    pub fn max_bodies(&mut self, n: usize) -> &mut Self {
+        self.max_bodies = n;
+        if !self.is_max_manifolds_overridden {
+            self.max_manifolds = n*n;
+        }
+        self
+    }
+    /// Set the initial capacity for physics body collisions each step
+    pub fn max_manifolds(&mut self, n: usize) -> &mut Self {
+        self.max_manifolds = n;
+        self.is_max_manifolds_overridden = true;
+        self
+    }
+    /// Set whether storage grows past `max_bodies` and `max_manifolds` as needed, instead of refusing to create more physics bodies
+    /// and ignoring further collisions (disabled by default)
+    ///
+    /// When enabled, `max_manifolds` defaults to `max_bodies` instead of its square, since it is only the initial capacity
+    pub fn growable_storage(&mut self, enabled: bool) -> &mut Self {
+        self.is_storage_growable = enabled;
+        self
+    }
+    /// Set how pairs of physics bodies that might be touching are found each step
+    ///
+    /// # Panics
+    ///
+    /// This method panics if the `cell_size` of [`Broadphase::Grid`] is not a positive finite number,
+    /// or if the `margin` of [`Broadphase::Tree`] is negative or not finite
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use physac::prelude::*;
+    /// let ph = init_physics::<24, 24>()
+    ///     .max_bodies(1024)
+    ///     .broadphase(Broadphase::Grid { cell_size: 50.0 })
+    ///     .build();
+    /// ```
+    pub fn broadphase(&mut self, broadphase: Broadphase) -> &mut Self {
+        match broadphase {
+            Broadphase::Grid { cell_size } => assert!(cell_size > 0.0 && cell_size.is_finite(), "the grid cell size should be positive and finite"),
+            Broadphase::Tree { margin } => assert!(margin >= 0.0 && margin.is_finite(), "the tree margin should be positive or zero and finite"),
+            Broadphase::AllPairs => {}
+        }
+        self.broadphase = broadphase;
+        self
+    }
+    /// Construct [`Physac`] with the chosen configuration and put it inside a [`PhysacHandle`]
+    pub fn build(&mut self) -> PhysacHandle<Physac<MAX_VERTICES, CIRCLE_VERTICES>> {
+        let mut phys = Physac {
+            #[cfg(feature = "phys_thread")]
+            fixed_time: self.fixed_time,
+            collision_iterations: self.collision_iterations,
+            position_iterations: self.position_iterations,
+            substeps: self.substeps,
+            penetration_allowance: self.penetration_allowance,
+            penetration_correction: self.penetration_correction,
+            sleep_steps: self.sleep_steps,
+            sleep_velocity: self.sleep_velocity,
+            sleep_angular_velocity: self.sleep_angular_velocity,
+            warm_starting: self.warm_starting,
+            friction_combine: self.friction_combine,
+            restitution_combine: self.restitution_combine,
+            #[cfg(feature = "phys_thread")]
+            physics_thread: None,
+            base_time: Instant::now(),
+            start_time: 0.0,
+            delta_time: self.fixed_time/10.0 * 1000.0,
+            current_time: 0.0,
+            accumulator: 0.0,
+            time_scale: 1.0,
+            is_paused: false,
+            pending_steps: 0,
+            steps_count: 0,
+            gravity: self.gravity,
+            broadphase: self.broadphase,
+            body_tree: match self.broadphase {
+                Broadphase::Tree { margin } => Some(BodyTree::new(margin)),
+                Broadphase::AllPairs | Broadphase::Grid { .. } => None,
+            },
+            bodies: Vec::with_capacity(self.max_bodies),
+            contacts: Vec::with_capacity(if self.is_storage_growable && !self.is_max_manifolds_overridden { self.max_bodies } else { self.max_manifolds }),
+            joints: Vec::new(),
+            joint_events: Vec::new(),
+            sensor_overlaps: Vec::new(),
+            sensor_events: Vec::new(),
+            touching_pairs: Vec::new(),
+            collision_events: Vec::new(),
+            ignored_pairs: Vec::new(),
+            pre_solve: None,
+            on_body_destroyed: None,
+            deferred_destructions: Vec::new(),
+            force_fields: Vec::new(),
+            next_force_field_id: 0,
+            buoyancy_zones: Vec::new(),
+            next_buoyancy_zone_id: 0,
+            soft_bodies: Vec::new(),
+            next_soft_body_id: 0,
+            particles: Vec::new(),
+            next_particle_id: 0,
+            particle_constraints: Vec::new(),
+            next_particle_constraint_id: 0,
+            fluids: Vec::new(),
+            next_fluid_id: 0,
+            contact_impulses: HashMap::new(),
+            rng: SplitMix64::new(self.seed),
+            recording: None,
+            replay: None,
+            is_storage_growable: self.is_storage_growable,
+            body_generations: Vec::new(),
+            stats: StepStats::default(),
+        };
+
+        // Initialize high resolution timer
+        phys.init_timer();
+
+        #[allow(unused_mut, reason = "used mutably if phys_thread is active")]
+        let mut ph = PhysacHandle::new(phys);
+
+        #[cfg(feature = "phys_thread")] {
+            // NOTE: if defined, user will need to create a thread for PhysicsThread function manually
+            // Create physics thread using POSIXS thread libraries
+            let phys_clone = ph.phys.clone();
+            let is_physics_thread_enabled = ph.is_physics_thread_enabled.clone();
+            ph.borrowed_mut(|ph| ph.physics_thread = Some(thread::spawn(move || physics_loop(phys_clone, is_physics_thread_enabled))));
+        }
+
+        debug_print!("[PHYSAC] physics module initialized successfully");
+
+        ph
+    }
+}
+
+impl<T> PhysacHandle<T> {
+    #[cfg(feature = "phys_thread")]
+    /// Returns true if physics thread is currently enabled
+    #[must_use]
+    pub fn is_physics_enabled(&self) -> bool {
+        self.is_physics_thread_enabled.load(Relaxed)
+    }
+
+    /// Borrow Physac from any other threads for the duration of the closure
+    #[cfg_attr(feature = "sync", doc = "\n # Panics\n\n This method may panic if another thread panicked while mutably borrowing Physac")]
+    pub fn borrowed<U, F>(&self, f: F) -> U
+    where
+        F: FnOnce(&T) -> U
+    {
+        let phys = &self.phys;
+        #[cfg(feature = "sync")]
+        let phys = phys.read().expect("thread poison recovery is not supported");
+        #[cfg(feature = "sync")]
+        let phys = &*phys;
+        f(phys)
+    }
+
+    /// Borrow Physac mutably from any other threads for the duration of the closure
+    #[cfg_attr(feature = "sync", doc = "\n # Panics\n\n This method may panic if another thread panicked while mutably borrowing Physac")]
+    pub fn borrowed_mut<U, F>(&mut self, f: F) -> U
+    where
+        F: FnOnce(&mut T) -> U
+    {
+        let phys = &mut self.phys;
+        #[cfg(feature = "sync")]
+        let mut phys = phys.write().expect("thread poison recovery is not supported");
+        f(&mut *phys)
+    }
+
+    /// Borrow Physac from any other threads until the guard goes out of scope
+    #[cfg_attr(feature = "sync", doc = "\n # Panics\n\n This method may panic if another thread panicked while mutably borrowing Physac")]
+    pub fn borrow(&self) -> PhysacHandleReadGuard<'_, T> {
+        let phys = &self.phys;
+        #[cfg(feature = "sync")]
+        let phys = phys.read().expect("thread poison recovery is not supported");
+        phys
+    }
+
+    /// Borrow Physac mutably from any other threads until the guard goes out of scope
+    #[cfg_attr(feature = "sync", doc = "\n # Panics\n\n This method may panic if another thread panicked while mutably borrowing Physac")]
+    pub fn borrow_mut(&mut self) -> PhysacHandleWriteGuard<'_, T> {
+        let phys = &mut self.phys;
+        #[cfg(feature = "sync")]
+        let phys = phys.write().expect("thread poison recovery is not supported");
+        phys
+    }
+
+    /// Returns a handle to the same Physac that can be cloned and sent to other threads, such as a dedicated game logic thread
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use physac::prelude::*;
+    /// let ph = init_physics::<24, 24>().build();
+    ///
+    /// let world = ph.share();
+    /// let logic = std::thread::spawn(move || {
+    ///     world.borrow_mut().create_physics_body_circle(Vector2 { x: 100.0, y: 100.0 }, 10.0, 1.0);
+    /// });
+    /// logic.join().unwrap();
+    ///
+    /// assert_eq!(ph.borrow().get_physics_bodies_count(), 1);
+    /// ```
+    #[cfg(feature = "sync")]
+    #[must_use]
+    pub fn share(&self) -> SharedPhysacHandle<T> {
+        SharedPhysacHandle { phys: self.phys.clone() }
+    }
+}
+
+/// The shape a [`BodyBuilder`] creates its physics body with
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum BodyBuilderShape {
+    Circle { radius: Real },
+    Rectangle { width: Real, height: Real },
+    RoundedRectangle { width: Real, height: Real, corner_radius: Real },
+    Polygon { radius: Real, sides: usize },
+    Capsule { radius: Real, height: Real },
+    Ellipse { radius_x: Real, radius_y: Real },
+}
+
+/// A helper for creating a physics body and setting it up in a single chain
+///
+/// Created with [`Physac::body`]
+///
+/// # Examples
+///
+/// ```
+/// # use physac::prelude::*;
+/// let mut ph = init_physics::<24, 24>().build();
+///
+/// // A static ramp, rotated 30 degrees
+/// let ramp = ph.borrow_mut().body()
+///     .rectangle(250.0, 250.0)
+///     .density(10.0)
+///     .at(Vector2 { x: 25.0, y: 445.0 })
+///     .rotation(Real::to_radians(30.0))
+///     .friction(0.1, 0.1)
+///     .fixed(true)
+///     .build()
+///     .clone();
+///
+/// let ramp = ramp.borrow();
+/// assert!(!ramp.enabled);
+/// assert_eq!(ramp.orient, Real::to_radians(30.0));
+/// assert_eq!(ramp.static_friction, 0.1);
+/// ```
+#[must_use]
+pub struct BodyBuilder<'a, const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize> {
+    physac: &'a mut Physac<MAX_VERTICES, CIRCLE_VERTICES>,
+    shape: Option<BodyBuilderShape>,
+    position: Vector2,
+    density: Real,
+    rotation: Real,
+    friction: Option<(Real, Real)>,
+    restitution: Option<Real>,
+    friction_combine: Option<CombineMode>,
+    restitution_combine: Option<CombineMode>,
+    is_fixed: bool,
+}
+
+impl<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize> BodyBuilder<'_, MAX_VERTICES, CIRCLE_VERTICES> {
+    /// Make the body a circle
+    pub fn circle(&mut self, radius: Real) -> &mut Self {
+        self.shape = Some(BodyBuilderShape::Circle { radius });
+        self
+    }
+    /// Make the body a rectangle
+    pub fn rectangle(&mut self, width: Real, height: Real) -> &mut Self {
+        self.shape = Some(BodyBuilderShape::Rectangle { width, height });
+        self
+    }
+    /// Make the body a rectangle with rounded corners
+    pub fn rounded_rectangle(&mut self, width: Real, height: Real, corner_radius: Real) -> &mut Self {
+        self.shape = Some(BodyBuilderShape::RoundedRectangle { width, height, corner_radius });
+        self
+    }
+    /// Make the body a regular polygon
+    pub fn polygon(&mut self, radius: Real, sides: usize) -> &mut Self {
+        self.shape = Some(BodyBuilderShape::Polygon { radius, sides });
+        self
+    }
+    /// Make the body a capsule
+    pub fn capsule(&mut self, radius: Real, height: Real) -> &mut Self {
+        self.shape = Some(BodyBuilderShape::Capsule { radius, height });
+        self
+    }
+    /// Make the body an ellipse
+    pub fn ellipse(&mut self, radius_x: Real, radius_y: Real) -> &mut Self {
+        self.shape = Some(BodyBuilderShape::Ellipse { radius_x, radius_y });
+        self
+    }
+    /// Set the density of the body (1.0 by default)
+    pub fn density(&mut self, density: Real) -> &mut Self {
+        self.density = density;
+        self
+    }
+    /// Set the position of the body
+    pub fn at(&mut self, position: impl Into<Vector2>) -> &mut Self {
+        let position = position.into();
+        self.position = position;
+        self
+    }
+    /// Set the orientation of the body in radians
+    pub fn rotation(&mut self, radians: Real) -> &mut Self {
+        self.rotation = radians;
+        self
+    }
+    /// Set the static and dynamic friction of the body
+    pub fn friction(&mut self, static_friction: Real, dynamic_friction: Real) -> &mut Self {
+        self.friction = Some((static_friction, dynamic_friction));
+        self
+    }
+    /// Set the restitution of the body
+    pub fn restitution(&mut self, restitution: Real) -> &mut Self {
+        self.restitution = Some(restitution);
+        self
+    }
+    /// Set the density, friction, restitution and combine modes of the body at once
+    pub fn material(&mut self, material: &Material) -> &mut Self {
+        self.density = material.density;
+        self.friction = Some((material.static_friction, material.dynamic_friction));
+        self.restitution = Some(material.restitution);
+        self.friction_combine = material.friction_combine;
+        self.restitution_combine = material.restitution_combine;
+        self
+    }
+    /// Make the body static, so that it collides with other bodies but isn't moved by anything
+    ///
+    /// This is the same as setting [`PhysicsBodyData::enabled`] to false
+    pub fn fixed(&mut self, is_fixed: bool) -> &mut Self {
+        self.is_fixed = is_fixed;
+        self
+    }
+
+    /// Creates the physics body
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PhysacError::MissingShape`] if no shape was chosen, or [`PhysacError::CapacityExceeded`] if every physics body slot is in use
+    pub fn try_build(&mut self) -> Result<&Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>, PhysacError> {
+        let (pos, density) = (self.position, self.density);
+        let body = match self.shape.ok_or(PhysacError::MissingShape)? {
+            BodyBuilderShape::Circle { radius } => self.physac.try_create_physics_body_circle(pos, radius, density),
+            BodyBuilderShape::Rectangle { width, height } => self.physac.try_create_physics_body_rectangle(pos, width, height, density),
+            BodyBuilderShape::RoundedRectangle { width, height, corner_radius } => self.physac.try_create_physics_body_rounded_rectangle(pos, width, height, corner_radius, density),
+            BodyBuilderShape::Polygon { radius, sides } => self.physac.try_create_physics_body_polygon(pos, radius, sides, density),
+            BodyBuilderShape::Capsule { radius, height } => self.physac.try_create_physics_body_capsule(pos, radius, height, density),
+            BodyBuilderShape::Ellipse { radius_x, radius_y } => self.physac.try_create_physics_body_ellipse(pos, radius_x, radius_y, density),
+        }?;
+
+        body.borrowed_mut(|body| {
+            body.set_rotation(self.rotation);
+            if let Some((static_friction, dynamic_friction)) = self.friction {
+                body.static_friction = static_friction;
+                body.dynamic_friction = dynamic_friction;
+            }
+            if let Some(restitution) = self.restitution {
+                body.restitution = restitution;
+            }
+            body.friction_combine = self.friction_combine;
+            body.restitution_combine = self.restitution_combine;
+            body.enabled = !self.is_fixed;
+        });
+        Ok(body)
+    }
+
+    /// Creates the physics body
+    ///
+    /// # Panics
+    ///
+    /// This method may panic if no shape was chosen or there are no available IDs
+    pub fn build(&mut self) -> &Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>> {
+        self.try_build().expect("body builder needs a shape and an available ID")
+    }
+}
+
+impl<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize> Physac<MAX_VERTICES, CIRCLE_VERTICES> {
+    /// Sets physics global gravity force
+    pub fn set_physics_gravity(&mut self, x: Real, y: Real) {
+        self.set_gravity_mode(GravityMode::Directional(Vector2 { x, y }));
+    }
+
+    /// Sets how gravity pulls, such as toward a point with [`GravityMode::Radial`]
+    ///
+    /// Every body is woken up, since resting bodies may not be at rest anymore
+    pub fn set_gravity_mode(&mut self, mode: GravityMode) {
+        self.gravity = mode;
+
+        for body in &self.bodies {
+            body.borrow_mut().wake_up();
+        }
+    }
+
+    /// Returns how gravity pulls
+    #[must_use]
+    pub const fn gravity_mode(&self) -> GravityMode {
+        self.gravity
+    }
+
+    /// Sets how many times every step solves the velocities of collisions and joints, and pushes overlapping physics bodies apart
+    ///
+    /// More iterations keep tall stacks and long chains stiffer, while fewer make large amounts of loose debris cheaper
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use physac::prelude::*;
+    /// let mut ph = init_physics::<24, 24>()
+    ///     .solver_iterations(40, 4)
+    ///     .build();
+    ///
+    /// // The tower has fallen, so the rubble doesn't need as much precision
+    /// ph.borrow_mut().set_solver_iterations(8, 1);
+    /// ```
+    pub fn set_solver_iterations(&mut self, velocity: usize, position: usize) {
+        self.collision_iterations = velocity;
+        self.position_iterations = position;
+    }
+
+    /// Starts creating a new physics body with a [`BodyBuilder`], which can set its shape, placement and material in one chain
+    pub fn body(&mut self) -> BodyBuilder<'_, MAX_VERTICES, CIRCLE_VERTICES> {
+        BodyBuilder {
+            physac: self,
+            shape: None,
+            position: Vector2 { x: 0.0, y: 0.0 },
+            density: 1.0,
+            rotation: 0.0,
+            friction: None,
+            restitution: None,
+            friction_combine: None,
+            restitution_combine: None,
+            is_fixed: false,
+        }
+    }
+
+    /// Creates a new physics body with the provided shape and generic parameters
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PhysacError::CapacityExceeded`] if every physics body slot is in use
+    pub fn try_create_physics_body(&mut self, pos: impl Into<Vector2>, shape: PhysicsShape<MAX_VERTICES, CIRCLE_VERTICES>, inertia: Real, mass: Real) -> Result<&Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>, PhysacError> {
+        let pos = pos.into();
+        if let Some(new_id) = self.find_available_body_index() {
+            // Initialize new body with generic values
+            let new_body = PhysicsBodyData {
+                id: new_id,
+                enabled: true,
+                position: pos,
+                velocity: Vector2::zero(),
+                force: Vector2::zero(),
+                angular_velocity: 0.0,
+                torque: 0.0,
+                orient: 0.0,
+                inertia,
+                inverse_inertia: if inertia != 0.0 { 1.0/inertia } else { 0.0 },
+                shape,
+                mass,
+                inverse_mass: if mass != 0.0 { 1.0/mass } else { 0.0 },
+                static_friction: 0.4,
+                dynamic_friction: 0.2,
+                restitution: 0.0,
+                use_gravity: true,
+                gravity_scale: 1.0,
+                linear_damping: 0.0,
+                angular_damping: 0.0,
+                max_linear_velocity: None,
+                max_angular_velocity: None,
+                friction_combine: None,
+                restitution_combine: None,
+                surface_velocity: 0.0,
+                user_data: 0,
+                name: String::new(),
+                is_grounded: false,
+                freeze_orient: false,
+                is_sensor: false,
+                collision_category: 1,
+                collision_mask: u32::MAX,
+                collision_group: 0,
+                can_sleep: true,
+                is_kinematic: false,
+                has_stale_contacts: false,
+                settled_transform: None,
+                has_new_shape: false,
+                is_sleeping: false,
+                resting_steps: 0,
+                previous_transform: None,
+                rotation_limits: None,
+                shape_offset: Vector2::zero(),
+                is_simulating: true,
+            };
+
+            // Add new body to bodies pointers array and update bodies count
+            self.bodies.push(Strong::new(new_body));
+
+            debug_print!("[PHYSAC] created physics body id {new_id}");
+            let [.., result] = &self.bodies[..] else { unreachable!("should have at least one element after pushing") };
+            Ok(result)
+        } else {
+            debug_print!("[PHYSAC] new physics body creation failed because there isn't any available id to use");
+            Err(PhysacError::CapacityExceeded { capacity: self.bodies.capacity() })
+        }
+    }
+
+    /// Creates a new physics body with the provided shape and generic parameters
+    ///
+    /// # Panics
+    ///
+    /// This method may panic if there are no available IDs
+    #[inline]
+    pub fn create_physics_body(&mut self, pos: impl Into<Vector2>, shape: PhysicsShape<MAX_VERTICES, CIRCLE_VERTICES>, inertia: Real, mass: Real) -> &Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>> {
+        let pos = pos.into();
+        self.try_create_physics_body(pos, shape, inertia, mass).unwrap()
+    }
+
+    /// Creates a new circle physics body with generic parameters
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PhysacError::CapacityExceeded`] if every physics body slot is in use
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use physac::prelude::*;
+    /// let mut ph = init_physics::<24, 24>().max_bodies(8).build();
+    /// let mut ph = ph.borrow_mut();
+    ///
+    /// for i in 0..10 {
+    ///     if let Err(error) = ph.try_create_physics_body_circle(Vector2 { x: 10.0*i as Real, y: 100.0 }, 2.0, 1.0) {
+    ///         // Stop spawning debris once the world is full, instead of failing without a word
+    ///         assert_eq!(error, PhysacError::CapacityExceeded { capacity: 8 });
+    ///         break;
+    ///     }
+    /// }
+    /// ```
+    pub fn try_create_physics_body_circle(&mut self, pos: impl Into<Vector2>, radius: Real, density: Real) -> Result<&Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>, PhysacError> {
+        let pos = pos.into();
+        if let Some(new_id) = self.find_available_body_index() {
+            // Initialize new body with generic values
+            let mut new_body = PhysicsBodyData {
+                id: new_id,
+                enabled: true,
+                position: pos,
+                velocity: Vector2::zero(),
+                force: Vector2::zero(),
+                angular_velocity: 0.0,
+                torque: 0.0,
+                orient: 0.0,
+                shape: PHYSICS_CIRCLE { radius },
+
+                mass: (PI*real_to_f64(radius)*real_to_f64(radius)*real_to_f64(density)) as Real,
+                static_friction: 0.4,
+                dynamic_friction: 0.2,
+                restitution: 0.0,
+                use_gravity: true,
+                is_grounded: false,
+                freeze_orient: false,
+                is_sensor: false,
+                collision_category: 1,
+                collision_mask: u32::MAX,
+                collision_group: 0,
+                can_sleep: true,
+                is_sleeping: false,
+                resting_steps: 0,
+                previous_transform: None,
+                rotation_limits: None,
+                is_simulating: true,
+                ..Default::default()
+            };
+            new_body.inverse_mass = if new_body.mass != 0.0 { 1.0/new_body.mass } else { 0.0 };
+            new_body.inertia = new_body.mass*radius*radius;
+            new_body.inverse_inertia = if new_body.inertia != 0.0 { 1.0/new_body.inertia } else { 0.0 };
+
+            // Add new body to bodies pointers array and update bodies count
+            self.bodies.push(Strong::new(new_body));
+
+            debug_print!("[PHYSAC] created polygon physics body id {new_id}");
+            let [.., result] = &self.bodies[..] else { unreachable!("should have at least one element after pushing") };
+            Ok(result)
+        } else {
+            debug_print!("[PHYSAC] new physics body creation failed because there isn't any available id to use");
+            Err(PhysacError::CapacityExceeded { capacity: self.bodies.capacity() })
+        }
+    }
+
+    /// Creates a new circle physics body with generic parameters
+    ///
+    /// # Panics
+    ///
+    /// This method may panic if there are no available IDs
+    #[inline]
+    pub fn create_physics_body_circle(&mut self, pos: impl Into<Vector2>, radius: Real, density: Real) -> &Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>> {
+        let pos = pos.into();
+        self.try_create_physics_body_circle(pos, radius, density).unwrap()
+    }
+
+    /// Creates a new rectangle physics body with generic parameters
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PhysacError::CapacityExceeded`] if every physics body slot is in use
+    pub fn try_create_physics_body_rectangle(&mut self, pos: impl Into<Vector2>, width: Real, height: Real, density: Real) -> Result<&Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>, PhysacError> {
+        let pos = pos.into();
+        if let Some(new_id) = self.find_available_body_index() {
+            // Initialize new body with generic values
+            let mut vertex_data = PolygonData::create_rectangle_polygon(pos, Vector2 { x: width, y: height });
+
+            // Calculate centroid and moment of inertia
+            let mut center = Vector2 { x: 0.0, y: 0.0 };
+            let mut area = 0.0;
+            let mut inertia = 0.0;
+
+            for i in 0..vertex_data.vertex_count {
+                // Triangle vertices, third vertex implied as (0, 0)
+                let p1 = vertex_data.positions[i];
+                let next_index = next_idx(i, vertex_data.vertex_count);
+                let p2 = vertex_data.positions[next_index];
+
+                #[allow(non_snake_case)]
+                let D = math_cross_vector2(p1, p2);
+                let triangle_area = D/2.0;
+
+                area += triangle_area;
+
+                // Use area to weight the centroid average, not just vertex position
+                center.x += triangle_area*PHYSAC_K*(p1.x + p2.x);
+                center.y += triangle_area*PHYSAC_K*(p1.y + p2.y);
+
+                let intx2 = p1.x*p1.x + p2.x*p1.x + p2.x*p2.x;
+                let inty2 = p1.y*p1.y + p2.y*p1.y + p2.y*p2.y;
+                inertia += (0.25*PHYSAC_K*D)*(intx2 + inty2);
+            }
+
+            center.x *= 1.0/area;
+            center.y *= 1.0/area;
+
+            // Translate vertices to centroid (make the centroid (0, 0) for the polygon in model space)
+            // Note: this is not really necessary
+            for i in 0..vertex_data.vertex_count {
+                vertex_data.positions[i].x -= center.x;
+                vertex_data.positions[i].y -= center.y;
+            }
+
+            let mut new_body = PhysicsBodyData {
+                id: new_id,
+                enabled: true,
+                position: pos,
+                velocity: Vector2::zero(),
+                force: Vector2::zero(),
+                angular_velocity: 0.0,
+                torque: 0.0,
+                orient: 0.0,
+                shape: PHYSICS_POLYGON {
+                    vertex_data,
+                    transform: Mat2::radians(0.0),
+                },
+                mass: density*area,
+                static_friction: 0.4,
+                dynamic_friction: 0.2,
+                restitution: 0.0,
+                use_gravity: true,
+                is_grounded: false,
+                freeze_orient: false,
+                is_sensor: false,
+                collision_category: 1,
+                collision_mask: u32::MAX,
+                collision_group: 0,
+                can_sleep: true,
+                is_sleeping: false,
+                resting_steps: 0,
+                previous_transform: None,
+                rotation_limits: None,
+                is_simulating: true,
+                ..Default::default()
+            };
+            new_body.inverse_mass = if new_body.mass != 0.0 { 1.0/new_body.mass } else { 0.0 };
+            new_body.inertia = density*inertia;
+            new_body.inverse_inertia = if new_body.inertia != 0.0 { 1.0/new_body.inertia } else { 0.0 };
+
+            // Add new body to bodies pointers array and update bodies count
+            self.bodies.push(Strong::new(new_body));
+
+            debug_print!("[PHYSAC] created polygon physics body id {new_id}");
+            let [.., result] = &self.bodies[..] else { unreachable!("should have at least one element after pushing") };
+            Ok(result)
+        } else {
+            debug_print!("[PHYSAC] new physics body creation failed because there isn't any available id to use");
+            Err(PhysacError::CapacityExceeded { capacity: self.bodies.capacity() })
+        }
+    }
+
+    /// Creates a new rectangle physics body with generic parameters
+    ///
+    /// # Panics
+    ///
+    /// This method may panic if there are no available IDs
+    #[inline]
+    pub fn create_physics_body_rectangle(&mut self, pos: impl Into<Vector2>, width: Real, height: Real, density: Real) -> &Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>> {
+        let pos = pos.into();
+        self.try_create_physics_body_rectangle(pos, width, height, density).unwrap()
+    }
+
+    /// Creates a new rectangle physics body with rounded corners
+    ///
+    /// `width` and `height` are the full size of the rectangle, including the rounded corners, and
+    /// `corner_radius` is limited to just under half of the smaller side
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PhysacError::CapacityExceeded`] if every physics body slot is in use
+    pub fn try_create_physics_body_rounded_rectangle(&mut self, pos: impl Into<Vector2>, width: Real, height: Real, corner_radius: Real, density: Real) -> Result<&Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>, PhysacError> {
+        let pos = pos.into();
+        if let Some(new_id) = self.find_available_body_index() {
+            let vertex_data = PolygonData::create_rounded_rectangle_polygon(Vector2 { x: width, y: height }, corner_radius);
+            let (_, area, inertia) = vertex_data.mass_data();
+
+            let mut new_body = PhysicsBodyData {
+                id: new_id,
+                enabled: true,
+                position: pos,
+                velocity: Vector2::zero(),
+                force: Vector2::zero(),
+                angular_velocity: 0.0,
+                torque: 0.0,
+                orient: 0.0,
+                shape: PHYSICS_POLYGON {
+                    vertex_data,
+                    transform: Mat2::radians(0.0),
+                },
+                mass: density*area,
+                static_friction: 0.4,
+                dynamic_friction: 0.2,
+                restitution: 0.0,
+                use_gravity: true,
+                is_grounded: false,
+                freeze_orient: false,
+                is_sensor: false,
+                collision_category: 1,
+                collision_mask: u32::MAX,
+                collision_group: 0,
+                can_sleep: true,
+                is_sleeping: false,
+                resting_steps: 0,
+                previous_transform: None,
+                rotation_limits: None,
+                is_simulating: true,
+                ..Default::default()
+            };
+            new_body.inverse_mass = if new_body.mass != 0.0 { 1.0/new_body.mass } else { 0.0 };
+            new_body.inertia = density*inertia;
+            new_body.inverse_inertia = if new_body.inertia != 0.0 { 1.0/new_body.inertia } else { 0.0 };
+
+            // Add new body to bodies pointers array and update bodies count
+            self.bodies.push(Strong::new(new_body));
+
+            debug_print!("[PHYSAC] created rounded polygon physics body id {new_id}");
+            let [.., result] = &self.bodies[..] else { unreachable!("should have at least one element after pushing") };
+            Ok(result)
+        } else {
+            debug_print!("[PHYSAC] new physics body creation failed because there isn't any available id to use");
+            Err(PhysacError::CapacityExceeded { capacity: self.bodies.capacity() })
+        }
+    }
+
+    /// Creates a new rectangle physics body with rounded corners
+    ///
+    /// `width` and `height` are the full size of the rectangle, including the rounded corners, and
+    /// `corner_radius` is limited to just under half of the smaller side
+    ///
+    /// # Panics
+    ///
+    /// This method may panic if there are no available IDs
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use physac::prelude::*;
+    /// let mut ph = init_physics::<24, 24>().build();
+    ///
+    /// let crate_body = ph.borrow_mut()
+    ///     .create_physics_body_rounded_rectangle(Vector2 { x: 100.0, y: 100.0 }, 40.0, 40.0, 5.0, 1.0)
+    ///     .clone();
+    ///
+    /// crate_body.borrowed(|crate_body| {
+    ///     let PHYSICS_POLYGON { vertex_data, .. } = &crate_body.shape else { unreachable!() };
+    ///     assert_eq!(vertex_data.corner_radius(), 5.0);
+    ///
+    ///     // Slightly lighter than a sharp 40x40 box
+    ///     assert!(crate_body.mass < 1600.0 && crate_body.mass > 1550.0);
+    /// });
+    /// ```
+    #[inline]
+    pub fn create_physics_body_rounded_rectangle(&mut self, pos: impl Into<Vector2>, width: Real, height: Real, corner_radius: Real, density: Real) -> &Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>> {
+        let pos = pos.into();
+        self.try_create_physics_body_rounded_rectangle(pos, width, height, corner_radius, density).unwrap()
+    }
+
+    /// Creates a new polygon physics body with generic parameters
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PhysacError::CapacityExceeded`] if every physics body slot is in use
+    pub fn try_create_physics_body_polygon(&mut self, pos: impl Into<Vector2>, radius: Real, sides: usize, density: Real) -> Result<&Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>, PhysacError> {
+        let pos = pos.into();
+        if let Some(new_id) = self.find_available_body_index() {
+            // Initialize new body with generic values
+            let mut vertex_data = PolygonData::create_random_polygon(radius, sides);
+
+            // Calculate centroid and moment of inertia
+            let mut center = Vector2 { x: 0.0, y: 0.0 };
+            let mut area = 0.0;
+            let mut inertia = 0.0;
+
+            for i in 0..vertex_data.vertex_count {
+                // Triangle vertices, third vertex implied as (0, 0)
+                let position1 = vertex_data.positions[i];
+                let next_index = next_idx(i, vertex_data.vertex_count);
+                let position2 = vertex_data.positions[next_index];
+
+                let cross = math_cross_vector2(position1, position2);
+                let triangle_area = cross/2.0;
+
+                area += triangle_area;
+
+                // Use area to weight the centroid average, not just vertex position
+                center.x += triangle_area*PHYSAC_K*(position1.x + position2.x);
+                center.y += triangle_area*PHYSAC_K*(position1.y + position2.y);
+
+                let intx2 = position1.x*position1.x + position2.x*position1.x + position2.x*position2.x;
+                let inty2 = position1.y*position1.y + position2.y*position1.y + position2.y*position2.y;
+                inertia += (0.25*PHYSAC_K*cross)*(intx2 + inty2);
+            }
+
+            center.x *= 1.0/area;
+            center.y *= 1.0/area;
+
+            // Translate vertices to centroid (make the centroid (0, 0) for the polygon in model space)
+            // Note: this is not really necessary
+            for i in 0..vertex_data.vertex_count {
+                vertex_data.positions[i].x -= center.x;
+                vertex_data.positions[i].y -= center.y;
+            }
+
+            let mut new_body = PhysicsBodyData {
+                id: new_id,
+                enabled: true,
+                position: pos,
+                velocity: Vector2::zero(),
+                force: Vector2::zero(),
+                angular_velocity: 0.0,
+                torque: 0.0,
+                orient: 0.0,
+                shape: PHYSICS_POLYGON {
+                    vertex_data,
+                    transform: Mat2::radians(0.0),
+                },
+                mass: density*area,
+                static_friction: 0.4,
+                dynamic_friction: 0.2,
+                restitution: 0.0,
+                use_gravity: true,
+                is_grounded: false,
+                freeze_orient: false,
+                is_sensor: false,
+                collision_category: 1,
+                collision_mask: u32::MAX,
+                collision_group: 0,
+                can_sleep: true,
+                is_sleeping: false,
+                resting_steps: 0,
+                previous_transform: None,
+                rotation_limits: None,
+                is_simulating: true,
+                ..Default::default()
+            };
+            new_body.inverse_mass = if new_body.mass != 0.0 { 1.0/new_body.mass } else { 0.0 };
+            new_body.inertia = density*inertia;
+            new_body.inverse_inertia = if new_body.inertia != 0.0 { 1.0/new_body.inertia } else { 0.0 };
+
+            // Add new body to bodies pointers array and update bodies count
+            self.bodies.push(Strong::new(new_body));
+
+            debug_print!("[PHYSAC] created polygon physics body id {new_id}");
+            let [.., result] = &self.bodies[..] else { unreachable!("should have at least one element after pushing") };
+            Ok(result)
+        } else {
+            debug_print!("[PHYSAC] new physics body creation failed because there isn't any available id to use");
+            Err(PhysacError::CapacityExceeded { capacity: self.bodies.capacity() })
+        }
+    }
+
+    /// Creates a new polygon physics body with generic parameters
+    ///
+    /// # Panics
+    ///
+    /// This method may panic if there are no available IDs
+    #[inline]
+    pub fn create_physics_body_polygon(&mut self, pos: impl Into<Vector2>, radius: Real, sides: usize, density: Real) -> &Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>> {
+        let pos = pos.into();
+        self.try_create_physics_body_polygon(pos, radius, sides, density).unwrap()
+    }
+
+    /// Creates a new convex polygon physics body from a list of vertices in world space
+    ///
+    /// The body is positioned at the centroid of the polygon, and the vertices may be given in either winding order
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PhysacError::InvalidPolygon`] if the vertices do not form a valid convex polygon
+    /// (see [`PolygonData::from_convex_vertices`]), or [`PhysacError::CapacityExceeded`] if there are no available IDs
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use physac::prelude::*;
+    /// let mut ph = init_physics::<24, 24>().build();
+    ///
+    /// let ramp = ph.borrow_mut()
+    ///     .create_physics_body_polygon_from_vertices(&[
+    ///         Vector2 { x:   0.0, y: 300.0 },
+    ///         Vector2 { x: 300.0, y: 300.0 },
+    ///         Vector2 { x: 300.0, y: 200.0 },
+    ///     ], 10.0)
+    ///     .unwrap()
+    ///     .clone();
+    ///
+    /// ramp.borrowed(|ramp| {
+    ///     assert_eq!(ramp.position, Vector2 { x: 200.0, y: 800.0/3.0 });
+    ///     assert_eq!(ramp.mass, 10.0*15_000.0);
+    /// });
+    /// ```
+    pub fn create_physics_body_polygon_from_vertices(&mut self, vertices: &[Vector2], density: Real) -> Result<&Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>, PhysacError> {
+        let mut vertex_data = PolygonData::from_convex_vertices(vertices)?;
+
+        if let Some(new_id) = self.find_available_body_index() {
+            // Translate vertices to centroid (make the centroid (0, 0) for the polygon in model space)
+            let (center, area, _) = vertex_data.mass_data();
+            for position in vertex_data.positions_mut() {
+                *position -= center;
+            }
+
+            // Calculate moment of inertia about the centroid
+            let (_, _, inertia) = vertex_data.mass_data();
+
+            let mut new_body = PhysicsBodyData {
+                id: new_id,
+                enabled: true,
+                position: center,
+                velocity: Vector2::zero(),
+                force: Vector2::zero(),
+                angular_velocity: 0.0,
+                torque: 0.0,
+                orient: 0.0,
+                shape: PHYSICS_POLYGON {
+                    vertex_data,
+                    transform: Mat2::radians(0.0),
+                },
+                mass: density*area,
+                static_friction: 0.4,
+                dynamic_friction: 0.2,
+                restitution: 0.0,
+                use_gravity: true,
+                is_grounded: false,
+                freeze_orient: false,
+                is_sensor: false,
+                collision_category: 1,
+                collision_mask: u32::MAX,
+                collision_group: 0,
+                can_sleep: true,
+                is_sleeping: false,
+                resting_steps: 0,
+                previous_transform: None,
+                rotation_limits: None,
+                is_simulating: true,
+                ..Default::default()
+            };
+            new_body.inverse_mass = if new_body.mass != 0.0 { 1.0/new_body.mass } else { 0.0 };
+            new_body.inertia = density*inertia;
+            new_body.inverse_inertia = if new_body.inertia != 0.0 { 1.0/new_body.inertia } else { 0.0 };
+
+            // Add new body to bodies pointers array and update bodies count
+            self.bodies.push(Strong::new(new_body));
+
+            debug_print!("[PHYSAC] created polygon physics body id {new_id}");
+            let [.., result] = &self.bodies[..] else { unreachable!("should have at least one element after pushing") };
+            Ok(result)
+        } else {
+            debug_print!("[PHYSAC] new physics body creation failed because there isn't any available id to use");
+            Err(PhysacError::CapacityExceeded { capacity: self.bodies.capacity() })
+        }
+    }
+
+    /// Creates a new capsule physics body with generic parameters
+    ///
+    /// `height` is the total height of the capsule, including both rounded ends, and is treated as at least `2*radius`
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PhysacError::CapacityExceeded`] if every physics body slot is in use
+    pub fn try_create_physics_body_capsule(&mut self, pos: impl Into<Vector2>, radius: Real, height: Real, density: Real) -> Result<&Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>, PhysacError> {
+        let pos = pos.into();
+        if let Some(new_id) = self.find_available_body_index() {
+            // Calculate mass and moment of inertia as a rectangle between two half circles
+            let shape = PHYSICS_CAPSULE { radius, height: height.max(2.0*radius) };
+            let (_, area, inertia) = shape_mass_data(&shape);
+
+            // Initialize new body with generic values
+            let mut new_body = PhysicsBodyData {
+                id: new_id,
+                enabled: true,
+                position: pos,
+                velocity: Vector2::zero(),
+                force: Vector2::zero(),
+                angular_velocity: 0.0,
+                torque: 0.0,
+                orient: 0.0,
+                shape,
+                mass: density*area,
+                static_friction: 0.4,
+                dynamic_friction: 0.2,
+                restitution: 0.0,
+                use_gravity: true,
+                is_grounded: false,
+                freeze_orient: false,
+                is_sensor: false,
+                collision_category: 1,
+                collision_mask: u32::MAX,
+                collision_group: 0,
+                can_sleep: true,
+                is_sleeping: false,
+                resting_steps: 0,
+                previous_transform: None,
+                rotation_limits: None,
+                is_simulating: true,
+                ..Default::default()
+            };
+            new_body.inverse_mass = if new_body.mass != 0.0 { 1.0/new_body.mass } else { 0.0 };
+            new_body.inertia = density*inertia;
+            new_body.inverse_inertia = if new_body.inertia != 0.0 { 1.0/new_body.inertia } else { 0.0 };
+
+            // Add new body to bodies pointers array and update bodies count
+            self.bodies.push(Strong::new(new_body));
+
+            debug_print!("[PHYSAC] created capsule physics body id {new_id}");
+            let [.., result] = &self.bodies[..] else { unreachable!("should have at least one element after pushing") };
+            Ok(result)
+        } else {
+            debug_print!("[PHYSAC] new physics body creation failed because there isn't any available id to use");
+            Err(PhysacError::CapacityExceeded { capacity: self.bodies.capacity() })
+        }
+    }
+
+    /// Creates a new capsule physics body with generic parameters
+    ///
+    /// `height` is the total height of the capsule, including both rounded ends, and is treated as at least `2*radius`
+    ///
+    /// # Panics
+    ///
+    /// This method may panic if there are no available IDs
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use physac::prelude::*;
+    /// let mut ph = init_physics::<24, 24>().build();
+    ///
+    /// let player = ph.borrow_mut()
+    ///     .create_physics_body_capsule(Vector2 { x: 100.0, y: 100.0 }, 10.0, 50.0, 1.0)
+    ///     .clone();
+    ///
+    /// player.borrowed(|player| {
+    ///     assert!(matches!(player.shape, PHYSICS_CAPSULE { radius: 10.0, height: 50.0 }));
+    ///     assert_eq!(player.get_physics_shape_vertices_count(), 24);
+    /// });
+    /// ```
+    #[inline]
+    pub fn create_physics_body_capsule(&mut self, pos: impl Into<Vector2>, radius: Real, height: Real, density: Real) -> &Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>> {
+        let pos = pos.into();
+        self.try_create_physics_body_capsule(pos, radius, height, density).unwrap()
+    }
+
+    /// Creates a new ellipse physics body with generic parameters
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PhysacError::CapacityExceeded`] if every physics body slot is in use
+    pub fn try_create_physics_body_ellipse(&mut self, pos: impl Into<Vector2>, radius_x: Real, radius_y: Real, density: Real) -> Result<&Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>, PhysacError> {
+        let pos = pos.into();
+        if let Some(new_id) = self.find_available_body_index() {
+            let shape = PhysicsShape::ellipse(radius_x, radius_y);
+            let (_, area, inertia) = shape_mass_data(&shape);
+
+            // Initialize new body with generic values
+            let mut new_body = PhysicsBodyData {
+                id: new_id,
+                enabled: true,
+                position: pos,
+                velocity: Vector2::zero(),
+                force: Vector2::zero(),
+                angular_velocity: 0.0,
+                torque: 0.0,
+                orient: 0.0,
+                shape,
+                mass: density*area,
+                static_friction: 0.4,
+                dynamic_friction: 0.2,
+                restitution: 0.0,
+                use_gravity: true,
+                is_grounded: false,
+                freeze_orient: false,
+                is_sensor: false,
+                collision_category: 1,
+                collision_mask: u32::MAX,
+                collision_group: 0,
+                can_sleep: true,
+                is_sleeping: false,
+                resting_steps: 0,
+                previous_transform: None,
+                rotation_limits: None,
+                is_simulating: true,
+                ..Default::default()
+            };
+            new_body.inverse_mass = if new_body.mass != 0.0 { 1.0/new_body.mass } else { 0.0 };
+            new_body.inertia = density*inertia;
+            new_body.inverse_inertia = if new_body.inertia != 0.0 { 1.0/new_body.inertia } else { 0.0 };
+
+            // Add new body to bodies pointers array and update bodies count
+            self.bodies.push(Strong::new(new_body));
+
+            debug_print!("[PHYSAC] created ellipse physics body id {new_id}");
+            let [.., result] = &self.bodies[..] else { unreachable!("should have at least one element after pushing") };
+            Ok(result)
+        } else {
+            debug_print!("[PHYSAC] new physics body creation failed because there isn't any available id to use");
+            Err(PhysacError::CapacityExceeded { capacity: self.bodies.capacity() })
+        }
+    }
+
+    /// Creates a new ellipse physics body with generic parameters
+    ///
+    /// Collisions use a rounded polygon that fits just inside the ellipse, while the mass is that of the exact ellipse.
+    /// The moment of inertia is `mass*(radius_x*radius_x + radius_y*radius_y)/2`, so equal radii turn like [`Physac::create_physics_body_circle`]
+    ///
+    /// # Panics
+    ///
+    /// This method may panic if there are no available IDs
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use physac::prelude::*;
+    /// let mut ph = init_physics::<24, 24>().build();
+    ///
+    /// let egg = ph.borrow_mut()
+    ///     .create_physics_body_ellipse(Vector2 { x: 100.0, y: 100.0 }, 20.0, 30.0, 1.0)
+    ///     .clone();
+    ///
+    /// egg.borrowed(|egg| {
+    ///     assert!(matches!(egg.shape, PHYSICS_ELLIPSE { radius_x: 20.0, radius_y: 30.0, .. }));
+    ///     assert_eq!(egg.get_physics_shape_vertex(0), Vector2 { x: 120.0, y: 100.0 });
+    ///     assert!((egg.mass - std::f32::consts::PI*20.0*30.0).abs() < 0.01);
+    /// });
+    /// ```
+    #[inline]
+    pub fn create_physics_body_ellipse(&mut self, pos: impl Into<Vector2>, radius_x: Real, radius_y: Real, density: Real) -> &Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>> {
+        let pos = pos.into();
+        self.try_create_physics_body_ellipse(pos, radius_x, radius_y, density).unwrap()
+    }
+
+    /// Creates a new physics body made of several shapes, each with its own offset and rotation relative to `pos`
+    ///
+    /// Mass and moment of inertia are combined from every fixture. The body is positioned at the centroid of all fixtures
+    /// (so that it rotates around its center of mass), and the fixture offsets are moved to be relative to that position
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PhysacError::EmptyCompound`] if `fixtures` is empty, or [`PhysacError::CapacityExceeded`] if every physics body slot is in use
+    pub fn try_create_physics_body_compound(&mut self, pos: impl Into<Vector2>, mut fixtures: Vec<PhysicsFixture<MAX_VERTICES, CIRCLE_VERTICES>>, density: Real) -> Result<&Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>, PhysacError> {
+        let pos = pos.into();
+        if fixtures.is_empty() {
+            return Err(PhysacError::EmptyCompound);
+        }
+        if let Some(new_id) = self.find_available_body_index() {
+            // Translate fixtures to centroid (make the centroid (0, 0) for the body in model space)
+            let (center, area, _) = fixtures_mass_data(&fixtures);
+            for fixture in &mut fixtures {
+                fixture.offset -= center;
+            }
+
+            // Calculate moment of inertia about the centroid
+            let (_, _, inertia) = fixtures_mass_data(&fixtures);
+
+            // Initialize new body with generic values
+            let mut new_body = PhysicsBodyData {
+                id: new_id,
+                enabled: true,
+                position: pos + center,
+                velocity: Vector2::zero(),
+                force: Vector2::zero(),
+                angular_velocity: 0.0,
+                torque: 0.0,
+                orient: 0.0,
+                shape: PHYSICS_COMPOUND { fixtures },
+                mass: density*area,
+                static_friction: 0.4,
+                dynamic_friction: 0.2,
+                restitution: 0.0,
+                use_gravity: true,
+                is_grounded: false,
+                freeze_orient: false,
+                is_sensor: false,
+                collision_category: 1,
+                collision_mask: u32::MAX,
+                collision_group: 0,
+                can_sleep: true,
+                is_sleeping: false,
+                resting_steps: 0,
+                previous_transform: None,
+                rotation_limits: None,
+                is_simulating: true,
+                ..Default::default()
+            };
+            new_body.inverse_mass = if new_body.mass != 0.0 { 1.0/new_body.mass } else { 0.0 };
+            new_body.inertia = density*inertia;
+            new_body.inverse_inertia = if new_body.inertia != 0.0 { 1.0/new_body.inertia } else { 0.0 };
+
+            // Add new body to bodies pointers array and update bodies count
+            self.bodies.push(Strong::new(new_body));
+
+            debug_print!("[PHYSAC] created compound physics body id {new_id}");
+            let [.., result] = &self.bodies[..] else { unreachable!("should have at least one element after pushing") };
+            Ok(result)
+        } else {
+            debug_print!("[PHYSAC] new physics body creation failed because there isn't any available id to use");
+            Err(PhysacError::CapacityExceeded { capacity: self.bodies.capacity() })
+        }
+    }
+
+    /// Creates a new physics body made of several shapes, each with its own offset and rotation relative to `pos`
+    ///
+    /// Mass and moment of inertia are combined from every fixture. The body is positioned at the centroid of all fixtures
+    /// (so that it rotates around its center of mass), and the fixture offsets are moved to be relative to that position
+    ///
+    /// # Panics
+    ///
+    /// This method may panic if `fixtures` is empty or if there are no available IDs
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use physac::prelude::*;
+    /// let mut ph = init_physics::<24, 24>().build();
+    ///
+    /// // A T-shaped piece
+    /// let tee = ph.borrow_mut()
+    ///     .create_physics_body_compound(Vector2 { x: 100.0, y: 100.0 }, vec![
+    ///         PhysicsFixture::rectangle(60.0, 20.0),
+    ///         PhysicsFixture {
+    ///             offset: Vector2 { x: 0.0, y: 30.0 },
+    ///             ..PhysicsFixture::rectangle(20.0, 40.0)
+    ///         },
+    ///     ], 1.0)
+    ///     .clone();
+    ///
+    /// tee.borrowed(|tee| {
+    ///     assert_eq!(tee.mass, 2000.0);
+    ///     assert_eq!(tee.position, Vector2 { x: 100.0, y: 112.0 });
+    /// });
+    /// ```
+    #[inline]
+    pub fn create_physics_body_compound(&mut self, pos: impl Into<Vector2>, fixtures: Vec<PhysicsFixture<MAX_VERTICES, CIRCLE_VERTICES>>, density: Real) -> &Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>> {
+        let pos = pos.into();
+        self.try_create_physics_body_compound(pos, fixtures, density).unwrap()
+    }
+
+    /// Creates a new physics body from the vertices of a simple (possibly concave) polygon in world space
+    ///
+    /// The polygon is decomposed into convex pieces (see [`PolygonData::from_concave_vertices`]), which are attached to the body as a [`PHYSICS_COMPOUND`].
+    /// If the polygon is already convex and fits in `MAX_VERTICES`, a [`PHYSICS_POLYGON`] is created instead
+    ///
+    /// The body is positioned at the centroid of the polygon, and the vertices may be given in either winding order
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PhysacError::InvalidPolygon`] if the vertices do not form a valid simple polygon,
+    /// or [`PhysacError::CapacityExceeded`] if there are no available IDs
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use physac::prelude::*;
+    /// let mut ph = init_physics::<24, 24>().build();
+    ///
+    /// let cup = ph.borrow_mut()
+    ///     .create_physics_body_concave(&[
+    ///         Vector2 { x:  0.0, y:  0.0 },
+    ///         Vector2 { x: 10.0, y:  0.0 },
+    ///         Vector2 { x: 10.0, y: 30.0 },
+    ///         Vector2 { x: 40.0, y: 30.0 },
+    ///         Vector2 { x: 40.0, y:  0.0 },
+    ///         Vector2 { x: 50.0, y:  0.0 },
+    ///         Vector2 { x: 50.0, y: 40.0 },
+    ///         Vector2 { x:  0.0, y: 40.0 },
+    ///     ], 1.0)
+    ///     .unwrap()
+    ///     .clone();
+    ///
+    /// cup.borrowed(|cup| {
+    ///     assert!(matches!(&cup.shape, PHYSICS_COMPOUND { fixtures } if fixtures.len() == 3));
+    ///     assert_eq!(cup.mass, 1100.0);
+    /// });
+    /// ```
+    pub fn create_physics_body_concave(&mut self, vertices: &[Vector2], density: Real) -> Result<&Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>, PhysacError> {
+        let pieces = PolygonData::from_concave_vertices(vertices)?;
+
+        if let [_] = pieces[..] {
+            return self.create_physics_body_polygon_from_vertices(vertices, density);
+        }
+
+        // Center every piece on its own centroid, placed where the piece is in world space
+        let fixtures = pieces.into_iter()
+            .map(|mut vertex_data| {
+                let (center, _, _) = vertex_data.mass_data();
+                for position in vertex_data.positions_mut() {
+                    *position -= center;
+                }
+
+                PhysicsFixture {
+                    offset: center,
+                    ..PhysicsFixture::polygon(vertex_data)
+                }
+            })
+            .collect();
+
+        self.try_create_physics_body_compound(Vector2::zero(), fixtures, density)
+    }
+
+    /// Creates a new static edge chain physics body from a list of vertices in world space
+    ///
+    /// Only the right side of the chain (when walking from one vertex to the next) collides, so an outline of terrain
+    /// should be given from left to right. If the last vertex is the same as the first, the chain is closed into a loop
+    ///
+    /// The body is positioned at the average of the vertices, and is disabled with no mass so that it never moves
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PhysacError::InvalidPolygon`] if there are fewer than 2 vertices or if two consecutive vertices are the same,
+    /// or [`PhysacError::CapacityExceeded`] if there are no available IDs
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use physac::prelude::*;
+    /// let mut ph = init_physics::<24, 24>().build();
+    ///
+    /// let ground = ph.borrow_mut()
+    ///     .create_physics_body_edge_chain(&[
+    ///         Vector2 { x:   0.0, y: 400.0 },
+    ///         Vector2 { x: 200.0, y: 400.0 },
+    ///         Vector2 { x: 300.0, y: 350.0 },
+    ///         Vector2 { x: 500.0, y: 350.0 },
+    ///     ])
+    ///     .unwrap()
+    ///     .clone();
+    ///
+    /// ground.borrowed(|ground| {
+    ///     assert!(!ground.enabled);
+    ///     assert_eq!(ground.get_physics_shape_vertices_count(), 4);
+    /// });
+    /// ```
+    pub fn create_physics_body_edge_chain(&mut self, vertices: &[Vector2]) -> Result<&Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>, PhysacError> {
+        if vertices.len() < 2 {
+            return Err(PolygonError::TooFewVertices.into());
+        }
+
+        // Repeated vertices would leave a segment without a normal
+        if vertices.windows(2).any(|pair| dist_sqr(pair[0], pair[1]) <= Real::EPSILON) {
+            return Err(PolygonError::Degenerate.into());
+        }
+
+        if let Some(new_id) = self.find_available_body_index() {
+            let center = vertices.iter().fold(Vector2::zero(), |sum, &v| sum + v)*(1.0/vertices.len() as Real);
+
+            let new_body = PhysicsBodyData {
+                id: new_id,
+                enabled: false,
+                position: center,
+                velocity: Vector2::zero(),
+                force: Vector2::zero(),
+                angular_velocity: 0.0,
+                torque: 0.0,
+                orient: 0.0,
+                shape: PHYSICS_CHAIN { vertices: vertices.iter().map(|&v| v - center).collect() },
+                mass: 0.0,
+                inverse_mass: 0.0,
+                inertia: 0.0,
+                inverse_inertia: 0.0,
+                static_friction: 0.4,
+                dynamic_friction: 0.2,
+                restitution: 0.0,
+                use_gravity: false,
+                gravity_scale: 1.0,
+                linear_damping: 0.0,
+                angular_damping: 0.0,
+                max_linear_velocity: None,
+                max_angular_velocity: None,
+                friction_combine: None,
+                restitution_combine: None,
+                surface_velocity: 0.0,
+                user_data: 0,
+                name: String::new(),
+                is_grounded: false,
+                freeze_orient: true,
+                is_sensor: false,
+                collision_category: 1,
+                collision_mask: u32::MAX,
+                collision_group: 0,
+                can_sleep: true,
+                is_kinematic: false,
+                has_stale_contacts: false,
+                settled_transform: None,
+                has_new_shape: false,
+                is_sleeping: false,
+                resting_steps: 0,
+                previous_transform: None,
+                rotation_limits: None,
+                shape_offset: Vector2::zero(),
+                is_simulating: true,
+            };
+
+            // Add new body to bodies pointers array and update bodies count
+            self.bodies.push(Strong::new(new_body));
+
+            debug_print!("[PHYSAC] created chain physics body id {new_id}");
+            let [.., result] = &self.bodies[..] else { unreachable!("should have at least one element after pushing") };
+            Ok(result)
+        } else {
+            debug_print!("[PHYSAC] new physics body creation failed because there isn't any available id to use");
+            Err(PhysacError::CapacityExceeded { capacity: self.bodies.capacity() })
+        }
+    }
+
+    /// Creates a new static heightfield physics body from a list of heights spaced `cell_width` apart
+    ///
+    /// The first sample is at `pos`, and each following sample is `cell_width` further right, offset vertically by its height.
+    /// Only the top of the surface collides, and only the cells underneath a body are checked against it,
+    /// so long terrain costs about the same as short terrain
+    ///
+    /// The body is disabled with no mass so that it never moves
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PhysacError::InvalidPolygon`] if there are fewer than 2 heights or if `cell_width` is not positive,
+    /// or [`PhysacError::CapacityExceeded`] if there are no available IDs
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use physac::prelude::*;
+    /// let mut ph = init_physics::<24, 24>().build();
+    ///
+    /// let ground = ph.borrow_mut()
+    ///     .create_physics_body_heightfield(Vector2 { x: 0.0, y: 400.0 }, &[0.0, -10.0, -25.0, -10.0, 0.0], 50.0)
+    ///     .unwrap()
+    ///     .clone();
+    ///
+    /// ground.borrowed(|ground| {
+    ///     assert!(!ground.enabled);
+    ///     assert_eq!(ground.get_physics_shape_vertices_count(), 5);
+    ///     assert_eq!(ground.get_physics_shape_vertex(2), Vector2 { x: 100.0, y: 375.0 });
+    /// });
+    /// ```
+    pub fn create_physics_body_heightfield(&mut self, pos: impl Into<Vector2>, heights: &[Real], cell_width: Real) -> Result<&Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>, PhysacError> {
+        let pos = pos.into();
+        if heights.len() < 2 {
+            return Err(PolygonError::TooFewVertices.into());
+        }
+
+        if cell_width.is_nan() || cell_width <= Real::EPSILON {
+            return Err(PolygonError::Degenerate.into());
+        }
+
+        if let Some(new_id) = self.find_available_body_index() {
+            let new_body = PhysicsBodyData {
+                id: new_id,
+                enabled: false,
+                position: pos,
+                velocity: Vector2::zero(),
+                force: Vector2::zero(),
+                angular_velocity: 0.0,
+                torque: 0.0,
+                orient: 0.0,
+                shape: PHYSICS_HEIGHTFIELD { heights: heights.to_vec(), cell_width },
+                mass: 0.0,
+                inverse_mass: 0.0,
+                inertia: 0.0,
+                inverse_inertia: 0.0,
+                static_friction: 0.4,
+                dynamic_friction: 0.2,
+                restitution: 0.0,
+                use_gravity: false,
+                gravity_scale: 1.0,
+                linear_damping: 0.0,
+                angular_damping: 0.0,
+                max_linear_velocity: None,
+                max_angular_velocity: None,
+                friction_combine: None,
+                restitution_combine: None,
+                surface_velocity: 0.0,
+                user_data: 0,
+                name: String::new(),
+                is_grounded: false,
+                freeze_orient: true,
+                is_sensor: false,
+                collision_category: 1,
+                collision_mask: u32::MAX,
+                collision_group: 0,
+                can_sleep: true,
+                is_kinematic: false,
+                has_stale_contacts: false,
+                settled_transform: None,
+                has_new_shape: false,
+                is_sleeping: false,
+                resting_steps: 0,
+                previous_transform: None,
+                rotation_limits: None,
+                shape_offset: Vector2::zero(),
+                is_simulating: true,
+            };
+
+            // Add new body to bodies pointers array and update bodies count
+            self.bodies.push(Strong::new(new_body));
+
+            debug_print!("[PHYSAC] created heightfield physics body id {new_id}");
+            let [.., result] = &self.bodies[..] else { unreachable!("should have at least one element after pushing") };
+            Ok(result)
+        } else {
+            debug_print!("[PHYSAC] new physics body creation failed because there isn't any available id to use");
+            Err(PhysacError::CapacityExceeded { capacity: self.bodies.capacity() })
+        }
+    }
+
+    /// Shatters a polygon shape physics body to little physics bodies with explosion force
+    ///
+    /// If there are not enough available IDs for every fragment, only some of the fragments are created
+    #[allow(
+        clippy::needless_pass_by_value,
+        reason = "this is basically another destroy method",
+    )]
+    pub fn physics_shatter(&mut self, body: Weak<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>, position: impl Into<Vector2>, force: Real) {
+        let position = position.into();
+        if let Some(phys_body) = body.upgrade() {
+            self.shatter_body(&phys_body, position, force, &ShatterOptions::default());
+        } else {
+            debug_print!("[PHYSAC] error when trying to shatter a null reference physics body");
+        }
+    }
+
+    /// Shatters a polygon shape physics body to little physics bodies with explosion force, returning handles to the fragments
+    ///
+    /// The body is only shattered if `position` is inside of it, in which case it is destroyed and replaced by one triangular fragment per face.
+    /// Otherwise (or if the body is not a polygon, or the handle refers to a destroyed body) nothing happens and no fragments are returned
+    ///
+    /// If there are not enough available IDs for every fragment, the fragments created until then are returned
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use physac::prelude::*;
+    /// let mut ph = init_physics::<24, 24>().build();
+    ///
+    /// let crate_body = ph.borrow_mut()
+    ///     .create_physics_body_rectangle(Vector2 { x: 100.0, y: 100.0 }, 40.0, 40.0, 1.0)
+    ///     .clone();
+    /// let crate_handle = ph.borrow().body_handle(&crate_body).unwrap();
+    ///
+    /// let fragments = ph.borrow_mut().shatter(crate_handle, Vector2 { x: 105.0, y: 95.0 }, 10.0);
+    ///
+    /// assert_eq!(fragments.len(), 4);
+    /// assert!(ph.borrow().get_body(crate_handle).is_none());
+    /// assert!(fragments.iter().all(|&fragment| ph.borrow().get_body(fragment).is_some()));
+    /// ```
+    pub fn shatter(&mut self, body: BodyHandle, position: impl Into<Vector2>, force: Real) -> Vec<BodyHandle> {
+        let position = position.into();
+        self.shatter_with(body, position, force, &ShatterOptions::default())
+    }
+
+    /// Shatters a polygon shape physics body to little physics bodies with explosion force, returning handles to the fragments
+    ///
+    /// The body is only shattered if `position` is inside of it, in which case it is destroyed and replaced by fragments.
+    /// Otherwise (or if the body is not a polygon, or the handle refers to a destroyed body) nothing happens and no fragments are returned
+    ///
+    /// Without a `fragment_count`, the body breaks like in the original Physac: one triangle per face, reaching in to `position`,
+    /// with a mass equal to its area and pushed out through the middle of its outer face by `force`.
+    /// With a `fragment_count`, the body breaks into convex wedges fanning out from `position`, with the density of the body
+    /// and pushed away from `position` by `force`. Wedges which would need more than `MAX_VERTICES` vertices are discarded
+    ///
+    /// If there are not enough available IDs for every fragment, the fragments created until then are returned
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use physac::prelude::*;
+    /// let mut ph = init_physics::<24, 24>().build();
+    ///
+    /// let boulder = ph.borrow_mut()
+    ///     .create_physics_body_rectangle(Vector2 { x: 100.0, y: 100.0 }, 200.0, 200.0, 1.0)
+    ///     .clone();
+    /// let boulder = ph.borrow().body_handle(&boulder).unwrap();
+    ///
+    /// let options = ShatterOptions {
+    ///     seed: Some(7),
+    ///     ..ShatterOptions::fragments_by_area(40_000.0, 2_500.0)
+    /// };
+    /// let fragments = ph.borrow_mut().shatter_with(boulder, Vector2 { x: 110.0, y: 90.0 }, 10.0, &options);
+    ///
+    /// assert_eq!(fragments.len(), 16);
+    /// ```
+    pub fn shatter_with(&mut self, body: BodyHandle, position: impl Into<Vector2>, force: Real, options: &ShatterOptions) -> Vec<BodyHandle> {
+        let position = position.into();
+        let Some(body) = self.get_body(body).cloned() else {
+            return Vec::new();
+        };
+        self.shatter_body(&body, position, force, options)
+            .iter()
+            .filter_map(|fragment| self.body_handle(fragment))
+            .collect()
+    }
+
+    /// Shatters a physics body like [`Physac::shatter_with`], returning the fragments
+    fn shatter_body(&mut self, body: &Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>, position: Vector2, force: Real, options: &ShatterOptions) -> Vec<Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>> {
+        let body_data = body.borrow();
+        let PHYSICS_POLYGON { vertex_data, transform } = body_data.shape else {
+            return Vec::new();
+        };
+
+        let outline: Vec<Vector2> = vertex_data.positions().iter().map(|&p| body_data.position + transform.multiply_vector2(p)).collect();
+        let normals: Vec<Vector2> = vertex_data.normals().iter().map(|&n| transform.multiply_vector2(n)).collect();
+
+        // Only shatter from inside of the body
+        if outline.iter().zip(&normals).any(|(&vertex, &normal)| normal.dot(position - vertex) >= 0.0) {
+            return Vec::new();
+        }
+
+        let (body_position, body_velocity, body_angular_velocity) = (body_data.position, body_data.velocity, body_data.angular_velocity);
+
+        // The position, shape, mass, inertia and explosion direction of every fragment
+        let mut pieces = Vec::new();
+
+        if let Some(fragment_count) = options.fragment_count {
+            let (_, area, _) = vertex_data.mass_data();
+            let density = if area > 0.0 { body_data.mass/area } else { 0.0 };
+
+            // Directions the body breaks along, going around the impact point
+            let fragment_count = fragment_count.max(3);
+            let spacing = 2.0*PI as Real/fragment_count as Real;
+            let mut rng = options.seed.map(SplitMix64::new);
+            let mut jitter = || rng.as_mut().map_or(0.0, |rng| rng.next_real() - 0.5);
+
+            // Jittering by at most a fifth of the spacing keeps every fragment narrower than half a turn, so that it stays convex
+            let start = jitter()*spacing;
+            let mut angles: Vec<Real> = (0..fragment_count).map(|i| start + (i as Real + 0.4*jitter())*spacing).collect();
+            angles.sort_by(Real::total_cmp);
+
+            for i in 0..angles.len() {
+                let direction = |angle: Real| Vector2 { x: math_cos(angle), y: math_sin(angle) };
+                let (from, to) = (direction(angles[i]), direction(angles[next_idx(i, angles.len())]));
+
+                // The part of the body between both directions
+                let mut piece = clip_polygon(&outline, |p| math_cross_vector2(from, p - position));
+                piece = clip_polygon(&piece, |p| -math_cross_vector2(to, p - position));
+                piece.dedup_by(|a, b| dist_sqr(*a, *b) <= Real::EPSILON);
+                if piece.len() > 1 && dist_sqr(piece[0], piece[piece.len() - 1]) <= Real::EPSILON {
+                    piece.pop();
+                }
+
+                let Ok(mut fragment_data) = PolygonData::<MAX_VERTICES, CIRCLE_VERTICES>::from_convex_vertices(&piece) else { continue };
+                let (center, fragment_area, _) = fragment_data.mass_data();
+                if fragment_area < options.min_fragment_area {
+                    continue;
+                }
+
+                // Separate vertices to avoid unnecessary physics collisions
+                for position in fragment_data.positions_mut() {
+                    *position = (*position - center)*0.95;
+                }
+                let (_, fragment_area, inertia) = fragment_data.mass_data();
+
+                // Apply explosion force away from the impact point
+                let mut force_direction = center - position;
+                math_normalize(&mut force_direction);
+
+                pieces.push((center, PHYSICS_POLYGON { vertex_data: fragment_data, transform: Mat2::identity() }, density*fragment_area, density*inertia, force_direction));
+            }
+        } else {
+            let vertices = vertex_data.positions();
+            for i in 0..vertices.len() {
+                let next_index = next_idx(i, vertices.len());
+                let offset = (vertices[i] + vertices[next_index])*PHYSAC_K;
+                let center = body_position + offset;
+
+                let mut fragment_data = PolygonData::<MAX_VERTICES, CIRCLE_VERTICES> {
+                    vertex_count: 3,
+                    ..Default::default()
+                };
+
+                // Separate vertices to avoid unnecessary physics collisions
+                fragment_data.positions[0] = (vertices[i] - offset)*0.95;
+                fragment_data.positions[1] = (vertices[next_index] - offset)*0.95;
+                fragment_data.positions[2] = (position - center)*0.95;
+                fragment_data.calculate_normals();
+
+                let (_, fragment_area, inertia) = fragment_data.mass_data();
+                if fragment_area < options.min_fragment_area {
+                    continue;
+                }
+
+                // Calculate explosion force direction
+                let mut force_direction = fragment_data.positions[0] + (fragment_data.positions[1] - fragment_data.positions[0])*0.5;
+                math_normalize(&mut force_direction);
+
+                pieces.push((center, PHYSICS_POLYGON { vertex_data: fragment_data, transform }, fragment_area, inertia, force_direction));
+            }
+        }
+
+        // Destroy shattered physics body
+        drop(body_data);
+        self.destroy_physics_body(body.clone());
+
+        let mut fragments = Vec::with_capacity(pieces.len());
+
+        for (center, shape, mass, inertia, force_direction) in pieces {
+            let Ok(new_body) = self.try_create_physics_body_polygon(center, 10.0, 3, 10.0) else {
+                debug_print!("[PHYSAC] shattering stopped after {} fragments because there isn't any available id to use", fragments.len());
+                break;
+            };
+            let new_body = new_body.clone();
+            let mut new_body_data = new_body.borrow_mut();
+
+            new_body_data.shape = shape;
+            new_body_data.mass = mass;
+            new_body_data.inverse_mass = if new_body_data.mass != 0.0 { 1.0/new_body_data.mass } else { 0.0 };
+            new_body_data.inertia = inertia;
+            new_body_data.inverse_inertia = if new_body_data.inertia != 0.0 { 1.0/new_body_data.inertia } else { 0.0 };
+
+            if options.inherit_velocity {
+                let r = center - body_position;
+                new_body_data.velocity = body_velocity + Vector2 { x: -body_angular_velocity*r.y, y: body_angular_velocity*r.x };
+                new_body_data.angular_velocity = body_angular_velocity;
+            }
+
+            new_body_data.add_force(force_direction*force);
+
+            drop(new_body_data);
+            fragments.push(new_body);
+        }
+
+        fragments
+    }
+
+    /// Carves a circular hole out of a polygon or compound physics body, for destructible terrain
+    ///
+    /// The polygon fixtures of the body which overlap the circle (approximated with `CIRCLE_VERTICES`) have it subtracted from them,
+    /// and are split up into convex fixtures so that the body becomes a compound. Any other fixtures are left as they are.
+    /// Mass and inertia are recalculated with the same density, so static terrain stays static.
+    /// The body `position` moves to the new center of mass, while the shape stays where it was in the world.
+    /// If nothing is left of the body, it is destroyed
+    ///
+    /// Returns true if the body was changed
+    ///
+    /// # Errors
+    ///
+    /// This method returns an error without changing the body if a piece left over from a fixture can't be split into convex fixtures
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use physac::prelude::*;
+    /// let mut ph = init_physics::<24, 24>().build();
+    ///
+    /// let ground = ph.borrow_mut()
+    ///     .create_physics_body_rectangle(Vector2 { x: 200.0, y: 400.0 }, 400.0, 100.0, 1.0)
+    ///     .clone();
+    /// ground.borrowed_mut(|ground| ground.enabled = false);
+    ///
+    /// // An explosion on the surface
+    /// assert!(ph.borrow_mut().carve_circle(&ground, Vector2 { x: 200.0, y: 350.0 }, 30.0).unwrap());
+    ///
+    /// ground.borrowed(|ground| {
+    ///     assert!(matches!(ground.shape, PHYSICS_COMPOUND { .. }));
+    ///     assert!(ground.mass < 40_000.0);
+    ///     // The center of mass sinks below the crater
+    ///     assert!(ground.position.y > 400.0);
+    /// });
+    ///
+    /// // Far away from the ground
+    /// assert!(!ph.borrow_mut().carve_circle(&ground, Vector2 { x: 200.0, y: 100.0 }, 30.0).unwrap());
+    /// ```
+    pub fn carve_circle(&mut self, body: &Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>, center: impl Into<Vector2>, radius: Real) -> Result<bool, PolygonError> {
+        let center = center.into();
+        let mut body_data = body.borrow_mut();
+
+        let (fixtures, transform) = match body_data.shape {
+            PHYSICS_POLYGON { vertex_data, transform } => (vec![PhysicsFixture::polygon(vertex_data)], transform),
+            PHYSICS_COMPOUND { ref fixtures } => (fixtures.clone(), Mat2::radians(body_data.orient)),
+            _ => return Ok(false),
+        };
+
+        // Work in the space of the body, with the circle as a convex polygon
+        let local_center = transform.transpose().multiply_vector2(center - body_data.position);
+        let hole: Vec<Vector2> = (0..CIRCLE_VERTICES)
+            .map(|i| {
+                let angle = 2.0*PI as Real*i as Real/CIRCLE_VERTICES as Real;
+                local_center + Vector2 { x: math_cos(angle)*radius, y: math_sin(angle)*radius }
+            })
+            .collect();
+        let hole_bounds = Aabb::from_points(hole.iter().copied());
+
+        let mut changed = false;
+        let mut new_fixtures = Vec::with_capacity(fixtures.len());
+
+        for fixture in fixtures {
+            let PHYSICS_POLYGON { vertex_data, .. } = fixture.shape else {
+                new_fixtures.push(fixture);
+                continue;
+            };
+
+            let (fixture_position, fixture_transform) = fixture_placement(&fixture, Vector2::zero(), Mat2::identity());
+            let outline: Vec<Vector2> = vertex_data.positions().iter().map(|&p| fixture_position + fixture_transform.multiply_vector2(p)).collect();
+
+            let pieces = if Aabb::from_points(outline.iter().copied()).overlaps(&hole_bounds) {
+                subtract_convex(&outline, &hole)
+            } else {
+                None
+            };
+
+            let Some(pieces) = pieces else {
+                new_fixtures.push(fixture);
+                continue;
+            };
+            changed = true;
+
+            // Center every piece on its own centroid, placed where the piece is in the body
+            for piece in pieces {
+                for mut piece_data in PolygonData::from_concave_vertices(&piece)? {
+                    let (piece_center, _, _) = piece_data.mass_data();
+                    for position in piece_data.positions_mut() {
+                        *position -= piece_center;
+                    }
+
+                    new_fixtures.push(PhysicsFixture {
+                        offset: piece_center,
+                        ..PhysicsFixture::polygon(piece_data)
+                    });
+                }
+            }
+        }
+
+        if !changed {
+            return Ok(false);
+        }
+
+        if new_fixtures.is_empty() {
+            drop(body_data);
+            self.destroy_physics_body(body.clone());
+            return Ok(true);
+        }
+
+        // Keep the body position on its center of mass
+        let (centroid, _, _) = fixtures_mass_data(&new_fixtures);
+        for fixture in &mut new_fixtures {
+            fixture.offset -= centroid;
+        }
+        body_data.position += transform.multiply_vector2(centroid);
+        body_data.replace_shape(PHYSICS_COMPOUND { fixtures: new_fixtures });
+
+        Ok(true)
+    }
+
+    /// Sweeps `shape` from `start` (rotated by `rotation` radians) along `translation` without rotating it,
+    /// and returns the first body it would touch along the way
+    ///
+    /// A shape that starts out overlapping a body hits it at a fraction of 0.
+    /// One-sided chain segments are only hit from the front, and sensors are never hit
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use physac::prelude::*;
+    /// let mut ph = init_physics::<24, 24>().build();
+    ///
+    /// let wall = ph.borrow_mut()
+    ///     .create_physics_body_rectangle(Vector2 { x: 200.0, y: 100.0 }, 20.0, 200.0, 1.0)
+    ///     .clone();
+    /// wall.borrow_mut().enabled = false;
+    ///
+    /// // Move a character as far as it can go towards the wall
+    /// let character = PHYSICS_CIRCLE { radius: 10.0 };
+    /// let start = Vector2 { x: 100.0, y: 100.0 };
+    /// let translation = Vector2 { x: 150.0, y: 0.0 };
+    /// let hit = ph.borrow().shape_cast(&character, start, 0.0, translation).unwrap();
+    ///
+    /// assert_eq!(hit.body.borrow().id, wall.borrow().id);
+    /// assert!((start.x + translation.x*hit.fraction - 180.0).abs() < 0.1);
+    /// assert_eq!(hit.normal, Vector2 { x: -1.0, y: 0.0 });
+    /// ```
+    #[must_use]
+    pub fn shape_cast(
+        &self,
+        shape: &PhysicsShape<MAX_VERTICES, CIRCLE_VERTICES>,
+        start: impl Into<Vector2>,
+        rotation: Real,
+        translation: impl Into<Vector2>,
+    ) -> Option<ShapeCastHit<MAX_VERTICES, CIRCLE_VERTICES>> {
+        let start = start.into();
+        let translation = translation.into();
+        self.shape_cast_filtered(shape, start, rotation, translation, |_, _| true)
+    }
+
+    /// Sweeps `shape` like [`Physac::shape_cast`], only stopping at hits that `accept` is true for given the body and the hit normal
+    fn shape_cast_filtered(
+        &self,
+        shape: &PhysicsShape<MAX_VERTICES, CIRCLE_VERTICES>,
+        start: Vector2,
+        rotation: Real,
+        translation: Vector2,
+        accept: impl Fn(&PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>, Vector2) -> bool,
+    ) -> Option<ShapeCastHit<MAX_VERTICES, CIRCLE_VERTICES>> {
+        let transform = Mat2::radians(rotation);
+        let swept_bounds = shape_bounds(shape, start, transform).union(shape_bounds(shape, start + translation, transform));
+
+        let mut cast_proxies = Vec::new();
+        push_shape_proxies(shape, start, transform, None, &mut cast_proxies);
+
+        let mut closest: Option<ShapeCastHit<MAX_VERTICES, CIRCLE_VERTICES>> = None;
+        for body in self.bodies_near(&swept_bounds) {
+            let body_data = body.borrow();
+            if body_data.is_sensor || !body_data.bounds().overlaps(&swept_bounds) {
+                continue;
+            }
+
+            for target in body_data.convex_proxies(Some(swept_bounds)) {
+                for cast in &cast_proxies {
+                    let Some((fraction, point, normal)) = cast_proxy(cast, &target, translation) else { continue };
+                    if closest.as_ref().is_none_or(|hit| fraction < hit.fraction) && accept(&body_data, normal) {
+                        closest = Some(ShapeCastHit { body: body.clone(), fraction, point, normal });
+                    }
+                }
+            }
+        }
+
+        closest
+    }
+
+    /// Returns every body whose shape contains `point`
+    ///
+    /// Chains and heightfields have no inside, so they only contain points exactly on their surface
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use physac::prelude::*;
+    /// let mut ph = init_physics::<24, 24>().build();
+    ///
+    /// let ball = ph.borrow_mut()
+    ///     .create_physics_body_circle(Vector2 { x: 100.0, y: 100.0 }, 20.0, 1.0)
+    ///     .clone();
+    ///
+    /// // Pick up the body under the mouse
+    /// let picked = ph.borrow().query_point(Vector2 { x: 110.0, y: 95.0 });
+    ///
+    /// assert_eq!(picked.len(), 1);
+    /// assert_eq!(picked[0].borrow().id, ball.borrow().id);
+    /// assert!(ph.borrow().query_point(Vector2 { x: 130.0, y: 100.0 }).is_empty());
+    /// ```
+    #[must_use]
+    pub fn query_point(&self, point: impl Into<Vector2>) -> Vec<Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>> {
+        let point = point.into();
+        let point_bounds = Aabb { min: point, max: point };
+
+        self.bodies_near(&point_bounds).into_iter()
+            .filter(|body| {
+                let body = body.borrow();
+                body.bounds().overlaps(&point_bounds) &&
+                    body.convex_proxies(Some(point_bounds)).iter().any(|proxy| proxy.contains_point(point))
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Returns every body whose bounding box overlaps the region from `min` (top left) to `max` (bottom right)
+    ///
+    /// Bounding boxes are larger than the shapes inside of them, so bodies near the region may be included even if their shapes don't reach into it
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use physac::prelude::*;
+    /// let mut ph = init_physics::<24, 24>().build();
+    ///
+    /// ph.borrow_mut().create_physics_body_circle(Vector2 { x: 100.0, y: 100.0 }, 20.0, 1.0);
+    /// ph.borrow_mut().create_physics_body_circle(Vector2 { x: 300.0, y: 100.0 }, 20.0, 1.0);
+    ///
+    /// // Only wake up bodies that are on screen
+    /// let on_screen = ph.borrow().query_aabb(Vector2 { x: 0.0, y: 0.0 }, Vector2 { x: 200.0, y: 200.0 });
+    ///
+    /// assert_eq!(on_screen.len(), 1);
+    /// ```
+    #[must_use]
+    pub fn query_aabb(&self, min: impl Into<Vector2>, max: impl Into<Vector2>) -> Vec<Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>> {
+        let min = min.into();
+        let max = max.into();
+        let region = Aabb::from_points([min, max]);
+
+        self.bodies_near(&region).into_iter()
+            .filter(|body| body.borrow().bounds().overlaps(&region))
+            .cloned()
+            .collect()
+    }
+
+    /// Returns every body whose shape overlaps a circle, without creating a body for it
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use physac::prelude::*;
+    /// let mut ph = init_physics::<24, 24>().build();
+    ///
+    /// ph.borrow_mut().create_physics_body_rectangle(Vector2 { x: 100.0, y: 100.0 }, 40.0, 40.0, 1.0);
+    /// ph.borrow_mut().create_physics_body_rectangle(Vector2 { x: 300.0, y: 100.0 }, 40.0, 40.0, 1.0);
+    ///
+    /// // Damage everything caught in an explosion
+    /// let caught = ph.borrow().overlap_circle(Vector2 { x: 150.0, y: 100.0 }, 40.0);
+    ///
+    /// assert_eq!(caught.len(), 1);
+    /// ```
+    #[must_use]
+    pub fn overlap_circle(&self, center: impl Into<Vector2>, radius: Real) -> Vec<Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>> {
+        let center = center.into();
+        self.overlap_proxy(&ConvexProxy {
+            vertices: vec![center],
+            normals: Vec::new(),
+            radius,
+            neighbor_normals: None,
+            part: 0,
+        })
+    }
+
+    /// Returns every body whose shape overlaps a convex polygon with `vertices` in world space, without creating a body for it
+    ///
+    /// The vertices can be in either winding order. Two vertices are treated as a line segment and one as a point
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use physac::prelude::*;
+    /// let mut ph = init_physics::<24, 24>().build();
+    ///
+    /// ph.borrow_mut().create_physics_body_circle(Vector2 { x: 100.0, y: 100.0 }, 10.0, 1.0);
+    /// ph.borrow_mut().create_physics_body_circle(Vector2 { x: 100.0, y: 200.0 }, 10.0, 1.0);
+    ///
+    /// // A sword swing in front of the player
+    /// let hitbox = [
+    ///     Vector2 { x: 80.0, y: 80.0 },
+    ///     Vector2 { x: 140.0, y: 90.0 },
+    ///     Vector2 { x: 140.0, y: 110.0 },
+    ///     Vector2 { x: 80.0, y: 120.0 },
+    /// ];
+    /// let hit = ph.borrow().overlap_polygon(&hitbox);
+    ///
+    /// assert_eq!(hit.len(), 1);
+    /// ```
+    #[must_use]
+    pub fn overlap_polygon(&self, vertices: &[Vector2]) -> Vec<Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>> {
+        if vertices.is_empty() {
+            return Vec::new();
+        }
+
+        let count = vertices.len();
+        let mut proxy = ConvexProxy {
+            vertices: vertices.to_vec(),
+            normals: Vec::new(),
+            radius: 0.0,
+            neighbor_normals: None,
+            part: 0,
+        };
+
+        if count > 2 {
+            let center = proxy.center();
+            proxy.normals = (0..count)
+                .map(|i| {
+                    let face = vertices[next_idx(i, count)] - vertices[i];
+                    let mut normal = Vector2 { x: face.y, y: -face.x };
+                    math_normalize(&mut normal);
+                    normal
+                })
+                .collect();
+
+            // Normals point out of the polygon regardless of which way it winds
+            if proxy.normals[0].dot(vertices[0] - center) < 0.0 {
+                for normal in &mut proxy.normals {
+                    *normal = -*normal;
+                }
+            }
+        }
+
+        self.overlap_proxy(&proxy)
+    }
+
+    /// Pushes every body whose shape overlaps a circle away from its center, and returns the bodies that were pushed
+    ///
+    /// Each body gets an impulse (see [`PhysicsBodyData::apply_linear_impulse`]) of `strength` at its center of mass,
+    /// weakened by `falloff` with the distance of its center of mass from `center`. Static, disabled and sensor bodies aren't pushed
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use physac::prelude::*;
+    /// let mut ph = init_physics::<24, 24>().gravity_force(0.0, 0.0).build();
+    ///
+    /// let near = ph.borrow_mut()
+    ///     .create_physics_body_rectangle(Vector2 { x: 120.0, y: 100.0 }, 10.0, 10.0, 1.0)
+    ///     .clone();
+    /// let far = ph.borrow_mut()
+    ///     .create_physics_body_rectangle(Vector2 { x: 160.0, y: 100.0 }, 10.0, 10.0, 1.0)
+    ///     .clone();
+    ///
+    /// let pushed = ph.borrow_mut().apply_explosion(Vector2 { x: 100.0, y: 100.0 }, 100.0, 50.0, ExplosionFalloff::Linear);
+    ///
+    /// assert_eq!(pushed.len(), 2);
+    /// assert!(near.borrow().velocity.x > far.borrow().velocity.x);
+    /// assert!(far.borrow().velocity.x > 0.0);
+    /// ```
+    pub fn apply_explosion(&mut self, center: impl Into<Vector2>, radius: Real, strength: Real, falloff: ExplosionFalloff) -> Vec<Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>> {
+        let center = center.into();
+        self.explode(center, radius, strength, falloff, false)
+    }
+
+    /// Pushes bodies away from the center of a circle like [`Physac::apply_explosion`], leaving out bodies sheltered by static bodies
+    ///
+    /// A body is sheltered if a static or disabled body is in the way of a ray from `center` to its center of mass
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use physac::prelude::*;
+    /// let mut ph = init_physics::<24, 24>().gravity_force(0.0, 0.0).build();
+    ///
+    /// let wall = ph.borrow_mut()
+    ///     .create_physics_body_rectangle(Vector2 { x: 130.0, y: 100.0 }, 10.0, 100.0, 1.0)
+    ///     .clone();
+    /// wall.borrow_mut().enabled = false;
+    /// let crate_body = ph.borrow_mut()
+    ///     .create_physics_body_rectangle(Vector2 { x: 160.0, y: 100.0 }, 10.0, 10.0, 1.0)
+    ///     .clone();
+    ///
+    /// let pushed = ph.borrow_mut().apply_explosion_occluded(Vector2 { x: 100.0, y: 100.0 }, 100.0, 50.0, ExplosionFalloff::Linear);
+    ///
+    /// assert!(pushed.is_empty());
+    /// assert_eq!(crate_body.borrow().velocity, Vector2::zero());
+    /// ```
+    pub fn apply_explosion_occluded(&mut self, center: impl Into<Vector2>, radius: Real, strength: Real, falloff: ExplosionFalloff) -> Vec<Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>> {
+        let center = center.into();
+        self.explode(center, radius, strength, falloff, true)
+    }
+
+    /// Pushes the bodies overlapping a circle away from its center, leaving out sheltered bodies if `is_occluded`
+    fn explode(&self, center: Vector2, radius: Real, strength: Real, falloff: ExplosionFalloff, is_occluded: bool) -> Vec<Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>> {
+        let ray = PHYSICS_CIRCLE { radius: 0.0 };
+
+        self.overlap_circle(center, radius).into_iter()
+            .filter(|body| {
+                let (id, target) = {
+                    let body = body.borrow();
+                    if body.is_sensor || body.inverse_mass == 0.0 || !body.enabled {
+                        return false;
+                    }
+                    (body.id, body.center_of_mass())
+                };
+
+                !is_occluded || self.shape_cast_filtered(&ray, center, 0.0, target - center, |other, _| {
+                    other.id != id && (other.inverse_mass == 0.0 || !other.enabled)
+                }).is_none()
+            })
+            .inspect(|body| {
+                let mut body = body.borrow_mut();
+                let point = body.center_of_mass();
+                let mut direction = point - center;
+                let distance = direction.length_sqr().sqrt();
+                math_normalize(&mut direction);
+
+                body.apply_linear_impulse(direction*(strength*falloff.factor(distance, radius)), point);
+            })
+            .collect()
+    }
+
+    /// Returns the distance between the surfaces of two bodies, along with the closest point on each of them (on a, on b)
+    ///
+    /// If the bodies overlap, the distance is negative (how deep they overlap) and both points are the contact point between them
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use physac::prelude::*;
+    /// let mut ph = init_physics::<24, 24>().build();
+    ///
+    /// let enemy = ph.borrow_mut()
+    ///     .create_physics_body_circle(Vector2 { x: 100.0, y: 100.0 }, 10.0, 1.0)
+    ///     .clone();
+    /// let wall = ph.borrow_mut()
+    ///     .create_physics_body_rectangle(Vector2 { x: 200.0, y: 100.0 }, 20.0, 200.0, 1.0)
+    ///     .clone();
+    ///
+    /// let (distance, on_enemy, on_wall) = ph.borrow().distance_between(&enemy, &wall);
+    ///
+    /// assert!((distance - 80.0).abs() < 0.01);
+    /// assert!((on_enemy.x - 110.0).abs() < 0.01);
+    /// assert!((on_wall.x - 190.0).abs() < 0.01);
+    /// ```
+    #[must_use]
+    #[allow(
+        clippy::unused_self,
+        reason = "the query belongs with the other queries on the world, even though both bodies are given directly",
+    )]
+    pub fn distance_between(
+        &self,
+        body_a: &Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>,
+        body_b: &Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>,
+    ) -> (Real, Vector2, Vector2) {
+        let proxies_a = body_a.borrow().convex_proxies(None);
+        let proxies_b = body_b.borrow().convex_proxies(None);
+
+        let mut closest = (Real::MAX, Vector2::zero(), Vector2::zero());
+        for proxy_a in &proxies_a {
+            for proxy_b in &proxies_b {
+                let result = if proxy_separation(proxy_a, proxy_b) > 0.0 {
+                    let (point_a, point_b) = closest_points(proxy_a, proxy_b);
+                    let mut normal = point_b - point_a;
+                    math_normalize(&mut normal);
+                    let distance = dist_sqr(point_a, point_b).sqrt() - proxy_a.radius - proxy_b.radius;
+                    (distance, point_a + normal*proxy_a.radius, point_b - normal*proxy_b.radius)
+                } else {
+                    // The cores overlap, so the contact the collision code would generate tells how deep
+                    let mut manifold = PhysicsManifoldData::new(body_a.clone(), body_b.clone());
+                    manifold.solve_convex_proxies(proxy_a, proxy_b);
+                    (-manifold.penetration, manifold.contacts[0], manifold.contacts[0])
+                };
+
+                if result.0 < closest.0 {
+                    closest = result;
+                }
+            }
+        }
+
+        closest
+    }
+
+    /// Returns the bodies that might overlap `region`, searching the body tree if there is one
+    fn bodies_near(&self, region: &Aabb) -> Vec<&Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>> {
+        match &self.body_tree {
+            Some(body_tree) => {
+                // Bodies created since the last step are at the end and aren't in the tree yet
+                let mut bodies = body_tree.query(region);
+                let unindexed = self.bodies.iter().rev().take_while(|body| !body_tree.is_indexed(body.borrow().id)).count();
+                bodies.extend(&self.bodies[(self.bodies.len() - unindexed)..]);
+                bodies
+            }
+            None => self.bodies.iter().collect(),
+        }
+    }
+
+    /// Returns every body whose shape overlaps a convex proxy in world space
+    fn overlap_proxy(&self, proxy: &ConvexProxy) -> Vec<Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>> {
+        let bounds = Aabb::from_points(proxy.vertices.iter().copied()).expanded(proxy.radius);
+
+        self.bodies_near(&bounds).into_iter()
+            .filter(|body| {
+                let body = body.borrow();
+                body.bounds().overlaps(&bounds) &&
+                    body.convex_proxies(Some(bounds)).iter().any(|target| proxy.overlaps(target))
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Stops or resumes collisions between two specific bodies, without changing how they collide with anything else
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use physac::prelude::*;
+    /// let mut ph = init_physics::<24, 24>().build();
+    ///
+    /// let player = ph.borrow_mut()
+    ///     .create_physics_body_rectangle(Vector2 { x: 100.0, y: 90.0 }, 20.0, 40.0, 1.0)
+    ///     .clone();
+    /// let platform = ph.borrow_mut()
+    ///     .create_physics_body_rectangle(Vector2 { x: 100.0, y: 120.0 }, 100.0, 10.0, 1.0)
+    ///     .clone();
+    /// platform.borrow_mut().enabled = false;
+    ///
+    /// // Drop down through the platform, then land on it again later
+    /// ph.borrow_mut().ignore_collision(&player, &platform, true);
+    /// assert!(ph.borrow().is_collision_ignored(&player, &platform));
+    ///
+    /// ph.borrow_mut().ignore_collision(&player, &platform, false);
+    /// assert!(!ph.borrow().is_collision_ignored(&player, &platform));
+    /// ```
+    pub fn ignore_collision(
+        &mut self,
+        body_a: &Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>,
+        body_b: &Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>,
+        ignore: bool,
+    ) {
+        let index = self.ignored_pair_index(body_a, body_b);
+        match (ignore, index) {
+            (true, None) => self.ignored_pairs.push([body_a.clone(), body_b.clone()]),
+            (false, Some(index)) => { self.ignored_pairs.swap_remove(index); }
+            _ => {}
+        }
+    }
+
+    /// Returns true if collisions between two specific bodies are ignored with [`Physac::ignore_collision`]
+    #[must_use]
+    pub fn is_collision_ignored(
+        &self,
+        body_a: &Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>,
+        body_b: &Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>,
+    ) -> bool {
+        self.ignored_pair_index(body_a, body_b).is_some()
+    }
+
+    /// Returns the index of a pair of bodies in the ignored pairs, in either order
+    fn ignored_pair_index(
+        &self,
+        body_a: &Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>,
+        body_b: &Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>,
+    ) -> Option<usize> {
+        let (id_a, id_b) = (body_a.borrow().id, body_b.borrow().id);
+        self.ignored_pairs.iter().position(|[ignored_a, ignored_b]| {
+            let ids = (ignored_a.borrow().id, ignored_b.borrow().id);
+            ids == (id_a, id_b) || ids == (id_b, id_a)
+        })
+    }
+
+    /// Sets a callback that is run for every contact after it is found and before it is solved, replacing the previous one
+    ///
+    /// The callback can cancel the contact or change how bouncy and slippery it is with a [`ContactResponse`].
+    /// It runs during the physics step while [`Physac`] is borrowed, so it can borrow the bodies of the contact but not [`Physac`] itself
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use physac::prelude::*;
+    /// let mut ph = init_physics::<24, 24>().build();
+    ///
+    /// let platform = ph.borrow_mut()
+    ///     .create_physics_body_rectangle(Vector2 { x: 100.0, y: 120.0 }, 100.0, 10.0, 1.0)
+    ///     .clone();
+    /// platform.borrow_mut().enabled = false;
+    /// let platform_id = platform.borrow().id;
+    ///
+    /// // Let bodies jump up through the platform and land on top of it
+    /// ph.borrow_mut().set_pre_solve(move |contact| {
+    ///     let other = if contact.body_a.borrow().id == platform_id {
+    ///         &contact.body_b
+    ///     } else if contact.body_b.borrow().id == platform_id {
+    ///         &contact.body_a
+    ///     } else {
+    ///         return ContactResponse::Accept;
+    ///     };
+    ///
+    ///     if other.borrow().velocity.y < 0.0 {
+    ///         ContactResponse::Cancel
+    ///     } else {
+    ///         ContactResponse::Accept
+    ///     }
+    /// });
+    /// ```
+    pub fn set_pre_solve<F>(&mut self, callback: F)
+    where
+        F: FnMut(&ContactInfo<MAX_VERTICES, CIRCLE_VERTICES>) -> ContactResponse + ThreadSafe + 'static
+    {
+        self.pre_solve = Some(Box::new(callback));
+    }
+
+    /// Removes the callback set with [`Physac::set_pre_solve`], so that every contact is solved as usual
+    pub fn clear_pre_solve(&mut self) {
+        self.pre_solve = None;
+    }
+
+    /// Sets a callback that is run for every physics body as it is destroyed, replacing the previous one
+    ///
+    /// This includes bodies destroyed with [`Physac::destroy_physics_body`], [`Physac::destroy_physics_bodies`],
+    /// [`Physac::clear_physics_bodies`] and [`Physac::destroy_body_deferred`], but not bodies dropped by rolling back with [`Physac::restore`].
+    /// It runs while [`Physac`] is borrowed, so it can't borrow [`Physac`] itself
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use physac::prelude::*;
+    /// # use std::sync::{Arc, Mutex};
+    /// let mut ph = init_physics::<24, 24>().build();
+    /// let mut ph = ph.borrow_mut();
+    ///
+    /// // Free the game objects of bodies however they are destroyed
+    /// let destroyed = Arc::new(Mutex::new(Vec::new()));
+    /// let queue = destroyed.clone();
+    /// ph.set_on_body_destroyed(move |body| queue.lock().unwrap().push(body.user_data));
+    ///
+    /// let crate_body = ph.create_physics_body_rectangle(Vector2 { x: 100.0, y: 100.0 }, 20.0, 20.0, 1.0).clone();
+    /// crate_body.borrow_mut().user_data = 7;
+    /// ph.destroy_physics_body(crate_body);
+    /// assert_eq!(*destroyed.lock().unwrap(), [7]);
+    /// ```
+    pub fn set_on_body_destroyed<F>(&mut self, callback: F)
+    where
+        F: FnMut(&PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>) + ThreadSafe + 'static
+    {
+        self.on_body_destroyed = Some(Box::new(callback));
+    }
+
+    /// Removes the callback set with [`Physac::set_on_body_destroyed`]
+    pub fn clear_on_body_destroyed(&mut self) {
+        self.on_body_destroyed = None;
+    }
+
+    /// Adds a force field, which pushes the bodies inside of it every step until it is removed, and returns its ID
+    ///
+    /// Every body is woken up, since resting bodies may not be at rest anymore
+    pub fn add_force_field(&mut self, field: ForceField) -> u32 {
+        let id = self.next_force_field_id;
+        self.next_force_field_id = self.next_force_field_id.wrapping_add(1);
+        self.force_fields.push((id, field));
+
+        for body in &self.bodies {
+            body.borrow_mut().wake_up();
+        }
+
+        id
+    }
+
+    /// Returns the force field with an ID, or [`None`] if it has been removed
+    #[must_use]
+    pub fn force_field(&self, id: u32) -> Option<&ForceField> {
+        self.force_fields.iter().find(|(field_id, _)| *field_id == id).map(|(_, field)| field)
+    }
+
+    /// Returns the force field with an ID to change it, or [`None`] if it has been removed
+    ///
+    /// Sleeping bodies aren't woken up by changes to the field
+    pub fn force_field_mut(&mut self, id: u32) -> Option<&mut ForceField> {
+        self.force_fields.iter_mut().find(|(field_id, _)| *field_id == id).map(|(_, field)| field)
+    }
+
+    /// Removes the force field with an ID, returning it if it hadn't been removed yet
+    ///
+    /// Every body is woken up, since resting bodies may not be at rest anymore
+    pub fn remove_force_field(&mut self, id: u32) -> Option<ForceField> {
+        let index = self.force_fields.iter().position(|(field_id, _)| *field_id == id)?;
+        let (_, field) = self.force_fields.remove(index);
+
+        for body in &self.bodies {
+            body.borrow_mut().wake_up();
+        }
+
+        Some(field)
+    }
+
+    /// Adds a region of fluid, which floats the bodies inside of it every step until it is removed, and returns its ID
+    ///
+    /// Every body is woken up, since resting bodies may not be at rest anymore
+    pub fn add_buoyancy_zone(&mut self, zone: BuoyancyZone) -> u32 {
+        let id = self.next_buoyancy_zone_id;
+        self.next_buoyancy_zone_id = self.next_buoyancy_zone_id.wrapping_add(1);
+        self.buoyancy_zones.push((id, zone));
+
+        for body in &self.bodies {
+            body.borrow_mut().wake_up();
+        }
+
+        id
+    }
+
+    /// Returns the buoyancy zone with an ID, or [`None`] if it has been removed
+    #[must_use]
+    pub fn buoyancy_zone(&self, id: u32) -> Option<&BuoyancyZone> {
+        self.buoyancy_zones.iter().find(|(zone_id, _)| *zone_id == id).map(|(_, zone)| zone)
+    }
+
+    /// Returns the buoyancy zone with an ID to change it, or [`None`] if it has been removed
+    ///
+    /// Sleeping bodies aren't woken up by changes to the zone
+    pub fn buoyancy_zone_mut(&mut self, id: u32) -> Option<&mut BuoyancyZone> {
+        self.buoyancy_zones.iter_mut().find(|(zone_id, _)| *zone_id == id).map(|(_, zone)| zone)
+    }
+
+    /// Removes the buoyancy zone with an ID, returning it if it hadn't been removed yet
+    ///
+    /// Every body is woken up, since resting bodies may not be at rest anymore
+    pub fn remove_buoyancy_zone(&mut self, id: u32) -> Option<BuoyancyZone> {
+        let index = self.buoyancy_zones.iter().position(|(zone_id, _)| *zone_id == id)?;
+        let (_, zone) = self.buoyancy_zones.remove(index);
+
+        for body in &self.bodies {
+            body.borrow_mut().wake_up();
+        }
+
+        Some(zone)
+    }
+
+    /// Returns every body overlapping a region (see [`ForceFieldRegion`])
+    fn bodies_in_region(&self, region: &ForceFieldRegion) -> Vec<Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>> {
+        match region {
+            ForceFieldRegion::Aabb { min, max } => self.query_aabb(*min, *max),
+            ForceFieldRegion::Circle { center, radius } => self.overlap_circle(*center, *radius),
+            ForceFieldRegion::Polygon { vertices } => self.overlap_polygon(vertices),
+        }
+    }
+
+    /// Adds a particle to the world, and returns its ID
+    pub fn add_particle(&mut self, particle: Particle) -> u32 {
+        let id = self.next_particle_id;
+        self.next_particle_id = self.next_particle_id.wrapping_add(1);
+        self.particles.push((id, particle));
+        id
+    }
+
+    /// Returns the particle with an ID, or [`None`] if it has been removed
+    #[must_use]
+    pub fn particle(&self, id: u32) -> Option<&Particle> {
+        self.particles.iter().find(|(particle_id, _)| *particle_id == id).map(|(_, particle)| particle)
+    }
+
+    /// Returns the particle with an ID to change it, or [`None`] if it has been removed
+    pub fn particle_mut(&mut self, id: u32) -> Option<&mut Particle> {
+        self.particles.iter_mut().find(|(particle_id, _)| *particle_id == id).map(|(_, particle)| particle)
+    }
+
+    /// Returns an iterator over every particle along with its ID, in the order they were added
+    #[must_use]
+    pub fn particles(&self) -> impl ExactSizeIterator<Item = (u32, &Particle)> {
+        self.particles.iter().map(|(id, particle)| (*id, particle))
+    }
+
+    /// Removes the particle with an ID along with its constraints, returning it if it hadn't been removed yet
+    pub fn remove_particle(&mut self, id: u32) -> Option<Particle> {
+        let index = self.particles.iter().position(|(particle_id, _)| *particle_id == id)?;
+        let (_, particle) = self.particles.remove(index);
+        self.particle_constraints.retain(|(_, constraint)| constraint.particles().all(|particle_id| particle_id != id));
+        Some(particle)
+    }
+
+    /// Adds a constraint on particles to the world, and returns its ID
+    ///
+    /// Returns [`None`] if any of the particles has been removed
+    pub fn add_particle_constraint(&mut self, constraint: ParticleConstraint<MAX_VERTICES, CIRCLE_VERTICES>) -> Option<u32> {
+        if constraint.particles().any(|id| self.particle(id).is_none()) {
+            debug_print!("[PHYSAC] new particle constraint creation failed because a particle has been removed");
+            return None;
+        }
+
+        let id = self.next_particle_constraint_id;
+        self.next_particle_constraint_id = self.next_particle_constraint_id.wrapping_add(1);
+        self.particle_constraints.push((id, constraint));
+        Some(id)
+    }
+
+    /// Links two particles with a distance constraint that keeps them as far apart as they are now, and returns its ID
+    ///
+    /// Returns [`None`] if either particle has been removed
+    pub fn connect_particles(&mut self, particle_a: u32, particle_b: u32, stiffness: Real) -> Option<u32> {
+        let length = dist_sqr(self.particle(particle_a)?.position, self.particle(particle_b)?.position).sqrt();
+        self.add_particle_constraint(ParticleConstraint::Distance { particle_a, particle_b, length, stiffness: stiffness.clamp(0.0, 1.0) })
+    }
+
+    /// Returns the particle constraint with an ID, or [`None`] if it has been removed
+    #[must_use]
+    pub fn particle_constraint(&self, id: u32) -> Option<&ParticleConstraint<MAX_VERTICES, CIRCLE_VERTICES>> {
+        self.particle_constraints.iter().find(|(constraint_id, _)| *constraint_id == id).map(|(_, constraint)| constraint)
+    }
+
+    /// Returns an iterator over every particle constraint along with its ID, in the order they were added
+    #[must_use]
+    pub fn particle_constraints(&self) -> impl ExactSizeIterator<Item = (u32, &ParticleConstraint<MAX_VERTICES, CIRCLE_VERTICES>)> {
+        self.particle_constraints.iter().map(|(id, constraint)| (*id, constraint))
+    }
+
+    /// Removes the particle constraint with an ID, returning it if it hadn't been removed yet
+    pub fn remove_particle_constraint(&mut self, id: u32) -> Option<ParticleConstraint<MAX_VERTICES, CIRCLE_VERTICES>> {
+        let index = self.particle_constraints.iter().position(|(constraint_id, _)| *constraint_id == id)?;
+        Some(self.particle_constraints.remove(index).1)
+    }
+
+    /// Creates a cloth hanging down from `top_left`: a grid of particles `spacing` apart, linked to their horizontal and vertical neighbors by distance constraints
+    ///
+    /// The particles are `spacing` across, so that bodies can't slip between them. Pin the top row to hang the cloth up
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use physac::prelude::*;
+    /// let mut ph = init_physics::<24, 24>().build();
+    ///
+    /// let banner = ph.borrow_mut().create_cloth(Vector2 { x: 100.0, y: 50.0 }, 10, 8, 10.0, 1.0);
+    /// for column in [0, 9] {
+    ///     let particle = banner.particle(column, 0);
+    ///     let point = ph.borrow().particle(particle).unwrap().position;
+    ///     ph.borrow_mut().add_particle_constraint(ParticleConstraint::Pin { particle, point });
+    /// }
+    ///
+    /// for _ in 0..120 {
+    ///     ph.borrow_mut().update(1.0/60.0).unwrap();
+    /// }
+    ///
+    /// // The pinned corners stay put while the rest of the banner sags
+    /// let ph = ph.borrow();
+    /// assert_eq!(ph.particle(banner.particle(0, 0)).unwrap().position, Vector2 { x: 100.0, y: 50.0 });
+    /// assert!(ph.particle(banner.particle(5, 0)).unwrap().position.y > 50.0);
+    /// ```
+    pub fn create_cloth(&mut self, top_left: impl Into<Vector2>, columns: usize, rows: usize, spacing: Real, stiffness: Real) -> Cloth {
+        let top_left = top_left.into();
+        let mut cloth = Cloth { particles: Vec::with_capacity(columns*rows), columns, rows };
+        for row in 0..rows {
+            for column in 0..columns {
+                let position = top_left + Vector2 { x: column as Real, y: row as Real }*spacing;
+                let id = self.add_particle(Particle::new(position, 0.5*spacing));
+                if column > 0 {
+                    self.connect_particles(cloth.particles[cloth.particles.len() - 1], id, stiffness);
+                }
+                if row > 0 {
+                    self.connect_particles(cloth.particles[cloth.particles.len() - columns], id, stiffness);
+                }
+                cloth.particles.push(id);
+            }
+        }
+
+        cloth
+    }
+
+    /// Creates a rope from `start` to `end` out of `particle_count` particles linked in a chain by distance constraints, and returns their IDs from start to end
+    ///
+    /// The particles touch their neighbors, so that bodies can't slip between them.
+    /// Pin either end to hang the rope up, such as to a body with [`ParticleConstraint::BodyPin`]
+    ///
+    /// Returns no particles if there are less than 2 of them
+    pub fn create_particle_rope(&mut self, start: impl Into<Vector2>, end: impl Into<Vector2>, particle_count: usize, stiffness: Real) -> Vec<u32> {
+        let start = start.into();
+        let end = end.into();
+        if particle_count < 2 {
+            return Vec::new();
+        }
+
+        let step = (end - start)*(1.0/(particle_count - 1) as Real);
+        let radius = 0.5*step.length_sqr().sqrt();
+        let mut rope: Vec<u32> = Vec::with_capacity(particle_count);
+        for i in 0..particle_count {
+            let id = self.add_particle(Particle::new(start + step*i as Real, radius));
+            if let Some(&previous) = rope.last() {
+                self.connect_particles(previous, id, stiffness);
+            }
+            rope.push(id);
+        }
+
+        rope
+    }
+
+    /// Adds a fluid to the world, and returns its ID
+    pub fn add_fluid(&mut self, fluid: Fluid) -> u32 {
+        let id = self.next_fluid_id;
+        self.next_fluid_id = self.next_fluid_id.wrapping_add(1);
+        self.fluids.push((id, fluid));
+        id
+    }
+
+    /// Returns the fluid with an ID, or [`None`] if it has been removed
+    #[must_use]
+    pub fn fluid(&self, id: u32) -> Option<&Fluid> {
+        self.fluids.iter().find(|(fluid_id, _)| *fluid_id == id).map(|(_, fluid)| fluid)
+    }
+
+    /// Returns the fluid with an ID to change it or add particles to it, or [`None`] if it has been removed
+    pub fn fluid_mut(&mut self, id: u32) -> Option<&mut Fluid> {
+        self.fluids.iter_mut().find(|(fluid_id, _)| *fluid_id == id).map(|(_, fluid)| fluid)
+    }
+
+    /// Removes the fluid with an ID, returning it if it hadn't been removed yet
+    pub fn remove_fluid(&mut self, id: u32) -> Option<Fluid> {
+        let index = self.fluids.iter().position(|(fluid_id, _)| *fluid_id == id)?;
+        Some(self.fluids.remove(index).1)
+    }
+
+    /// Returns an iterator over the contacts between bodies found during the last step
+    ///
+    /// Bodies made of several parts can touch in several places, giving one contact per pair of touching parts
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use physac::prelude::*;
+    /// let mut ph = init_physics::<24, 24>().build();
+    ///
+    /// let floor = ph.borrow_mut()
+    ///     .create_physics_body_rectangle(Vector2 { x: 200.0, y: 300.0 }, 400.0, 20.0, 1.0)
+    ///     .clone();
+    /// floor.borrow_mut().enabled = false;
+    ///
+    /// ph.borrow_mut().create_physics_body_circle(Vector2 { x: 200.0, y: 282.0 }, 10.0, 1.0);
+    ///
+    /// for contact in ph.borrow().contact_iter() {
+    ///     println!("bodies {} and {} overlap by {} at {:?}", contact.body_a.borrow().id, contact.body_b.borrow().id, contact.penetration, contact.points());
+    /// }
+    /// ```
+    pub fn contact_iter(&self) -> impl Iterator<Item = ContactInfo<MAX_VERTICES, CIRCLE_VERTICES>> + '_ {
+        self.contacts.iter()
+            .filter(|manifold| manifold.contacts_count > 0)
+            .map(ContactInfo::from)
+    }
+
+    /// Returns the current amount of created physics bodies
+    #[must_use]
+    pub fn get_physics_bodies_count(&self) -> usize {
+        self.bodies.len()
+    }
+
+    /// Returns a handle to a physics body, or [`None`] if it isn't being simulated by this world
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use physac::prelude::*;
+    /// let mut ph = init_physics::<24, 24>().build();
+    ///
+    /// let crate_body = ph.borrow_mut()
+    ///     .create_physics_body_rectangle(Vector2 { x: 100.0, y: 100.0 }, 20.0, 20.0, 1.0)
+    ///     .clone();
+    /// let handle = ph.borrow().body_handle(&crate_body).unwrap();
+    /// ph.borrow_mut().destroy_physics_body(crate_body);
+    ///
+    /// // A new body takes over the ID, but the handle still refers to the destroyed one
+    /// ph.borrow_mut().create_physics_body_circle(Vector2 { x: 100.0, y: 100.0 }, 10.0, 1.0);
+    /// assert!(ph.borrow().get_body(handle).is_none());
+    /// ```
+    #[must_use]
+    pub fn body_handle(&self, body: &Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>) -> Option<BodyHandle> {
+        let body = body.borrow();
+        let is_in_world = body.is_simulating && self.bodies.iter().any(|other| other.borrow().id == body.id);
+        is_in_world.then(|| BodyHandle {
+            index: body.id,
+            generation: self.body_generations.get(body.id as usize).copied().unwrap_or(0),
+        })
+    }
+
+    /// Returns the physics body a handle refers to, or [`None`] if it has been destroyed
+    #[must_use]
+    pub fn get_body(&self, handle: BodyHandle) -> Option<&Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>> {
+        if !self.is_handle_current(handle) {
+            return None;
+        }
+        self.bodies.iter().find(|body| body.borrow().id == handle.index)
+    }
+
+    /// Returns true if no body has been given the ID of the handle since the handle was made
+    fn is_handle_current(&self, handle: BodyHandle) -> bool {
+        self.body_generations.get(handle.index as usize).copied().unwrap_or(0) == handle.generation
+    }
+
+    /// Returns the first physics body named `name` (see [`PhysicsBodyData::set_name`]), or [`None`] if there isn't one
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use physac::prelude::*;
+    /// let mut ph = init_physics::<24, 24>().build();
+    ///
+    /// // Wire up bodies exported by a level editor
+    /// ph.borrow_mut()
+    ///     .create_physics_body_rectangle(Vector2 { x: 200.0, y: 300.0 }, 80.0, 10.0, 1.0)
+    ///     .borrow_mut()
+    ///     .set_name("drawbridge");
+    ///
+    /// let bridge = ph.borrow().find_body("drawbridge").cloned().unwrap();
+    /// assert_eq!(bridge.borrow().name(), "drawbridge");
+    /// assert!(ph.borrow().find_body("portcullis").is_none());
+    /// ```
+    #[must_use]
+    pub fn find_body(&self, name: &str) -> Option<&Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>> {
+        self.bodies.iter().find(|body| body.borrow().name == name)
+    }
+
+    /// Borrows the physics body a handle refers to for the duration of the closure, or returns [`None`] if it has been destroyed
+    fn with_body<U>(&self, handle: BodyHandle, f: impl FnOnce(&mut PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>) -> U) -> Option<U> {
+        self.get_body(handle).map(|body| body.borrowed_mut(f))
+    }
+
+    /// Returns the position of the physics body a handle refers to, or [`None`] if it has been destroyed
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use physac::prelude::*;
+    /// let mut ph = init_physics::<24, 24>().build();
+    ///
+    /// let body = ph.borrow_mut()
+    ///     .create_physics_body_circle(Vector2 { x: 100.0, y: 100.0 }, 10.0, 1.0)
+    ///     .clone();
+    /// let player = ph.borrow().body_handle(&body).unwrap();
+    ///
+    /// // No need to borrow the body
+    /// let mut ph = ph.borrow_mut();
+    /// ph.set_body_velocity(player, Vector2 { x: 0.5, y: 0.0 });
+    /// ph.apply_force(player, Vector2 { x: 0.0, y: -20.0 });
+    /// assert_eq!(ph.body_position(player), Some(Vector2 { x: 100.0, y: 100.0 }));
+    /// ```
+    #[must_use]
+    pub fn body_position(&self, handle: BodyHandle) -> Option<Vector2> {
+        self.with_body(handle, |body| body.position)
+    }
+
+    /// Moves the physics body a handle refers to (see [`PhysicsBodyData::teleport`])
+    ///
+    /// Returns false if the body has been destroyed
+    pub fn set_body_position(&mut self, handle: BodyHandle, position: impl Into<Vector2>) -> bool {
+        let position = position.into();
+        let Some(body) = self.get_body(handle).cloned() else { return false };
+        body.borrowed_mut(|body| body.teleport(position, body.orient));
+        self.refit_in_body_tree(&body);
+        true
+    }
+
+    /// Returns the orientation (in radians) of the physics body a handle refers to, or [`None`] if it has been destroyed
+    #[must_use]
+    pub fn body_rotation(&self, handle: BodyHandle) -> Option<Real> {
+        self.with_body(handle, |body| body.orient)
+    }
+
+    /// Rotates the physics body a handle refers to (see [`PhysicsBodyData::teleport`])
+    ///
+    /// Returns false if the body has been destroyed
+    pub fn set_body_rotation(&mut self, handle: BodyHandle, radians: Real) -> bool {
+        let Some(body) = self.get_body(handle).cloned() else { return false };
+        body.borrowed_mut(|body| body.teleport(body.position, radians));
+        self.refit_in_body_tree(&body);
+        true
+    }
+
+    /// Moves the box of a body in the body tree (if there is one) straight away,
+    /// so that queries made before the next step find the body where it is now
+    fn refit_in_body_tree(&mut self, body: &Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>) {
+        if let Some(body_tree) = &mut self.body_tree {
+            body_tree.refit(body);
+        }
+    }
+
+    /// Returns the velocity of the physics body a handle refers to, or [`None`] if it has been destroyed
+    #[must_use]
+    pub fn body_velocity(&self, handle: BodyHandle) -> Option<Vector2> {
+        self.with_body(handle, |body| body.velocity)
+    }
+
+    /// Sets the velocity of the physics body a handle refers to (see [`PhysicsBodyData::set_linear_velocity`])
+    ///
+    /// Returns false if the body has been destroyed
+    pub fn set_body_velocity(&mut self, handle: BodyHandle, velocity: impl Into<Vector2>) -> bool {
+        let velocity = velocity.into();
+        self.with_body(handle, |body| body.set_linear_velocity(velocity)).is_some()
+    }
+
+    /// Returns the angular velocity of the physics body a handle refers to, or [`None`] if it has been destroyed
+    #[must_use]
+    pub fn body_angular_velocity(&self, handle: BodyHandle) -> Option<Real> {
+        self.with_body(handle, |body| body.angular_velocity)
+    }
+
+    /// Sets the angular velocity of the physics body a handle refers to (see [`PhysicsBodyData::set_angular_velocity`])
+    ///
+    /// Returns false if the body has been destroyed
+    pub fn set_body_angular_velocity(&mut self, handle: BodyHandle, angular_velocity: Real) -> bool {
+        self.with_body(handle, |body| body.set_angular_velocity(angular_velocity)).is_some()
+    }
+
+    /// Adds a force to the physics body a handle refers to (see [`PhysicsBodyData::add_force`])
+    ///
+    /// Returns false if the body has been destroyed
+    pub fn apply_force(&mut self, handle: BodyHandle, force: impl Into<Vector2>) -> bool {
+        let force = force.into();
+        self.with_body(handle, |body| body.add_force(force)).is_some()
+    }
+
+    /// Adds an angular force to the physics body a handle refers to (see [`PhysicsBodyData::add_torque`])
+    ///
+    /// Returns false if the body has been destroyed
+    pub fn apply_torque(&mut self, handle: BodyHandle, torque: Real) -> bool {
+        self.with_body(handle, |body| body.add_torque(torque)).is_some()
+    }
+
+    /// Returns true if the physics body a handle refers to is asleep, or [`None`] if it has been destroyed
+    #[must_use]
+    pub fn is_body_sleeping(&self, handle: BodyHandle) -> Option<bool> {
+        self.with_body(handle, |body| body.is_sleeping)
+    }
+
+    /// Calls `write` with the position and orientation (in radians) of the physics body of every entity, skipping entities whose body has been destroyed
+    ///
+    /// Meant for copying the results of a step back into the components of an ECS (such as hecs or legion) in one go, instead of looking up
+    /// and borrowing every body on its own. The entity can be anything, such as an entity ID or a mutable reference to its transform component
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use physac::prelude::*;
+    /// let mut ph = init_physics::<24, 24>().build();
+    ///
+    /// struct Sprite {
+    ///     body: BodyHandle,
+    ///     position: Vector2,
+    ///     rotation: Real,
+    /// }
+    ///
+    /// let body = ph.borrow_mut()
+    ///     .create_physics_body_rectangle(Vector2 { x: 100.0, y: 100.0 }, 20.0, 20.0, 1.0)
+    ///     .clone();
+    /// body.borrowed_mut(|body| {
+    ///     body.enabled = false;
+    ///     body.orient = 0.5;
+    /// });
+    /// let mut sprites = vec![Sprite { body: ph.borrow().body_handle(&body).unwrap(), position: Vector2::zero(), rotation: 0.0 }];
+    ///
+    /// // After each step, with something like `world.query_mut::<(&mut Sprite,)>()` in an ECS
+    /// let entities = sprites.iter_mut().map(|sprite| {
+    ///     let body = sprite.body;
+    ///     (sprite, body)
+    /// });
+    /// ph.borrow().sync_entities(entities, |sprite, position, rotation| {
+    ///     sprite.position = position;
+    ///     sprite.rotation = rotation;
+    /// });
+    ///
+    /// assert_eq!(sprites[0].position, Vector2 { x: 100.0, y: 100.0 });
+    /// assert_eq!(sprites[0].rotation, 0.5);
+    /// ```
+    pub fn sync_entities<E>(&self, entities: impl IntoIterator<Item = (E, BodyHandle)>, mut write: impl FnMut(E, Vector2, Real)) {
+        let poses: HashMap<u32, _> = self.bodies.iter()
+            .map(|body| {
+                let body = body.borrow();
+                (body.id, (body.position, body.orient))
+            })
+            .collect();
+
+        for (entity, handle) in entities {
+            if let Some(&(position, orient)) = poses.get(&handle.index).filter(|_| self.is_handle_current(handle)) {
+                write(entity, position, orient);
+            }
+        }
+    }
+
+    /// Returns a physics body of the bodies pool at a specific index
+    ///
+    /// Returns [`None`] if `index` is out of bounds
+    #[must_use]
+    pub fn try_get_physics_body(&self, index: usize) -> Option<&Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>> {
+        if index >= self.bodies.len() {
+            debug_print!("[PHYSAC] physics body index is out of bounds");
+        }
+
+        self.bodies.get(index)
+    }
+
+    /// Returns a physics body of the bodies pool at a specific index
+    ///
+    /// # Panics
+    ///
+    /// This method may panic if `index` is out of bounds
+    #[must_use]
+    pub fn get_physics_body(&self, index: usize) -> &Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>> {
+        self.try_get_physics_body(index).unwrap()
+    }
+
+    /// Returns a physics body of the bodies pool at a specific index
+    ///
+    /// # Panics
+    ///
+    /// This method may panic if `index` is out of bounds
+    #[cfg_attr(not(feature = "sync"), must_use)]
+    pub fn borrow_physics_body(&self, index: usize) -> PhysacReadGuard<'_, PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>> {
+        self.get_physics_body(index).borrow()
+    }
+
+    /// Returns a physics body of the bodies pool at a specific index
+    ///
+    /// # Panics
+    ///
+    /// This method may panic if `index` is out of bounds
+    #[cfg_attr(not(feature = "sync"), must_use)]
+    pub fn borrow_physics_body_mut(&self, index: usize) -> PhysacWriteGuard<'_, PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>> {
+        self.get_physics_body(index).borrow_mut()
+    }
+
+    /// Returns an iterator over borrows of each physics body in the simulation
+    ///
+    /// See [`Physac::physics_body_iter_mut`] for changing the bodies
+    #[must_use]
+    pub fn physics_body_iter(&self) -> impl DoubleEndedIterator<Item = PhysacReadGuard<'_, PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>> + ExactSizeIterator {
+        self.bodies.iter().map(|body| body.borrow())
+    }
+
+    /// Returns an iterator over mutable borrows of each physics body in the simulation
+    ///
+    /// Only one body is borrowed at a time, while the world stays borrowed for the whole loop
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use physac::prelude::*;
+    /// let mut ph = init_physics::<24, 24>().build();
+    ///
+    /// for i in 0..3 {
+    ///     ph.borrow_mut()
+    ///         .create_physics_body_circle(Vector2 { x: 100.0 + 30.0*i as Real, y: 100.0 }, 10.0, 1.0)
+    ///         .borrow_mut()
+    ///         .velocity = Vector2 { x: 1.0, y: 0.0 };
+    /// }
+    ///
+    /// // Slow every body down with a single borrow of the world
+    /// let world = ph.borrow();
+    /// for mut body in world.physics_body_iter_mut() {
+    ///     body.velocity = body.velocity*0.5;
+    /// }
+    /// assert!(world.physics_body_iter().all(|body| body.velocity.x == 0.5));
+    /// ```
+    #[must_use]
+    pub fn physics_body_iter_mut(&self) -> impl DoubleEndedIterator<Item = PhysacWriteGuard<'_, PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>> + ExactSizeIterator {
+        self.bodies.iter().map(|body| body.borrow_mut())
+    }
+
+    /// Calls `f` with a mutable borrow of each physics body in the simulation, spreading the bodies across threads
+    ///
+    /// Like [`Physac::physics_body_iter_mut`], but for updates that are worth running in parallel, such as custom forces on many bodies.
+    /// Lock guards can't be sent between threads, so the bodies are handed to a closure instead of being yielded by an iterator
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use physac::prelude::*;
+    /// let mut ph = init_physics::<24, 24>().max_bodies(100).build();
+    ///
+    /// for i in 0..100 {
+    ///     ph.borrow_mut().create_physics_body_circle(Vector2 { x: 10.0*i as Real, y: 100.0 }, 4.0, 1.0);
+    /// }
+    ///
+    /// // Quadratic drag
+    /// ph.borrow().par_physics_body_for_each_mut(|body| {
+    ///     let speed = body.velocity.length_sqr().sqrt();
+    ///     body.add_force(body.velocity*(-0.01*speed));
+    /// });
+    /// ```
+    #[cfg(feature = "parallel")]
+    pub fn par_physics_body_for_each_mut<F>(&self, f: F)
+    where
+        F: Fn(&mut PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>) + Sync + Send,
+    {
+        self.bodies.par_iter().for_each(|body| body.borrowed_mut(&f));
+    }
+
+    /// Returns an iterator over [`Strong`] references to each physics body in the simulation
+    #[must_use]
+    pub fn strong_physics_body_iter(&self) -> impl DoubleEndedIterator<Item = &Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>> + ExactSizeIterator {
+        self.bodies.iter()
+    }
+
+    /// Unitializes and destroys a physics body
+    ///
+    /// Sleeping bodies that were touching it or joined to it wake up, along with the sleeping bodies resting on them
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use physac::prelude::*;
+    /// let mut ph = init_physics::<24, 24>().sleep_steps(10).build();
+    /// let mut ph = ph.borrow_mut();
+    ///
+    /// let shelf = ph.body().rectangle(200.0, 20.0).at(Vector2 { x: 100.0, y: 200.0 }).fixed(true).build().clone();
+    /// let book = ph.create_physics_body_rectangle(Vector2 { x: 100.0, y: 180.0 }, 20.0, 20.0, 1.0).clone();
+    /// for _ in 0..120 {
+    ///     ph.step_once();
+    ///     ph.update(0.0).unwrap();
+    /// }
+    /// assert!(book.borrow().is_sleeping());
+    ///
+    /// // The book falls once the shelf is gone
+    /// ph.destroy_physics_body(shelf);
+    /// assert!(!book.borrow().is_sleeping());
+    /// ```
+    #[allow(
+        clippy::needless_pass_by_value,
+        reason = "the user will presumably pass in an upgrade they made specifically for this, and it indicates the end of that instance's life.",
+    )]
+    pub fn destroy_physics_body(&mut self, body: Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>) {
+        let id = body.borrow().id;
+
+        let index = self.bodies.iter().position(|body| body.borrow().id == id);
+
+        if let Some(index) = index {
+            body.borrow_mut().is_simulating = false;
+            if let Some(on_body_destroyed) = &mut self.on_body_destroyed {
+                on_body_destroyed.call(&body.borrow());
+            }
+            #[cfg(debug_assertions)]
+            let weak = body.downgrade();
+            // Free body allocated memory
+            drop(body);
+            self.bodies.remove(index);
+            self.wake_partners(&[id]);
+            self.destroy_detached_joints();
+
+            debug_print!("[PHYSAC] destroyed physics body id {id}");
+            #[cfg(debug_assertions)] {
+                let strong_count = weak.strong_count();
+                if strong_count > 0 {
+                    debug_print!("[PHYSAC] physics body id {id} is destroyed, but still has {strong_count} strong references");
+                }
+            }
+        } else {
+            debug_print!("[PHYSAC] Not possible to find body id {id} in pointers array");
+        }
+    }
+
+    /// Unitializes and destroys a physics body
+    #[allow(
+        clippy::needless_pass_by_value,
+        reason = "the user will presumably pass in a downgrade they made specifically for this, and it indicates the end of that instance's life.",
+    )]
+    pub fn try_destroy_physics_body(&mut self, body: Weak<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>) {
+        if let Some(body) = body.upgrade() {
+            self.destroy_physics_body(body);
+        } else {
+            debug_print!("[PHYSAC] error trying to destroy a null referenced body");
+        }
+    }
+
+    /// Queues a physics body to be destroyed at the end of the next step, instead of right away
+    ///
+    /// Meant for destroying bodies in response to what happened during a step, such as from collision events,
+    /// without changing the bodies while the step or other code is still going over them.
+    /// Queueing the same body more than once, or destroying it before the step ends, only destroys it once
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use physac::prelude::*;
+    /// let mut ph = init_physics::<24, 24>().build();
+    /// let mut ph = ph.borrow_mut();
+    ///
+    /// let bullet = ph.create_physics_body_circle(Vector2 { x: 100.0, y: 100.0 }, 2.0, 1.0).clone();
+    /// ph.destroy_body_deferred(&bullet);
+    /// assert!(bullet.borrow().is_simulating());
+    ///
+    /// ph.step_once();
+    /// ph.update(0.0).unwrap();
+    /// assert!(!bullet.borrow().is_simulating());
+    /// ```
+    pub fn destroy_body_deferred(&mut self, body: &Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>) {
+        self.deferred_destructions.push(body.downgrade());
+    }
+
+    /// Destroys the physics bodies queued with [`Physac::destroy_body_deferred`] that haven't been destroyed yet
+    fn destroy_deferred_bodies(&mut self) {
+        for body in std::mem::take(&mut self.deferred_destructions) {
+            // A body destroyed since it was queued may have given its ID to a new body
+            if let Some(body) = body.upgrade().filter(|body| body.borrow().is_simulating) {
+                self.destroy_physics_body(body);
+            }
+        }
+    }
+
+    /// Destroy all physics bodies that meet a condition
+    ///
+    /// This method operates in place, visiting each element exactly once in the original order, and preserves the order of the retained elements
+    pub fn destroy_physics_bodies<P>(&mut self, mut predicate: P)
+    where
+        P: FnMut(&PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>) -> bool,
+    {
+        self.destroy_physics_bodies_mut(|body| predicate(body));
+    }
+
+    /// Destroy all physics bodies that meet a condition
+    ///
+    /// This method operates in place, visiting each element exactly once in the original order, and preserves the order of the retained elements
+    pub fn destroy_physics_bodies_mut<P>(&mut self, mut predicate: P)
+    where
+        P: FnMut(&mut PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>) -> bool,
+    {
+        let mut destroyed_ids = Vec::new();
+        self.bodies.retain_mut(|body| {
+            let mut body = body.borrow_mut();
+            let is_destroyed = predicate(&mut body);
+            if is_destroyed {
+                body.is_simulating = false;
+                if let Some(on_body_destroyed) = &mut self.on_body_destroyed {
+                    on_body_destroyed.call(&body);
+                }
+                destroyed_ids.push(body.id);
+            }
+            !is_destroyed
+        });
+        self.wake_partners(&destroyed_ids);
+        self.destroy_detached_joints();
+    }
+
+    /// Removes all physics bodies
+    pub fn clear_physics_bodies(&mut self) {
+        for body in self.bodies.drain(..) {
+            body.borrow_mut().is_simulating = false;
+            if let Some(on_body_destroyed) = &mut self.on_body_destroyed {
+                on_body_destroyed.call(&body.borrow());
+            }
+        }
+        self.destroy_detached_joints();
+    }
+
+    /// Destroys every dynamic physics body, keeping static and kinematic ones (such as the geometry of a level)
+    ///
+    /// Joints attached to destroyed bodies are destroyed with them, and sleeping bodies they touched or were joined to wake up
+    pub fn clear_dynamic_bodies(&mut self) {
+        self.destroy_physics_bodies(|body| body.enabled);
+    }
+
+    /// Empties the world while keeping its settings, so that a level can be restarted without building a new [`Physac`]
+    ///
+    /// Every physics body, joint, soft body, particle, fluid, force field and buoyancy zone is removed, and undrained events are dropped.
+    /// Time built up for steps and single steps that haven't run yet are dropped too, so the new level doesn't start with a burst of steps,
+    /// and any recording or replay is stopped, since its commands refer to bodies that no longer exist.
+    /// The step count keeps going up, so that it stays unique for the world.
+    /// Gravity, the time step, the solver settings and the callbacks set with [`Physac::set_pre_solve`] and [`Physac::set_on_body_destroyed`] stay as they are.
+    /// To keep static level geometry, use [`Physac::clear_dynamic_bodies`] instead
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use physac::prelude::*;
+    /// let mut ph = init_physics::<24, 24>().build();
+    /// let mut ph = ph.borrow_mut();
+    ///
+    /// let floor = ph.body().rectangle(500.0, 20.0).at(Vector2 { x: 250.0, y: 400.0 }).fixed(true).build().clone();
+    /// ph.create_physics_body_circle(Vector2 { x: 250.0, y: 100.0 }, 10.0, 1.0);
+    ///
+    /// // The player fell, so throw the crates back but keep the floor
+    /// ph.clear_dynamic_bodies();
+    /// assert_eq!(ph.get_physics_bodies_count(), 1);
+    /// assert!(floor.borrow().is_simulating());
+    ///
+    /// // Next level
+    /// ph.reset();
+    /// assert_eq!(ph.get_physics_bodies_count(), 0);
+    /// ```
+    pub fn reset(&mut self) {
+        self.clear_physics_bodies();
+        self.clear_joints();
+        self.contacts.clear();
+        self.contact_impulses.clear();
+        self.joint_events.clear();
+        self.sensor_overlaps.clear();
+        self.sensor_events.clear();
+        self.touching_pairs.clear();
+        self.collision_events.clear();
+        self.ignored_pairs.clear();
+        self.deferred_destructions.clear();
+        self.soft_bodies.clear();
+        self.particles.clear();
+        self.particle_constraints.clear();
+        self.fluids.clear();
+        self.force_fields.clear();
+        self.buoyancy_zones.clear();
+        if let Some(body_tree) = &mut self.body_tree {
+            *body_tree = BodyTree::new(body_tree.margin);
+        }
+        self.accumulator = 0.0;
+        self.pending_steps = 0;
+        self.recording = None;
+        self.replay = None;
+    }
+
+    /// Creates a joint that keeps `anchor_a` on `body_a` and `anchor_b` on `body_b` a fixed `length` apart
+    ///
+    /// The anchors are given in world space and stay attached to their bodies as they move and rotate.
+    /// `stiffness` (0 to 1) controls how rigidly the length is enforced: 1 behaves like a solid rod, while lower values let the joint stretch
+    ///
+    /// Returns [`None`] if both bodies are the same or there are no available IDs
+    pub fn try_create_distance_joint(
+        &mut self,
+        body_a: &Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>,
+        body_b: &Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>,
+        anchor_a: impl Into<Vector2>,
+        anchor_b: impl Into<Vector2>,
+        length: Real,
+        stiffness: Real,
+    ) -> Option<&Strong<PhysicsJointData<MAX_VERTICES, CIRCLE_VERTICES>>> {
+        let anchor_a = anchor_a.into();
+        let anchor_b = anchor_b.into();
+        let kind = PhysicsJointKind::Distance { length: length.max(0.0), stiffness: stiffness.clamp(0.0, 1.0) };
+        self.try_create_joint(body_a, Some(body_b), anchor_a, anchor_b, kind)
+    }
+
+    /// Creates a joint that keeps `anchor_a` on `body_a` and `anchor_b` on `body_b` a fixed `length` apart
+    ///
+    /// The anchors are given in world space and stay attached to their bodies as they move and rotate.
+    /// `stiffness` (0 to 1) controls how rigidly the length is enforced: 1 behaves like a solid rod, while lower values let the joint stretch
+    ///
+    /// # Panics
+    ///
+    /// This method may panic if both bodies are the same or there are no available IDs
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use physac::prelude::*;
+    /// let mut ph = init_physics::<24, 24>().build();
+    ///
+    /// let pivot = ph.borrow_mut()
+    ///     .create_physics_body_circle(Vector2 { x: 100.0, y: 50.0 }, 5.0, 1.0)
+    ///     .clone();
+    /// pivot.borrow_mut().enabled = false;
+    ///
+    /// let bob = ph.borrow_mut()
+    ///     .create_physics_body_circle(Vector2 { x: 150.0, y: 50.0 }, 10.0, 1.0)
+    ///     .clone();
+    ///
+    /// let joint = ph.borrow_mut()
+    ///     .create_distance_joint(&pivot, &bob, Vector2 { x: 100.0, y: 50.0 }, Vector2 { x: 150.0, y: 50.0 }, 50.0, 1.0)
+    ///     .clone();
+    ///
+    /// assert_eq!(joint.borrow().anchor_b(), Vector2 { x: 150.0, y: 50.0 });
+    ///
+    /// // The bob swings down, staying 50 units away from the pivot
+    /// for _ in 0..60 {
+    ///     ph.borrow_mut().update(1.0/60.0).unwrap();
+    /// }
+    /// let length = (joint.borrow().anchor_b() - joint.borrow().anchor_a()).length_sqr().sqrt();
+    /// assert!((length - 50.0).abs() < 0.5);
+    ///
+    /// // Destroying either body also destroys the joint
+    /// ph.borrow_mut().destroy_physics_body(bob);
+    /// assert!(!joint.borrow().is_simulating());
+    /// assert_eq!(ph.borrow().get_joints_count(), 0);
+    /// ```
+    #[inline]
+    pub fn create_distance_joint(
+        &mut self,
+        body_a: &Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>,
+        body_b: &Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>,
+        anchor_a: impl Into<Vector2>,
+        anchor_b: impl Into<Vector2>,
+        length: Real,
+        stiffness: Real,
+    ) -> &Strong<PhysicsJointData<MAX_VERTICES, CIRCLE_VERTICES>> {
+        let anchor_a = anchor_a.into();
+        let anchor_b = anchor_b.into();
+        self.try_create_distance_joint(body_a, body_b, anchor_a, anchor_b, length, stiffness).unwrap()
+    }
+
+    /// Creates a joint that keeps `anchor_a` on `body_a` and `anchor_b` on `body_b` (in world space) from getting more than `max_length` apart
+    ///
+    /// Unlike a distance joint, the bodies move freely while the rope is slack
+    ///
+    /// Returns [`None`] if both bodies are the same or there are no available IDs
+    pub fn try_create_rope_joint(
+        &mut self,
+        body_a: &Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>,
+        body_b: &Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>,
+        anchor_a: impl Into<Vector2>,
+        anchor_b: impl Into<Vector2>,
+        max_length: Real,
+    ) -> Option<&Strong<PhysicsJointData<MAX_VERTICES, CIRCLE_VERTICES>>> {
+        let anchor_a = anchor_a.into();
+        let anchor_b = anchor_b.into();
+        let kind = PhysicsJointKind::Rope { max_length: max_length.max(0.0) };
+        self.try_create_joint(body_a, Some(body_b), anchor_a, anchor_b, kind)
+    }
+
+    /// Creates a joint that keeps `anchor_a` on `body_a` and `anchor_b` on `body_b` (in world space) from getting more than `max_length` apart
+    ///
+    /// Unlike a distance joint, the bodies move freely while the rope is slack
+    ///
+    /// # Panics
+    ///
+    /// This method may panic if both bodies are the same or there are no available IDs
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use physac::prelude::*;
+    /// let mut ph = init_physics::<24, 24>().build();
+    ///
+    /// let player = ph.borrow_mut()
+    ///     .create_physics_body_rectangle(Vector2 { x: 100.0, y: 300.0 }, 20.0, 40.0, 1.0)
+    ///     .clone();
+    ///
+    /// let ceiling = ph.borrow_mut()
+    ///     .create_physics_body_rectangle(Vector2 { x: 200.0, y: 50.0 }, 400.0, 20.0, 1.0)
+    ///     .clone();
+    /// ceiling.borrow_mut().enabled = false;
+    ///
+    /// // Fire a grappling hook at the ceiling
+    /// let hook = Vector2 { x: 180.0, y: 60.0 };
+    /// let player_pos = player.borrow().position;
+    /// let hook_length = (hook - player_pos).length_sqr().sqrt();
+    /// let grapple = ph.borrow_mut()
+    ///     .create_rope_joint(&player, &ceiling, player_pos, hook, hook_length)
+    ///     .clone();
+    ///
+    /// assert_eq!(grapple.borrow().anchor_b(), hook);
+    ///
+    /// // The player swings from the hook instead of falling
+    /// for _ in 0..60 {
+    ///     ph.borrow_mut().update(1.0/60.0).unwrap();
+    /// }
+    /// let length = (grapple.borrow().anchor_b() - grapple.borrow().anchor_a()).length_sqr().sqrt();
+    /// assert!(length <= hook_length + 0.5);
+    /// ```
+    #[inline]
+    pub fn create_rope_joint(
+        &mut self,
+        body_a: &Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>,
+        body_b: &Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>,
+        anchor_a: impl Into<Vector2>,
+        anchor_b: impl Into<Vector2>,
+        max_length: Real,
+    ) -> &Strong<PhysicsJointData<MAX_VERTICES, CIRCLE_VERTICES>> {
+        let anchor_a = anchor_a.into();
+        let anchor_b = anchor_b.into();
+        self.try_create_rope_joint(body_a, body_b, anchor_a, anchor_b, max_length).unwrap()
+    }
+
+    /// Hangs the positions of `body_a` and `body_b` from a rope running over `ground_anchor_a` and `ground_anchor_b` (in world space)
+    ///
+    /// The length of the first side plus `ratio` times the length of the second side stays the same as when the joint was created,
+    /// so lowering one body by some distance raises the other by that distance divided by `ratio`
+    ///
+    /// Returns [`None`] if both bodies are the same, `ratio` isn't positive, or there are no available IDs
+    pub fn try_create_pulley_joint(
+        &mut self,
+        body_a: &Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>,
+        body_b: &Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>,
+        ground_anchor_a: impl Into<Vector2>,
+        ground_anchor_b: impl Into<Vector2>,
+        ratio: Real,
+    ) -> Option<&Strong<PhysicsJointData<MAX_VERTICES, CIRCLE_VERTICES>>> {
+        let ground_anchor_a = ground_anchor_a.into();
+        let ground_anchor_b = ground_anchor_b.into();
+        if ratio <= 0.0 {
+            debug_print!("[PHYSAC] new pulley joint creation failed because the ratio isn't positive");
+            return None;
+        }
+
+        let (anchor_a, anchor_b) = (body_a.borrow().position, body_b.borrow().position);
+        let length = (anchor_a - ground_anchor_a).length_sqr().sqrt() + ratio*(anchor_b - ground_anchor_b).length_sqr().sqrt();
+        let kind = PhysicsJointKind::Pulley { ground_anchor_a, ground_anchor_b, length, ratio };
+        self.try_create_joint(body_a, Some(body_b), anchor_a, anchor_b, kind)
+    }
+
+    /// Hangs the positions of `body_a` and `body_b` from a rope running over `ground_anchor_a` and `ground_anchor_b` (in world space)
+    ///
+    /// The length of the first side plus `ratio` times the length of the second side stays the same as when the joint was created,
+    /// so lowering one body by some distance raises the other by that distance divided by `ratio`
+    ///
+    /// # Panics
+    ///
+    /// This method may panic if both bodies are the same, `ratio` isn't positive, or there are no available IDs
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use physac::prelude::*;
+    /// let mut ph = init_physics::<24, 24>().build();
+    ///
+    /// let elevator = ph.borrow_mut()
+    ///     .create_physics_body_rectangle(Vector2 { x: 100.0, y: 300.0 }, 60.0, 10.0, 1.0)
+    ///     .clone();
+    /// let counterweight = ph.borrow_mut()
+    ///     .create_physics_body_rectangle(Vector2 { x: 300.0, y: 200.0 }, 20.0, 20.0, 1.0)
+    ///     .clone();
+    ///
+    /// // Both sides of the cable run over wheels at the top of the shaft
+    /// let cable = ph.borrow_mut()
+    ///     .create_pulley_joint(&elevator, &counterweight, Vector2 { x: 100.0, y: 50.0 }, Vector2 { x: 300.0, y: 50.0 }, 1.0)
+    ///     .clone();
+    ///
+    /// assert_eq!(cable.borrow().joint_translation(), 400.0);
+    ///
+    /// // The heavier elevator sinks and lifts the counterweight, without the cable getting any longer
+    /// for _ in 0..60 {
+    ///     ph.borrow_mut().update(1.0/60.0).unwrap();
+    /// }
+    /// assert!(elevator.borrow().position.y > 300.0);
+    /// assert!((cable.borrow().joint_translation() - 400.0).abs() < 1.0);
+    /// ```
+    #[inline]
+    pub fn create_pulley_joint(
+        &mut self,
+        body_a: &Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>,
+        body_b: &Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>,
+        ground_anchor_a: impl Into<Vector2>,
+        ground_anchor_b: impl Into<Vector2>,
+        ratio: Real,
+    ) -> &Strong<PhysicsJointData<MAX_VERTICES, CIRCLE_VERTICES>> {
+        let ground_anchor_a = ground_anchor_a.into();
+        let ground_anchor_b = ground_anchor_b.into();
+        self.try_create_pulley_joint(body_a, body_b, ground_anchor_a, ground_anchor_b, ratio).unwrap()
+    }
+
+    /// Creates a damped spring between the positions of `body_a` and `body_b` that relaxes at `rest_length`
+    ///
+    /// `stiffness` is the force applied per unit the spring is stretched or compressed, and `damping` is the force applied per unit of speed it is stretched or compressed at.
+    /// The spring is solved implicitly, so stiff springs stay stable even at low frame rates
+    ///
+    /// Returns [`None`] if both bodies are the same or there are no available IDs
+    pub fn try_create_spring_joint(
+        &mut self,
+        body_a: &Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>,
+        body_b: &Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>,
+        rest_length: Real,
+        stiffness: Real,
+        damping: Real,
+    ) -> Option<&Strong<PhysicsJointData<MAX_VERTICES, CIRCLE_VERTICES>>> {
+        let (anchor_a, anchor_b) = (body_a.borrow().position, body_b.borrow().position);
+        let kind = PhysicsJointKind::Spring { rest_length: rest_length.max(0.0), stiffness: stiffness.max(0.0), damping: damping.max(0.0) };
+        self.try_create_joint(body_a, Some(body_b), anchor_a, anchor_b, kind)
+    }
+
+    /// Creates a damped spring between the positions of `body_a` and `body_b` that relaxes at `rest_length`
+    ///
+    /// `stiffness` is the force applied per unit the spring is stretched or compressed, and `damping` is the force applied per unit of speed it is stretched or compressed at.
+    /// The spring is solved implicitly, so stiff springs stay stable even at low frame rates
+    ///
+    /// # Panics
+    ///
+    /// This method may panic if both bodies are the same or there are no available IDs
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use physac::prelude::*;
+    /// let mut ph = init_physics::<24, 24>().build();
+    ///
+    /// let chassis = ph.borrow_mut()
+    ///     .create_physics_body_rectangle(Vector2 { x: 200.0, y: 100.0 }, 120.0, 20.0, 1.0)
+    ///     .clone();
+    ///
+    /// let wheel = ph.borrow_mut()
+    ///     .create_physics_body_circle(Vector2 { x: 160.0, y: 140.0 }, 15.0, 1.0)
+    ///     .clone();
+    ///
+    /// // Suspension that holds the wheel 40 units away from the chassis
+    /// let suspension = ph.borrow_mut()
+    ///     .create_spring_joint(&chassis, &wheel, 40.0, 50.0, 5.0)
+    ///     .clone();
+    ///
+    /// assert_eq!(suspension.borrow().anchor_a(), Vector2 { x: 200.0, y: 100.0 });
+    ///
+    /// // The wheel settles near the rest length
+    /// for _ in 0..180 {
+    ///     ph.borrow_mut().update(1.0/60.0).unwrap();
+    /// }
+    /// let length = (suspension.borrow().anchor_b() - suspension.borrow().anchor_a()).length_sqr().sqrt();
+    /// assert!((length - 40.0).abs() < 2.0);
+    /// ```
+    #[inline]
+    pub fn create_spring_joint(
+        &mut self,
+        body_a: &Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>,
+        body_b: &Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>,
+        rest_length: Real,
+        stiffness: Real,
+        damping: Real,
+    ) -> &Strong<PhysicsJointData<MAX_VERTICES, CIRCLE_VERTICES>> {
+        self.try_create_spring_joint(body_a, body_b, rest_length, stiffness, damping).unwrap()
+    }
+
+    /// Creates a joint that pins `body_a` and `body_b` together at `anchor` (in world space), letting them rotate around it
+    ///
+    /// The joint has no limits or motor until they are set with [`PhysicsJointData::set_limits`] and [`PhysicsJointData::set_motor`]
+    ///
+    /// Returns [`None`] if both bodies are the same or there are no available IDs
+    pub fn try_create_revolute_joint(
+        &mut self,
+        body_a: &Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>,
+        body_b: &Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>,
+        anchor: impl Into<Vector2>,
+    ) -> Option<&Strong<PhysicsJointData<MAX_VERTICES, CIRCLE_VERTICES>>> {
+        let anchor = anchor.into();
+        let reference_angle = body_b.borrow().orient - body_a.borrow().orient;
+        let kind = PhysicsJointKind::Revolute { reference_angle, limits: None, motor: None };
+        self.try_create_joint(body_a, Some(body_b), anchor, anchor, kind)
+    }
+
+    /// Creates a joint that pins `body_a` and `body_b` together at `anchor` (in world space), letting them rotate around it
+    ///
+    /// The joint has no limits or motor until they are set with [`PhysicsJointData::set_limits`] and [`PhysicsJointData::set_motor`]
+    ///
+    /// # Panics
+    ///
+    /// This method may panic if both bodies are the same or there are no available IDs
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use physac::prelude::*;
+    /// # #[cfg(not(feature = "f64"))] use std::f32::consts::FRAC_PI_2;
+    /// # #[cfg(feature = "f64")] use std::f64::consts::FRAC_PI_2;
+    /// let mut ph = init_physics::<24, 24>().build();
+    ///
+    /// let wall = ph.borrow_mut()
+    ///     .create_physics_body_rectangle(Vector2 { x: 100.0, y: 100.0 }, 10.0, 100.0, 1.0)
+    ///     .clone();
+    /// wall.borrow_mut().enabled = false;
+    ///
+    /// let door = ph.borrow_mut()
+    ///     .create_physics_body_rectangle(Vector2 { x: 140.0, y: 100.0 }, 60.0, 10.0, 1.0)
+    ///     .clone();
+    ///
+    /// // Hinge the door on the wall, letting it swing up to a quarter turn either way
+    /// let hinge = ph.borrow_mut()
+    ///     .create_revolute_joint(&wall, &door, Vector2 { x: 110.0, y: 100.0 })
+    ///     .clone();
+    /// hinge.borrow_mut().set_limits(Some(JointLimits { lower: -FRAC_PI_2, upper: FRAC_PI_2 }));
+    ///
+    /// assert_eq!(hinge.borrow().joint_angle(), 0.0);
+    ///
+    /// // The door swings down until it reaches the limit, without coming off the hinge
+    /// for _ in 0..120 {
+    ///     ph.borrow_mut().update(1.0/60.0).unwrap();
+    /// }
+    /// assert!((hinge.borrow().joint_angle() - FRAC_PI_2).abs() < 0.01);
+    /// assert!((hinge.borrow().anchor_b() - hinge.borrow().anchor_a()).length_sqr().sqrt() < 0.1);
+    /// ```
+    #[inline]
+    pub fn create_revolute_joint(
+        &mut self,
+        body_a: &Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>,
+        body_b: &Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>,
+        anchor: impl Into<Vector2>,
+    ) -> &Strong<PhysicsJointData<MAX_VERTICES, CIRCLE_VERTICES>> {
+        let anchor = anchor.into();
+        self.try_create_revolute_joint(body_a, body_b, anchor).unwrap()
+    }
+
+    /// Creates a joint that lets `body_b` slide along `axis` (in world space) through `anchor` on `body_a`, without rotating relative to it
+    ///
+    /// The joint has no limits or motor until they are set with [`PhysicsJointData::set_limits`] and [`PhysicsJointData::set_motor`]
+    ///
+    /// Returns [`None`] if both bodies are the same, `axis` has no length, or there are no available IDs
+    pub fn try_create_prismatic_joint(
+        &mut self,
+        body_a: &Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>,
+        body_b: &Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>,
+        anchor: impl Into<Vector2>,
+        axis: impl Into<Vector2>,
+    ) -> Option<&Strong<PhysicsJointData<MAX_VERTICES, CIRCLE_VERTICES>>> {
+        let anchor = anchor.into();
+        let axis = axis.into();
+        let length = axis.length_sqr().sqrt();
+        if length <= Real::EPSILON {
+            debug_print!("[PHYSAC] new joint creation failed because the axis has no length");
+            return None;
+        }
+
+        let (orient_a, orient_b) = (body_a.borrow().orient, body_b.borrow().orient);
+        let local_axis = Mat2::radians(orient_a).transpose().multiply_vector2(axis*(1.0/length));
+        let kind = PhysicsJointKind::Prismatic { local_axis, reference_angle: orient_b - orient_a, limits: None, motor: None };
+        self.try_create_joint(body_a, Some(body_b), anchor, anchor, kind)
+    }
+
+    /// Creates a joint that lets `body_b` slide along `axis` (in world space) through `anchor` on `body_a`, without rotating relative to it
+    ///
+    /// The joint has no limits or motor until they are set with [`PhysicsJointData::set_limits`] and [`PhysicsJointData::set_motor`]
+    ///
+    /// # Panics
+    ///
+    /// This method may panic if both bodies are the same, `axis` has no length, or there are no available IDs
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use physac::prelude::*;
+    /// let mut ph = init_physics::<24, 24>().build();
+    ///
+    /// let shaft = ph.borrow_mut()
+    ///     .create_physics_body_rectangle(Vector2 { x: 100.0, y: 300.0 }, 10.0, 10.0, 1.0)
+    ///     .clone();
+    /// shaft.borrow_mut().enabled = false;
+    ///
+    /// let elevator = ph.borrow_mut()
+    ///     .create_physics_body_rectangle(Vector2 { x: 200.0, y: 300.0 }, 80.0, 10.0, 1.0)
+    ///     .clone();
+    ///
+    /// // Move the elevator up to 200 units above where it started
+    /// let piston = ph.borrow_mut()
+    ///     .create_prismatic_joint(&shaft, &elevator, Vector2 { x: 200.0, y: 300.0 }, Vector2 { x: 0.0, y: -1.0 })
+    ///     .clone();
+    /// piston.borrowed_mut(|piston| {
+    ///     piston.set_limits(Some(JointLimits { lower: 0.0, upper: 200.0 }));
+    ///     piston.set_motor(Some(JointMotor { speed: 0.05, max_force: 1000.0 }));
+    /// });
+    ///
+    /// assert_eq!(piston.borrow().joint_translation(), 0.0);
+    ///
+    /// // The elevator rises straight up until it reaches the upper limit
+    /// for _ in 0..300 {
+    ///     ph.borrow_mut().update(1.0/60.0).unwrap();
+    /// }
+    /// assert!((piston.borrow().joint_translation() - 200.0).abs() < 0.5);
+    /// assert!((elevator.borrow().position.x - 200.0).abs() < 0.01);
+    /// assert!(elevator.borrow().orient.abs() < 0.01);
+    /// ```
+    #[inline]
+    pub fn create_prismatic_joint(
+        &mut self,
+        body_a: &Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>,
+        body_b: &Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>,
+        anchor: impl Into<Vector2>,
+        axis: impl Into<Vector2>,
+    ) -> &Strong<PhysicsJointData<MAX_VERTICES, CIRCLE_VERTICES>> {
+        let anchor = anchor.into();
+        let axis = axis.into();
+        self.try_create_prismatic_joint(body_a, body_b, anchor, axis).unwrap()
+    }
+
+    /// Creates a joint that locks the position and angle of `body_b` relative to `body_a`, holding them together at `anchor` (in world space)
+    ///
+    /// Set [`PhysicsJointData::break_force`] to let the weld break apart under load
+    ///
+    /// Returns [`None`] if both bodies are the same or there are no available IDs
+    pub fn try_create_weld_joint(
+        &mut self,
+        body_a: &Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>,
+        body_b: &Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>,
+        anchor: impl Into<Vector2>,
+    ) -> Option<&Strong<PhysicsJointData<MAX_VERTICES, CIRCLE_VERTICES>>> {
+        let anchor = anchor.into();
+        let reference_angle = body_b.borrow().orient - body_a.borrow().orient;
+        self.try_create_joint(body_a, Some(body_b), anchor, anchor, PhysicsJointKind::Weld { reference_angle })
+    }
+
+    /// Creates a joint that locks the position and angle of `body_b` relative to `body_a`, holding them together at `anchor` (in world space)
+    ///
+    /// Set [`PhysicsJointData::break_force`] to let the weld break apart under load
+    ///
+    /// # Panics
+    ///
+    /// This method may panic if both bodies are the same or there are no available IDs
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use physac::prelude::*;
+    /// let mut ph = init_physics::<24, 24>().build();
+    ///
+    /// let beam = ph.borrow_mut()
+    ///     .create_physics_body_rectangle(Vector2 { x: 100.0, y: 100.0 }, 100.0, 10.0, 1.0)
+    ///     .clone();
+    /// beam.borrow_mut().enabled = false;
+    ///
+    /// let post = ph.borrow_mut()
+    ///     .create_physics_body_rectangle(Vector2 { x: 145.0, y: 130.0 }, 10.0, 50.0, 1.0)
+    ///     .clone();
+    ///
+    /// let weld = ph.borrow_mut()
+    ///     .create_weld_joint(&beam, &post, Vector2 { x: 145.0, y: 105.0 })
+    ///     .clone();
+    /// // Strong enough to hold the post, which weighs about 4900
+    /// weld.borrow_mut().break_force = Some(10000.0);
+    ///
+    /// assert_eq!(weld.borrow().joint_angle(), 0.0);
+    ///
+    /// for _ in 0..60 {
+    ///     ph.borrow_mut().update(1.0/60.0).unwrap();
+    /// }
+    /// assert!(weld.borrow().is_simulating());
+    /// assert!(weld.borrow().joint_angle().abs() < 0.01);
+    /// assert!((post.borrow().position - Vector2 { x: 145.0, y: 130.0 }).length_sqr().sqrt() < 0.5);
+    /// ```
+    #[inline]
+    pub fn create_weld_joint(
+        &mut self,
+        body_a: &Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>,
+        body_b: &Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>,
+        anchor: impl Into<Vector2>,
+    ) -> &Strong<PhysicsJointData<MAX_VERTICES, CIRCLE_VERTICES>> {
+        let anchor = anchor.into();
+        self.try_create_weld_joint(body_a, body_b, anchor).unwrap()
+    }
+
+    /// Creates a joint that pulls `body` towards `target` (in world space) with up to `max_force`, grabbing it at the point currently under `target`
+    ///
+    /// Move the target every frame with [`PhysicsJointData::set_target`] to drag the body around while it still collides with other bodies.
+    /// The pull behaves like a spring that oscillates 5 times per second with a damping ratio of 0.7, which can be changed through [`PhysicsJointData::kind`]
+    ///
+    /// Returns [`None`] if there are no available IDs
+    pub fn try_create_target_joint(
+        &mut self,
+        body: &Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>,
+        target: impl Into<Vector2>,
+        max_force: Real,
+    ) -> Option<&Strong<PhysicsJointData<MAX_VERTICES, CIRCLE_VERTICES>>> {
+        let target = target.into();
+        let kind = PhysicsJointKind::Target { max_force: max_force.max(0.0), frequency: 5.0, damping_ratio: 0.7 };
+        self.try_create_joint(body, None, target, target, kind)
+    }
+
+    /// Creates a joint that pulls `body` towards `target` (in world space) with up to `max_force`, grabbing it at the point currently under `target`
+    ///
+    /// Move the target every frame with [`PhysicsJointData::set_target`] to drag the body around while it still collides with other bodies.
+    /// The pull behaves like a spring that oscillates 5 times per second with a damping ratio of 0.7, which can be changed through [`PhysicsJointData::kind`]
+    ///
+    /// # Panics
+    ///
+    /// This method may panic if there are no available IDs
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use physac::prelude::*;
+    /// let mut ph = init_physics::<24, 24>().build();
+    ///
+    /// let ball = ph.borrow_mut()
+    ///     .create_physics_body_circle(Vector2 { x: 100.0, y: 100.0 }, 20.0, 1.0)
+    ///     .clone();
+    ///
+    /// // Grab the ball where the mouse was pressed
+    /// let drag = ph.borrow_mut()
+    ///     .create_target_joint(&ball, Vector2 { x: 110.0, y: 100.0 }, 1000.0)
+    ///     .clone();
+    ///
+    /// // Then follow the mouse as it moves
+    /// drag.borrow_mut().set_target(Vector2 { x: 150.0, y: 80.0 });
+    ///
+    /// assert_eq!(drag.borrow().anchor_b(), Vector2 { x: 150.0, y: 80.0 });
+    /// assert!(drag.borrow().body_b().is_none());
+    ///
+    /// // The grabbed point is pulled most of the way to the mouse, while gravity drags the ball down
+    /// for _ in 0..60 {
+    ///     ph.borrow_mut().update(1.0/60.0).unwrap();
+    /// }
+    /// let gap = (drag.borrow().anchor_b() - drag.borrow().anchor_a()).length_sqr().sqrt();
+    /// assert!(gap < 15.0);
+    /// ```
+    #[inline]
+    pub fn create_target_joint(
+        &mut self,
+        body: &Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>,
+        target: impl Into<Vector2>,
+        max_force: Real,
+    ) -> &Strong<PhysicsJointData<MAX_VERTICES, CIRCLE_VERTICES>> {
+        let target = target.into();
+        self.try_create_target_joint(body, target, max_force).unwrap()
+    }
+
+    /// Creates a joint that couples the angles of two revolute joints, so that the angle of `joint_a` plus `ratio` times the angle of `joint_b` stays constant
+    ///
+    /// The gear acts on the second body of each revolute joint, relative to the body it's mounted on.
+    /// For two meshing gears, `ratio` is the radius of the second gear divided by the radius of the first, and they turn in opposite directions
+    ///
+    /// Returns [`None`] if either joint isn't a revolute joint, both joints turn the same body, or there are no available IDs
+    pub fn try_create_gear_joint(
+        &mut self,
+        joint_a: &Strong<PhysicsJointData<MAX_VERTICES, CIRCLE_VERTICES>>,
+        joint_b: &Strong<PhysicsJointData<MAX_VERTICES, CIRCLE_VERTICES>>,
+        ratio: Real,
+    ) -> Option<&Strong<PhysicsJointData<MAX_VERTICES, CIRCLE_VERTICES>>> {
+        let (Some(body_a), Some(body_b)) = (joint_a.borrow().body_b.clone(), joint_b.borrow().body_b.clone()) else {
+            debug_print!("[PHYSAC] new gear joint creation failed because a linked joint isn't a revolute joint");
+            return None;
+        };
+        let is_revolute = |joint: &Strong<PhysicsJointData<MAX_VERTICES, CIRCLE_VERTICES>>| matches!(joint.borrow().kind, PhysicsJointKind::Revolute { .. });
+        if !is_revolute(joint_a) || !is_revolute(joint_b) {
+            debug_print!("[PHYSAC] new gear joint creation failed because a linked joint isn't a revolute joint");
+            return None;
+        }
+
+        let relative_orient = |joint: &Strong<PhysicsJointData<MAX_VERTICES, CIRCLE_VERTICES>>, body: &Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>| {
+            body.borrow().orient - joint.borrow().body_a.borrow().orient
+        };
+        let reference = relative_orient(joint_a, &body_a) + ratio*relative_orient(joint_b, &body_b);
+        let (anchor_a, anchor_b) = (body_a.borrow().position, body_b.borrow().position);
+        let joint = self.try_create_joint(&body_a, Some(&body_b), anchor_a, anchor_b, PhysicsJointKind::Gear { ratio, reference })?;
+        joint.borrow_mut().linked_joints = Some([joint_a.clone(), joint_b.clone()]);
+        Some(joint)
+    }
+
+    /// Creates a joint that couples the angles of two revolute joints, so that the angle of `joint_a` plus `ratio` times the angle of `joint_b` stays constant
+    ///
+    /// The gear acts on the second body of each revolute joint, relative to the body it's mounted on.
+    /// For two meshing gears, `ratio` is the radius of the second gear divided by the radius of the first, and they turn in opposite directions
+    ///
+    /// # Panics
+    ///
+    /// This method may panic if either joint isn't a revolute joint, both joints turn the same body, or there are no available IDs
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use physac::prelude::*;
+    /// let mut ph = init_physics::<24, 24>().build();
+    ///
+    /// let frame = ph.borrow_mut()
+    ///     .create_physics_body_rectangle(Vector2 { x: 150.0, y: 50.0 }, 200.0, 10.0, 1.0)
+    ///     .clone();
+    /// frame.borrow_mut().enabled = false;
+    ///
+    /// let small_gear = ph.borrow_mut()
+    ///     .create_physics_body_circle(Vector2 { x: 100.0, y: 100.0 }, 20.0, 1.0)
+    ///     .clone();
+    /// let large_gear = ph.borrow_mut()
+    ///     .create_physics_body_circle(Vector2 { x: 160.0, y: 100.0 }, 40.0, 1.0)
+    ///     .clone();
+    ///
+    /// let axle_a = ph.borrow_mut().create_revolute_joint(&frame, &small_gear, Vector2 { x: 100.0, y: 100.0 }).clone();
+    /// let axle_b = ph.borrow_mut().create_revolute_joint(&frame, &large_gear, Vector2 { x: 160.0, y: 100.0 }).clone();
+    ///
+    /// // The large gear turns once for every two turns of the small one
+    /// let gears = ph.borrow_mut().create_gear_joint(&axle_a, &axle_b, 2.0).clone();
+    ///
+    /// assert!(gears.borrow().body_b().is_some());
+    ///
+    /// // Spin the small gear, and the large one turns back half as far
+    /// small_gear.borrow_mut().angular_velocity = 0.01;
+    /// for _ in 0..60 {
+    ///     ph.borrow_mut().update(1.0/60.0).unwrap();
+    /// }
+    /// let (angle_a, angle_b) = (axle_a.borrow().joint_angle(), axle_b.borrow().joint_angle());
+    /// assert!(angle_a > 1.0);
+    /// assert!((angle_a + 2.0*angle_b).abs() < 0.01);
+    /// ```
+    #[inline]
+    pub fn create_gear_joint(
+        &mut self,
+        joint_a: &Strong<PhysicsJointData<MAX_VERTICES, CIRCLE_VERTICES>>,
+        joint_b: &Strong<PhysicsJointData<MAX_VERTICES, CIRCLE_VERTICES>>,
+        ratio: Real,
+    ) -> &Strong<PhysicsJointData<MAX_VERTICES, CIRCLE_VERTICES>> {
+        self.try_create_gear_joint(joint_a, joint_b, ratio).unwrap()
+    }
+
+    /// Creates a rope from `start` to `end` out of `segment_count` rectangle bodies `thickness` wide, linked by revolute joints
+    ///
+    /// Neighboring segments leave a gap of up to `thickness` around each joint so that they can bend without colliding.
+    /// Attach either end to other bodies by creating a joint at [`Rope::start_anchor`] or [`Rope::end_anchor`]
+    ///
+    /// Returns [`None`] if `segment_count` is zero, `start` and `end` are the same point, `thickness` isn't positive, or there are not enough available IDs
+    pub fn try_create_rope(&mut self, start: impl Into<Vector2>, end: impl Into<Vector2>, segment_count: usize, thickness: Real, density: Real) -> Option<Rope<MAX_VERTICES, CIRCLE_VERTICES>> {
+        let start = start.into();
+        let end = end.into();
+        let delta = end - start;
+        let length = delta.length_sqr().sqrt();
+        if segment_count == 0 || length <= Real::EPSILON || thickness <= 0.0 {
+            debug_print!("[PHYSAC] new rope creation failed because it has no segments or no size");
+            return None;
+        }
+
+        let direction = delta*(1.0/length);
+        let angle = math_atan2(direction.y, direction.x);
+        let segment_length = length/segment_count as Real;
+        let segment_width = segment_length - thickness.min(0.5*segment_length);
+
+        let mut rope = Rope { segments: Vec::with_capacity(segment_count), joints: Vec::with_capacity(segment_count - 1), segment_length };
+        for i in 0..segment_count {
+            let center = start + direction*(segment_length*(i as Real + 0.5));
+            let Ok(segment) = self.try_create_physics_body_rectangle(center, segment_width, thickness, density).cloned() else {
+                self.destroy_rope(rope);
+                return None;
+            };
+            segment.borrow_mut().set_rotation(angle);
+
+            if let Some(previous) = rope.segments.last() {
+                let anchor = start + direction*(segment_length*i as Real);
+                let Some(joint) = self.try_create_revolute_joint(previous, &segment, anchor).cloned() else {
+                    rope.segments.push(segment);
+                    self.destroy_rope(rope);
+                    return None;
+                };
+                rope.joints.push(joint);
+            }
+            rope.segments.push(segment);
+        }
+
+        Some(rope)
+    }
+
+    /// Creates a rope from `start` to `end` out of `segment_count` rectangle bodies `thickness` wide, linked by revolute joints
+    ///
+    /// Neighboring segments leave a gap of up to `thickness` around each joint so that they can bend without colliding.
+    /// Attach either end to other bodies by creating a joint at [`Rope::start_anchor`] or [`Rope::end_anchor`]
+    ///
+    /// # Panics
+    ///
+    /// This method may panic if `segment_count` is zero, `start` and `end` are the same point, `thickness` isn't positive, or there are not enough available IDs
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use physac::prelude::*;
+    /// let mut ph = init_physics::<24, 24>().build();
+    ///
+    /// let ceiling = ph.borrow_mut()
+    ///     .create_physics_body_rectangle(Vector2 { x: 200.0, y: 40.0 }, 400.0, 20.0, 1.0)
+    ///     .clone();
+    /// ceiling.borrow_mut().enabled = false;
+    ///
+    /// let lamp = ph.borrow_mut()
+    ///     .create_physics_body_circle(Vector2 { x: 200.0, y: 270.0 }, 20.0, 1.0)
+    ///     .clone();
+    ///
+    /// let rope = ph.borrow_mut().create_rope(Vector2 { x: 200.0, y: 50.0 }, Vector2 { x: 200.0, y: 250.0 }, 10, 4.0, 1.0);
+    /// ph.borrow_mut().create_revolute_joint(&ceiling, rope.start(), rope.start_anchor());
+    /// ph.borrow_mut().create_revolute_joint(rope.end(), &lamp, rope.end_anchor());
+    ///
+    /// assert_eq!(rope.segments.len(), 10);
+    /// assert_eq!(ph.borrow().get_joints_count(), 11);
+    /// ```
+    #[inline]
+    pub fn create_rope(&mut self, start: impl Into<Vector2>, end: impl Into<Vector2>, segment_count: usize, thickness: Real, density: Real) -> Rope<MAX_VERTICES, CIRCLE_VERTICES> {
+        let start = start.into();
+        let end = end.into();
+        self.try_create_rope(start, end, segment_count, thickness, density).unwrap()
+    }
+
+    /// Destroys every segment of a rope, along with its joints and any joints attaching it to other bodies
+    pub fn destroy_rope(&mut self, rope: Rope<MAX_VERTICES, CIRCLE_VERTICES>) {
+        for segment in rope.segments {
+            self.destroy_physics_body(segment);
+        }
+    }
+
+    /// Creates a standing ragdoll with its torso centered at `position`, sized by `bones`
+    ///
+    /// Every part hangs from the torso by a revolute joint, limited to a human range of motion.
+    /// Neighboring parts leave a gap of half the limb thickness around each joint so that they can bend without colliding
+    ///
+    /// Returns [`None`] if there are not enough available IDs
+    pub fn try_create_ragdoll(&mut self, position: impl Into<Vector2>, bones: &RagdollBones, density: Real) -> Option<Ragdoll<MAX_VERTICES, CIRCLE_VERTICES>> {
+        let position = position.into();
+        let mut bodies = Vec::with_capacity(10);
+        let mut joints = Vec::with_capacity(9);
+        if self.try_create_ragdoll_parts(position, bones, density, &mut bodies, &mut joints).is_none() {
+            debug_print!("[PHYSAC] new ragdoll creation failed because there are not enough available IDs");
+            for body in bodies {
+                self.destroy_physics_body(body);
+            }
+            return None;
+        }
+
+        let Ok([head, torso, upper_arm_l, upper_arm_r, lower_arm_l, lower_arm_r, upper_leg_l, upper_leg_r, lower_leg_l, lower_leg_r]) = <[_; 10]>::try_from(bodies) else {
+            unreachable!("every ragdoll body was created")
+        };
+        let Ok([neck, shoulder_l, shoulder_r, elbow_l, elbow_r, hip_l, hip_r, knee_l, knee_r]) = <[_; 9]>::try_from(joints) else {
+            unreachable!("every ragdoll joint was created")
+        };
+        Some(Ragdoll {
+            head,
+            torso,
+            upper_arms: [upper_arm_l, upper_arm_r],
+            lower_arms: [lower_arm_l, lower_arm_r],
+            upper_legs: [upper_leg_l, upper_leg_r],
+            lower_legs: [lower_leg_l, lower_leg_r],
+            neck,
+            shoulders: [shoulder_l, shoulder_r],
+            elbows: [elbow_l, elbow_r],
+            hips: [hip_l, hip_r],
+            knees: [knee_l, knee_r],
+        })
+    }
+
+    /// Creates a standing ragdoll with its torso centered at `position`, sized by `bones`
+    ///
+    /// Every part hangs from the torso by a revolute joint, limited to a human range of motion.
+    /// Neighboring parts leave a gap of half the limb thickness around each joint so that they can bend without colliding
+    ///
+    /// # Panics
+    ///
+    /// This method may panic if there are not enough available IDs
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use physac::prelude::*;
+    /// let mut ph = init_physics::<24, 24>().build();
+    ///
+    /// let ragdoll = ph.borrow_mut().create_ragdoll(Vector2 { x: 200.0, y: 100.0 }, &RagdollBones::with_height(180.0), 1.0);
+    ///
+    /// assert_eq!(ragdoll.bodies().count(), 10);
+    /// assert_eq!(ragdoll.joints().count(), 9);
+    ///
+    /// // Throw it across the screen
+    /// for body in ragdoll.bodies() {
+    ///     body.borrow_mut().velocity = Vector2 { x: 0.5, y: -0.5 };
+    /// }
+    /// ```
+    #[inline]
+    pub fn create_ragdoll(&mut self, position: impl Into<Vector2>, bones: &RagdollBones, density: Real) -> Ragdoll<MAX_VERTICES, CIRCLE_VERTICES> {
+        let position = position.into();
+        self.try_create_ragdoll(position, bones, density).unwrap()
+    }
+
+    /// Destroys every body of a ragdoll, along with its joints and any joints attaching it to other bodies
+    pub fn destroy_ragdoll(&mut self, ragdoll: Ragdoll<MAX_VERTICES, CIRCLE_VERTICES>) {
+        let Ragdoll { head, torso, upper_arms, lower_arms, upper_legs, lower_legs, .. } = ragdoll;
+        for body in [head, torso].into_iter().chain(upper_arms).chain(lower_arms).chain(upper_legs).chain(lower_legs) {
+            self.destroy_physics_body(body);
+        }
+    }
+
+    /// Creates the bodies of a ragdoll in the order of the fields of [`Ragdoll`], along with its joints,
+    /// stopping as soon as one can't be created
+    fn try_create_ragdoll_parts(
+        &mut self,
+        position: Vector2,
+        bones: &RagdollBones,
+        density: Real,
+        bodies: &mut Vec<Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>>,
+        joints: &mut Vec<Strong<PhysicsJointData<MAX_VERTICES, CIRCLE_VERTICES>>>,
+    ) -> Option<()> {
+        use real_consts::PI;
+
+        let thickness = bones.limb_thickness;
+        let gap = 0.5*thickness;
+        let shoulder_y = position.y - 0.5*bones.torso_length;
+        let hip_y = position.y + 0.5*bones.torso_length + gap;
+        let arm_x = 0.5*bones.torso_width + gap + 0.5*thickness;
+        let leg_x = (0.25*bones.torso_width).max(0.5*(thickness + gap));
+
+        let head_position = Vector2 { x: position.x, y: shoulder_y - gap - bones.head_radius };
+        bodies.push(self.try_create_physics_body_circle(head_position, bones.head_radius, density).ok()?.clone());
+        bodies.push(self.try_create_physics_body_rectangle(position, bones.torso_width, bones.torso_length, density).ok()?.clone());
+
+        // Limbs hang straight down, with the top of each one `gap` below the bottom of the part before it
+        let limbs = [
+            (-arm_x, shoulder_y, bones.upper_arm_length), (arm_x, shoulder_y, bones.upper_arm_length),
+            (-arm_x, shoulder_y + bones.upper_arm_length + gap, bones.lower_arm_length), (arm_x, shoulder_y + bones.upper_arm_length + gap, bones.lower_arm_length),
+            (-leg_x, hip_y, bones.upper_leg_length), (leg_x, hip_y, bones.upper_leg_length),
+            (-leg_x, hip_y + bones.upper_leg_length + gap, bones.lower_leg_length), (leg_x, hip_y + bones.upper_leg_length + gap, bones.lower_leg_length),
+        ];
+        for (x, top, length) in limbs {
+            let center = Vector2 { x: position.x + x, y: top + 0.5*length };
+            bodies.push(self.try_create_physics_body_rectangle(center, thickness, length, density).ok()?.clone());
+        }
+
+        // Each joint connects a parent and child body at a point, bending within limits given for the left side and mirrored for the right
+        let [head, torso, upper_arm_l, upper_arm_r, lower_arm_l, lower_arm_r, upper_leg_l, upper_leg_r, lower_leg_l, lower_leg_r] = &bodies[..] else {
+            unreachable!("every ragdoll body was just created")
+        };
+        let connections = [
+            (torso, head, Vector2 { x: 0.0, y: shoulder_y - 0.5*gap }, (-0.25*PI, 0.25*PI)),
+            (torso, upper_arm_l, Vector2 { x: -arm_x, y: shoulder_y }, (-PI/6.0, 0.9*PI)),
+            (torso, upper_arm_r, Vector2 { x: arm_x, y: shoulder_y }, (-PI/6.0, 0.9*PI)),
+            (upper_arm_l, lower_arm_l, Vector2 { x: -arm_x, y: shoulder_y + bones.upper_arm_length + 0.5*gap }, (-0.75*PI, 0.0)),
+            (upper_arm_r, lower_arm_r, Vector2 { x: arm_x, y: shoulder_y + bones.upper_arm_length + 0.5*gap }, (-0.75*PI, 0.0)),
+            (torso, upper_leg_l, Vector2 { x: -leg_x, y: hip_y - 0.5*gap }, (-PI/6.0, PI/3.0)),
+            (torso, upper_leg_r, Vector2 { x: leg_x, y: hip_y - 0.5*gap }, (-PI/6.0, PI/3.0)),
+            (upper_leg_l, lower_leg_l, Vector2 { x: -leg_x, y: hip_y + bones.upper_leg_length + 0.5*gap }, (0.0, 0.5*PI)),
+            (upper_leg_r, lower_leg_r, Vector2 { x: leg_x, y: hip_y + bones.upper_leg_length + 0.5*gap }, (0.0, 0.5*PI)),
+        ];
+        for (parent, child, offset, (lower, upper)) in connections {
+            let is_right = offset.x > 0.0;
+            let anchor = Vector2 { x: position.x + offset.x, y: offset.y };
+            let joint = self.try_create_revolute_joint(parent, child, anchor)?.clone();
+            let limits = if is_right { JointLimits { lower: -upper, upper: -lower } } else { JointLimits { lower, upper } };
+            joint.borrow_mut().set_limits(Some(limits));
+            joints.push(joint);
+        }
+
+        Some(())
+    }
+
+    /// Creates a soft body: a ring of `point_count` circle bodies around `center`, linked to their neighbors by springs and inflated by `pressure`
+    ///
+    /// The points touch their neighbors, and don't collide with them so that the ring can bend.
+    /// `stiffness` is the force per unit the springs are stretched or compressed (see [`Physac::create_spring_joint`]),
+    /// and `pressure` is the outward force per unit of length on the ring when it is squashed to half of its area.
+    /// A ring stretched past its area pulls inwards the same way
+    ///
+    /// Returns [`None`] if `point_count` is less than 3, `radius` isn't positive, or there are not enough available IDs
+    pub fn try_create_soft_body(&mut self, center: impl Into<Vector2>, radius: Real, point_count: usize, stiffness: Real, pressure: Real, density: Real) -> Option<SoftBody<MAX_VERTICES, CIRCLE_VERTICES>> {
+        let center = center.into();
+        if point_count < 3 || radius <= 0.0 {
+            debug_print!("[PHYSAC] new soft body creation failed because it has less than 3 points or no size");
+            return None;
+        }
+
+        let angle_step = 2.0*PI as Real/point_count as Real;
+        let edge_length = 2.0*radius*math_sin(0.5*angle_step);
+        let positions: Vec<Vector2> = (0..point_count)
+            .map(|i| {
+                let angle = angle_step*i as Real;
+                center + Vector2 { x: math_cos(angle), y: math_sin(angle) }*radius
+            })
+            .collect();
+
+        let mut soft_body = SoftBody { points: Vec::with_capacity(point_count), springs: Vec::with_capacity(point_count), id: self.next_soft_body_id };
+        for &position in &positions {
+            let Ok(point) = self.try_create_physics_body_circle(position, 0.5*edge_length, density).cloned() else {
+                self.destroy_soft_body(soft_body);
+                return None;
+            };
+            soft_body.points.push(point);
+        }
+
+        // Damp the springs to half of critical damping, so that the ring wobbles a little without ringing
+        let point_mass = soft_body.points[0].borrow().mass;
+        let damping = (stiffness.max(0.0)*point_mass).sqrt();
+        for i in 0..point_count {
+            let (point, next_point) = (&soft_body.points[i], &soft_body.points[next_idx(i, point_count)]);
+            let Some(spring) = self.try_create_spring_joint(point, next_point, edge_length, stiffness, damping).cloned() else {
+                self.destroy_soft_body(soft_body);
+                return None;
+            };
+            soft_body.springs.push(spring);
+            self.ignore_collision(point, next_point, true);
+        }
+
+        self.next_soft_body_id = self.next_soft_body_id.wrapping_add(1);
+        self.soft_bodies.push(SoftBodyPressure {
+            id: soft_body.id,
+            points: soft_body.points.clone(),
+            rest_area: polygon_area_centroid(&positions).0.abs(),
+            pressure,
+        });
+
+        Some(soft_body)
+    }
+
+    /// Creates a soft body: a ring of `point_count` circle bodies around `center`, linked to their neighbors by springs and inflated by `pressure`
+    ///
+    /// The points touch their neighbors, and don't collide with them so that the ring can bend.
+    /// `stiffness` is the force per unit the springs are stretched or compressed (see [`Physac::create_spring_joint`]),
+    /// and `pressure` is the outward force per unit of length on the ring when it is squashed to half of its area.
+    /// A ring stretched past its area pulls inwards the same way
+    ///
+    /// # Panics
+    ///
+    /// This method may panic if `point_count` is less than 3, `radius` isn't positive, or there are not enough available IDs
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use physac::prelude::*;
+    /// let mut ph = init_physics::<24, 24>().build();
+    ///
+    /// let floor = ph.borrow_mut()
+    ///     .create_physics_body_rectangle(Vector2 { x: 200.0, y: 300.0 }, 400.0, 20.0, 1.0)
+    ///     .clone();
+    /// floor.borrow_mut().enabled = false;
+    ///
+    /// let ball = ph.borrow_mut().create_soft_body(Vector2 { x: 200.0, y: 200.0 }, 40.0, 16, 20.0, 40.0, 1.0);
+    /// let rest_area = ball.area();
+    ///
+    /// for _ in 0..300 {
+    ///     ph.borrow_mut().update(1.0/60.0).unwrap();
+    /// }
+    ///
+    /// // The ball rests on the floor, squashed a little by its own weight
+    /// assert!(ball.center().y > 240.0 && ball.center().y < 260.0);
+    /// assert!(ball.area() < rest_area && ball.area() > 0.8*rest_area);
+    /// ```
+    #[inline]
+    pub fn create_soft_body(&mut self, center: impl Into<Vector2>, radius: Real, point_count: usize, stiffness: Real, pressure: Real, density: Real) -> SoftBody<MAX_VERTICES, CIRCLE_VERTICES> {
+        let center = center.into();
+        self.try_create_soft_body(center, radius, point_count, stiffness, pressure, density).unwrap()
+    }
+
+    /// Destroys every point of a soft body, along with its springs and any joints attaching it to other bodies
+    pub fn destroy_soft_body(&mut self, soft_body: SoftBody<MAX_VERTICES, CIRCLE_VERTICES>) {
+        self.soft_bodies.retain(|pressure| pressure.id != soft_body.id);
+        for point in soft_body.points {
+            self.destroy_physics_body(point);
+        }
+    }
+
+    /// Returns the pressure of a soft body, or [`None`] if it has been destroyed
+    #[must_use]
+    pub fn soft_body_pressure(&self, soft_body: &SoftBody<MAX_VERTICES, CIRCLE_VERTICES>) -> Option<Real> {
+        self.soft_bodies.iter().find(|pressure| pressure.id == soft_body.id).map(|pressure| pressure.pressure)
+    }
+
+    /// Changes the pressure of a soft body, inflating or deflating it, and wakes it up
+    ///
+    /// Does nothing if the soft body has been destroyed
+    pub fn set_soft_body_pressure(&mut self, soft_body: &SoftBody<MAX_VERTICES, CIRCLE_VERTICES>, pressure: Real) {
+        if let Some(soft_body) = self.soft_bodies.iter_mut().find(|other| other.id == soft_body.id) {
+            soft_body.pressure = pressure;
+            for point in &soft_body.points {
+                point.borrow_mut().wake_up();
+            }
+        }
+    }
+
+    /// Creates a new joint of any kind between two bodies, with anchors given in world space
+    ///
+    /// Without a second body, `anchor_b` stays fixed in the world
+    fn try_create_joint(
+        &mut self,
+        body_a: &Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>,
+        body_b: Option<&Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>>,
+        anchor_a: Vector2,
+        anchor_b: Vector2,
+        kind: PhysicsJointKind,
+    ) -> Option<&Strong<PhysicsJointData<MAX_VERTICES, CIRCLE_VERTICES>>> {
+        let (id_a, local_anchor_a) = body_a.borrowed(|body| (body.id, body.local_point(anchor_a)));
+        let local_anchor_b = if let Some(body_b) = body_b {
+            let (id_b, local_anchor_b) = body_b.borrowed(|body| (body.id, body.local_point(anchor_b)));
+            if id_a == id_b {
+                debug_print!("[PHYSAC] new joint creation failed because both bodies are the same");
+                return None;
+            }
+            local_anchor_b
+        } else {
+            anchor_b
+        };
+
+        if let Some(new_id) = self.find_available_joint_index() {
+            let new_joint = PhysicsJointData::new(new_id, body_a.clone(), body_b.cloned(), local_anchor_a, local_anchor_b, kind);
+
+            // Add new joint to joints pointers array
+            self.joints.push(Strong::new(new_joint));
+
+            debug_print!("[PHYSAC] created joint id {new_id} on physics body id {id_a}");
+            let [.., result] = &self.joints[..] else { unreachable!("should have at least one element after pushing") };
+            Some(result)
+        } else {
+            debug_print!("[PHYSAC] new joint creation failed because there isn't any available id to use");
+            None
+        }
+    }
+
+    /// Returns the current amount of created joints
+    #[must_use]
+    pub fn get_joints_count(&self) -> usize {
+        self.joints.len()
+    }
+
+    /// Returns an iterator over [`Strong`] references to each joint in the simulation
+    #[must_use]
+    pub fn strong_joint_iter(&self) -> impl DoubleEndedIterator<Item = &Strong<PhysicsJointData<MAX_VERTICES, CIRCLE_VERTICES>>> + ExactSizeIterator {
+        self.joints.iter()
+    }
+
+    /// Unitializes and destroys a joint, leaving its bodies unconstrained
+    #[allow(
+        clippy::needless_pass_by_value,
+        reason = "the user will presumably pass in an upgrade they made specifically for this, and it indicates the end of that instance's life.",
+    )]
+    pub fn destroy_joint(&mut self, joint: Strong<PhysicsJointData<MAX_VERTICES, CIRCLE_VERTICES>>) {
+        let id = joint.borrow().id;
+
+        if let Some(index) = self.joints.iter().position(|joint| joint.borrow().id == id) {
+            joint.borrow_mut().is_simulating = false;
+            self.joints.remove(index);
+
+            debug_print!("[PHYSAC] destroyed joint id {id}");
+            self.destroy_detached_joints();
+        } else {
+            debug_print!("[PHYSAC] Not possible to find joint id {id} in pointers array");
+        }
+    }
+
+    /// Returns an iterator over the joints that broke since the last time this was called, in the order they broke
+    ///
+    /// Events are kept until they are drained, so this should be called regularly (such as once per frame) if joints can break
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use physac::prelude::*;
+    /// let mut ph = init_physics::<24, 24>().build();
+    ///
+    /// let plank = ph.borrow_mut()
+    ///     .create_physics_body_rectangle(Vector2 { x: 100.0, y: 100.0 }, 60.0, 10.0, 1.0)
+    ///     .clone();
+    ///
+    /// let post = ph.borrow_mut()
+    ///     .create_physics_body_rectangle(Vector2 { x: 60.0, y: 100.0 }, 10.0, 10.0, 1.0)
+    ///     .clone();
+    /// post.borrow_mut().enabled = false;
+    ///
+    /// let nail = ph.borrow_mut()
+    ///     .create_revolute_joint(&post, &plank, Vector2 { x: 70.0, y: 100.0 })
+    ///     .clone();
+    /// nail.borrowed_mut(|nail| {
+    ///     nail.break_force = Some(1.0);
+    ///     nail.break_torque = Some(1.0);
+    /// });
+    ///
+    /// for event in ph.borrow_mut().drain_joint_events() {
+    ///     println!("joint {} broke under {} force", event.joint.borrow().id, event.force);
+    /// }
+    /// ```
+    pub fn drain_joint_events(&mut self) -> impl Iterator<Item = JointBroken<MAX_VERTICES, CIRCLE_VERTICES>> + '_ {
+        self.joint_events.drain(..)
+    }
+
+    /// Returns an iterator over the bodies that started or stopped overlapping a sensor since the last time this was called, in the order it happened
+    ///
+    /// Events are kept until they are drained, so this should be called regularly (such as once per frame) if there are sensors
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use physac::prelude::*;
+    /// let mut ph = init_physics::<24, 24>().build();
+    ///
+    /// let checkpoint = ph.borrow_mut()
+    ///     .create_physics_body_rectangle(Vector2 { x: 300.0, y: 100.0 }, 20.0, 200.0, 1.0)
+    ///     .clone();
+    /// checkpoint.borrowed_mut(|checkpoint| {
+    ///     checkpoint.enabled = false;
+    ///     checkpoint.is_sensor = true;
+    /// });
+    ///
+    /// for event in ph.borrow_mut().drain_sensor_events() {
+    ///     if let SensorEvent::Begin { visitor, .. } = event {
+    ///         println!("body {} reached the checkpoint", visitor.borrow().id);
+    ///     }
+    /// }
+    /// ```
+    pub fn drain_sensor_events(&mut self) -> impl Iterator<Item = SensorEvent<MAX_VERTICES, CIRCLE_VERTICES>> + '_ {
+        self.sensor_events.drain(..)
+    }
+
+    /// Returns an iterator over the pairs of bodies that started touching, kept touching or stopped touching since the last time this was called, in the order it happened
+    ///
+    /// Events are kept until they are drained, so this should be called regularly (such as once per frame).
+    /// Only the latest [`CollisionEvent::Persist`] of each pair is kept
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use physac::prelude::*;
+    /// let mut ph = init_physics::<24, 24>().build();
+    ///
+    /// let floor = ph.borrow_mut()
+    ///     .create_physics_body_rectangle(Vector2 { x: 200.0, y: 300.0 }, 400.0, 20.0, 1.0)
+    ///     .clone();
+    /// floor.borrow_mut().enabled = false;
+    ///
+    /// ph.borrow_mut().create_physics_body_circle(Vector2 { x: 200.0, y: 100.0 }, 10.0, 1.0);
+    ///
+    /// for event in ph.borrow_mut().drain_collision_events() {
+    ///     if let CollisionEvent::Begin(contact) = event {
+    ///         println!("bodies {} and {} touched at {:?}", contact.body_a.borrow().id, contact.body_b.borrow().id, contact.point);
+    ///
+    ///         // Play louder impact sounds for harder hits
+    ///         let volume = (contact.normal_impulse/100.0).min(1.0);
+    ///         println!("impact volume: {volume}");
+    ///     }
+    /// }
+    /// ```
+    pub fn drain_collision_events(&mut self) -> impl Iterator<Item = CollisionEvent<MAX_VERTICES, CIRCLE_VERTICES>> + '_ {
+        self.collision_events.drain(..).map(|(_, event)| event)
+    }
+
+    /// Moves the collision events since the last time they were drained to the end of `queue`, in the order they happened
+    ///
+    /// Meant for handing events over to a queue kept by a game loop or ECS (such as a `Vec` or `VecDeque` resource). See [`Physac::drain_collision_events`]
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use physac::prelude::*;
+    /// # use std::collections::VecDeque;
+    /// let mut ph = init_physics::<24, 24>().build();
+    ///
+    /// let mut collisions = VecDeque::new();
+    /// ph.borrow_mut().drain_collision_events_into(&mut collisions);
+    ///
+    /// while let Some(event) = collisions.pop_front() {
+    ///     if let CollisionEvent::Begin(contact) = event {
+    ///         println!("bodies {} and {} touched", contact.body_a.borrow().id, contact.body_b.borrow().id);
+    ///     }
+    /// }
+    /// ```
+    pub fn drain_collision_events_into(&mut self, queue: &mut impl Extend<CollisionEvent<MAX_VERTICES, CIRCLE_VERTICES>>) {
+        queue.extend(self.drain_collision_events());
+    }
+
+    /// Removes all joints
+    pub fn clear_joints(&mut self) {
+        for joint in self.joints.drain(..) {
+            joint.borrow_mut().is_simulating = false;
+        }
+    }
+
+    /// Destroys every joint attached to a body or linked to a joint that is no longer being simulated
+    fn destroy_detached_joints(&mut self) {
+        self.joints.retain(|joint| {
+            let mut joint = joint.borrow_mut();
+            joint.is_simulating = joint.body_a.borrow().is_simulating &&
+                joint.body_b.as_ref().is_none_or(|body_b| body_b.borrow().is_simulating) &&
+                joint.linked_joints.as_ref().is_none_or(|links| links.iter().all(|link| link.borrow().is_simulating));
+            if !joint.is_simulating {
+                debug_print!("[PHYSAC] destroyed joint id {} along with its body", joint.id);
+            }
+            joint.is_simulating
+        });
+    }
+}
+
+impl<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize> PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES> {
+    /// Returns true if the body is still being simulated, returns false if it has been destroyed
+    #[must_use]
+    pub fn is_simulating(&self) -> bool {
+        self.is_simulating
+    }
+
+    /// Returns the name of the body, or an empty string if it hasn't been given one
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Names the body, so it can be told apart while debugging and found with [`Physac::find_body`]
+    ///
+    /// Names don't have to be unique, and the physics never reads them
+    pub fn set_name(&mut self, name: &str) {
+        name.clone_into(&mut self.name);
+    }
+
+    /// Returns true if the body is asleep, which means it stays where it is without being simulated until something disturbs it
+    ///
+    /// See [`PhysacBuilder::sleep_steps`]
+    #[must_use]
+    pub fn is_sleeping(&self) -> bool {
+        self.is_sleeping
+    }
+
+    /// Wakes the body up if it is asleep, so that it is simulated again
+    ///
+    /// Sleeping bodies wake up by themselves when they are hit, pushed by a force, torque or velocity, moved by a joint or moved by hand.
+    /// They also wake up when a body they touch or are joined to is destroyed, moved by hand or given a new shape
+    pub fn wake_up(&mut self) {
+        self.is_sleeping = false;
+        self.resting_steps = 0;
+    }
+
+    /// Returns the position of the body `alpha` of the way from where it was before the last step to where it is now
+    ///
+    /// Drawing bodies at [`Physac::interpolation_alpha`] keeps their motion smooth when frames and steps don't line up
+    #[must_use]
+    pub fn interpolated_position(&self, alpha: Real) -> Vector2 {
+        let Some((previous_position, _)) = self.previous_transform else { return self.position };
+        previous_position + (self.position - previous_position)*alpha
+    }
+
+    /// Returns the orientation of the body `alpha` of the way from what it was before the last step to what it is now
+    ///
+    /// See [`PhysicsBodyData::interpolated_position`]
+    #[must_use]
+    pub fn interpolated_orient(&self, alpha: Real) -> Real {
+        let Some((_, previous_orient)) = self.previous_transform else { return self.orient };
+        previous_orient + (self.orient - previous_orient)*alpha
+    }
+
+    /// Makes the body be drawn exactly where it is until the next step, such as after teleporting it
+    pub fn reset_interpolation(&mut self) {
+        self.previous_transform = None;
+    }
+
+    /// Sets the velocity of the body and wakes it up
+    ///
+    /// Unlike writing [`PhysicsBodyData::velocity`], this also drops the impulses its contacts carried over from the last step
+    /// (see [`PhysacBuilder::warm_starting`]), which would otherwise be applied to the new motion for one step
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use physac::prelude::*;
+    /// let mut ph = init_physics::<24, 24>().warm_starting(true).build();
+    ///
+    /// let ball = ph.borrow_mut()
+    ///     .create_physics_body_circle(Vector2 { x: 100.0, y: 100.0 }, 10.0, 1.0)
+    ///     .clone();
+    ///
+    /// // Launch the ball, whatever it was resting on
+    /// ball.borrow_mut().set_linear_velocity(Vector2 { x: 0.5, y: -1.0 });
+    /// assert_eq!(ball.borrow().velocity, Vector2 { x: 0.5, y: -1.0 });
+    /// ```
+    pub fn set_linear_velocity(&mut self, velocity: impl Into<Vector2>) {
+        let velocity = velocity.into();
+        self.velocity = velocity;
+        self.has_stale_contacts = true;
+        self.wake_up();
+    }
+
+    /// Sets the angular velocity of the body and wakes it up, dropping the impulses its contacts carried over from the last step
+    ///
+    /// See [`PhysicsBodyData::set_linear_velocity`]
+    pub fn set_angular_velocity(&mut self, angular_velocity: Real) {
+        self.angular_velocity = angular_velocity;
+        self.has_stale_contacts = true;
+        self.wake_up();
+    }
+
+    /// Moves the body to `position` and turns it to `orient` radians at once, waking it up
+    ///
+    /// The impulses its contacts carried over from the last step are dropped, and the body is drawn at its new place
+    /// straight away instead of moving there (see [`PhysicsBodyData::reset_interpolation`]).
+    /// With [`Broadphase::Tree`], [`Physac::set_body_position`] also moves the body in the tree for queries made before the next step
+    pub fn teleport(&mut self, position: impl Into<Vector2>, orient: Real) {
+        let position = position.into();
+        self.position = position;
+        self.set_rotation(orient);
+        self.reset_interpolation();
+        self.has_stale_contacts = true;
+        self.wake_up();
+    }
+
+    /// Adds a force to a physics body
+    pub fn add_force(&mut self, force: impl Into<Vector2>) {
+        let force = force.into();
+        self.force += force;
+    }
+
+    /// Adds a force to a physics body, pushing it at the world point `point`
+    ///
+    /// Pushing off the center of mass also adds the torque the force causes, such as a thruster on the edge of a ship turning it
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use physac::prelude::*;
+    /// let mut ph = init_physics::<24, 24>().build();
+    ///
+    /// let ship = ph.borrow_mut()
+    ///     .create_physics_body_rectangle(Vector2 { x: 100.0, y: 100.0 }, 40.0, 20.0, 1.0)
+    ///     .clone();
+    ///
+    /// let mut ship = ship.borrow_mut();
+    /// let left_edge = ship.position - Vector2 { x: 20.0, y: 0.0 };
+    /// ship.add_force_at_point(Vector2 { x: 0.0, y: -5.0 }, left_edge);
+    ///
+    /// assert_eq!(ship.force, Vector2 { x: 0.0, y: -5.0 });
+    /// assert_eq!(ship.torque, 100.0);
+    /// ```
+    pub fn add_force_at_point(&mut self, force: impl Into<Vector2>, point: impl Into<Vector2>) {
+        let force = force.into();
+        let point = point.into();
+        self.force += force;
+        self.torque += math_cross_vector2(point - self.position, force);
+    }
+
+    /// Adds an angular force to a physics body
+    pub fn add_torque(&mut self, amount: Real) {
+        self.torque += amount;
+    }
+
+    /// Changes the velocity of the body at once by pushing it with `impulse` at the world point `point`, waking it up
+    ///
+    /// The velocity changes by `impulse` times [`PhysicsBodyData::inverse_mass`], and pushing off the center of mass also spins the body.
+    /// Unlike [`PhysicsBodyData::add_force`], the result doesn't depend on the length of the step, which suits jumps and knockback.
+    /// Static and disabled bodies are left as they are
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use physac::prelude::*;
+    /// let mut ph = init_physics::<24, 24>().build();
+    ///
+    /// let ball = ph.borrow_mut()
+    ///     .create_physics_body_circle(Vector2 { x: 100.0, y: 100.0 }, 10.0, 1.0)
+    ///     .clone();
+    ///
+    /// let mut ball = ball.borrow_mut();
+    /// let (position, mass) = (ball.position, ball.mass);
+    ///
+    /// // Jump straight up at half a pixel per millisecond
+    /// ball.apply_linear_impulse(Vector2 { x: 0.0, y: -0.5*mass }, position);
+    /// assert!((ball.velocity.y + 0.5).abs() < 1e-6);
+    /// assert_eq!(ball.angular_velocity, 0.0);
+    ///
+    /// // A push at the top of the ball also spins it (clockwise on screen, with y pointing down)
+    /// ball.apply_linear_impulse(Vector2 { x: 0.1*mass, y: 0.0 }, position - Vector2 { x: 0.0, y: 10.0 });
+    /// assert!(ball.angular_velocity > 0.0);
+    /// ```
+    pub fn apply_linear_impulse(&mut self, impulse: impl Into<Vector2>, point: impl Into<Vector2>) {
+        let impulse = impulse.into();
+        let point = point.into();
+        if !self.enabled {
+            return;
+        }
+
+        self.velocity += impulse*self.inverse_mass;
+        if !self.freeze_orient {
+            self.angular_velocity += self.inverse_inertia*math_cross_vector2(point - self.position, impulse);
+        }
+        self.wake_up();
+    }
+
+    /// Changes the angular velocity of the body at once by `impulse` times [`PhysicsBodyData::inverse_inertia`], waking it up
+    ///
+    /// See [`PhysicsBodyData::apply_linear_impulse`]
+    pub fn apply_angular_impulse(&mut self, impulse: Real) {
+        if !self.enabled || self.freeze_orient {
+            return;
+        }
+
+        self.angular_velocity += impulse*self.inverse_inertia;
+        self.wake_up();
+    }
+
+    /// Returns the material of the body, with the density its mass is spread over its shape with
+    #[must_use]
+    pub fn material(&self) -> Material {
+        let (_, area, _) = shape_mass_data(&self.shape);
+        Material {
+            static_friction: self.static_friction,
+            dynamic_friction: self.dynamic_friction,
+            restitution: self.restitution,
+            density: if area > 0.0 { self.mass/area } else { 0.0 },
+            friction_combine: self.friction_combine,
+            restitution_combine: self.restitution_combine,
+        }
+    }
+
+    /// Gives the body a material, scaling its mass and inertia to the new density
+    ///
+    /// Bodies without mass (such as static terrain) stay without mass
+    pub fn set_material(&mut self, material: &Material) {
+        self.static_friction = material.static_friction;
+        self.dynamic_friction = material.dynamic_friction;
+        self.restitution = material.restitution;
+        self.friction_combine = material.friction_combine;
+        self.restitution_combine = material.restitution_combine;
+
+        let (_, area, _) = shape_mass_data(&self.shape);
+        if self.mass > 0.0 && area > 0.0 {
+            let factor = material.density*area/self.mass;
+            self.mass *= factor;
+            self.inertia *= factor;
+            self.inverse_mass = if self.mass != 0.0 { 1.0/self.mass } else { 0.0 };
+            self.inverse_inertia = if self.inertia != 0.0 { 1.0/self.inertia } else { 0.0 };
+        }
+    }
+
+    /// Returns true if the collision filters of both bodies let them collide (or detect each other, if one is a sensor)
+    ///
+    /// A shared [`PhysicsBodyData::collision_group`] decides first, and otherwise the categories and masks decide
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use physac::prelude::*;
+    /// const PLAYER: u32 = 1 << 0;
+    /// const ENEMY: u32 = 1 << 1;
+    /// const BULLET: u32 = 1 << 2;
+    ///
+    /// let mut ph = init_physics::<24, 24>().build();
+    ///
+    /// let enemy = ph.borrow_mut()
+    ///     .create_physics_body_circle(Vector2 { x: 100.0, y: 100.0 }, 20.0, 1.0)
+    ///     .clone();
+    /// enemy.borrow_mut().collision_category = ENEMY;
+    ///
+    /// // Bullets hit players and enemies, but pass through each other
+    /// let bullets: Vec<_> = (0..2).map(|i| {
+    ///     let bullet = ph.borrow_mut()
+    ///         .create_physics_body_circle(Vector2 { x: 50.0, y: 100.0 + 10.0*i as Real }, 2.0, 1.0)
+    ///         .clone();
+    ///     bullet.borrowed_mut(|bullet| {
+    ///         bullet.collision_category = BULLET;
+    ///         bullet.collision_mask = PLAYER | ENEMY;
+    ///     });
+    ///     bullet
+    /// }).collect();
+    ///
+    /// assert!(bullets[0].borrow().should_collide(&enemy.borrow()));
+    /// assert!(!bullets[0].borrow().should_collide(&bullets[1].borrow()));
+    ///
+    /// // Parts of the same ragdoll never collide with each other
+    /// let ragdoll = ph.borrow_mut().create_ragdoll(Vector2 { x: 300.0, y: 100.0 }, &RagdollBones::with_height(100.0), 1.0);
+    /// for body in ragdoll.bodies() {
+    ///     body.borrow_mut().collision_group = -1;
+    /// }
+    ///
+    /// assert!(!ragdoll.head.borrow().should_collide(&ragdoll.torso.borrow()));
+    /// ```
+    #[must_use]
+    pub fn should_collide(&self, other: &Self) -> bool {
+        if self.collision_group != 0 && self.collision_group == other.collision_group {
+            return self.collision_group > 0;
+        }
+
+        (self.collision_category & other.collision_mask) != 0 &&
+        (other.collision_category & self.collision_mask) != 0
+    }
+
+    /// Keeps the orientation of the body between `min` and `max` radians while it is simulated
+    ///
+    /// The limits are enforced by the solver alongside collisions, so the body still turns freely within them.
+    /// A body that is outside of the limits is brought back over the next few steps
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use physac::prelude::*;
+    /// # #[cfg(not(feature = "f64"))] use std::f32::consts::PI;
+    /// # #[cfg(feature = "f64")] use std::f64::consts::PI;
+    /// let mut ph = init_physics::<24, 24>().build();
+    ///
+    /// let platform = ph.borrow_mut()
+    ///     .create_physics_body_rectangle(Vector2 { x: 200.0, y: 200.0 }, 200.0, 10.0, 1.0)
+    ///     .clone();
+    ///
+    /// // Let the platform tilt up to 15 degrees either way
+    /// platform.borrow_mut().set_rotation_limits(-PI/12.0, PI/12.0);
+    ///
+    /// assert_eq!(platform.borrow().rotation_limits(), Some((-PI/12.0, PI/12.0)));
+    /// ```
+    pub fn set_rotation_limits(&mut self, min: Real, max: Real) {
+        self.rotation_limits = Some(JointLimits { lower: min.min(max), upper: max.max(min) });
+    }
+
+    /// Lets the body turn freely again after [`PhysicsBodyData::set_rotation_limits`]
+    pub fn remove_rotation_limits(&mut self) {
+        self.rotation_limits = None;
+    }
+
+    /// Returns the range the orientation of the body is kept within, if it is limited
+    #[must_use]
+    pub fn rotation_limits(&self) -> Option<(Real, Real)> {
+        self.rotation_limits.map(|limits| (limits.lower, limits.upper))
+    }
+
+    /// Locks or unlocks the rotation of the body
+    ///
+    /// A locked body stops turning and ignores torques and angular impulses from collisions and joints.
+    /// This is the same as setting [`PhysicsBodyData::freeze_orient`], and also stops the body if it was turning
+    pub fn lock_rotation(&mut self, locked: bool) {
+        self.freeze_orient = locked;
+        if locked {
+            self.angular_velocity = 0.0;
+        }
+    }
+
+    /// Sets physics body shape transform based on radians parameter
+    pub fn set_rotation(&mut self, radians: Real) {
+        self.orient = radians;
+
+        if let PHYSICS_POLYGON { transform, .. } = &mut self.shape {
+            *transform = Mat2::radians(radians);
+        }
+    }
+
+    /// Returns the number of vertices of a physics body shape
+    #[must_use]
+    pub fn get_physics_shape_vertices_count(&self) -> usize {
+        shape_vertices_count(&self.shape)
+    }
+
+    /// Returns transformed position of a body shape (body position + vertex transformed position)
+    ///
+    /// Returns [`None`] if `vertex` index is out of bounds
+    #[must_use]
+    pub fn try_get_physics_body_shape_vertex(&self, vertex: usize) -> Option<Vector2> {
+        let result = shape_vertex(&self.shape, self.position, self.shape_transform(), vertex);
+        if result.is_none() {
+            debug_print!("[PHYSAC] physics shape vertex index is out of bounds");
+        }
+        result
+    }
+
+    /// Returns the transform the body shape is rotated by
+    fn shape_transform(&self) -> Mat2 {
+        if let PHYSICS_POLYGON { transform, .. } = &self.shape {
+            *transform
+        } else {
+            Mat2::radians(self.orient)
+        }
+    }
+
+    /// Returns transformed position of a body shape (body position + vertex transformed position)
+    ///
+    /// # Panics
+    ///
+    /// This method may panic if `vertex` index is out of bounds
+    #[must_use]
+    pub fn get_physics_shape_vertex(&self, vertex: usize) -> Vector2 {
+        self.try_get_physics_body_shape_vertex(vertex).unwrap()
+    }
+
+    /// Returns an iterator over the transformed positions of a body shape (body position + vertex transformed position)
+    #[must_use]
+    pub fn vertices_iter(&self) -> impl DoubleEndedIterator<Item = Vector2> + ExactSizeIterator {
+        (0..self.get_physics_shape_vertices_count())
+            .map(|i| self.get_physics_shape_vertex(i))
+    }
+
+    /// Returns an iterator over the transformed positions of a body shape (body position + vertex transformed position), repeating the first vertex one more time after the final vertex
+    #[must_use]
+    pub fn vertices_iter_closed(&self) -> impl DoubleEndedIterator<Item = Vector2> {
+        self.vertices_iter()
+            .chain(std::iter::once_with(|| self.get_physics_shape_vertex(0)))
+    }
+
+    /// Returns the physics body shape type ([`PHYSICS_CIRCLE`] or [`PHYSICS_POLYGON`])
+    #[must_use]
+    pub fn get_physics_shape_type(&self) -> std::mem::Discriminant<PhysicsShape<MAX_VERTICES, CIRCLE_VERTICES>> {
+        std::mem::discriminant(&self.shape)
+    }
+
+    /// Replaces the body shape with a circle, recalculating mass and inertia with the same density
+    ///
+    /// The body keeps its ID, position, velocity and everything else, so existing handles stay valid
+    pub fn set_shape_circle(&mut self, radius: Real) {
+        self.replace_shape(PHYSICS_CIRCLE { radius });
+    }
+
+    /// Replaces the body shape with a rectangle centered on the body position, recalculating mass and inertia with the same density
+    ///
+    /// The body keeps its ID, position, velocity and everything else, so existing handles stay valid
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use physac::prelude::*;
+    /// let mut ph = init_physics::<24, 24>().build();
+    ///
+    /// let player = ph.borrow_mut()
+    ///     .create_physics_body_rectangle(Vector2 { x: 100.0, y: 100.0 }, 20.0, 40.0, 1.0)
+    ///     .clone();
+    ///
+    /// // Crouch
+    /// player.borrowed_mut(|player| player.set_shape_rectangle(20.0, 20.0));
+    ///
+    /// player.borrowed(|player| {
+    ///     assert_eq!(player.position, Vector2 { x: 100.0, y: 100.0 });
+    ///     assert_eq!(player.mass, 400.0);
+    /// });
+    /// ```
+    pub fn set_shape_rectangle(&mut self, width: Real, height: Real) {
+        self.replace_shape(PHYSICS_POLYGON {
+            vertex_data: PolygonData::create_rectangle_polygon(Vector2::zero(), Vector2 { x: width, y: height }),
+            transform: Mat2::radians(self.orient),
+        });
+    }
+
+    /// Replaces the body shape with a convex polygon, recalculating mass, inertia and normals with the same density
+    ///
+    /// The vertices are relative to the body position (before the body is rotated), and may be given in either winding order.
+    /// The body is then moved so that its position is at the centroid of the new polygon, keeping the polygon where it was given
+    ///
+    /// The body keeps its ID, velocity and everything else, so existing handles stay valid
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`PolygonError`] if the vertices do not form a valid convex polygon (see [`PolygonData::from_convex_vertices`]),
+    /// in which case the body is left unchanged
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use physac::prelude::*;
+    /// let mut ph = init_physics::<24, 24>().build();
+    ///
+    /// let body = ph.borrow_mut()
+    ///     .create_physics_body_circle(Vector2 { x: 100.0, y: 100.0 }, 10.0, 1.0)
+    ///     .clone();
+    ///
+    /// body.borrowed_mut(|body| body.set_shape_vertices(&[
+    ///     Vector2 { x: 0.0, y: 0.0 },
+    ///     Vector2 { x: 30.0, y: 0.0 },
+    ///     Vector2 { x: 0.0, y: 30.0 },
+    /// ])).unwrap();
+    ///
+    /// body.borrowed(|body| {
+    ///     assert_eq!(body.position, Vector2 { x: 110.0, y: 110.0 });
+    ///     assert_eq!(body.get_physics_shape_vertices_count(), 3);
+    /// });
+    /// ```
+    pub fn set_shape_vertices(&mut self, vertices: &[Vector2]) -> Result<(), PolygonError> {
+        let mut vertex_data = PolygonData::from_convex_vertices(vertices)?;
+
+        // Translate vertices to centroid (make the centroid (0, 0) for the polygon in model space)
+        let (center, _, _) = vertex_data.mass_data();
+        for position in vertex_data.positions_mut() {
+            *position -= center;
+        }
+
+        let transform = Mat2::radians(self.orient);
+        self.position += transform.multiply_vector2(center);
+        self.replace_shape(PHYSICS_POLYGON { vertex_data, transform });
+
+        Ok(())
+    }
+
+    /// Scales the body shape around the anchor point of the body, recalculating mass and inertia with the same density
+    ///
+    /// Negative factors are treated as positive. A factor of zero, infinity or NaN is ignored, leaving the body unchanged
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use physac::prelude::*;
+    /// let mut ph = init_physics::<24, 24>().build();
+    ///
+    /// let player = ph.borrow_mut()
+    ///     .create_physics_body_rectangle(Vector2 { x: 100.0, y: 100.0 }, 20.0, 20.0, 1.0)
+    ///     .clone();
+    ///
+    /// // Power up
+    /// player.borrowed_mut(|player| player.scale_shape(2.0));
+    ///
+    /// player.borrowed(|player| {
+    ///     assert_eq!(player.mass, 1600.0);
+    ///     assert_eq!(player.get_physics_shape_vertex(1), Vector2 { x: 120.0, y: 120.0 });
+    /// });
+    /// ```
+    pub fn scale_shape(&mut self, factor: Real) {
+        self.scale_shape_xy(factor, factor);
+    }
+
+    /// Scales the body shape around the anchor point of the body by different factors along the body's local `x` and `y` axes,
+    /// recalculating mass and inertia with the same density
+    ///
+    /// A circle scaled unevenly becomes an ellipse, and the rounded corners of a polygon are scaled by the smaller factor.
+    /// Compound fixtures are stretched along their own axes, so a rotated fixture scaled unevenly is stretched along its rotated axes
+    ///
+    /// Negative factors are treated as positive. If either factor is zero, infinite or NaN, the body is left unchanged,
+    /// since the shape would lose all of its area or its vertices would no longer be finite
+    pub fn scale_shape_xy(&mut self, factor_x: Real, factor_y: Real) {
+        if factor_x == 0.0 || factor_y == 0.0 || !factor_x.is_finite() || !factor_y.is_finite() {
+            debug_print!("[PHYSAC] physics body shape can't be scaled by {factor_x}, {factor_y}");
+            return;
+        }
+
+        let shape = scaled_shape(&self.shape, factor_x.abs(), factor_y.abs());
+        self.replace_shape(shape);
+
+        // The anchor stays put, so an offset shape grows away from it
+        let anchor = self.anchor();
+        self.shape_offset = Vector2 { x: self.shape_offset.x*factor_x.abs(), y: self.shape_offset.y*factor_y.abs() };
+        self.set_anchor(anchor);
+    }
+
+    /// Returns the offset of the shape from the anchor point, before the body is rotated
+    #[must_use]
+    pub fn shape_offset(&self) -> Vector2 {
+        self.shape_offset
+    }
+
+    /// Offsets the shape from the anchor point of the body, keeping the anchor where it is
+    ///
+    /// The body position always follows the center of mass, so that the body moves and rotates naturally,
+    /// while the anchor stays a fixed distance from it. This lets the anchor be used as a visual origin that isn't
+    /// the center of the shape, such as the feet of a character
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use physac::prelude::*;
+    /// let mut ph = init_physics::<24, 24>().build();
+    ///
+    /// let feet = Vector2 { x: 100.0, y: 200.0 };
+    /// let player = ph.borrow_mut()
+    ///     .create_physics_body_rectangle(feet, 20.0, 40.0, 1.0)
+    ///     .clone();
+    ///
+    /// // Stand the player on its feet
+    /// player.borrowed_mut(|player| player.set_shape_offset(Vector2 { x: 0.0, y: -20.0 }));
+    ///
+    /// player.borrowed(|player| {
+    ///     assert_eq!(player.anchor(), feet);
+    ///     assert_eq!(player.position, Vector2 { x: 100.0, y: 180.0 });
+    /// });
+    /// ```
+    pub fn set_shape_offset(&mut self, offset: impl Into<Vector2>) {
+        let offset = offset.into();
+        let anchor = self.anchor();
+        self.shape_offset = offset;
+        self.set_anchor(anchor);
+    }
+
+    /// Returns the world position of the anchor point of the body (see [`PhysicsBodyData::set_shape_offset`])
+    ///
+    /// The anchor is the same as the body position unless the shape has been offset
+    #[must_use]
+    pub fn anchor(&self) -> Vector2 {
+        self.position - Mat2::radians(self.orient).multiply_vector2(self.shape_offset)
+    }
+
+    /// Moves the body so that its anchor point is at `anchor` (see [`PhysicsBodyData::set_shape_offset`])
+    pub fn set_anchor(&mut self, anchor: impl Into<Vector2>) {
+        let anchor = anchor.into();
+        self.position = anchor + Mat2::radians(self.orient).multiply_vector2(self.shape_offset);
+    }
+
+    /// Returns the center of mass of the body in world space
+    ///
+    /// The body position always follows the center of mass, so this is the same as the body position
+    #[must_use]
+    pub fn center_of_mass(&self) -> Vector2 {
+        self.position
+    }
+
+    /// Returns the offset of the center of mass from the centroid of the shape, before the body is rotated
+    ///
+    /// This is zero unless it has been changed with [`PhysicsBodyData::set_center_of_mass_offset`]
+    #[must_use]
+    pub fn center_of_mass_offset(&self) -> Vector2 {
+        let (centroid, _, _) = shape_mass_data(&self.shape);
+        -centroid
+    }
+
+    /// Moves the center of mass of the body away from the centroid of its shape by `offset`, before the body is rotated
+    ///
+    /// The shape and the anchor point stay where they are, while the body position moves with the center of mass,
+    /// and the moment of inertia is recalculated around it. A low center of mass keeps vehicles from tipping over
+    ///
+    /// Shapes that can't be off-center on their own (circles, capsules, ellipses and heightfields) become a compound with a single fixture,
+    /// unless the offset is unchanged. A circle keeps the moment of inertia of [`Physac::create_physics_body_circle`], moved to the new center of mass
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use physac::prelude::*;
+    /// let mut ph = init_physics::<24, 24>().build();
+    ///
+    /// let car = ph.borrow_mut()
+    ///     .create_physics_body_rectangle(Vector2 { x: 100.0, y: 100.0 }, 80.0, 30.0, 1.0)
+    ///     .clone();
+    ///
+    /// car.borrowed_mut(|car| car.set_center_of_mass_offset(Vector2 { x: 0.0, y: 10.0 }));
+    ///
+    /// car.borrowed(|car| {
+    ///     assert!((car.center_of_mass() - Vector2 { x: 100.0, y: 110.0 }).length_sqr() < 1e-6);
+    ///     assert!((car.center_of_mass_offset() - Vector2 { x: 0.0, y: 10.0 }).length_sqr() < 1e-6);
+    ///     assert_eq!(car.mass, 2400.0);
+    /// });
+    /// ```
+    pub fn set_center_of_mass_offset(&mut self, offset: impl Into<Vector2>) {
+        let offset = offset.into();
+        let delta = offset - self.center_of_mass_offset();
+        if delta == Vector2::zero() {
+            return;
+        }
+        let shape = translated_shape(&self.shape, -delta);
+
+        // Keep the shape and the anchor in place
+        let transform = self.shape_transform();
+        self.position += transform.multiply_vector2(delta);
+        self.shape_offset += delta;
+        self.replace_shape(shape);
+    }
+
+    /// Converts a point relative to the body position, before the body is rotated, to world space
+    #[must_use]
+    pub fn world_point(&self, local_point: impl Into<Vector2>) -> Vector2 {
+        let local_point = local_point.into();
+        self.position + Mat2::radians(self.orient).multiply_vector2(local_point)
+    }
+
+    /// Converts a point in world space to be relative to the body position, before the body is rotated
+    #[must_use]
+    pub fn local_point(&self, world_point: impl Into<Vector2>) -> Vector2 {
+        let world_point = world_point.into();
+        Mat2::radians(self.orient).transpose().multiply_vector2(world_point - self.position)
+    }
+
+    /// Replaces the body shape, recalculating mass and inertia from the density of the old shape
+    ///
+    /// Bodies without mass (such as static terrain) stay without mass
+    fn replace_shape(&mut self, shape: PhysicsShape<MAX_VERTICES, CIRCLE_VERTICES>) {
+        let (_, old_area, _) = shape_mass_data(&self.shape);
+        let density = if old_area > 0.0 { self.mass/old_area } else { 0.0 };
+        self.has_new_shape = true;
+
+        // Calculate moment of inertia about the body position, which is where the body rotates around
+        let (_, area, inertia) = shape_mass_data(&shape);
+        self.shape = shape;
+
+        self.mass = density*area;
+        self.inverse_mass = if self.mass != 0.0 { 1.0/self.mass } else { 0.0 };
+        self.inertia = density*inertia;
+        self.inverse_inertia = if self.inertia != 0.0 { 1.0/self.inertia } else { 0.0 };
+    }
+}
+
+//----------------------------------------------------------------------------------
+// Module Internal Functions Definition
+//----------------------------------------------------------------------------------
+impl<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize> PolygonData<MAX_VERTICES, CIRCLE_VERTICES> {
+    /// Creates a random polygon shape with max vertex distance from polygon pivot
+    fn create_random_polygon(radius: Real, sides: usize) -> PolygonData<MAX_VERTICES, CIRCLE_VERTICES> {
+        let mut data = PolygonData {
+            vertex_count: sides,
+            ..Default::default()
+        };
+
+        // Calculate polygon vertices positions
+        for i in 0..data.vertex_count {
+            data.positions[i].x = math_cos(360.0/sides as Real*i as Real*DEG2RAD as Real)*radius;
+            data.positions[i].y = math_sin(360.0/sides as Real*i as Real*DEG2RAD as Real)*radius;
+        }
+
+        // Calculate polygon faces normals
+        for i in 0..data.vertex_count {
+            let next_index = next_idx(i, sides);
+            let face = data.positions[next_index] - data.positions[i];
+
+            data.normals[i] = Vector2 { x: face.y, y: -face.x };
+            math_normalize(&mut data.normals[i]);
+        }
+
+        data
+    }
+
+    /// Calculates the polygon faces normals from the vertex positions
+    fn calculate_normals(&mut self) {
+        for i in 0..self.vertex_count {
+            let next_index = next_idx(i, self.vertex_count);
+            let face = self.positions[next_index] - self.positions[i];
+
+            self.normals[i] = Vector2 { x: face.y, y: -face.x };
+            math_normalize(&mut self.normals[i]);
+        }
+    }
+
+    /// Calculates the centroid, area and moment of inertia (about the origin, per unit of density) of the polygon
+    ///
+    /// Rounded corners are included, approximated by the outline used for drawing
+    fn mass_data(&self) -> (Vector2, Real, Real) {
+        if self.radius > 0.0 {
+            let outline: Vec<Vector2> = (0..self.outline_len()).filter_map(|i| self.outline_vertex(i)).collect();
+            return outline_mass_data(&outline);
+        }
+
+        outline_mass_data(self.positions())
+    }
+
+    /// Creates a rectangle polygon shape based on a min and max positions
+    fn create_rectangle_polygon(pos: Vector2, size: Vector2) -> PolygonData<MAX_VERTICES, CIRCLE_VERTICES> {
+        let mut data = PolygonData {
+            vertex_count: 4,
+            ..Default::default()
+        };
+
+        // Calculate polygon vertices positions
+        data.positions[0] = Vector2 { x: pos.x + size.x/2.0, y: pos.y - size.y/2.0 };
+        data.positions[1] = Vector2 { x: pos.x + size.x/2.0, y: pos.y + size.y/2.0 };
+        data.positions[2] = Vector2 { x: pos.x - size.x/2.0, y: pos.y + size.y/2.0 };
+        data.positions[3] = Vector2 { x: pos.x - size.x/2.0, y: pos.y - size.y/2.0 };
+
+        // Calculate polygon faces normals
+        for i in 0..data.vertex_count {
+            let next_index = next_idx(i, data.vertex_count);
+            let face = data.positions[next_index] - data.positions[i];
+
+            data.normals[i] = Vector2 { x: face.y, y: -face.x };
+            math_normalize(&mut data.normals[i]);
+        }
+
+        data
+    }
+
+    /// Creates a rectangle polygon shape of `size` centered on the origin, with its corners rounded by `corner_radius`
+    ///
+    /// The radius is limited to just under half of the smaller side, so that the core rectangle keeps some area
+    fn create_rounded_rectangle_polygon(size: Vector2, corner_radius: Real) -> PolygonData<MAX_VERTICES, CIRCLE_VERTICES> {
+        let radius = corner_radius.clamp(0.0, 0.49*size.x.min(size.y));
+        Self::create_rectangle_polygon(Vector2::zero(), Vector2 { x: size.x - 2.0*radius, y: size.y - 2.0*radius })
+            .with_corner_radius(radius)
+    }
+}
+
+/// Calculates the centroid, area and moment of inertia (about the origin, per unit of density) of a polygon outline
+fn outline_mass_data(points: &[Vector2]) -> (Vector2, Real, Real) {
+    let mut center = Vector2 { x: 0.0, y: 0.0 };
+    let mut area = 0.0;
+    let mut inertia = 0.0;
+
+    for i in 0..points.len() {
+        // Triangle vertices, third vertex implied as (0, 0)
+        let p1 = points[i];
+        let next_index = next_idx(i, points.len());
+        let p2 = points[next_index];
+
+        let cross = math_cross_vector2(p1, p2);
+        let triangle_area = cross/2.0;
+
+        area += triangle_area;
+
+        // Use area to weight the centroid average, not just vertex position
+        center.x += triangle_area*PHYSAC_K*(p1.x + p2.x);
+        center.y += triangle_area*PHYSAC_K*(p1.y + p2.y);
+
+        let intx2 = p1.x*p1.x + p2.x*p1.x + p2.x*p2.x;
+        let inty2 = p1.y*p1.y + p2.y*p1.y + p2.y*p2.y;
+        inertia += (0.25*PHYSAC_K*cross)*(intx2 + inty2);
+    }
+
+    if area != 0.0 {
+        center.x *= 1.0/area;
+        center.y *= 1.0/area;
+    }
+
+    (center, area, inertia)
+}
+
+/// Counts and timings of a physics step, returned by [`Physac::stats`]
+///
+/// Timings add up the substeps of the step. The broadphase finds the pairs of bodies that might touch,
+/// the narrowphase checks which of them do, and the solver resolves the contacts and joints
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct StepStats {
+    /// Number of physics bodies in the world
+    pub body_count: usize,
+    /// Number of physics bodies that can move and aren't asleep
+    pub awake_body_count: usize,
+    /// Number of manifolds the solver worked on in the last substep, from touching pairs of bodies (or parts of bodies)
+    pub manifold_count: usize,
+    /// Number of pairs of bodies the broadphase found might be touching, over every substep
+    pub broadphase_pairs: usize,
+    /// Iterations the solver ran over the contacts and joints of each island, over every substep
+    pub velocity_iterations: usize,
+    /// Iterations the solver ran to push overlapping bodies apart, over every substep
+    pub position_iterations: usize,
+    /// Time the whole step took
+    pub step_duration: Duration,
+    /// Time spent finding pairs of bodies that might be touching
+    pub broadphase_duration: Duration,
+    /// Time spent creating contacts between the pairs found by the broadphase
+    pub narrowphase_duration: Duration,
+    /// Time spent solving contacts and joints and pushing overlapping bodies apart
+    pub solver_duration: Duration,
+}
+impl std::fmt::Display for StepStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let milliseconds = |duration: Duration| duration.as_secs_f64()*1000.0;
+        writeln!(f, "bodies: {} ({} awake)", self.body_count, self.awake_body_count)?;
+        writeln!(f, "manifolds: {}, broadphase pairs: {}", self.manifold_count, self.broadphase_pairs)?;
+        writeln!(f, "iterations: {} velocity, {} position", self.velocity_iterations, self.position_iterations)?;
+        write!(
+            f, "step: {:.3} ms (broadphase {:.3} ms, narrowphase {:.3} ms, solver {:.3} ms)",
+            milliseconds(self.step_duration), milliseconds(self.broadphase_duration),
+            milliseconds(self.narrowphase_duration), milliseconds(self.solver_duration),
+        )
+    }
+}
+
+/// An error that occurs during [`Physac::run_physics_step`]
+#[derive(Debug)]
+pub enum PhysicsStepError {
+    /// [`Physac`] is poisoned
+    PhysacPoison,
+    /// A [`Strong<PhysicsBodyData>`] is poisoned
+    PhysicsBodyPoison,
+    /// A [`Strong<PhysicsJointData>`] is poisoned
+    PhysicsJointPoison,
+    /// [`Physac`] is out of available IDs to satisfy the demand for physics bodies and manifolds
+    OutOfIDs,
+    /// An index is out of bounds
+    OutOfBounds,
+    /// A denominator is zero
+    DivByZero,
+    /// The bodies of a replayed world no longer match the recording after the given step (see [`Physac::start_replay`])
+    ReplayDiverged(u32),
+}
+impl std::fmt::Display for PhysicsStepError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::PhysacPoison => write!(f, "a panic occurred while Physac was borrowed mutably"),
+            Self::PhysicsBodyPoison => write!(f, "a panic occurred while a physics body was borrowed mutably"),
+            Self::PhysicsJointPoison => write!(f, "a panic occurred while a joint was borrowed mutably"),
+            Self::OutOfIDs => write!(f, "insufficient IDs are available"),
+            Self::OutOfBounds => write!(f, "an out of bounds error occurred"),
+            Self::DivByZero => write!(f, "tried to divide by zero"),
+            Self::ReplayDiverged(step) => write!(f, "the replay no longer matches the recording after step {step}"),
+        }
+    }
+}
+impl std::error::Error for PhysicsStepError {}
+impl<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize> From<std::sync::PoisonError<PhysacReadGuard<'_, PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>>> for PhysicsStepError {
+    fn from(_: std::sync::PoisonError<PhysacReadGuard<'_, PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>>) -> Self {
+        Self::PhysicsBodyPoison
+    }
+}
+impl<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize> From<std::sync::PoisonError<PhysacWriteGuard<'_, PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>>> for PhysicsStepError {
+    fn from(_: std::sync::PoisonError<PhysacWriteGuard<'_, PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>>) -> Self {
+        Self::PhysicsBodyPoison
+    }
+}
+impl<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize> From<std::sync::PoisonError<PhysacReadGuard<'_, PhysicsJointData<MAX_VERTICES, CIRCLE_VERTICES>>>> for PhysicsStepError {
+    fn from(_: std::sync::PoisonError<PhysacReadGuard<'_, PhysicsJointData<MAX_VERTICES, CIRCLE_VERTICES>>>) -> Self {
+        Self::PhysicsJointPoison
+    }
+}
+impl<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize> From<std::sync::PoisonError<PhysacWriteGuard<'_, PhysicsJointData<MAX_VERTICES, CIRCLE_VERTICES>>>> for PhysicsStepError {
+    fn from(_: std::sync::PoisonError<PhysacWriteGuard<'_, PhysicsJointData<MAX_VERTICES, CIRCLE_VERTICES>>>) -> Self {
+        Self::PhysicsJointPoison
+    }
+}
+
+/// Physics loop thread function
+#[cfg(feature = "phys_thread")]
+#[allow(
+    clippy::needless_pass_by_value,
+    reason = "replacing these with references would cause those references to be dropped when the caller finishes, but we want this function to outlive that.",
+)]
+fn physics_loop<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize>(phys: Arc<RwLock<Physac<MAX_VERTICES, CIRCLE_VERTICES>>>, is_physics_thread_enabled: Arc<AtomicBool>) {
+    debug_print!("[PHYSAC] physics thread created successfully");
+
+    // Initialize physics loop thread values
+    is_physics_thread_enabled.store(true, Relaxed);
+
+    // Physics update loop
+    while is_physics_thread_enabled.load(Relaxed) {
+        let fixed_time = match phys.write().map_err(|_| PhysicsStepError::PhysacPoison).and_then(|mut lock| lock.run_physics_step().map(|()| lock.fixed_time)) {
+            Ok(fixed_time) => fixed_time,
+            Err(e) => {
+                debug_print!("[PHYSAC] {e}; physics thread will now close");
+                is_physics_thread_enabled.store(false, Release);
+                return;
+            }
+        };
+
+        let req = Duration::from_secs_f64(fixed_time);
+
+        std::thread::sleep(req);
+    }
+}
+
+impl<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize> Physac<MAX_VERTICES, CIRCLE_VERTICES> {
+    /// Physics steps calculations (dynamics, collisions and position corrections), split into substeps
+    fn physics_step(&mut self) -> Result<(), PhysicsStepError> {
+        trace_span!("step");
+        let step_start = Instant::now();
+        self.stats = StepStats::default();
+
+        // Replayed commands run at the same point between steps as when they were recorded
+        self.run_replay_commands();
+
+        // Update current steps count
+        self.steps_count += 1;
+
+        // Remember where the bodies were, so they can be drawn in between steps
+        for body in &self.bodies {
+            let mut body = body.write()?;
+            body.previous_transform = Some((body.position, body.orient));
+        }
+
+        let step_time = self.delta_time;
+        self.delta_time = step_time/self.substeps.get() as f64;
+        let result = (0..self.substeps.get()).try_for_each(|_| self.physics_substep());
+        self.delta_time = step_time;
+        result?;
+
+        if self.recording.is_some() || self.replay.is_some() {
+            let checksum = self.bodies_checksum()?;
+            if let Some(recording) = &mut self.recording {
+                recording.checksums.push(checksum);
+            }
+            if let Some(replay) = &self.replay {
+                let recorded_step = (self.steps_count - replay.recording.start.steps_count - 1) as usize;
+                if replay.recording.checksums.get(recorded_step).is_some_and(|&recorded| recorded != checksum) {
+                    self.replay = None;
+                    return Err(PhysicsStepError::ReplayDiverged(self.steps_count));
+                }
+            }
+        }
+
+        if self.replay.as_ref().is_some_and(|replay| self.steps_count >= replay.recording.end_step) {
+            // Commands executed after the last recorded step still happened
+            self.run_replay_commands();
+            self.replay = None;
+            debug_print!("[PHYSAC] replay finished at step {}", self.steps_count);
+        }
+
+        self.stats.body_count = self.bodies.len();
+        for body in &self.bodies {
+            let body = body.read()?;
+            self.stats.awake_body_count += usize::from(body.enabled && !body.is_sleeping);
+        }
+        self.stats.manifold_count = self.contacts.len();
+        self.stats.velocity_iterations = self.collision_iterations*self.substeps.get();
+        self.stats.position_iterations = self.position_iterations*self.substeps.get();
+        self.stats.step_duration = step_start.elapsed();
+
+        Ok(())
+    }
+
+    /// Returns counts and timings of the last step, for finding out what makes the simulation slow
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use physac::prelude::*;
+    /// let mut ph = init_physics::<24, 24>().build();
+    ///
+    /// ph.borrow_mut()
+    ///     .create_physics_body_rectangle(Vector2 { x: 100.0, y: 120.0 }, 200.0, 20.0, 1.0)
+    ///     .borrow_mut()
+    ///     .enabled = false;
+    /// ph.borrow_mut().create_physics_body_circle(Vector2 { x: 100.0, y: 100.0 }, 10.0, 1.0);
+    ///
+    /// ph.borrow_mut().update(1.0/60.0).unwrap();
+    ///
+    /// let stats = ph.borrow().stats();
+    /// assert_eq!(stats.body_count, 2);
+    /// assert_eq!(stats.awake_body_count, 1);
+    /// assert!(stats.manifold_count > 0);
+    /// println!("{stats}");
+    /// ```
+    #[must_use]
+    pub const fn stats(&self) -> StepStats {
+        self.stats
+    }
+
+    /// Hashes the IDs, transforms and velocities of the bodies, so that a replay can tell when it stops matching its recording
+    fn bodies_checksum(&self) -> Result<u64, PhysicsStepError> {
+        // FNV-1a, which gives the same checksum on every platform and version of Rust
+        let mut hash = 0xCBF2_9CE4_8422_2325u64;
+        for body in &self.bodies {
+            let body = body.read()?;
+            let values = [body.position.x, body.position.y, body.orient, body.velocity.x, body.velocity.y, body.angular_velocity];
+            let bytes = body.id.to_le_bytes().into_iter().chain(values.into_iter().flat_map(Real::to_le_bytes));
+            for byte in bytes {
+                hash = (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01B3);
+            }
+        }
+        Ok(hash)
+    }
+
+    /// Runs the commands of the replay that were executed before the current step
+    fn run_replay_commands(&mut self) {
+        while let Some(replay) = &mut self.replay {
+            let Some(&(step, command)) = replay.recording.commands.get(replay.next_command) else { break };
+            if step > self.steps_count {
+                break;
+            }
+            replay.next_command += 1;
+            self.run_command(command);
+        }
+    }
+
+    /// Physics substep calculations (dynamics, collisions and position corrections)
+    fn physics_substep(&mut self) -> Result<(), PhysicsStepError> {
+        trace_span!("substep");
+        // Clear previous generated collisions information
+        self.contacts.clear();
+
+        // Reset physics bodies grounded state (sleeping bodies stay where they were)
+        for body in &self.bodies {
+            let mut body = body.write()?;
+            body.is_grounded &= body.is_sleeping;
+        }
+
+        self.wake_disturbed_bodies()?;
+
+        let broadphase_start = Instant::now();
+        if let Some(body_tree) = &mut self.body_tree {
+            body_tree.sync(&self.bodies)?;
+        }
+        self.stats.broadphase_duration += broadphase_start.elapsed();
+
+        // Generate new collision information
+        self.create_manifolds()?;
+
+        let islands = self.build_islands()?;
+        self.wake_touched_bodies(&islands)?;
+
+        self.apply_force_fields()?;
+        self.apply_buoyancy()?;
+        self.apply_soft_body_pressure()?;
+
+        self.integrate_forces()?;
+
+        // Initialize physics manifolds to solve collisions
+        let solver_start = Instant::now();
+        for manifold in &mut self.contacts {
+            Self::initialize_physics_manifolds(manifold, self.delta_time, &self.gravity, self.friction_combine, self.restitution_combine)?;
+        }
+
+        self.pre_solve_contacts();
+        self.load_contact_impulses()?;
+
+        self.solve_islands(&islands)?;
+        self.store_contact_impulses()?;
+        self.stats.solver_duration += solver_start.elapsed();
+
+        // Break joints that had to hold more force or torque than they can withstand
+        let events_count = self.joint_events.len();
+        let mut i = 0;
+        while i < self.joints.len() {
+            let overload = {
+                let mut joint = self.joints[i].write()?;
+                let overload = joint.overload((1000.0/self.delta_time) as Real);
+                joint.is_simulating = overload.is_none();
+                overload
+            };
+
+            if let Some((force, torque)) = overload {
+                let joint = self.joints.remove(i);
+                debug_print!("[PHYSAC] joint id {} broke", joint.borrow().id);
+                self.joint_events.push(JointBroken { joint, force, torque });
+            } else {
+                i += 1;
+            }
+        }
+        if self.joint_events.len() > events_count {
+            self.destroy_detached_joints();
+        }
+
+        // Velocities are settled by now, before gravity is added for the next step
+        self.update_sleep(&islands)?;
+
+        self.integrate_velocities()?;
+
+        // Correct physics bodies positions based on manifolds collision information
+        let correction_start = Instant::now();
+        self.correct_positions()?;
+        self.stats.solver_duration += correction_start.elapsed();
+
+        self.update_collision_events()?;
+
+        // Keep the tree up to date for queries made before the next step
+        if let Some(body_tree) = &mut self.body_tree {
+            body_tree.sync(&self.bodies)?;
+        }
+
+        // Particles move after the bodies, so that they collide with where the bodies ended up
+        let mut particles = std::mem::take(&mut self.particles);
+        let result = self.step_particles(&mut particles);
+        self.particles = particles;
+        result?;
+        let mut fluids = std::mem::take(&mut self.fluids);
+        let result = self.step_fluids(&mut fluids);
+        self.fluids = fluids;
+        result?;
+
+        // Clear physics bodies forces, and remember where the step left them
+        for body in &self.bodies {
+            let mut body = body.write()?;
+            body.force = Vector2::zero();
+            body.torque = 0.0;
+            body.settled_transform = Some((body.position, body.orient));
+        }
+
+        self.destroy_deferred_bodies();
+
+        Ok(())
+    }
+
+    /// Integrates the forces and damping of every physics body into its velocity
+    fn integrate_forces(&self) -> Result<(), PhysicsStepError> {
+        trace_span!("integrate_forces");
+        for body in &self.bodies {
+            let mut body = body.write()?;
+            Self::integrate_physics_forces(&mut body, self.delta_time, &self.gravity);
+            Self::integrate_physics_damping(&mut body, self.delta_time);
+        }
+
+        Ok(())
+    }
+
+    /// Integrates the velocity of every physics body into its position
+    fn integrate_velocities(&self) -> Result<(), PhysicsStepError> {
+        trace_span!("integrate_velocities");
+        for body in &self.bodies {
+            Self::integrate_physics_velocity(&mut *body.write()?, self.delta_time, &self.gravity);
+        }
+
+        Ok(())
+    }
+
+    /// Wrapper to ensure `physics_step` is run with at a fixed time step
+    ///
+    /// # Errors
+    ///
+    /// This method may return a [`PhysicsStepError`] if an error occurs at some point during the physics step.
+    ///
+    /// See [`PhysicsStepError`] for information about the specific errors that can occur.
+    pub fn run_physics_step(&mut self) -> Result<(), PhysicsStepError> {
+        // Calculate current time
+        self.current_time = self.get_curr_time();
+
+        // Calculate current delta time
+        let delta: f64 = self.current_time - self.start_time;
+
+        // Store the time elapsed since the last frame began
+        if !self.is_paused {
+            self.accumulator += delta*self.time_scale;
+        }
+
+        // Fixed time stepping loop
+        self.run_accumulated_steps()?;
+
+        // Record the starting of this frame
+        self.start_time = self.current_time;
+
+        Ok(())
+    }
+
+    /// Advances the simulation by `frame_time` seconds, running as many fixed steps as fit into the time built up so far
+    ///
+    /// Returns how many steps were run. Leftover time carries over to the next call, and [`Physac::interpolation_alpha`]
+    /// tells how far along the next step it is
+    ///
+    /// Meant for running physics in step with the frames of the main thread. With the `phys_thread` feature, the physics thread
+    /// already steps the simulation with [`Physac::run_physics_step`]
+    ///
+    /// # Errors
+    ///
+    /// This method may return a [`PhysicsStepError`] if an error occurs at some point during the physics step.
+    ///
+    /// See [`PhysicsStepError`] for information about the specific errors that can occur.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use physac::prelude::*;
+    /// let mut ph = init_physics::<24, 24>().build();
+    ///
+    /// let ball = ph.borrow_mut()
+    ///     .create_physics_body_circle(Vector2 { x: 100.0, y: 100.0 }, 10.0, 1.0)
+    ///     .clone();
+    ///
+    /// // Every frame, with the time the frame took (such as from `RaylibHandle::get_frame_time`)
+    /// let frame_time = 1.0/144.0;
+    /// let alpha = {
+    ///     let mut ph = ph.borrow_mut();
+    ///     ph.update(frame_time).unwrap();
+    ///     ph.interpolation_alpha()
+    /// };
+    ///
+    /// // Draw the ball between where it was and where it is, so it moves smoothly at any frame rate
+    /// let draw_position = ball.borrow().interpolated_position(alpha);
+    /// # let _ = draw_position;
+    /// ```
+    pub fn update(&mut self, frame_time: f64) -> Result<usize, PhysicsStepError> {
+        if !self.is_paused {
+            self.accumulator += frame_time*1000.0*self.time_scale;
+        }
+        self.run_accumulated_steps()
+    }
+
+    /// Returns how far the simulation is along the next step, from 0 (just stepped) to 1 (about to step)
+    ///
+    /// See [`PhysicsBodyData::interpolated_position`]
+    #[must_use]
+    pub fn interpolation_alpha(&self) -> Real {
+        (self.accumulator/self.delta_time).clamp(0.0, 1.0) as Real
+    }
+
+    /// Runs a step for every fixed time step that fits into the accumulated time, returning how many were run
+    fn run_accumulated_steps(&mut self) -> Result<usize, PhysicsStepError> {
+        let mut steps = 0;
+
+        // Single steps run even while paused
+        while self.pending_steps > 0 {
+            self.physics_step()?;
+            self.pending_steps -= 1;
+            steps += 1;
+        }
+
+        while self.accumulator >= self.delta_time {
+            self.physics_step()?;
+            self.accumulator -= self.delta_time;
+            steps += 1;
+        }
+
+        Ok(steps)
+    }
+
+    /// Sets the time step of the physics simulation
+    pub fn set_physics_time_step(&mut self, delta: f64) {
+        self.delta_time = delta;
+    }
+
+    /// Returns the time step of the physics simulation
+    #[must_use]
+    pub fn get_physics_time_step(&self) -> f64 {
+        self.delta_time
+    }
+
+    /// Sets the fixed time in seconds, the same as [`PhysacBuilder::fixed_time`] does when building
+    ///
+    /// Every step simulates a tenth of the fixed time, and the physics thread (with the `phys_thread` feature) waits for the fixed time
+    /// between running steps. Time that has already built up is kept, so the change takes effect from the next step
+    ///
+    /// # Panics
+    ///
+    /// This method panics if `seconds` is not a positive finite number
+    pub fn set_fixed_timestep(&mut self, seconds: f64) {
+        assert!(seconds > 0.0 && seconds.is_finite(), "the fixed time step should be positive and finite");
+
+        #[cfg(feature = "phys_thread")] {
+            self.fixed_time = seconds;
+        }
+        self.delta_time = seconds/10.0 * 1000.0;
+    }
+
+    /// Returns the fixed time in seconds
+    ///
+    /// See [`Physac::set_fixed_timestep`]
+    #[must_use]
+    pub fn get_fixed_timestep(&self) -> f64 {
+        self.delta_time/1000.0 * 10.0
+    }
+
+    /// Sets how fast simulated time passes compared to real time, without changing the size of each step
+    ///
+    /// Values below 1 slow the simulation down and values above 1 speed it up, by running fewer or more steps for the same time.
+    /// A scale of 0 pauses the simulation
+    ///
+    /// # Panics
+    ///
+    /// This method panics if `scale` is negative or not finite
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use physac::prelude::*;
+    /// let mut ph = init_physics::<24, 24>().build();
+    ///
+    /// // Bullet time
+    /// ph.borrow_mut().set_time_scale(0.25);
+    ///
+    /// // The pause menu is open
+    /// ph.borrow_mut().set_time_scale(0.0);
+    ///
+    /// assert_eq!(ph.borrow().get_time_scale(), 0.0);
+    /// ```
+    pub fn set_time_scale(&mut self, scale: f64) {
+        assert!(scale >= 0.0 && scale.is_finite(), "the time scale should be positive or zero and finite");
+        self.time_scale = scale;
+    }
+
+    /// Returns how fast simulated time passes compared to real time
+    ///
+    /// See [`Physac::set_time_scale`]
+    #[must_use]
+    pub fn get_time_scale(&self) -> f64 {
+        self.time_scale
+    }
+
+    /// Returns the next seed from the world seed set with [`PhysacBuilder::seed`]
+    ///
+    /// Worlds built with the same seed hand out the same seeds in the same order, so that randomness stays reproducible
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use physac::prelude::*;
+    /// let mut ph = init_physics::<24, 24>()
+    ///     .seed(1234)
+    ///     .build();
+    ///
+    /// // Every crate breaks differently, but the same way on every machine in the match
+    /// let options = ShatterOptions {
+    ///     seed: Some(ph.borrow_mut().random_seed()),
+    ///     ..ShatterOptions::fragments(6)
+    /// };
+    /// # let _ = options;
+    /// ```
+    pub fn random_seed(&mut self) -> u64 {
+        self.rng.next_u64()
+    }
+
+    /// Pauses the simulation, so that stepping it (including on the physics thread) doesn't run any steps
+    /// other than the ones requested with [`Physac::step_once`]
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use physac::prelude::*;
+    /// let mut ph = init_physics::<24, 24>().build();
+    ///
+    /// // Go through a collision frame by frame
+    /// ph.borrow_mut().pause();
+    /// ph.borrow_mut().step_once();
+    /// ph.borrow_mut().step_once();
+    ///
+    /// assert!(ph.borrow().is_paused());
+    ///
+    /// ph.borrow_mut().resume();
+    /// ```
+    pub fn pause(&mut self) {
+        self.is_paused = true;
+    }
+
+    /// Resumes the simulation after [`Physac::pause`], without catching up on the time that passed while it was paused
+    pub fn resume(&mut self) {
+        if self.is_paused {
+            self.is_paused = false;
+            self.start_time = self.get_curr_time();
+        }
+    }
+
+    /// Returns true if the simulation is paused
+    ///
+    /// See [`Physac::pause`]
+    #[must_use]
+    pub fn is_paused(&self) -> bool {
+        self.is_paused
+    }
+
+    /// Requests a single step, which runs the next time the simulation is stepped even if it is paused
+    ///
+    /// With the `phys_thread` feature, the physics thread runs it on its next update
+    pub fn step_once(&mut self) {
+        self.pending_steps += 1;
+    }
+
+    /// Takes a snapshot of the state of every body and joint, along with the contacts and solver values carried from step to step,
+    /// so that the world can be rolled back to it with [`Physac::restore`]
+    ///
+    /// Settings (such as gravity and iterations), the pre-solve callback and undrained events aren't part of the snapshot
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use physac::prelude::*;
+    /// let mut ph = init_physics::<24, 24>().build();
+    ///
+    /// let ball = ph.borrow_mut()
+    ///     .create_physics_body_circle(Vector2 { x: 100.0, y: 100.0 }, 10.0, 1.0)
+    ///     .clone();
+    ///
+    /// // Remember the last state every player agreed on
+    /// let confirmed = ph.borrow().snapshot();
+    ///
+    /// // Predict ahead with local input, then roll back when the remote input arrives
+    /// ph.borrow_mut().create_physics_body_circle(Vector2 { x: 200.0, y: 100.0 }, 10.0, 1.0);
+    /// ball.borrow_mut().velocity.x = 1.0;
+    /// ph.borrow_mut().restore(&confirmed);
+    ///
+    /// assert_eq!(ph.borrow().get_physics_bodies_count(), 1);
+    /// assert_eq!(ball.borrow().velocity.x, 0.0);
+    /// ```
+    #[must_use]
+    pub fn snapshot(&self) -> WorldSnapshot<MAX_VERTICES, CIRCLE_VERTICES> {
+        WorldSnapshot {
+            bodies: self.bodies.iter().map(|body| (body.clone(), body.borrow().clone())).collect(),
+            joints: self.joints.iter().map(|joint| (joint.clone(), joint.borrow().clone())).collect(),
+            contacts: self.contacts.clone(),
+            contact_impulses: self.contact_impulses.clone(),
+            body_tree: self.body_tree.clone(),
+            sensor_overlaps: self.sensor_overlaps.clone(),
+            touching_pairs: self.touching_pairs.clone(),
+            ignored_pairs: self.ignored_pairs.clone(),
+            soft_bodies: self.soft_bodies.clone(),
+            next_soft_body_id: self.next_soft_body_id,
+            particles: self.particles.clone(),
+            next_particle_id: self.next_particle_id,
+            particle_constraints: self.particle_constraints.clone(),
+            next_particle_constraint_id: self.next_particle_constraint_id,
+            fluids: self.fluids.clone(),
+            next_fluid_id: self.next_fluid_id,
+            body_generations: self.body_generations.clone(),
+            accumulator: self.accumulator,
+            steps_count: self.steps_count,
+            rng: self.rng.clone(),
+        }
+    }
+
+    /// Rolls the world back to a snapshot taken with [`Physac::snapshot`]
+    ///
+    /// The same [`Strong`] references stay valid: bodies and joints destroyed since the snapshot are simulated again,
+    /// and bodies and joints created since the snapshot are destroyed.
+    /// Restoring the same snapshot and making the same changes always gives the same steps (see [Determinism](crate#determinism))
+    ///
+    /// The snapshot must have been taken from this world
+    pub fn restore(&mut self, snapshot: &WorldSnapshot<MAX_VERTICES, CIRCLE_VERTICES>) {
+        // Anything not in the snapshot is destroyed, and everything in it is simulated again when its data is restored
+        for body in &self.bodies {
+            body.borrow_mut().is_simulating = false;
+        }
+        for joint in &self.joints {
+            joint.borrow_mut().is_simulating = false;
+        }
+
+        // Cleared rather than replaced, to keep the capacities that limit how many there can be
+        self.bodies.clear();
+        for (body, data) in &snapshot.bodies {
+            body.borrow_mut().clone_from(data);
+            self.bodies.push(body.clone());
+        }
+        self.joints.clear();
+        for (joint, data) in &snapshot.joints {
+            joint.borrow_mut().clone_from(data);
+            self.joints.push(joint.clone());
+        }
+        self.contacts.clear();
+        self.contacts.extend_from_slice(&snapshot.contacts);
+
+        self.contact_impulses.clone_from(&snapshot.contact_impulses);
+        self.body_tree.clone_from(&snapshot.body_tree);
+        self.sensor_overlaps.clone_from(&snapshot.sensor_overlaps);
+        self.touching_pairs.clone_from(&snapshot.touching_pairs);
+        self.ignored_pairs.clone_from(&snapshot.ignored_pairs);
+        self.soft_bodies.clone_from(&snapshot.soft_bodies);
+        self.next_soft_body_id = snapshot.next_soft_body_id;
+        self.particles.clone_from(&snapshot.particles);
+        self.next_particle_id = snapshot.next_particle_id;
+        self.particle_constraints.clone_from(&snapshot.particle_constraints);
+        self.next_particle_constraint_id = snapshot.next_particle_constraint_id;
+        self.fluids.clone_from(&snapshot.fluids);
+        self.next_fluid_id = snapshot.next_fluid_id;
+        self.body_generations.clone_from(&snapshot.body_generations);
+        self.accumulator = snapshot.accumulator;
+        self.steps_count = snapshot.steps_count;
+        self.rng = snapshot.rng.clone();
+
+        debug_print!("[PHYSAC] restored snapshot from step {}", snapshot.steps_count);
+    }
+
+    /// Saves a copy of the whole world, including its settings, that can be loaded into any world with [`Physac::load_state`]
+    ///
+    /// Unlike [`Physac::snapshot`], the state doesn't share any bodies or joints with the world, and can be serialized with the `serde` feature.
+    /// The pre-solve callback, the broadphase and undrained events aren't part of the state
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use physac::prelude::*;
+    /// let mut ph = init_physics::<24, 24>().build();
+    ///
+    /// ph.borrow_mut().create_physics_body_circle(Vector2 { x: 100.0, y: 100.0 }, 10.0, 1.0);
+    /// ph.borrow_mut().set_solver_iterations(8, 2);
+    ///
+    /// let save = ph.borrow().save_state();
+    ///
+    /// // Continue the game in a new world
+    /// let mut loaded = init_physics::<24, 24>().build();
+    /// loaded.borrow_mut().load_state(&save).unwrap();
+    ///
+    /// assert_eq!(loaded.borrow().get_physics_bodies_count(), 1);
+    /// assert_eq!(loaded.borrow().collision_iterations, 8);
+    /// ```
+    #[must_use]
+    pub fn save_state(&self) -> WorldState<MAX_VERTICES, CIRCLE_VERTICES> {
+        // Impulses of bodies that were moved since the last step are dropped, just like the next step would
+        let stale_ids: Vec<u32> = self.bodies.iter()
+            .map(|body| body.borrow())
+            .filter(|body| body.has_stale_contacts)
+            .map(|body| body.id)
+            .collect();
+        let mut contact_impulses: Vec<_> = self.contact_impulses.iter()
+            .filter(|((id_a, id_b, _), _)| !stale_ids.contains(id_a) && !stale_ids.contains(id_b))
+            .map(|(&(id_a, id_b, contact_id), &(normal_impulse, tangent_impulse))| (id_a, id_b, contact_id, normal_impulse, tangent_impulse))
+            .collect();
+        contact_impulses.sort_unstable_by_key(|&(id_a, id_b, contact_id, _, _)| (id_a, id_b, contact_id));
+
+        WorldState {
+            version: WORLD_STATE_VERSION,
+            gravity: self.gravity.into(),
+            delta_time: self.delta_time,
+            time_scale: self.time_scale,
+            collision_iterations: self.collision_iterations,
+            position_iterations: self.position_iterations,
+            substeps: self.substeps,
+            penetration_allowance: self.penetration_allowance,
+            penetration_correction: self.penetration_correction,
+            sleep_steps: self.sleep_steps,
+            sleep_velocity: self.sleep_velocity,
+            sleep_angular_velocity: self.sleep_angular_velocity,
+            warm_starting: self.warm_starting,
+            bodies: self.bodies.iter().map(|body| body.borrow().clone()).collect(),
+            joints: self.joints.iter().map(|joint| {
+                let joint = joint.borrow();
+                JointState {
+                    id: joint.id,
+                    body_a: joint.body_a.borrow().id,
+                    body_b: joint.body_b.as_ref().map(|body_b| body_b.borrow().id),
+                    local_anchor_a: joint.local_anchor_a,
+                    local_anchor_b: joint.local_anchor_b,
+                    kind: joint.kind,
+                    break_force: joint.break_force,
+                    break_torque: joint.break_torque,
+                    linked_joints: joint.linked_joints.as_ref().map(|links| links.each_ref().map(|link| link.borrow().id)),
+                    solver: joint.solver,
+                    user_data: joint.user_data,
+                }
+            }).collect(),
+            ignored_pairs: self.ignored_pairs.iter().map(|pair| pair.each_ref().map(|body| body.borrow().id)).collect(),
+            contact_impulses,
+            accumulator: self.accumulator,
+            steps_count: self.steps_count,
+            rng: self.rng.clone(),
+            body_generations: self.body_generations.clone(),
+            friction_combine: self.friction_combine,
+            restitution_combine: self.restitution_combine,
+            force_fields: self.force_fields.clone(),
+            next_force_field_id: self.next_force_field_id,
+            buoyancy_zones: self.buoyancy_zones.clone(),
+            next_buoyancy_zone_id: self.next_buoyancy_zone_id,
+            soft_bodies: self.soft_bodies.iter().map(|soft_body| {
+                SoftBodyState {
+                    id: soft_body.id,
+                    points: soft_body.points.iter().map(|point| point.borrow().id).collect(),
+                    rest_area: soft_body.rest_area,
+                    pressure: soft_body.pressure,
+                }
+            }).collect(),
+            next_soft_body_id: self.next_soft_body_id,
+            particles: self.particles.clone(),
+            next_particle_id: self.next_particle_id,
+            particle_constraints: self.particle_constraints.iter().map(|(id, constraint)| {
+                let constraint = match *constraint {
+                    ParticleConstraint::Distance { particle_a, particle_b, length, stiffness } => ParticleConstraintState::Distance { particle_a, particle_b, length, stiffness },
+                    ParticleConstraint::Pin { particle, point } => ParticleConstraintState::Pin { particle, point },
+                    ParticleConstraint::BodyPin { particle, ref body, local_anchor } => ParticleConstraintState::BodyPin { particle, body: body.borrow().id, local_anchor },
+                };
+                (*id, constraint)
+            }).collect(),
+            next_particle_constraint_id: self.next_particle_constraint_id,
+            fluids: self.fluids.clone(),
+            next_fluid_id: self.next_fluid_id,
+        }
+    }
+
+    /// Replaces every body and joint, along with the settings, with the ones in a state saved with [`Physac::save_state`]
+    ///
+    /// Existing bodies and joints are destroyed, and new ones are created for the state
+    ///
+    /// # Errors
+    ///
+    /// This method returns an error without changing the world if the state was saved with a newer format,
+    /// has more bodies than the world has room for, or refers to bodies or joints it doesn't have
+    pub fn load_state(&mut self, state: &WorldState<MAX_VERTICES, CIRCLE_VERTICES>) -> Result<(), LoadStateError> {
+        if state.version > WORLD_STATE_VERSION {
+            return Err(LoadStateError::UnsupportedVersion(state.version));
+        }
+        if !self.is_storage_growable && state.bodies.len() > self.bodies.capacity() {
+            return Err(LoadStateError::TooManyBodies);
+        }
+        let find_body = |id: u32| state.bodies.iter().position(|body| body.id == id).ok_or(LoadStateError::MissingBody(id));
+        let find_joint = |id: u32| state.joints.iter().position(|joint| joint.id == id).ok_or(LoadStateError::MissingJoint(id));
+        for joint in &state.joints {
+            find_body(joint.body_a)?;
+            if let Some(body_b) = joint.body_b {
+                find_body(body_b)?;
+            }
+            for &link in joint.linked_joints.iter().flatten() {
+                find_joint(link)?;
+            }
+        }
+        for &id in state.ignored_pairs.iter().flatten().chain(state.soft_bodies.iter().flat_map(|soft_body| &soft_body.points)) {
+            find_body(id)?;
+        }
+        for (_, constraint) in &state.particle_constraints {
+            if let ParticleConstraintState::BodyPin { body, .. } = *constraint {
+                find_body(body)?;
+            }
+        }
+
+        self.clear_physics_bodies();
+        self.clear_joints();
+        self.contacts.clear();
+        self.sensor_overlaps.clear();
+        self.touching_pairs.clear();
+        self.ignored_pairs.clear();
+        self.soft_bodies.clear();
+        if let Some(body_tree) = &mut self.body_tree {
+            *body_tree = BodyTree::new(body_tree.margin);
+        }
+
+        self.load_unlinked_state(state);
+
+        for body in &state.bodies {
+            let mut body = body.clone();
+            body.is_simulating = true;
+            self.bodies.push(Strong::new(body));
+        }
+        // Gear joints are linked once every joint exists, since they may come before the joints they link
+        let joints: Vec<_> = state.joints.iter().map(|joint| {
+            let mut new_joint = PhysicsJointData::new(
+                joint.id,
+                self.bodies[find_body(joint.body_a)?].clone(),
+                joint.body_b.map(find_body).transpose()?.map(|index| self.bodies[index].clone()),
+                joint.local_anchor_a,
+                joint.local_anchor_b,
+                joint.kind,
+            );
+            new_joint.break_force = joint.break_force;
+            new_joint.break_torque = joint.break_torque;
+            new_joint.user_data = joint.user_data;
+            new_joint.solver = joint.solver;
+            Ok(Strong::new(new_joint))
+        }).collect::<Result<_, LoadStateError>>()?;
+        for (joint, new_joint) in state.joints.iter().zip(&joints) {
+            if let Some(links) = joint.linked_joints {
+                let links = links.map(|link| find_joint(link).map(|index| joints[index].clone()));
+                let [link_a, link_b] = links;
+                new_joint.borrow_mut().linked_joints = Some([link_a?, link_b?]);
+            }
+        }
+        self.joints.extend(joints);
+        for &[id_a, id_b] in &state.ignored_pairs {
+            self.ignored_pairs.push([self.bodies[find_body(id_a)?].clone(), self.bodies[find_body(id_b)?].clone()]);
+        }
+        for soft_body in &state.soft_bodies {
+            self.soft_bodies.push(SoftBodyPressure {
+                id: soft_body.id,
+                points: soft_body.points.iter().map(|&id| Ok(self.bodies[find_body(id)?].clone())).collect::<Result<_, LoadStateError>>()?,
+                rest_area: soft_body.rest_area,
+                pressure: soft_body.pressure,
+            });
+        }
+        self.next_soft_body_id = state.next_soft_body_id;
+        self.particles.clone_from(&state.particles);
+        self.next_particle_id = state.next_particle_id;
+        self.particle_constraints = state.particle_constraints.iter().map(|(id, constraint)| {
+            let constraint = match *constraint {
+                ParticleConstraintState::Distance { particle_a, particle_b, length, stiffness } => ParticleConstraint::Distance { particle_a, particle_b, length, stiffness },
+                ParticleConstraintState::Pin { particle, point } => ParticleConstraint::Pin { particle, point },
+                ParticleConstraintState::BodyPin { particle, body, local_anchor } => ParticleConstraint::BodyPin { particle, body: self.bodies[find_body(body)?].clone(), local_anchor },
+            };
+            Ok((*id, constraint))
+        }).collect::<Result<_, LoadStateError>>()?;
+        self.next_particle_constraint_id = state.next_particle_constraint_id;
+
+        self.contact_impulses = state.contact_impulses.iter()
+            .map(|&(id_a, id_b, contact_id, normal_impulse, tangent_impulse)| ((id_a, id_b, contact_id), (normal_impulse, tangent_impulse)))
+            .collect();
+        self.accumulator = state.accumulator;
+        self.steps_count = state.steps_count;
+        self.rng = state.rng.clone();
+        self.body_generations.clone_from(&state.body_generations);
+
+        debug_print!("[PHYSAC] loaded world state with {} physics bodies and {} joints", state.bodies.len(), state.joints.len());
+        Ok(())
+    }
+
+    /// Replaces the settings of the world, and everything in it that isn't linked to bodies, with the ones in a state
+    fn load_unlinked_state(&mut self, state: &WorldState<MAX_VERTICES, CIRCLE_VERTICES>) {
+        self.gravity = match state.gravity {
+            GravityState::Directional(acceleration) => GravityMode::Directional(acceleration),
+            GravityState::Radial { center, strength } => GravityMode::Radial { center, strength },
+            GravityState::Custom => self.gravity,
+        };
+        #[cfg(feature = "phys_thread")] {
+            self.fixed_time = state.delta_time/1000.0 * 10.0;
+        }
+        self.delta_time = state.delta_time;
+        self.time_scale = state.time_scale;
+        self.collision_iterations = state.collision_iterations;
+        self.position_iterations = state.position_iterations;
+        self.substeps = state.substeps;
+        self.penetration_allowance = state.penetration_allowance;
+        self.penetration_correction = state.penetration_correction;
+        self.sleep_steps = state.sleep_steps;
+        self.sleep_velocity = state.sleep_velocity;
+        self.sleep_angular_velocity = state.sleep_angular_velocity;
+        self.warm_starting = state.warm_starting;
+        self.friction_combine = state.friction_combine;
+        self.restitution_combine = state.restitution_combine;
+        self.force_fields.clone_from(&state.force_fields);
+        self.next_force_field_id = state.next_force_field_id;
+        self.buoyancy_zones.clone_from(&state.buoyancy_zones);
+        self.next_buoyancy_zone_id = state.next_buoyancy_zone_id;
+        self.fluids.clone_from(&state.fluids);
+        self.next_fluid_id = state.next_fluid_id;
+    }
+
+    /// Runs a command on the world, adding it to the recording if the world is being recorded (see [`Physac::start_recording`])
+    ///
+    /// Returns the body the command created or changed, or [`None`] if there is no body with its ID, a body couldn't be created,
+    /// or the command doesn't change a body
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use physac::prelude::*;
+    /// let mut ph = init_physics::<24, 24>().build();
+    ///
+    /// let player = ph.borrow_mut()
+    ///     .execute(PhysicsCommand::CreateCircle { position: Vector2 { x: 100.0, y: 100.0 }, radius: 10.0, density: 1.0 })
+    ///     .unwrap();
+    ///
+    /// // Route input through commands, so that it ends up in recordings
+    /// let id = player.borrow().id;
+    /// ph.borrow_mut().execute(PhysicsCommand::AddForce { id, force: Vector2 { x: 5.0, y: 0.0 } });
+    /// ```
+    pub fn execute(&mut self, command: PhysicsCommand) -> Option<Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>> {
+        if let Some(recording) = &mut self.recording {
+            recording.commands.push((self.steps_count, command));
+        }
+        self.run_command(command)
+    }
+
+    /// Runs a command on the world without recording it
+    fn run_command(&mut self, command: PhysicsCommand) -> Option<Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>> {
+        let find_body = |id: u32| self.bodies.iter().find(|body| body.borrow().id == id).cloned();
+        match command {
+            PhysicsCommand::CreateCircle { position, radius, density } => self.try_create_physics_body_circle(position, radius, density).cloned().ok(),
+            PhysicsCommand::CreateRectangle { position, width, height, density } => self.try_create_physics_body_rectangle(position, width, height, density).cloned().ok(),
+            PhysicsCommand::CreatePolygon { position, radius, sides, density } => self.try_create_physics_body_polygon(position, radius, sides, density).cloned().ok(),
+            PhysicsCommand::DestroyBody { id } => {
+                let body = find_body(id)?;
+                self.destroy_physics_body(body.clone());
+                Some(body)
+            }
+            PhysicsCommand::Shatter { id, position, force } => {
+                let body = find_body(id)?;
+                self.physics_shatter(body.downgrade(), position, force);
+                Some(body)
+            }
+            PhysicsCommand::SetGravity { gravity } => {
+                self.set_physics_gravity(gravity.x, gravity.y);
+                None
+            }
+            PhysicsCommand::AddForce { id, .. } |
+            PhysicsCommand::AddTorque { id, .. } |
+            PhysicsCommand::SetPosition { id, .. } |
+            PhysicsCommand::SetRotation { id, .. } |
+            PhysicsCommand::SetVelocity { id, .. } |
+            PhysicsCommand::SetAngularVelocity { id, .. } |
+            PhysicsCommand::SetEnabled { id, .. } => {
+                let body = find_body(id)?;
+                body.borrowed_mut(|body| match command {
+                    PhysicsCommand::AddForce { force, .. } => body.add_force(force),
+                    PhysicsCommand::AddTorque { torque, .. } => body.add_torque(torque),
+                    PhysicsCommand::SetPosition { position, .. } => body.teleport(position, body.orient),
+                    PhysicsCommand::SetRotation { radians, .. } => body.teleport(body.position, radians),
+                    PhysicsCommand::SetVelocity { velocity, .. } => body.set_linear_velocity(velocity),
+                    PhysicsCommand::SetAngularVelocity { angular_velocity, .. } => body.set_angular_velocity(angular_velocity),
+                    PhysicsCommand::SetEnabled { enabled, .. } => body.enabled = enabled,
+                    _ => unreachable!("only commands that change a body are matched"),
+                });
+                if matches!(command, PhysicsCommand::SetPosition { .. } | PhysicsCommand::SetRotation { .. }) {
+                    self.refit_in_body_tree(&body);
+                }
+                Some(body)
+            }
+        }
+    }
+
+    /// Starts recording the commands run on the world, saving its state and every command run with [`Physac::execute`] from now on
+    ///
+    /// Changes made any other way aren't recorded, so input that should end up in the recording has to go through [`PhysicsCommand`]s.
+    /// The world should be stepped with [`Physac::update`] or [`Physac::step_once`] so that replays run the same steps
+    /// (see [Determinism](crate#determinism)). Starting a new recording discards the current one
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use physac::prelude::*;
+    /// let mut ph = init_physics::<24, 24>().build();
+    ///
+    /// ph.borrow_mut().start_recording();
+    /// let ball = ph.borrow_mut()
+    ///     .execute(PhysicsCommand::CreateCircle { position: Vector2 { x: 100.0, y: 100.0 }, radius: 10.0, density: 1.0 })
+    ///     .unwrap();
+    /// let recording = ph.borrow_mut().stop_recording().unwrap();
+    ///
+    /// // Attach the recording to a bug report, and watch it play out again later
+    /// let mut replayed = init_physics::<24, 24>().build();
+    /// replayed.borrow_mut().start_replay(&recording).unwrap();
+    /// # drop(ball);
+    /// ```
+    pub fn start_recording(&mut self) {
+        self.recording = Some(CommandRecording {
+            start: self.save_state(),
+            commands: Vec::new(),
+            end_step: self.steps_count,
+            checksums: Vec::new(),
+        });
+    }
+
+    /// Stops recording the world and returns the recording, or [`None`] if it isn't being recorded
+    pub fn stop_recording(&mut self) -> Option<CommandRecording<MAX_VERTICES, CIRCLE_VERTICES>> {
+        let mut recording = self.recording.take()?;
+        recording.end_step = self.steps_count;
+        Some(recording)
+    }
+
+    /// Returns true if the world is being recorded
+    ///
+    /// See [`Physac::start_recording`]
+    #[must_use]
+    pub fn is_recording(&self) -> bool {
+        self.recording.is_some()
+    }
+
+    /// Loads the state a recording started from, and runs its commands as the world reaches the steps they were recorded at,
+    /// until it has run as many steps as were recorded
+    ///
+    /// If the bodies stop matching the recording after a step, the replay is stopped and the step fails with
+    /// [`PhysicsStepError::ReplayDiverged`]. That happens when the world was changed without [`Physac::execute`] while it was
+    /// being recorded or replayed, or when it is stepped differently. See [`Physac::load_state`]
+    ///
+    /// # Errors
+    ///
+    /// This method returns an error without changing the world if the starting state of the recording can't be loaded
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use physac::prelude::*;
+    /// let mut ph = init_physics::<24, 24>().build();
+    ///
+    /// ph.borrow_mut().start_recording();
+    /// ph.borrow_mut().execute(PhysicsCommand::CreateCircle { position: Vector2 { x: 100.0, y: 100.0 }, radius: 10.0, density: 1.0 });
+    /// for _ in 0..10 {
+    ///     ph.borrow_mut().update(1.0/60.0).unwrap();
+    /// }
+    /// let recording = ph.borrow_mut().stop_recording().unwrap();
+    ///
+    /// // Pushing the ball without a command makes the replay stop matching the recording
+    /// let mut replayed = init_physics::<24, 24>().build();
+    /// replayed.borrow_mut().start_replay(&recording).unwrap();
+    /// replayed.borrow_mut().update(1.0/60.0).unwrap();
+    /// replayed.borrow().get_physics_body(0).borrow_mut().velocity.x = 1.0;
+    /// assert!(matches!(replayed.borrow_mut().update(1.0/60.0), Err(PhysicsStepError::ReplayDiverged(_))));
+    /// assert!(!replayed.borrow().is_replaying());
+    /// ```
+    pub fn start_replay(&mut self, recording: &CommandRecording<MAX_VERTICES, CIRCLE_VERTICES>) -> Result<(), LoadStateError> {
+        self.load_state(&recording.start)?;
+        self.replay = Some(Replay { recording: recording.clone(), next_command: 0 });
+        Ok(())
+    }
+
+    /// Returns true if the world is replaying a recording
+    ///
+    /// See [`Physac::start_replay`]
+    #[must_use]
+    pub fn is_replaying(&self) -> bool {
+        self.replay.is_some()
+    }
+
+    /// Saves the whole world to a file, in the binary format of [`WorldState::to_bytes`]
+    ///
+    /// # Errors
+    ///
+    /// This method returns an error if the file can't be written
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use physac::prelude::*;
+    /// let mut ph = init_physics::<24, 24>().build();
+    ///
+    /// ph.borrow_mut().create_physics_body_rectangle(Vector2 { x: 200.0, y: 300.0 }, 400.0, 20.0, 1.0);
+    ///
+    /// // Round-trip a level through the editor
+    /// let path = std::env::temp_dir().join("physac_level.phys");
+    /// ph.borrow().save_world(&path).unwrap();
+    ///
+    /// let mut level = init_physics::<24, 24>().build();
+    /// level.borrow_mut().load_world(&path).unwrap();
+    ///
+    /// assert_eq!(level.borrow().get_physics_bodies_count(), 1);
+    /// # std::fs::remove_file(path).unwrap();
+    /// ```
+    pub fn save_world(&self, path: impl AsRef<Path>) -> std::io::Result<()> {
+        std::fs::write(path, self.save_state().to_bytes())
+    }
+
+    /// Replaces every body and joint, along with the settings, with the ones in a file saved with [`Physac::save_world`]
+    ///
+    /// See [`Physac::load_state`]
+    ///
+    /// # Errors
+    ///
+    /// This method returns an error without changing the world if the file can't be read, or contains a state that can't be loaded
+    /// (with a [`LoadStateError`] of kind [`InvalidData`](std::io::ErrorKind::InvalidData))
+    pub fn load_world(&mut self, path: impl AsRef<Path>) -> std::io::Result<()> {
+        let bytes = std::fs::read(path)?;
+        WorldState::from_bytes(&bytes)
+            .and_then(|state| self.load_state(&state))
+            .map_err(|error| std::io::Error::new(std::io::ErrorKind::InvalidData, error))
+    }
+
+    /// Finds a valid index for a new physics body initialization, and invalidates the handles to the bodies that had it before
+    fn find_available_body_index(&mut self) -> Option<u32> {
+        // Growable storage always has room for one more, and there is always a free ID among one more than there are bodies
+        let max_bodies = if self.is_storage_growable { self.bodies.len() + 1 } else { self.bodies.capacity() };
+        let mut index = None;
+        for i in 0..max_bodies as u32 {
+            let mut current_id = i;
+
+            // Check if current id already exist in other physics body
+            for body in &self.bodies {
+                if body.borrow().id == current_id {
+                    current_id += 1;
+                    break;
+                }
+            }
+
+            // If it is not used, use it as new physics body id
+            if current_id == i {
+                index = Some(i);
+                break;
+            }
+        }
+
+        if let Some(id) = index {
+            let id = id as usize;
+            if id >= self.body_generations.len() {
+                self.body_generations.resize(id + 1, 0);
+            }
+            self.body_generations[id] = self.body_generations[id].wrapping_add(1);
+        }
+
+        index
+    }
+
+    /// Finds a valid index for a new joint initialization
+    fn find_available_joint_index(&self) -> Option<u32> {
+        (0..=self.joints.len() as u32).find(|&id| self.joints.iter().all(|joint| joint.borrow().id != id))
+    }
+
+    /// Finds a valid index for a new manifold initialization
+    fn find_available_manifold_index(&self) -> Option<u32> {
+        (self.contacts.len() as u32).checked_add(1)
+            .filter(|id| self.is_storage_growable || *id < self.contacts.capacity() as u32)
+    }
+
+    /// Creates a new physics manifold to solve collision
+    fn create_physics_manifold(&mut self, a: Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>, b: Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>) -> Option<&mut PhysicsManifoldData<MAX_VERTICES, CIRCLE_VERTICES>> {
+        if let Some(new_id) = self.find_available_manifold_index() {
+            let mut new_manifold = PhysicsManifoldData::new(a, b);
+
+            // Initialize new manifold with generic values
+            new_manifold.id = new_id;
+            new_manifold.penetration = 0.0;
+            new_manifold.normal = Vector2::zero();
+            new_manifold.contacts[0] = Vector2::zero();
+            new_manifold.contacts[1] = Vector2::zero();
+            new_manifold.contacts_count = 0;
+            new_manifold.restitution = 0.0;
+            new_manifold.dynamic_friction = 0.0;
+            new_manifold.static_friction = 0.0;
+            new_manifold.normal_impulse = 0.0;
+            new_manifold.contact_ids = [ContactId::default(); 2];
+            new_manifold.normal_impulses = [0.0; 2];
+            new_manifold.tangent_impulses = [0.0; 2];
+            new_manifold.velocity_biases = [0.0; 2];
+
+            // Add new body to bodies pointers array and update bodies count
+            self.contacts.push(new_manifold);
+            Some(&mut *self.contacts.last_mut().expect("should have at least one element after push"))
+        } else {
+            debug_print!("[PHYSAC] new physics manifold creation failed because there isn't any available id to use");
+            None
+        }
+    }
+
+    /// Creates a physics manifold for every pair of touching parts between two physics bodies
+    fn create_part_manifolds(&mut self, a: &Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>, b: &Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>) -> Result<(), PhysicsStepError> {
+        let (proxies_a, proxies_b) = {
+            let (body_a, body_b) = (a.read()?, b.read()?);
+            let (bounds_a, bounds_b) = (body_a.bounds(), body_b.bounds());
+
+            if !bounds_a.overlaps(&bounds_b) {
+                return Ok(());
+            }
+
+            // Only the parts of each body near the other one can touch it
+            (body_a.convex_proxies(Some(bounds_b)), body_b.convex_proxies(Some(bounds_a)))
+        };
+
+        for proxy_a in &proxies_a {
+            for proxy_b in &proxies_b {
+                let mut manifold = PhysicsManifoldData::new(a.clone(), b.clone());
+                manifold.solve_convex_proxies(proxy_a, proxy_b);
+
+                if manifold.contacts_count > 0 &&
+                   proxy_a.accepts_contact(manifold.normal, proxy_b.center()) &&
+                   proxy_b.accepts_contact(-manifold.normal, proxy_a.center())
+                {
+                    // Update physics bodies grounded state if normal direction is down
+                    if manifold.normal.y > 0.0 {
+                        a.write()?.is_grounded = true;
+                    } else if manifold.normal.y < 0.0 {
+                        b.write()?.is_grounded = true;
+                    }
+
+                    let new_manifold = self.create_physics_manifold(a.clone(), b.clone()).ok_or(PhysicsStepError::OutOfIDs)?;
+                    new_manifold.penetration = manifold.penetration;
+                    new_manifold.normal = manifold.normal;
+                    new_manifold.contacts = manifold.contacts;
+                    new_manifold.contacts_count = manifold.contacts_count;
+                    new_manifold.contact_ids = manifold.contact_ids.map(|id| ContactId { parts: [proxy_a.part, proxy_b.part], ..id });
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Integrates physics forces into velocity
+    fn integrate_physics_forces(body: &mut PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>, delta_time: f64, gravity: &GravityMode) {
+        if body.is_inert() {
+            return;
+        }
+
+        body.velocity.x += (real_to_f64(body.force.x*body.inverse_mass)*(delta_time/2.0)) as Real;
+        body.velocity.y += (real_to_f64(body.force.y*body.inverse_mass)*(delta_time/2.0)) as Real;
+
+        if body.use_gravity {
+            let gravity_force = gravity.acceleration_at(body.center_of_mass());
+            body.velocity.x += (real_to_f64(gravity_force.x*body.gravity_scale)*(delta_time/1000.0/2.0)) as Real;
+            body.velocity.y += (real_to_f64(gravity_force.y*body.gravity_scale)*(delta_time/1000.0/2.0)) as Real;
+        }
+
+        if !body.freeze_orient {
+            body.angular_velocity += (real_to_f64(body.torque)*real_to_f64(body.inverse_inertia)*(delta_time/2.0)) as Real;
+        }
+    }
+
+    /// Adds the forces of the force fields to the bodies inside of them
+    fn apply_force_fields(&self) -> Result<(), PhysicsStepError> {
+        for (_, field) in &self.force_fields {
+            let found;
+            let bodies = match &field.region {
+                None => &self.bodies,
+                Some(region) => { found = self.bodies_in_region(region); &found },
+            };
+
+            for body in bodies {
+                let mut body = body.write()?;
+                if body.is_inert() || body.collision_category & field.mask == 0 {
+                    continue;
+                }
+
+                let force = field.kind.force_at(body.center_of_mass());
+                body.add_force(force);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Floats the bodies in each buoyancy zone by how much of them is submerged, and drags them along with the fluid
+    fn apply_buoyancy(&self) -> Result<(), PhysicsStepError> {
+        for (_, zone) in &self.buoyancy_zones {
+            let mut outline = zone.region.outline();
+            // Keep the inside of every edge on the same side, whichever way the region winds
+            if polygon_area_centroid(&outline).0 < 0.0 {
+                outline.reverse();
+            }
+            let inside_edge = |k: usize| {
+                let (q1, q2) = (outline[k], outline[next_idx(k, outline.len())]);
+                move |p: Vector2| math_cross_vector2(q2 - q1, p - q1)
+            };
+
+            for body in self.bodies_in_region(&zone.region) {
+                let mut body = body.write()?;
+                if body.is_inert() || body.collision_category & zone.mask == 0 {
+                    continue;
+                }
+
+                // Clip the outline of every part of the body to the fluid
+                let (mut outline_area, mut submerged_area, mut submerged_center) = (0.0, 0.0, Vector2::zero());
+                for proxy in body.convex_proxies(None) {
+                    let part = proxy.outline();
+                    outline_area += polygon_area_centroid(&part).0.abs();
+
+                    let submerged = (0..outline.len()).fold(part, |piece, k| clip_polygon(&piece, inside_edge(k)));
+                    let (area, center) = polygon_area_centroid(&submerged);
+                    submerged_area += area.abs();
+                    submerged_center += center*area.abs();
+                }
+                if submerged_area <= 0.0 || outline_area <= 0.0 {
+                    continue;
+                }
+                submerged_center *= 1.0/submerged_area;
+
+                // Outlines of round parts are a little smaller than the parts themselves
+                let (_, area, _) = shape_mass_data(&body.shape);
+                let fraction = (submerged_area/outline_area).min(1.0);
+                let submerged_area = if area > 0.0 { area*fraction } else { submerged_area };
+
+                // The fluid pushes against gravity with the weight of the fluid the body displaces
+                if body.use_gravity {
+                    let buoyancy = self.gravity.acceleration_at(submerged_center)*(-body.gravity_scale*zone.density*submerged_area/1000.0);
+                    body.add_force_at_point(buoyancy, submerged_center);
+                }
+
+                // Drag slows the submerged part of the body down to the speed of the fluid
+                let radius = submerged_center - body.center_of_mass();
+                let velocity = body.velocity + math_cross(body.angular_velocity, radius);
+                let drag = (zone.flow - velocity)*(body.mass*fraction*zone.linear_drag/1000.0);
+                body.add_force_at_point(drag, submerged_center);
+                body.torque -= body.inertia*fraction*zone.angular_drag/1000.0*body.angular_velocity;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Pushes out on the edges of every soft body by how much it is squashed, forgetting soft bodies that lost a point since their ring is broken
+    fn apply_soft_body_pressure(&mut self) -> Result<(), PhysicsStepError> {
+        let mut i = 0;
+        while i < self.soft_bodies.len() {
+            let soft_body = &self.soft_bodies[i];
+            let mut positions = Vec::with_capacity(soft_body.points.len());
+            let (mut is_broken, mut is_sleeping) = (false, true);
+            for point in &soft_body.points {
+                let point = point.read()?;
+                positions.push(point.position);
+                is_broken |= !point.is_simulating;
+                is_sleeping &= point.is_sleeping;
+            }
+            if is_broken {
+                self.soft_bodies.remove(i);
+                continue;
+            }
+            i += 1;
+            if is_sleeping {
+                continue;
+            }
+
+            // Edge normals point outwards on the side the points wind towards
+            let (area, _) = polygon_area_centroid(&positions);
+            let pressure = soft_body.pressure*(soft_body.rest_area/area.abs().max(0.1*soft_body.rest_area) - 1.0)*area.signum();
+            for k in 0..positions.len() {
+                let next = next_idx(k, positions.len());
+                let edge = positions[next] - positions[k];
+                let force = Vector2 { x: edge.y, y: -edge.x }*(0.5*pressure);
+                soft_body.points[k].write()?.add_force(force);
+                soft_body.points[next].write()?.add_force(force);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Moves particles by their velocity and gravity, then solves their constraints and pushes them out of bodies
+    ///
+    /// The particles are taken out of the world while they are moved, so that it can still be queried for bodies
+    fn step_particles(&mut self, particles: &mut [(u32, Particle)]) -> Result<(), PhysicsStepError> {
+        trace_span!("particles");
+        if particles.is_empty() {
+            return Ok(());
+        }
+
+        let delta_time = self.delta_time as Real;
+        for (_, particle) in particles.iter_mut() {
+            let velocity = (particle.position - particle.previous_position)*(1.0/(1.0 + delta_time/1000.0*particle.damping));
+            particle.previous_position = particle.position;
+            particle.position += velocity + self.gravity.acceleration_at(particle.position)*(particle.gravity_scale*delta_time*delta_time/1000.0);
+        }
+
+        // Forget constraints on removed particles or destroyed bodies, and find where the pins are
+        let indices: HashMap<u32, usize> = particles.iter().enumerate().map(|(index, (id, _))| (*id, index)).collect();
+        let mut is_pinned = vec![false; particles.len()];
+        let mut pins = Vec::new();
+        let mut i = 0;
+        while i < self.particle_constraints.len() {
+            let constraint = &self.particle_constraints[i].1;
+            let mut is_valid = constraint.particles().all(|id| indices.contains_key(&id));
+            if is_valid {
+                match constraint {
+                    ParticleConstraint::Distance { .. } => {},
+                    ParticleConstraint::Pin { particle, point } => pins.push((indices[particle], *point)),
+                    ParticleConstraint::BodyPin { particle, body, local_anchor } => {
+                        let body = body.read()?;
+                        is_valid = body.is_simulating;
+                        pins.push((indices[particle], body.world_point(*local_anchor)));
+                    },
+                }
+            }
+
+            if is_valid {
+                i += 1;
+            } else {
+                self.particle_constraints.remove(i);
+            }
+        }
+        for &(index, _) in &pins {
+            is_pinned[index] = true;
+        }
+
+        for _ in 0..self.collision_iterations.max(1) {
+            for &(index, point) in &pins {
+                particles[index].1.position = point;
+            }
+
+            for (_, constraint) in &self.particle_constraints {
+                let ParticleConstraint::Distance { particle_a, particle_b, length, stiffness } = *constraint else { continue };
+                let (index_a, index_b) = (indices[&particle_a], indices[&particle_b]);
+                let weight_a = if is_pinned[index_a] { 0.0 } else { 1.0 };
+                let weight_b = if is_pinned[index_b] { 0.0 } else { 1.0 };
+                let delta = particles[index_b].1.position - particles[index_a].1.position;
+                let distance = delta.length_sqr().sqrt();
+                if weight_a + weight_b == 0.0 || distance <= Real::EPSILON {
+                    continue;
+                }
+
+                let correction = delta*(stiffness*(distance - length)/(distance*(weight_a + weight_b)));
+                particles[index_a].1.position += correction*weight_a;
+                particles[index_b].1.position += correction*(-weight_b);
+            }
+        }
+
+        // Push particles out of the bodies they ended up in, slowing down their sliding along them
+        for (index, (_, particle)) in particles.iter_mut().enumerate() {
+            if is_pinned[index] {
+                continue;
+            }
+
+            let friction = particle.friction.clamp(0.0, 1.0);
+            let previous_position = &mut particle.previous_position;
+            self.push_out_of_bodies(&mut particle.position, particle.radius, particle.mask, |position, normal, body_velocity| {
+                let sliding = position - *previous_position - body_velocity*delta_time;
+                let tangent_sliding = sliding - normal*sliding.dot(normal);
+                *previous_position += tangent_sliding*friction;
+            })?;
+        }
+
+        Ok(())
+    }
+
+    /// Moves the particles of every fluid with double density relaxation, then pushes them out of bodies
+    ///
+    /// The fluids are taken out of the world while they are moved, so that it can still be queried for bodies
+    fn step_fluids(&self, fluids: &mut [(u32, Fluid)]) -> Result<(), PhysicsStepError> {
+        trace_span!("fluids");
+        let delta_time = self.delta_time as Real;
+        let seconds = delta_time/1000.0;
+        for (_, fluid) in fluids.iter_mut() {
+            let radius = fluid.interaction_radius.max(Real::EPSILON);
+            let pairs = fluid.neighbor_pairs();
+
+            for particle in &mut fluid.particles {
+                particle.velocity += self.gravity.acceleration_at(particle.position)*(fluid.gravity_scale*delta_time/1000.0);
+            }
+
+            // Viscosity evens out the velocities of neighbors moving towards each other
+            for &(i, j) in &pairs {
+                let (a, b) = (fluid.particles[i], fluid.particles[j]);
+                let delta = b.position - a.position;
+                let distance = delta.length_sqr().sqrt();
+                if distance <= Real::EPSILON || distance >= radius {
+                    continue;
+                }
+                let direction = delta*(1.0/distance);
+                let inward_speed = (a.velocity - b.velocity).dot(direction);
+                if inward_speed > 0.0 {
+                    let impulse = direction*(0.5*(fluid.viscosity*seconds*(1.0 - distance/radius)).min(1.0)*inward_speed);
+                    fluid.particles[i].velocity -= impulse;
+                    fluid.particles[j].velocity += impulse;
+                }
+            }
+
+            let previous_positions: Vec<Vector2> = fluid.particles.iter().map(|particle| particle.position).collect();
+            for particle in &mut fluid.particles {
+                particle.position += particle.velocity*delta_time;
+            }
+
+            // Pressure pushes crowded particles apart, and near pressure keeps them from clumping
+            let mut densities = vec![(0.0, 0.0); fluid.particles.len()];
+            let mut pair_weights = Vec::with_capacity(pairs.len());
+            for &(i, j) in &pairs {
+                let distance = dist_sqr(fluid.particles[i].position, fluid.particles[j].position).sqrt();
+                let weight = (1.0 - distance/radius).max(0.0);
+                pair_weights.push(weight);
+                for index in [i, j] {
+                    densities[index].0 += weight*weight;
+                    densities[index].1 += weight*weight*weight;
+                }
+            }
+            let pressures: Vec<(Real, Real)> = densities.iter()
+                .map(|&(density, near_density)| (fluid.stiffness*(density - fluid.rest_density), fluid.near_stiffness*near_density))
+                .collect();
+            let mut displacements = vec![Vector2::zero(); fluid.particles.len()];
+            for (&(i, j), &weight) in pairs.iter().zip(&pair_weights) {
+                let delta = fluid.particles[j].position - fluid.particles[i].position;
+                let distance = delta.length_sqr().sqrt();
+                if weight <= 0.0 || distance <= Real::EPSILON {
+                    continue;
+                }
+                let pressure = 0.5*(pressures[i].0 + pressures[j].0);
+                let near_pressure = 0.5*(pressures[i].1 + pressures[j].1);
+                let displacement = delta*(0.5*seconds*seconds*(pressure*weight + near_pressure*weight*weight)/distance);
+                displacements[i] -= displacement;
+                displacements[j] += displacement;
+            }
+
+            for ((particle, displacement), previous_position) in fluid.particles.iter_mut().zip(displacements).zip(previous_positions) {
+                particle.position += displacement;
+                particle.velocity = (particle.position - previous_position)*(1.0/delta_time);
+
+                // Particles can't flow into bodies, but slide along them freely
+                let velocity = &mut particle.velocity;
+                self.push_out_of_bodies(&mut particle.position, fluid.particle_radius, fluid.mask, |_, normal, body_velocity| {
+                    let normal_speed = (*velocity - body_velocity).dot(normal);
+                    if normal_speed < 0.0 {
+                        *velocity -= normal*normal_speed;
+                    }
+                })?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Pushes a circle out of every body it overlaps, except sensors and bodies outside of `mask`,
+    /// calling `on_contact` with the new center, the direction it was pushed in, and the velocity of the surface of the body it touched
+    fn push_out_of_bodies<F: FnMut(Vector2, Vector2, Vector2)>(&self, center: &mut Vector2, radius: Real, mask: u32, mut on_contact: F) -> Result<(), PhysicsStepError> {
+        let extent = Vector2 { x: radius, y: radius };
+        let bounds = Aabb { min: *center - extent, max: *center + extent };
+        for body in self.overlap_circle(*center, radius) {
+            let body = body.read()?;
+            if body.is_sensor || body.collision_category & mask == 0 {
+                continue;
+            }
+
+            for proxy in body.convex_proxies(Some(bounds)) {
+                let Some((normal, depth)) = proxy.circle_penetration(*center, radius) else { continue };
+                if !proxy.accepts_contact(normal, *center) {
+                    continue;
+                }
+                *center += normal*depth;
+
+                let body_velocity = body.velocity + math_cross(body.angular_velocity, *center - body.center_of_mass());
+                on_contact(*center, normal, body_velocity);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Slows physics bodies down by their damping
+    fn integrate_physics_damping(body: &mut PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>, delta_time: f64) {
+        if body.is_inert() {
+            return;
+        }
+
+        let seconds = delta_time/1000.0;
+        body.velocity *= (1.0/(1.0 + seconds*real_to_f64(body.linear_damping))) as Real;
+
+        if !body.freeze_orient {
+            body.angular_velocity *= (1.0/(1.0 + seconds*real_to_f64(body.angular_damping))) as Real;
+        }
+    }
+
+    /// Initializes physics manifolds to solve collisions
+    fn initialize_physics_manifolds(
+        manifold: &mut PhysicsManifoldData<MAX_VERTICES, CIRCLE_VERTICES>,
+        delta_time: f64,
+        gravity: &GravityMode,
+        friction_combine: CombineMode,
+        restitution_combine: CombineMode,
+    ) -> Result<(), PhysicsStepError> {
+        let body_a = manifold.body_a.read()?;
+        let body_b = manifold.body_b.read()?;
+
+        // Mix restitution, static and dynamic friction
+        let friction_combine = CombineMode::resolve(friction_combine, body_a.friction_combine, body_b.friction_combine);
+        let restitution_combine = CombineMode::resolve(restitution_combine, body_a.restitution_combine, body_b.restitution_combine);
+        manifold.restitution = restitution_combine.combine(body_a.restitution, body_b.restitution);
+        manifold.static_friction = friction_combine.combine(body_a.static_friction, body_b.static_friction);
+        manifold.dynamic_friction = friction_combine.combine(body_a.dynamic_friction, body_b.dynamic_friction);
+
+        // Both surfaces move clockwise around their own body, which is opposite ways along the contact
+        let tangent = Vector2 { x: manifold.normal.y, y: -manifold.normal.x };
+        manifold.surface_velocity = tangent*(body_a.surface_velocity + body_b.surface_velocity);
+
+        for i in 0..manifold.contacts_count {
+            // Caculate radius from center of mass to contact
+            let radius_a = manifold.contacts[i as usize] - body_a.position;
+            let radius_b = manifold.contacts[i as usize] - body_b.position;
+
+            let cross_a = math_cross(body_a.angular_velocity, radius_a);
+            let cross_b = math_cross(body_b.angular_velocity, radius_b);
+
+            let radius_v = Vector2 {
+                x: body_b.velocity.x + cross_b.x - body_a.velocity.x - cross_a.x,
+                y: body_b.velocity.y + cross_b.y - body_a.velocity.y - cross_a.y,
+            };
+
+            // Determine if we should perform a resting collision or not;
+            // The idea is if the only thing moving this object is gravity, then the collision should be performed without any restitution
+            let gravity_force = gravity.acceleration_at(manifold.contacts[i as usize]);
+            if radius_v.length_sqr() < ((Vector2 { x: gravity_force.x*delta_time as Real/1000.0, y: gravity_force.y*delta_time as Real/1000.0, }).length_sqr() + Real::EPSILON) {
+                manifold.restitution = 0.0;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Integrates physics collisions impulses to solve collisions
+    fn integrate_physics_impulses(manifold: &mut PhysicsManifoldData<MAX_VERTICES, CIRCLE_VERTICES>) -> Result<(), PhysicsStepError> {
+        let mut body_a = manifold.body_a.write()?;
+        let mut body_b = manifold.body_b.write()?;
+
+        // Early out and positional correct if both objects have infinite mass (kinematic bodies keep moving)
+        if (body_a.inverse_mass + body_b.inverse_mass).abs() <= Real::EPSILON {
+            if !body_a.is_kinematic {
+                body_a.velocity = Vector2::zero();
+            }
+            if !body_b.is_kinematic {
+                body_b.velocity = Vector2::zero();
+            }
+            return Ok(());
+        }
+
+        for i in 0..manifold.contacts_count {
+            // Calculate radius from center of mass to contact
+            let radius_a = manifold.contacts[i as usize] - body_a.position;
+            let radius_b = manifold.contacts[i as usize] - body_b.position;
+
+            // Calculate relative velocity
+            let mut radius_v = Vector2::zero();
+            radius_v.x = body_b.velocity.x + math_cross(body_b.angular_velocity, radius_b).x - body_a.velocity.x - math_cross(body_a.angular_velocity, radius_a).x;
+            radius_v.y = body_b.velocity.y + math_cross(body_b.angular_velocity, radius_b).y - body_a.velocity.y - math_cross(body_a.angular_velocity, radius_a).y;
+            radius_v += manifold.surface_velocity;
+
+            // Relative velocity along the normal
+            let contact_velocity = radius_v.dot(manifold.normal);
+
+            // Do not resolve if velocities are separating
+            if contact_velocity > 0.0 {
+                return Ok(());
+            }
+
+            let inverse_mass_sum = contact_inverse_mass(&body_a, &body_b, radius_a, radius_b, manifold.normal);
+
+            // Neither body can be pushed, such as two kinematic bodies
+            if inverse_mass_sum <= 0.0 {
+                return Ok(());
+            }
+
+            // Calculate impulse scalar value
+            let mut impulse = -(1.0 + manifold.restitution)*contact_velocity;
+            impulse /= inverse_mass_sum;
+            impulse /= count_to_real(manifold.contacts_count);
+
+            // Apply impulse to each physics body
+            let impulse_v = manifold.normal*impulse;
+            manifold.normal_impulse += impulse;
+
+            if body_a.enabled {
+                body_a.velocity.x += body_a.inverse_mass*(-impulse_v.x);
+                body_a.velocity.y += body_a.inverse_mass*(-impulse_v.y);
+
+                if !body_a.freeze_orient {
+                    body_a.angular_velocity += body_a.inverse_inertia*math_cross_vector2(radius_a, -impulse_v);
+                }
+            }
+
+            if body_b.enabled {
+                body_b.velocity.x += body_b.inverse_mass*(impulse_v.x);
+                body_b.velocity.y += body_b.inverse_mass*(impulse_v.y);
+
+                if !body_b.freeze_orient {
+                    body_b.angular_velocity += body_b.inverse_inertia*math_cross_vector2(radius_b, impulse_v);
+                }
+            }
+
+            // Apply friction impulse to each physics body
+            radius_v.x = body_b.velocity.x + math_cross(body_b.angular_velocity, radius_b).x - body_a.velocity.x - math_cross(body_a.angular_velocity, radius_a).x;
+            radius_v.y = body_b.velocity.y + math_cross(body_b.angular_velocity, radius_b).y - body_a.velocity.y - math_cross(body_a.angular_velocity, radius_a).y;
+            radius_v += manifold.surface_velocity;
+
+            let mut tangent = Vector2 {
+                x: radius_v.x - (manifold.normal.x*radius_v.dot(manifold.normal)),
+                y: radius_v.y - (manifold.normal.y*radius_v.dot(manifold.normal)),
+            };
+            math_normalize(&mut tangent);
+
+            // Calculate impulse tangent magnitude
+            let mut impulse_tangent = -radius_v.dot(tangent);
+            impulse_tangent /= inverse_mass_sum;
+            impulse_tangent /= count_to_real(manifold.contacts_count);
+
+            let abs_impulse_tangent = impulse_tangent.abs();
+
+            // Don't apply tiny friction impulses
+            if abs_impulse_tangent <= Real::EPSILON {
+                return Ok(());
+            }
+
+            // Apply coulumb's law
+            let tangent_impulse = if abs_impulse_tangent < impulse*manifold.static_friction {
+                Vector2 { x: tangent.x*impulse_tangent, y: tangent.y*impulse_tangent }
+            } else {
+                Vector2 { x: tangent.x*-impulse*manifold.dynamic_friction, y: tangent.y*-impulse*manifold.dynamic_friction }
+            };
+
+            // Apply friction impulse
+            if body_a.enabled {
+                body_a.velocity.x += body_a.inverse_mass*(-tangent_impulse.x);
+                body_a.velocity.y += body_a.inverse_mass*(-tangent_impulse.y);
+
+                if !body_a.freeze_orient {
+                    body_a.angular_velocity += body_a.inverse_inertia*math_cross_vector2(radius_a, -tangent_impulse);
+                }
+            }
+
+            if body_b.enabled {
+                body_b.velocity.x += body_b.inverse_mass*(tangent_impulse.x);
+                body_b.velocity.y += body_b.inverse_mass*(tangent_impulse.y);
+
+                if !body_b.freeze_orient {
+                    body_b.angular_velocity += body_b.inverse_inertia*math_cross_vector2(radius_b, tangent_impulse);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Applies the impulses carried over from the previous step to the bodies of a manifold,
+    /// and finds how fast each contact point should bounce back
+    fn warm_start_physics_impulses(manifold: &mut PhysicsManifoldData<MAX_VERTICES, CIRCLE_VERTICES>) -> Result<(), PhysicsStepError> {
+        let mut body_a = manifold.body_a.write()?;
+        let mut body_b = manifold.body_b.write()?;
+
+        let tangent = Vector2 { x: manifold.normal.y, y: -manifold.normal.x };
+
+        for i in 0..manifold.contacts_count as usize {
+            let radius_a = manifold.contacts[i] - body_a.position;
+            let radius_b = manifold.contacts[i] - body_b.position;
+
+            // Bounce back with the speed the contact point hit with
+            let contact_velocity = contact_relative_velocity(&body_a, &body_b, radius_a, radius_b).dot(manifold.normal);
+            manifold.velocity_biases[i] = -manifold.restitution*contact_velocity.min(0.0);
+
+            manifold.normal_impulse += manifold.normal_impulses[i];
+            let impulse = manifold.normal*manifold.normal_impulses[i] + tangent*manifold.tangent_impulses[i];
+            apply_contact_impulse(&mut body_a, &mut body_b, radius_a, radius_b, impulse);
+        }
+
+        Ok(())
+    }
+
+    /// Integrates physics collisions impulses to solve collisions, accumulating the impulses of each contact point
+    /// so that they can be clamped to the total instead of to each iteration
+    fn integrate_accumulated_impulses(manifold: &mut PhysicsManifoldData<MAX_VERTICES, CIRCLE_VERTICES>) -> Result<(), PhysicsStepError> {
+        let mut body_a = manifold.body_a.write()?;
+        let mut body_b = manifold.body_b.write()?;
+
+        if (body_a.inverse_mass + body_b.inverse_mass).abs() <= Real::EPSILON {
+            return Ok(());
+        }
+
+        let tangent = Vector2 { x: manifold.normal.y, y: -manifold.normal.x };
+
+        for i in 0..manifold.contacts_count as usize {
+            let radius_a = manifold.contacts[i] - body_a.position;
+            let radius_b = manifold.contacts[i] - body_b.position;
+
+            // Push the contact point apart until it moves at the bounce velocity, without ever pulling it together
+            let contact_velocity = contact_relative_velocity(&body_a, &body_b, radius_a, radius_b).dot(manifold.normal);
+            let normal_mass = contact_inverse_mass(&body_a, &body_b, radius_a, radius_b, manifold.normal);
+            if normal_mass <= 0.0 {
+                continue;
+            }
+            let impulse = (manifold.velocity_biases[i] - contact_velocity)/normal_mass;
+
+            let previous_impulse = manifold.normal_impulses[i];
+            manifold.normal_impulses[i] = (previous_impulse + impulse).max(0.0);
+            let impulse = manifold.normal_impulses[i] - previous_impulse;
+            manifold.normal_impulse += impulse;
+            apply_contact_impulse(&mut body_a, &mut body_b, radius_a, radius_b, manifold.normal*impulse);
+
+            // Apply coulumb's law: the contact point sticks under static friction, and slides with dynamic friction otherwise
+            let tangent_velocity = (contact_relative_velocity(&body_a, &body_b, radius_a, radius_b) + manifold.surface_velocity).dot(tangent);
+            let tangent_mass = contact_inverse_mass(&body_a, &body_b, radius_a, radius_b, tangent);
+            let impulse_tangent = -tangent_velocity/tangent_mass;
+
+            let previous_tangent_impulse = manifold.tangent_impulses[i];
+            let mut tangent_impulse = previous_tangent_impulse + impulse_tangent;
+            if tangent_impulse.abs() > manifold.static_friction*manifold.normal_impulses[i] {
+                let max_friction = manifold.dynamic_friction*manifold.normal_impulses[i];
+                tangent_impulse = tangent_impulse.clamp(-max_friction, max_friction);
+            }
+            manifold.tangent_impulses[i] = tangent_impulse;
+            apply_contact_impulse(&mut body_a, &mut body_b, radius_a, radius_b, tangent*(tangent_impulse - previous_tangent_impulse));
+        }
+
+        Ok(())
+    }
+
+    /// Calculates the world space anchors, axis and effective mass of a joint for the current step
+    fn initialize_joint(joint: &mut PhysicsJointData<MAX_VERTICES, CIRCLE_VERTICES>, delta_time: f64) -> Result<(), PhysicsStepError> {
+        if let PhysicsJointKind::Target { max_force, frequency, damping_ratio } = joint.kind {
+            return Self::initialize_target_joint(joint, delta_time, max_force, frequency, damping_ratio);
+        }
+
+        // Read the bodies gears are mounted on before locking the joint's own bodies
+        let (frame_angle, frame_speed) = match joint.kind {
+            PhysicsJointKind::Gear { ratio, .. } => Self::gear_frame(joint, ratio)?,
+            _ => (0.0, 0.0),
+        };
+
+        let body_a = joint.body_a.read()?;
+        let Some(body_b) = &joint.body_b else { return Ok(()) };
+        let body_b = body_b.read()?;
+
+        let (inverse_mass_a, inverse_inertia_a) = joint_inverse_mass(&body_a);
+        let (inverse_mass_b, inverse_inertia_b) = joint_inverse_mass(&body_b);
+
+        let solver = &mut joint.solver;
+        *solver = JointSolverData::default();
+        solver.radius_a = Mat2::radians(body_a.orient).multiply_vector2(joint.local_anchor_a);
+        solver.radius_b = Mat2::radians(body_b.orient).multiply_vector2(joint.local_anchor_b);
+
+        let (radius_a, radius_b) = (solver.radius_a, solver.radius_b);
+        let delta = (body_b.position + radius_b) - (body_a.position + radius_a);
+
+        match joint.kind {
+            PhysicsJointKind::Distance { length, stiffness } => {
+                let distance = delta.length_sqr().sqrt();
+
+                // The direction is undefined while the anchors overlap
+                if distance <= Real::EPSILON || stiffness <= 0.0 {
+                    return Ok(());
+                }
+                solver.axis = delta*(1.0/distance);
+
+                let inverse_mass_sum = joint_axis_inverse_mass((inverse_mass_a, inverse_inertia_a), (inverse_mass_b, inverse_inertia_b), radius_a, radius_b, solver.axis);
+                if inverse_mass_sum <= 0.0 {
+                    return Ok(());
+                }
+
+                // A less stiff joint gives way to part of the impulse it would need to stay rigid
+                solver.gamma = inverse_mass_sum*(1.0 - stiffness)/stiffness;
+                solver.axial_mass = 1.0/(inverse_mass_sum + solver.gamma);
+                solver.axial_bias = stiffness*JOINT_BAUMGARTE*(distance - length)/delta_time as Real;
+            }
+
+            PhysicsJointKind::Rope { .. } => {
+                let distance = delta.length_sqr().sqrt();
+
+                // The direction is undefined while the anchors overlap, but the rope can't be taut then anyway
+                if distance <= Real::EPSILON {
+                    return Ok(());
+                }
+                solver.axis = delta*(1.0/distance);
+                solver.translation = distance;
+
+                let inverse_mass_sum = joint_axis_inverse_mass((inverse_mass_a, inverse_inertia_a), (inverse_mass_b, inverse_inertia_b), radius_a, radius_b, solver.axis);
+                solver.axial_mass = if inverse_mass_sum > 0.0 { 1.0/inverse_mass_sum } else { 0.0 };
+            }
+
+            PhysicsJointKind::Pulley { ground_anchor_a, ground_anchor_b, length, ratio } => {
+                let (length_a, side_mass_a) = pulley_side(solver, &body_a, ground_anchor_a, false);
+                let (length_b, side_mass_b) = pulley_side(solver, &body_b, ground_anchor_b, true);
+
+                let inverse_mass_sum = side_mass_a + ratio*ratio*side_mass_b;
+                solver.axial_mass = if inverse_mass_sum > 0.0 { 1.0/inverse_mass_sum } else { 0.0 };
+                solver.translation = length_a + ratio*length_b;
+                solver.axial_bias = JOINT_BAUMGARTE*(solver.translation - length)/delta_time as Real;
+            }
+
+            PhysicsJointKind::Spring { rest_length, stiffness, damping } => {
+                let distance = delta.length_sqr().sqrt();
+                let dt = delta_time as Real;
+
+                // A spring with neither stiffness nor damping applies no force
+                if distance <= Real::EPSILON || stiffness*dt + damping <= 0.0 {
+                    return Ok(());
+                }
+                solver.axis = delta*(1.0/distance);
+
+                let inverse_mass_sum = joint_axis_inverse_mass((inverse_mass_a, inverse_inertia_a), (inverse_mass_b, inverse_inertia_b), radius_a, radius_b, solver.axis);
+                if inverse_mass_sum <= 0.0 {
+                    return Ok(());
+                }
+
+                // Solve the spring implicitly as a soft constraint, which stays stable at any stiffness and time step
+                solver.gamma = 1.0/(dt*(damping + dt*stiffness));
+                solver.axial_mass = 1.0/(inverse_mass_sum + solver.gamma);
+                solver.axial_bias = (distance - rest_length)*dt*stiffness*solver.gamma;
+            }
+
+            PhysicsJointKind::Revolute { reference_angle, .. } |
+            PhysicsJointKind::Weld { reference_angle } => {
+                let inverse_mass_sum = inverse_mass_a + inverse_mass_b;
+                let k = Mat2 {
+                    m00: inverse_mass_sum + inverse_inertia_a*radius_a.y*radius_a.y + inverse_inertia_b*radius_b.y*radius_b.y,
+                    m01: -inverse_inertia_a*radius_a.x*radius_a.y - inverse_inertia_b*radius_b.x*radius_b.y,
+                    m10: -inverse_inertia_a*radius_a.x*radius_a.y - inverse_inertia_b*radius_b.x*radius_b.y,
+                    m11: inverse_mass_sum + inverse_inertia_a*radius_a.x*radius_a.x + inverse_inertia_b*radius_b.x*radius_b.x,
+                };
+                solver.point_mass = mat2_inverse(k);
+                solver.point_bias = delta*(JOINT_BAUMGARTE/delta_time as Real);
+
+                initialize_joint_angle(solver, inverse_inertia_a + inverse_inertia_b, body_b.orient - body_a.orient - reference_angle, delta_time);
+            }
+
+            PhysicsJointKind::Prismatic { local_axis, reference_angle, .. } => {
+                let axis = Mat2::radians(body_a.orient).multiply_vector2(local_axis);
+                let perpendicular = Vector2 { x: -axis.y, y: axis.x };
+                solver.axis = axis;
+                solver.lever_a = radius_a + delta;
+                solver.translation = delta.dot(axis);
+
+                let axial_mass_sum = joint_axis_inverse_mass((inverse_mass_a, inverse_inertia_a), (inverse_mass_b, inverse_inertia_b), solver.lever_a, radius_b, axis);
+                solver.axial_mass = if axial_mass_sum > 0.0 { 1.0/axial_mass_sum } else { 0.0 };
+
+                let perpendicular_mass_sum = joint_axis_inverse_mass((inverse_mass_a, inverse_inertia_a), (inverse_mass_b, inverse_inertia_b), solver.lever_a, radius_b, perpendicular);
+                solver.perpendicular_mass = if perpendicular_mass_sum > 0.0 { 1.0/perpendicular_mass_sum } else { 0.0 };
+                solver.perpendicular_bias = JOINT_BAUMGARTE*delta.dot(perpendicular)/delta_time as Real;
+
+                initialize_joint_angle(solver, inverse_inertia_a + inverse_inertia_b, body_b.orient - body_a.orient - reference_angle, delta_time);
+            }
+
+            PhysicsJointKind::Gear { ratio, reference } => {
+                initialize_joint_angle(solver, inverse_inertia_a + ratio*ratio*inverse_inertia_b, body_a.orient + ratio*body_b.orient - frame_angle - reference, delta_time);
+                solver.frame_speed = frame_speed;
+            }
+
+            PhysicsJointKind::Target { .. } => unreachable!("target joints are initialized separately"),
+        }
+
+        Ok(())
+    }
+
+    /// Returns the orientation and angular velocity of the bodies the linked joints of a gear are mounted on, weighted by the ratio
+    fn gear_frame(joint: &PhysicsJointData<MAX_VERTICES, CIRCLE_VERTICES>, ratio: Real) -> Result<(Real, Real), PhysicsStepError> {
+        let Some([joint_a, joint_b]) = &joint.linked_joints else { return Ok((0.0, 0.0)) };
+
+        let frame_a = joint_a.read()?.body_a.clone();
+        let frame_b = joint_b.read()?.body_a.clone();
+        let (orient_a, speed_a) = frame_a.read().map(|frame| (frame.orient, frame.angular_velocity))?;
+        let (orient_b, speed_b) = frame_b.read().map(|frame| (frame.orient, frame.angular_velocity))?;
+
+        Ok((orient_a + ratio*orient_b, speed_a + ratio*speed_b))
+    }
+
+    /// Calculates the soft constraint that pulls the body of a target joint towards its target for the current step
+    fn initialize_target_joint(joint: &mut PhysicsJointData<MAX_VERTICES, CIRCLE_VERTICES>, delta_time: f64, max_force: Real, frequency: Real, damping_ratio: Real) -> Result<(), PhysicsStepError> {
+        let body = joint.body_a.read()?;
+        let (inverse_mass, inverse_inertia) = joint_inverse_mass(&body);
+
+        let solver = &mut joint.solver;
+        *solver = JointSolverData::default();
+        if inverse_mass <= 0.0 {
+            return Ok(());
+        }
+
+        let radius = Mat2::radians(body.orient).multiply_vector2(joint.local_anchor_a);
+        solver.radius_a = radius;
+
+        // Behave like a damped spring, converting the frequency from seconds to the milliseconds used by the time step
+        let dt = delta_time as Real;
+        let omega = 2.0*real_consts::PI*frequency/1000.0;
+        let stiffness = body.mass*omega*omega;
+        let damping = 2.0*body.mass*damping_ratio*omega;
+        let softness = dt*(damping + dt*stiffness);
+        solver.gamma = if softness > 0.0 { 1.0/softness } else { 0.0 };
+
+        let k = Mat2 {
+            m00: inverse_mass + inverse_inertia*radius.y*radius.y + solver.gamma,
+            m01: -inverse_inertia*radius.x*radius.y,
+            m10: -inverse_inertia*radius.x*radius.y,
+            m11: inverse_mass + inverse_inertia*radius.x*radius.x + solver.gamma,
+        };
+        solver.point_mass = mat2_inverse(k);
+        solver.point_bias = (body.position + radius - joint.local_anchor_b)*(dt*stiffness*solver.gamma);
+        solver.max_impulse = max_force*dt;
+
+        Ok(())
+    }
+
+    /// Applies the impulses needed to satisfy a joint's velocity constraints
+    fn integrate_joint_impulses(joint: &mut PhysicsJointData<MAX_VERTICES, CIRCLE_VERTICES>, delta_time: f64) -> Result<(), PhysicsStepError> {
+        let mut body_a = joint.body_a.write()?;
+        let solver = &mut joint.solver;
+        let dt = delta_time as Real;
+
+        let Some(body_b) = &joint.body_b else {
+            if let PhysicsJointKind::Target { .. } = joint.kind {
+                solve_target_joint(solver, &mut body_a);
+            }
+            return Ok(());
+        };
+        let mut body_b = body_b.write()?;
+
+        match joint.kind {
+            PhysicsJointKind::Distance { .. } | PhysicsJointKind::Spring { .. } => {
+                if solver.axial_mass == 0.0 {
+                    return Ok(());
+                }
+
+                let relative_velocity = anchor_relative_velocity(&body_a, &body_b, solver.radius_a, solver.radius_b).dot(solver.axis);
+
+                let impulse = -solver.axial_mass*(relative_velocity + solver.axial_bias + solver.gamma*solver.axial_impulse);
+                solver.axial_impulse += impulse;
+                apply_joint_impulse_pair(&mut body_a, &mut body_b, solver.axis*impulse, solver.radius_a, solver.radius_b);
+            }
+
+            PhysicsJointKind::Rope { max_length } => {
+                if solver.axial_mass == 0.0 {
+                    return Ok(());
+                }
+
+                // Only ever pull the anchors together, once they are about to get too far apart
+                let relative_speed = anchor_relative_velocity(&body_a, &body_b, solver.radius_a, solver.radius_b).dot(solver.axis);
+                let old_impulse = solver.upper_impulse;
+                solver.upper_impulse = limit_impulse(old_impulse, solver.axial_mass, max_length - solver.translation, -relative_speed, dt);
+                apply_joint_impulse_pair(&mut body_a, &mut body_b, solver.axis*(old_impulse - solver.upper_impulse), solver.radius_a, solver.radius_b);
+            }
+
+            PhysicsJointKind::Pulley { ratio, .. } => {
+                if solver.axial_mass == 0.0 {
+                    return Ok(());
+                }
+
+                // Lengthening one side shortens the other
+                let speed_a = (body_a.velocity + math_cross(body_a.angular_velocity, solver.radius_a)).dot(solver.axis);
+                let speed_b = (body_b.velocity + math_cross(body_b.angular_velocity, solver.radius_b)).dot(solver.axis_b);
+
+                let impulse = -solver.axial_mass*(speed_a + ratio*speed_b + solver.axial_bias);
+                solver.axial_impulse += impulse;
+                apply_joint_impulse(&mut body_a, solver.axis*impulse, solver.radius_a);
+                apply_joint_impulse(&mut body_b, solver.axis_b*(ratio*impulse), solver.radius_b);
+            }
+
+            PhysicsJointKind::Revolute { limits, motor, .. } => solve_revolute_joint(solver, limits, motor, &mut body_a, &mut body_b, dt),
+
+            PhysicsJointKind::Prismatic { limits, motor, .. } => solve_prismatic_joint(solver, limits, motor, &mut body_a, &mut body_b, dt),
+
+            PhysicsJointKind::Weld { .. } => {
+                // Keep the relative angle fixed
+                solve_joint_angle_lock(solver, &mut body_a, &mut body_b);
+
+                // Keep the anchors together
+                solve_joint_point(solver, &mut body_a, &mut body_b);
+            }
+
+            PhysicsJointKind::Gear { ratio, .. } => {
+                if solver.angular_mass == 0.0 {
+                    return Ok(());
+                }
+
+                let (_, inverse_inertia_a) = joint_inverse_mass(&body_a);
+                let (_, inverse_inertia_b) = joint_inverse_mass(&body_b);
+                let relative_speed = body_a.angular_velocity + ratio*body_b.angular_velocity - solver.frame_speed;
+                let impulse = -solver.angular_mass*(relative_speed + solver.angular_bias);
+                solver.angular_impulse += impulse;
+
+                body_a.angular_velocity += inverse_inertia_a*impulse;
+                body_b.angular_velocity += inverse_inertia_b*ratio*impulse;
+            }
+
+            PhysicsJointKind::Target { .. } => unreachable!("target joints are attached to the world"),
+        }
+
+        Ok(())
+    }
+
+    /// Returns the IDs of the ignored pairs of bodies (lowest first), forgetting pairs where either body was destroyed since its ID may be reused
+    fn ignored_pair_ids(&mut self) -> Result<Vec<(u32, u32)>, PhysicsStepError> {
+        let mut ignored_ids = Vec::with_capacity(self.ignored_pairs.len());
+        let mut i = 0;
+        while i < self.ignored_pairs.len() {
+            let [body_a, body_b] = &self.ignored_pairs[i];
+            let ids = {
+                let (body_a, body_b) = (body_a.read()?, body_b.read()?);
+                (body_a.is_simulating && body_b.is_simulating).then(|| (body_a.id.min(body_b.id), body_a.id.max(body_b.id)))
+            };
+
+            if let Some(ids) = ids {
+                ignored_ids.push(ids);
+                i += 1;
+            } else {
+                self.ignored_pairs.swap_remove(i);
+            }
+        }
+
+        Ok(ignored_ids)
+    }
+
+    /// Returns true if the collision filters of the bodies or an ignored pair keep them from colliding
+    fn is_pair_filtered(body_a: &PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>, body_b: &PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>, ignored_ids: &[(u32, u32)]) -> bool {
+        !body_a.should_collide(body_b) || ignored_ids.contains(&(body_a.id.min(body_b.id), body_a.id.max(body_b.id)))
+    }
+
+    /// Returns the indices of every pair of physics bodies that might be touching, lowest first and in ascending order
+    fn candidate_pairs(&self) -> Result<Vec<(usize, usize)>, PhysicsStepError> {
+        trace_span!("broadphase");
+        if let Some(body_tree) = &self.body_tree {
+            let mut pairs = body_tree.overlapping_pairs();
+            pairs.sort_unstable();
+            pairs.dedup();
+            return Ok(pairs);
+        }
+
+        let count = self.bodies.len();
+        let Broadphase::Grid { cell_size } = self.broadphase else {
+            // Pairs whose bounds don't touch can't collide, so they skip the narrowphase
+            let bounds = self.bodies.iter()
+                .map(|body| Ok(body.read()?.bounds()))
+                .collect::<Result<Vec<_>, PhysicsStepError>>()?;
+            #[cfg(feature = "simd")]
+            return Ok(overlapping_pairs_x4(&bounds));
+            #[cfg(not(feature = "simd"))]
+            return Ok(overlapping_pairs(&bounds));
+        };
+
+        let mut cells: HashMap<(i32, i32), Vec<usize>> = HashMap::new();
+        let mut large_bodies = Vec::new();
+        for (i, body) in self.bodies.iter().enumerate() {
+            let bounds = body.read()?.bounds();
+            let (min_x, min_y) = ((bounds.min.x/cell_size).floor(), (bounds.min.y/cell_size).floor());
+            let (max_x, max_y) = ((bounds.max.x/cell_size).floor(), (bounds.max.y/cell_size).floor());
+
+            // Also catches bodies with infinite bounds
+            let cells_count = (max_x - min_x + 1.0)*(max_y - min_y + 1.0);
+            if cells_count.is_nan() || cells_count > GRID_MAX_BODY_CELLS {
+                large_bodies.push(i);
+                continue;
+            }
+
+            for x in (min_x as i32)..=(max_x as i32) {
+                for y in (min_y as i32)..=(max_y as i32) {
+                    cells.entry((x, y)).or_default().push(i);
+                }
+            }
+        }
+
+        // Bodies are added to each cell in ascending order
+        let mut pairs = Vec::new();
+        for cell in cells.values() {
+            for (k, &i) in cell.iter().enumerate() {
+                pairs.extend(cell[(k + 1)..].iter().map(|&j| (i, j)));
+            }
+        }
+        for &i in &large_bodies {
+            pairs.extend((0..count).filter(|&j| j != i).map(|j| (i.min(j), i.max(j))));
+        }
+
+        // Keep the same order as testing every pair, so both broadphases give the same results
+        pairs.sort_unstable();
+        pairs.dedup();
+        Ok(pairs)
+    }
+
+    /// Finds the collisions between every pair of physics bodies, and the overlaps of sensors
+    fn create_manifolds(&mut self) -> Result<(), PhysicsStepError> {
+        trace_span!("narrowphase");
+        let ignored_ids = self.ignored_pair_ids()?;
+        let mut sensor_overlaps = Vec::new();
+
+        let broadphase_start = Instant::now();
+        let pairs = self.candidate_pairs()?;
+        self.stats.broadphase_duration += broadphase_start.elapsed();
+        self.stats.broadphase_pairs += pairs.len();
+
+        let narrowphase_start = Instant::now();
+        for (i, j) in pairs {
+            let body_a = self.bodies[i].clone();
+            let body_b = self.bodies[j].clone();
+            if Self::is_pair_filtered(&*body_a.read()?, &*body_b.read()?, &ignored_ids) {
+                continue;
+            }
+
+            if Self::detect_sensor_overlap(&body_a, &body_b, &mut sensor_overlaps)? {
+                continue;
+            }
+
+            if (body_a.read()?.inverse_mass == 0.0) &&
+               (body_b.read()?.inverse_mass == 0.0) {
+                continue;
+            }
+
+            // Sleeping bodies can only be disturbed by bodies that are awake
+            if Self::is_pair_asleep(&*body_a.read()?, &*body_b.read()?) {
+                continue;
+            }
+
+            if body_a.read()?.is_multi_part() || body_b.read()?.is_multi_part() {
+                self.create_part_manifolds(&body_a, &body_b)?;
+                continue;
+            }
+
+            let manifold = self.create_physics_manifold(body_a.clone(), body_b.clone()).ok_or(PhysicsStepError::OutOfIDs)?;
+            manifold.solve(&mut *(body_a.write()?), &mut *(body_b.write()?));
+
+            if manifold.contacts_count > 0 {
+                let manifold = manifold.clone();
+                // Create a new manifold with same information as previously solved manifold and add it to the manifolds pool last slot
+                let new_manifold = self.create_physics_manifold(body_a, body_b).ok_or(PhysicsStepError::OutOfIDs)?;
+                new_manifold.penetration = manifold.penetration;
+                new_manifold.normal = manifold.normal;
+                new_manifold.contacts[0] = manifold.contacts[0];
+                new_manifold.contacts[1] = manifold.contacts[1];
+                new_manifold.contacts_count = manifold.contacts_count;
+                new_manifold.restitution = manifold.restitution;
+                new_manifold.dynamic_friction = manifold.dynamic_friction;
+                new_manifold.static_friction = manifold.static_friction;
+            }
+        }
+
+        self.update_sensor_events(sensor_overlaps);
+        self.stats.narrowphase_duration += narrowphase_start.elapsed();
+
+        Ok(())
+    }
+
+    /// Returns true if neither body of a pair can move, and at least one of them is asleep
+    fn is_pair_asleep(body_a: &PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>, body_b: &PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>) -> bool {
+        (body_a.is_sleeping || body_b.is_sleeping) && body_a.is_inert() && body_b.is_inert() &&
+        !body_a.is_driving() && !body_b.is_driving()
+    }
+
+    /// Returns true if none of the bodies of a joint can move, and at least one of them is asleep
+    fn is_joint_asleep(joint: &PhysicsJointData<MAX_VERTICES, CIRCLE_VERTICES>) -> Result<bool, PhysicsStepError> {
+        let body_a = joint.body_a.read()?;
+        Ok(match &joint.body_b {
+            Some(body_b) => Self::is_pair_asleep(&body_a, &*body_b.read()?),
+            None => body_a.is_sleeping,
+        })
+    }
+
+    /// Returns how many substeps in a row a body has to rest before it falls asleep
+    fn sleep_substeps(&self) -> u32 {
+        self.sleep_steps.saturating_mul(self.substeps.get().try_into().unwrap_or(u32::MAX))
+    }
+
+    /// Wakes sleeping bodies that were given a velocity, force or torque since the last step,
+    /// and the bodies touching or joined to bodies that were moved by hand or given a new shape
+    fn wake_disturbed_bodies(&self) -> Result<(), PhysicsStepError> {
+        if self.sleep_steps == 0 {
+            return Ok(());
+        }
+
+        let mut disturbed_ids = Vec::new();
+        for body in &self.bodies {
+            let mut body = body.write()?;
+            let is_disturbed = body.has_new_shape || body.settled_transform.is_some_and(|transform| transform != (body.position, body.orient));
+            if is_disturbed {
+                disturbed_ids.push(body.id);
+            }
+            body.has_new_shape = false;
+
+            if body.is_sleeping &&
+               (is_disturbed || body.velocity != Vector2::zero() || body.force != Vector2::zero() || body.angular_velocity != 0.0 || body.torque != 0.0)
+            {
+                body.wake_up();
+            }
+        }
+        self.wake_partners(&disturbed_ids);
+
+        Ok(())
+    }
+
+    /// Wakes the sleeping bodies that touched or are joined to any of the bodies with the IDs `ids`, since they may have been resting on them
+    ///
+    /// Waking spreads on through the sleeping bodies that touch or are joined to the woken ones, so that a whole stack wakes up together
+    fn wake_partners(&self, ids: &[u32]) {
+        if ids.is_empty() {
+            return;
+        }
+
+        let mut links: Vec<(u32, u32)> = self.touching_pairs.iter().map(|&(ids, _)| ids).collect();
+        for joint in &self.joints {
+            let joint = joint.borrow();
+            if let Some(body_b) = &joint.body_b {
+                links.push((joint.body_a.borrow().id, body_b.borrow().id));
+            }
+        }
+        let bodies: HashMap<u32, &Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>> = self.bodies.iter()
+            .map(|body| (body.borrow().id, body))
+            .collect();
+
+        // Only sleeping bodies pass waking on, so a body woken on a static floor doesn't wake everything else on that floor
+        let mut pending = ids.to_vec();
+        while let Some(id) = pending.pop() {
+            for &(id_a, id_b) in &links {
+                let partner_id = if id_a == id { id_b } else if id_b == id { id_a } else { continue };
+                if let Some(partner) = bodies.get(&partner_id) {
+                    let mut partner = partner.borrow_mut();
+                    if partner.is_sleeping {
+                        partner.wake_up();
+                        pending.push(partner_id);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Sorts the physics bodies into islands of bodies that touch each other or are connected by joints,
+    /// and orders the manifolds by island
+    fn build_islands(&mut self) -> Result<Vec<Island<MAX_VERTICES, CIRCLE_VERTICES>>, PhysicsStepError> {
+        trace_span!("islands");
+        let mut indices = HashMap::with_capacity(self.bodies.len());
+        let mut is_dynamic = Vec::with_capacity(self.bodies.len());
+        for (index, body) in self.bodies.iter().enumerate() {
+            let body = body.read()?;
+            indices.insert(body.id, index);
+            is_dynamic.push(body.inverse_mass != 0.0 && body.enabled);
+        }
+        let index_of = |body: &Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>| -> Result<usize, PhysicsStepError> {
+            let id = body.read()?.id;
+            Ok(indices[&id])
+        };
+
+        // Links between bodies, where the first body is the one that owns the link
+        let mut contact_links = Vec::with_capacity(self.contacts.len());
+        for manifold in &self.contacts {
+            let (index_a, index_b) = (index_of(&manifold.body_a)?, index_of(&manifold.body_b)?);
+            let is_touching = manifold.contacts_count > 0;
+            contact_links.push(if is_dynamic[index_a] { (index_a, index_b, is_touching) } else { (index_b, index_a, is_touching) });
+        }
+        let mut joint_links = Vec::with_capacity(self.joints.len());
+        for joint in &self.joints {
+            let joint = joint.read()?;
+            // Gears read the bodies of the joints they link, so those are part of the island too
+            let mut joint_bodies = vec![index_of(&joint.body_a)?];
+            if let Some(body_b) = &joint.body_b {
+                joint_bodies.push(index_of(body_b)?);
+            }
+            for link in joint.linked_joints.iter().flatten() {
+                joint_bodies.push(index_of(&link.read()?.body_a)?);
+            }
+            joint_bodies.sort_by_key(|&index| !is_dynamic[index]);
+            joint_links.push(joint_bodies);
+        }
+
+        // Static bodies don't pass movement on, so they stay in islands of their own
+        let mut groups: Vec<usize> = (0..self.bodies.len()).collect();
+        let find = |groups: &mut Vec<usize>, mut index: usize| {
+            while groups[index] != index {
+                groups[index] = groups[groups[index]];
+                index = groups[index];
+            }
+            index
+        };
+        let links = contact_links.iter().filter(|(_, _, is_touching)| *is_touching).map(|&(index_a, index_b, _)| (index_a, index_b))
+            .chain(joint_links.iter().flat_map(|joint_bodies| joint_bodies.iter().skip(1).map(|&index| (joint_bodies[0], index))));
+        for (index_a, index_b) in links {
+            if is_dynamic[index_a] && is_dynamic[index_b] {
+                let (group_a, group_b) = (find(&mut groups, index_a), find(&mut groups, index_b));
+                groups[group_a] = group_b;
+            }
+        }
+
+        let mut island_of_group = vec![usize::MAX; groups.len()];
+        let mut islands: Vec<Island<MAX_VERTICES, CIRCLE_VERTICES>> = Vec::new();
+        for (index, body) in self.bodies.iter().enumerate() {
+            let group = find(&mut groups, index);
+            if island_of_group[group] == usize::MAX {
+                island_of_group[group] = islands.len();
+                islands.push(Island { is_static: !is_dynamic[index], bodies: Vec::new(), contacts: 0..0, joints: Vec::new() });
+            }
+            islands[island_of_group[group]].bodies.push(body.clone());
+        }
+        let mut island_of = |index: usize| island_of_group[find(&mut groups, index)];
+
+        for (joint, joint_bodies) in self.joints.iter().zip(&joint_links) {
+            islands[island_of(joint_bodies[0])].joints.push(joint.clone());
+        }
+
+        // Keep the order of the manifolds within each island
+        let mut contacts: Vec<(usize, PhysicsManifoldData<MAX_VERTICES, CIRCLE_VERTICES>)> = self.contacts.drain(..)
+            .zip(&contact_links)
+            .map(|(manifold, &(owner, _, _))| (island_of(owner), manifold))
+            .collect();
+        contacts.sort_by_key(|(island, _)| *island);
+
+        let mut start = 0;
+        for (index, island) in islands.iter_mut().enumerate() {
+            let count = contacts[start..].iter().take_while(|(contact_island, _)| *contact_island == index).count();
+            island.contacts = start..(start + count);
+            start += count;
+        }
+        self.contacts.extend(contacts.into_iter().map(|(_, manifold)| manifold));
+
+        Ok(islands)
+    }
+
+    /// Solves the collisions and constraints of every island, since islands can't affect each other
+    #[cfg(not(feature = "parallel"))]
+    fn solve_islands(&mut self, islands: &[Island<MAX_VERTICES, CIRCLE_VERTICES>]) -> Result<(), PhysicsStepError> {
+        trace_span!("solve");
+        for island in islands {
+            Self::solve_island(island, &mut self.contacts[island.contacts.clone()], self.delta_time, self.collision_iterations, self.warm_starting)?;
+        }
+
+        Ok(())
+    }
+
+    /// Solves the collisions and constraints of every island, solving islands with moving bodies at the same time
+    /// since islands can't affect each other
+    #[cfg(feature = "parallel")]
+    fn solve_islands(&mut self, islands: &[Island<MAX_VERTICES, CIRCLE_VERTICES>]) -> Result<(), PhysicsStepError> {
+        trace_span!("solve");
+        let mut remaining = self.contacts.as_mut_slice();
+        let mut static_islands = Vec::new();
+        let mut moving_islands = Vec::new();
+        for island in islands {
+            let (contacts, rest) = remaining.split_at_mut(island.contacts.len());
+            remaining = rest;
+            if island.is_static {
+                static_islands.push((island, contacts));
+            } else {
+                moving_islands.push((island, contacts));
+            }
+        }
+
+        // Static bodies are shared between islands, so islands that lock more than one of them are solved one at a time
+        let (delta_time, iterations, warm_starting) = (self.delta_time, self.collision_iterations, self.warm_starting);
+        for (island, contacts) in static_islands {
+            Self::solve_island(island, contacts, delta_time, iterations, warm_starting)?;
+        }
+
+        moving_islands.into_par_iter()
+            .try_for_each(|(island, contacts)| Self::solve_island(island, contacts, delta_time, iterations, warm_starting))
+    }
+
+    /// Solves the collisions, joints and rotation limits of an island
+    fn solve_island(
+        island: &Island<MAX_VERTICES, CIRCLE_VERTICES>,
+        contacts: &mut [PhysicsManifoldData<MAX_VERTICES, CIRCLE_VERTICES>],
+        delta_time: f64,
+        iterations: usize,
+        warm_starting: bool,
+    ) -> Result<(), PhysicsStepError> {
+        let mut joints = Vec::with_capacity(island.joints.len());
+        for joint in &island.joints {
+            if !Self::is_joint_asleep(&*joint.read()?)? {
+                joints.push(joint);
+            }
+        }
+
+        let mut limited_bodies = Vec::new();
+        for body in &island.bodies {
+            let body_data = body.read()?;
+            if body_data.rotation_limits.is_some() && !body_data.is_inert() {
+                limited_bodies.push(body);
+            }
+        }
+
+        if contacts.is_empty() && joints.is_empty() && limited_bodies.is_empty() {
+            return Ok(());
+        }
+
+        if warm_starting {
+            for manifold in contacts.iter_mut() {
+                Self::warm_start_physics_impulses(manifold)?;
+            }
+        }
+
+        // Initialize joints to solve constraints
+        for joint in &joints {
+            Self::initialize_joint(&mut *joint.write()?, delta_time)?;
+        }
+
+        // Integrate physics collisions and joint impulses to solve collisions and constraints
+        for _ in 0..iterations {
+            for manifold in contacts.iter_mut() {
+                if warm_starting {
+                    Self::integrate_accumulated_impulses(manifold)?;
+                } else {
+                    Self::integrate_physics_impulses(manifold)?;
+                }
+            }
+
+            for joint in &joints {
+                Self::integrate_joint_impulses(&mut *joint.write()?, delta_time)?;
+            }
+
+            for body in &limited_bodies {
+                Self::integrate_rotation_limits(&mut *body.write()?, delta_time);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Wakes every sleeping body in an island with a body that is still moving
+    fn wake_touched_bodies(&self, islands: &[Island<MAX_VERTICES, CIRCLE_VERTICES>]) -> Result<(), PhysicsStepError> {
+        if self.sleep_steps == 0 {
+            return Ok(());
+        }
+
+        // Kinematic bodies and conveyors are in islands of their own, so they wake the bodies they touch directly
+        for manifold in self.contacts.iter().filter(|manifold| manifold.contacts_count > 0) {
+            let mut body_a = manifold.body_a.write()?;
+            let mut body_b = manifold.body_b.write()?;
+            if body_a.is_driving() && body_b.is_sleeping {
+                body_b.wake_up();
+            }
+            if body_b.is_driving() && body_a.is_sleeping {
+                body_a.wake_up();
+            }
+        }
+
+        for island in islands {
+            let mut is_moving = false;
+            for body in &island.bodies {
+                let body = body.read()?;
+                is_moving |= !body.is_inert() && body.resting_steps < self.sleep_substeps();
+            }
+
+            if is_moving {
+                for body in &island.bodies {
+                    let mut body = body.write()?;
+                    if body.is_sleeping {
+                        body.wake_up();
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Counts how long every body has been resting, and puts islands whose bodies have all been resting long enough to sleep
+    fn update_sleep(&self, islands: &[Island<MAX_VERTICES, CIRCLE_VERTICES>]) -> Result<(), PhysicsStepError> {
+        trace_span!("sleep");
+        if self.sleep_steps == 0 {
+            return Ok(());
+        }
+
+        for island in islands {
+            let mut can_sleep = true;
+            for body in &island.bodies {
+                let mut body = body.write()?;
+                if body.is_inert() {
+                    continue;
+                }
+
+                let is_resting = body.velocity.length_sqr() < self.sleep_velocity*self.sleep_velocity &&
+                    body.angular_velocity.abs() < self.sleep_angular_velocity;
+                body.resting_steps = if is_resting { body.resting_steps.saturating_add(1) } else { 0 };
+                can_sleep &= body.can_sleep && body.resting_steps >= self.sleep_substeps();
+            }
+
+            if can_sleep {
+                for body in &island.bodies {
+                    let mut body = body.write()?;
+                    if body.inverse_mass != 0.0 && body.enabled {
+                        body.is_sleeping = true;
+                        body.velocity = Vector2::zero();
+                        body.angular_velocity = 0.0;
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Pushes overlapping physics bodies apart once for every position iteration
+    fn correct_positions(&self) -> Result<(), PhysicsStepError> {
+        trace_span!("correct");
+        let mut start_positions = Vec::with_capacity(self.contacts.len());
+        for manifold in &self.contacts {
+            start_positions.push((manifold.body_a.read()?.position, manifold.body_b.read()?.position));
+        }
+
+        let mut penetrations = Vec::with_capacity(self.contacts.len());
+        for _ in 0..self.position_iterations {
+            // Earlier passes have already pushed the bodies apart by how far they moved along the normal
+            penetrations.clear();
+            for (manifold, &(start_a, start_b)) in self.contacts.iter().zip(&start_positions) {
+                let moved_a = manifold.body_a.read()?.position - start_a;
+                let moved_b = manifold.body_b.read()?.position - start_b;
+                penetrations.push(manifold.penetration - (moved_b - moved_a).dot(manifold.normal));
+            }
+
+            for (manifold, &penetration) in self.contacts.iter().zip(&penetrations) {
+                Self::correct_physics_positions(manifold, penetration, self.penetration_allowance, self.penetration_correction)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Gives every contact point the impulses found for it during the previous step, when warm starting
+    fn load_contact_impulses(&mut self) -> Result<(), PhysicsStepError> {
+        if self.warm_starting {
+            for manifold in &mut self.contacts {
+                let (body_a, body_b) = (manifold.body_a.read()?, manifold.body_b.read()?);
+                let is_stale = body_a.has_stale_contacts || body_b.has_stale_contacts;
+                for i in 0..manifold.contacts_count as usize {
+                    let (normal_impulse, tangent_impulse) = if is_stale {
+                        (0.0, 0.0)
+                    } else {
+                        self.contact_impulses.get(&(body_a.id, body_b.id, manifold.contact_ids[i])).copied().unwrap_or_default()
+                    };
+                    manifold.normal_impulses[i] = normal_impulse;
+                    manifold.tangent_impulses[i] = tangent_impulse;
+                }
+            }
+        }
+
+        // Impulses solved from here on fit the bodies again
+        for body in &self.bodies {
+            body.write()?.has_stale_contacts = false;
+        }
+
+        Ok(())
+    }
+
+    /// Remembers the impulses of every contact point for the next step, when warm starting
+    fn store_contact_impulses(&mut self) -> Result<(), PhysicsStepError> {
+        self.contact_impulses.clear();
+        if !self.warm_starting {
+            return Ok(());
+        }
+
+        for manifold in &self.contacts {
+            let (id_a, id_b) = (manifold.body_a.read()?.id, manifold.body_b.read()?.id);
+            for i in 0..manifold.contacts_count as usize {
+                self.contact_impulses.insert((id_a, id_b, manifold.contact_ids[i]), (manifold.normal_impulses[i], manifold.tangent_impulses[i]));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Lets the pre-solve callback cancel or modify every contact before it is solved
+    fn pre_solve_contacts(&mut self) {
+        let Some(pre_solve) = &mut self.pre_solve else {
+            return;
+        };
+
+        for manifold in self.contacts.iter_mut().filter(|manifold| manifold.contacts_count > 0) {
+            match pre_solve.call(&ContactInfo::from(&*manifold)) {
+                ContactResponse::Accept => {}
+                // A contact without points or penetration is neither solved nor corrected
+                ContactResponse::Cancel => {
+                    manifold.contacts_count = 0;
+                    manifold.penetration = 0.0;
+                }
+                ContactResponse::Modify { restitution, static_friction, dynamic_friction } => {
+                    manifold.restitution = restitution;
+                    manifold.static_friction = static_friction;
+                    manifold.dynamic_friction = dynamic_friction;
+                }
+            }
+        }
+    }
+
+    /// Emits events for the pairs of bodies that began, kept or stopped touching since the last step
+    fn update_collision_events(&mut self) -> Result<(), PhysicsStepError> {
+        // Bodies made of several parts can have several manifolds, but the first one stands for the pair and the impulses of all of them add up
+        let mut touching_pairs: Vec<((u32, u32), CollisionContact<MAX_VERTICES, CIRCLE_VERTICES>)> = Vec::new();
+        for manifold in self.contacts.iter().filter(|manifold| manifold.contacts_count > 0) {
+            let (id_a, id_b) = (manifold.body_a.read()?.id, manifold.body_b.read()?.id);
+            let ids = (id_a.min(id_b), id_a.max(id_b));
+            if let Some((_, contact)) = touching_pairs.iter_mut().find(|(touching_ids, _)| *touching_ids == ids) {
+                contact.normal_impulse += manifold.normal_impulse;
+                continue;
+            }
+
+            let point = if manifold.contacts_count > 1 { (manifold.contacts[0] + manifold.contacts[1])*0.5 } else { manifold.contacts[0] };
+            touching_pairs.push((ids, CollisionContact {
+                body_a: manifold.body_a.clone(),
+                body_b: manifold.body_b.clone(),
+                point,
+                normal: manifold.normal,
+                normal_impulse: manifold.normal_impulse,
+            }));
+        }
+
+        // A destroyed body's ID may already belong to a new body, so its contacts end regardless
+        let mut sleeping_pairs = Vec::new();
+        for (ids, contact) in &self.touching_pairs {
+            let (body_a, body_b) = (contact.body_a.read()?, contact.body_b.read()?);
+            let is_simulating = body_a.is_simulating && body_b.is_simulating;
+            if is_simulating && touching_pairs.iter().any(|(touching_ids, _)| touching_ids == ids) {
+                continue;
+            }
+
+            // Sleeping bodies aren't tested against each other, but they keep touching quietly until they wake up
+            if is_simulating && Self::is_pair_asleep(&body_a, &body_b) {
+                sleeping_pairs.push((*ids, contact.clone()));
+            } else {
+                self.collision_events.push((*ids, CollisionEvent::End(contact.clone())));
+            }
+        }
+
+        for (ids, contact) in &touching_pairs {
+            let previous = self.touching_pairs.iter().find(|(touching_ids, _)| touching_ids == ids);
+            let was_touching = match previous {
+                Some((_, previous)) => previous.body_a.read()?.is_simulating && previous.body_b.read()?.is_simulating,
+                None => false,
+            };
+
+            if was_touching {
+                self.collision_events.retain(|(event_ids, event)| event_ids != ids || !matches!(event, CollisionEvent::Persist(_)));
+                self.collision_events.push((*ids, CollisionEvent::Persist(contact.clone())));
+            } else {
+                self.collision_events.push((*ids, CollisionEvent::Begin(contact.clone())));
+            }
+        }
+
+        touching_pairs.append(&mut sleeping_pairs);
+        self.touching_pairs = touching_pairs;
+        Ok(())
+    }
+
+    /// Records the pair if exactly one of the bodies is a sensor and they overlap
+    ///
+    /// Returns true if either body is a sensor, in which case the pair doesn't collide
+    fn detect_sensor_overlap(
+        body_a: &Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>,
+        body_b: &Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>,
+        sensor_overlaps: &mut Vec<(Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>, Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>)>,
+    ) -> Result<bool, PhysicsStepError> {
+        let (data_a, data_b) = (body_a.read()?, body_b.read()?);
+        let (sensor, visitor) = match (data_a.is_sensor, data_b.is_sensor) {
+            (false, false) => return Ok(false),
+            (true, true) => return Ok(true),
+            (true, false) => (body_a, body_b),
+            (false, true) => (body_b, body_a),
+        };
+
+        let (bounds_a, bounds_b) = (data_a.bounds(), data_b.bounds());
+        if bounds_a.overlaps(&bounds_b) {
+            let proxies_b = data_b.convex_proxies(Some(bounds_a));
+            let is_overlapping = data_a.convex_proxies(Some(bounds_b)).iter()
+                .any(|proxy_a| proxies_b.iter().any(|proxy_b| proxy_a.overlaps(proxy_b)));
+
+            if is_overlapping {
+                sensor_overlaps.push((sensor.clone(), visitor.clone()));
+            }
+        }
+
+        Ok(true)
+    }
+
+    /// Emits events for the sensor overlaps that began or ended since the last step
+    fn update_sensor_events(&mut self, sensor_overlaps: Vec<(Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>, Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>)>) {
+        let pair_ids = |(sensor, visitor): &(Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>, Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>)| {
+            (sensor.borrow().id, visitor.borrow().id)
+        };
+        let current_ids: Vec<(u32, u32)> = sensor_overlaps.iter().map(pair_ids).collect();
+
+        // A destroyed body's ID may already belong to a new body, so its overlaps end regardless
+        let is_simulating = |(sensor, visitor): &(Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>, Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>)| {
+            sensor.borrow().is_simulating && visitor.borrow().is_simulating
+        };
+        let previous_ids: Vec<Option<(u32, u32)>> = self.sensor_overlaps.iter()
+            .map(|pair| is_simulating(pair).then(|| pair_ids(pair)))
+            .collect();
+
+        for (pair, ids) in self.sensor_overlaps.iter().zip(&previous_ids) {
+            if ids.is_none_or(|ids| !current_ids.contains(&ids)) {
+                let (sensor, visitor) = pair.clone();
+                self.sensor_events.push(SensorEvent::End { sensor, visitor });
+            }
+        }
+
+        for (pair, ids) in sensor_overlaps.iter().zip(&current_ids) {
+            if !previous_ids.contains(&Some(*ids)) {
+                let (sensor, visitor) = pair.clone();
+                self.sensor_events.push(SensorEvent::Begin { sensor, visitor });
+            }
+        }
+
+        self.sensor_overlaps = sensor_overlaps;
+    }
+
+    /// Keeps the angular velocity of a physics body from turning it past its rotation limits
+    fn integrate_rotation_limits(body: &mut PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>, delta_time: f64) {
+        let Some(JointLimits { lower, upper }) = body.rotation_limits else { return };
+        if !body.enabled || body.freeze_orient || body.is_sleeping {
+            return;
+        }
+
+        // Only the body itself can turn, so limiting its speed directly is the same as applying the limit impulse
+        let dt = delta_time as Real;
+        let limit_speed = |error: Real| if error > 0.0 { error/dt } else { JOINT_BAUMGARTE*error/dt };
+        body.angular_velocity = body.angular_velocity
+            .max(-limit_speed(body.orient - lower))
+            .min(limit_speed(upper - body.orient));
+    }
+
+    /// Integrates physics velocity into position and forces
+    fn integrate_physics_velocity(body: &mut PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>, delta_time: f64, gravity: &GravityMode) {
+        if !(body.enabled || body.is_kinematic) || body.is_sleeping {
+            return;
+        }
+
+        if let Some(max_linear_velocity) = body.max_linear_velocity {
+            let speed = body.velocity.length_sqr().sqrt();
+            if speed > max_linear_velocity {
+                body.velocity *= max_linear_velocity/speed;
+            }
+        }
+        if let Some(max_angular_velocity) = body.max_angular_velocity {
+            body.angular_velocity = body.angular_velocity.clamp(-max_angular_velocity, max_angular_velocity);
+        }
+
+        body.position.x += (real_to_f64(body.velocity.x)*delta_time) as Real;
+        body.position.y += (real_to_f64(body.velocity.y)*delta_time) as Real;
+
+        if !body.freeze_orient {
+            body.orient += (real_to_f64(body.angular_velocity)*delta_time) as Real;
+        }
+
+        let orient = body.orient;
+        if let PHYSICS_POLYGON { transform, .. } = &mut body.shape {
+            transform.set(orient);
+        }
+
+        Self::integrate_physics_forces(body, delta_time, gravity);
+    }
+
+    /// Corrects physics bodies positions based on manifolds collision information
+    fn correct_physics_positions(manifold: &PhysicsManifoldData<MAX_VERTICES, CIRCLE_VERTICES>, penetration: Real, penetration_allowance: Real, penetration_correction: Real) -> Result<(), PhysicsStepError> {
+        let mut body_a = manifold.body_a.write()?;
+        let mut body_b = manifold.body_b.write()?;
+
+        let inverse_mass_sum = body_a.inverse_mass + body_b.inverse_mass;
+        if inverse_mass_sum == 0.0 {
+            return Err(PhysicsStepError::DivByZero);
+        }
+
+        let correction = Vector2 {
+            x: ((penetration - penetration_allowance).max(0.0)/inverse_mass_sum)*manifold.normal.x*penetration_correction,
+            y: ((penetration - penetration_allowance).max(0.0)/inverse_mass_sum)*manifold.normal.y*penetration_correction,
+        };
+
+        if body_a.enabled {
+            body_a.position.x -= correction.x*body_a.inverse_mass;
+            body_a.position.y -= correction.y*body_a.inverse_mass;
+        }
+
+        if body_b.enabled {
+            body_b.position.x += correction.x*body_b.inverse_mass;
+            body_b.position.y += correction.y*body_b.inverse_mass;
+        }
+
+        Ok(())
+    }
+
+    /// Initializes hi-resolution MONOTONIC timer
+    fn init_timer(&mut self) {
+        self.base_time = Instant::now(); // Get MONOTONIC clock time offset
+        self.start_time = self.get_curr_time(); // Get current time
+    }
+
+    /// Get current time in milliseconds
+    fn get_curr_time(&self) -> f64 {
+        let duration = self.base_time.elapsed();
+        duration.as_secs_f64() * 1_000.0
+    }
+}
+
+impl<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize> PhysicsManifoldData<MAX_VERTICES, CIRCLE_VERTICES> {
+    /// Solves a created physics manifold between two physics bodies
+    fn solve(&mut self, body_a: &mut PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>, body_b: &mut PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>) {
+        let is_rounded = |shape: &PhysicsShape<MAX_VERTICES, CIRCLE_VERTICES>| matches!(shape, PHYSICS_POLYGON { vertex_data, .. } if vertex_data.radius > 0.0);
+
+        match (&body_a.shape, &body_b.shape) {
+            (PHYSICS_COMPOUND { .. } | PHYSICS_CHAIN { .. } | PHYSICS_HEIGHTFIELD { .. }, _) |
+            (_, PHYSICS_COMPOUND { .. } | PHYSICS_CHAIN { .. } | PHYSICS_HEIGHTFIELD { .. }) => unreachable!("multi part bodies should be solved per part"),
+            // The sharp polygon solvers don't know about rounded corners
+            (shape_a, shape_b) if is_rounded(shape_a) || is_rounded(shape_b) => self.solve_convex_to_convex(body_a, body_b),
+            (PHYSICS_CIRCLE { .. }, PHYSICS_CIRCLE { .. }) => self.solve_circle_to_circle(body_a, body_b),
+            (PHYSICS_CIRCLE { .. }, PHYSICS_POLYGON { .. }) => self.solve_circle_to_polygon(body_a, body_b),
+            (PHYSICS_POLYGON { .. }, PHYSICS_CIRCLE { .. }) => self.solve_polygon_to_circle(body_a, body_b),
+            (PHYSICS_POLYGON { .. }, PHYSICS_POLYGON { .. }) => self.solve_polygon_to_polygon(body_a, body_b),
+            (PHYSICS_CAPSULE { .. } | PHYSICS_ELLIPSE { .. }, _) | (_, PHYSICS_CAPSULE { .. } | PHYSICS_ELLIPSE { .. }) => self.solve_convex_to_convex(body_a, body_b),
+        }
+
+        // Update physics body grounded state if normal direction is down and grounded state is not set yet in previous manifolds
+        if !body_b.is_grounded {
+            body_b.is_grounded = self.normal.y < 0.0;
+        }
+    }
+
+    // Solves collision between two circle shape physics bodies
+    fn solve_circle_to_circle(&mut self, body_a: &mut PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>, body_b: &mut PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>) {
+        let PHYSICS_CIRCLE { radius: radus_a } = body_a.shape else { unreachable!("only circle bodies should be passed to solve_circle_to_circle") };
+        let PHYSICS_CIRCLE { radius: radus_b } = body_b.shape else { unreachable!("only circle bodies should be passed to solve_circle_to_circle") };
+
+        // Calculate translational vector, which is normal
+        let normal = body_b.position - body_a.position;
+
+        let dist_sqr = normal.length_sqr();
+        let radius = radus_a + radus_b;
+
+        // Check if circles are not in contact
+        if dist_sqr >= radius*radius {
+            self.contacts_count = 0;
+            return;
+        }
+
+        let distance = dist_sqr.sqrt();
+        self.contacts_count = 1;
+
+        if distance == 0.0 {
+            self.penetration = radus_a;
+            self.normal = Vector2 { x: 1.0, y: 0.0 };
+            self.contacts[0] = body_a.position;
+        } else {
+            self.penetration = radius - distance;
+            self.normal = Vector2 { x: normal.x/distance, y: normal.y/distance }; // Faster than using math_normalize() due to sqrt is already performed
+            self.contacts[0] = Vector2 { x: self.normal.x*radus_a + body_a.position.x, y: self.normal.y*radus_a + body_a.position.y };
+        }
+
+        // Update physics body grounded state if normal direction is down
+        if !body_a.is_grounded {
+            body_a.is_grounded = self.normal.y < 0.0;
+        }
+    }
+
+    // Solves collision between a circle to a polygon shape physics bodies
+    fn solve_circle_to_polygon(&mut self, body_a: &mut PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>, body_b: &mut PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>) {
+        self.solve_different_shapes(body_a, body_b);
+    }
+
+    // Solves collision between a circle to a polygon shape physics bodies
+    fn solve_polygon_to_circle(&mut self, body_a: &mut PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>, body_b: &mut PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>) {
+        self.solve_different_shapes(body_b, body_a);
 
-            for i in 0..vertex_data.vertex_count {
-                // Triangle vertices, third vertex implied as (0, 0)
-                let p1 = vertex_data.positions[i];
-                let next_index = next_idx(i, vertex_data.vertex_count);
-                let p2 = vertex_data.positions[next_index];
+        self.normal.x *= -1.0;
+        self.normal.y *= -1.0;
+    }
 
-                #[allow(non_snake_case)]
-                let D = math_cross_vector2(p1, p2);
-                let triangle_area = D/2.0;
+    // Solve collision between two different types of shapes
+    fn solve_different_shapes(&mut self, body_a: &mut PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>, body_b: &mut PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>) {
+        let PHYSICS_CIRCLE { radius: radius_a } = body_a.shape else { unreachable!("only circle bodies should be passed to solve_different_shapes body_a") };
+        let PHYSICS_POLYGON { vertex_data: vertex_data_b, transform: transform_b } = &body_b.shape else { unreachable!("only circle bodies should be passed to solve_different_shapes body_b") };
 
-                area += triangle_area;
+        self.contacts_count = 0;
 
-                // Use area to weight the centroid average, not just vertex position
-                center.x += triangle_area*PHYSAC_K*(p1.x + p2.x);
-                center.y += triangle_area*PHYSAC_K*(p1.y + p2.y);
+        // Transform circle center to polygon transform space
+        let mut center = body_a.position;
+        center = transform_b.transpose().multiply_vector2(center - body_b.position);
 
-                let intx2 = p1.x*p1.x + p2.x*p1.x + p2.x*p2.x;
-                let inty2 = p1.y*p1.y + p2.y*p1.y + p2.y*p2.y;
-                inertia += (0.25*PHYSAC_K*D)*(intx2 + inty2);
-            }
+        // Find edge with minimum penetration
+        // It is the same concept as using support points in SolvePolygonToPolygon
+        let mut separation = Real::MIN;
+        let mut face_normal = 0;
+        let vertex_data = vertex_data_b;
 
-            center.x *= 1.0/area;
-            center.y *= 1.0/area;
+        for i in 0..vertex_data.vertex_count {
+            let current_separation = vertex_data.normals[i].dot(center - vertex_data.positions[i]);
 
-            // Translate vertices to centroid (make the centroid (0, 0) for the polygon in model space)
-            // Note: this is not really necessary
-            for i in 0..vertex_data.vertex_count {
-                vertex_data.positions[i].x -= center.x;
-                vertex_data.positions[i].y -= center.y;
+            if current_separation > radius_a {
+                return;
             }
 
-            let mut new_body = PhysicsBodyData {
-                id: new_id,
-                enabled: true,
-                position: pos,
-                velocity: Vector2::zero(),
-                force: Vector2::zero(),
-                angular_velocity: 0.0,
-                torque: 0.0,
-                orient: 0.0,
-                shape: PHYSICS_POLYGON {
-                    vertex_data,
-                    transform: Mat2::radians(0.0),
-                },
-                mass: density*area,
-                static_friction: 0.4,
-                dynamic_friction: 0.2,
-                restitution: 0.0,
-                use_gravity: true,
-                is_grounded: false,
-                freeze_orient: false,
-                is_simulating: true,
-                ..Default::default()
-            };
-            new_body.inverse_mass = if new_body.mass != 0.0 { 1.0/new_body.mass } else { 0.0 };
-            new_body.inertia = density*inertia;
-            new_body.inverse_inertia = if new_body.inertia != 0.0 { 1.0/new_body.inertia } else { 0.0 };
+            if current_separation > separation {
+                separation = current_separation;
+                face_normal = i;
+            }
+        }
 
-            // Add new body to bodies pointers array and update bodies count
-            self.bodies.push(Strong::new(new_body));
+        // Grab face's vertices
+        let mut v1 = vertex_data.positions[face_normal];
+        let next_index = next_idx(face_normal, vertex_data.vertex_count);
+        let mut v2 = vertex_data.positions[next_index];
 
-            debug_print!("[PHYSAC] created polygon physics body id {new_id}");
-            let [.., result] = &self.bodies[..] else { unreachable!("should have at least one element after pushing") };
-            Some(result)
-        } else {
-            debug_print!("[PHYSAC] new physics body creation failed because there isn't any available id to use");
-            None
+        // Check to see if center is within polygon
+        if separation < Real::EPSILON {
+            self.contacts_count = 1;
+            let normal = transform_b.multiply_vector2(vertex_data.normals[face_normal]);
+            self.normal = -normal;
+            self.contacts[0] = Vector2 { x: self.normal.x*radius_a + body_a.position.x, y: self.normal.y*radius_a + body_a.position.y };
+            self.penetration = radius_a;
+            return;
         }
-    }
 
-    /// Creates a new rectangle physics body with generic parameters
-    ///
-    /// # Panics
-    ///
-    /// This method may panic if there are no available IDs
-    #[inline]
-    pub fn create_physics_body_rectangle(&mut self, pos: Vector2, width: f32, height: f32, density: f32) -> &Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>> {
-        self.try_create_physics_body_rectangle(pos, width, height, density).unwrap()
-    }
+        // Determine which voronoi region of the edge center of circle lies within
+        let dot1 = (center - v1).dot(v2 - v1);
+        let dot2 = (center - v2).dot(v1 - v2);
+        self.penetration = radius_a - separation;
 
-    /// Creates a new polygon physics body with generic parameters
-    ///
-    /// Returns [`None`] if there are no available IDs
-    pub fn try_create_physics_body_polygon(&mut self, pos: Vector2, radius: f32, sides: usize, density: f32) -> Option<&Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>> {
-        if let Some(new_id) = self.find_available_body_index() {
-            // Initialize new body with generic values
-            let mut vertex_data = PolygonData::create_random_polygon(radius, sides);
+        if dot1 <= 0.0 { // Closest to v1
+            if dist_sqr(center, v1) > radius_a*radius_a {
+                return;
+            }
 
-            // Calculate centroid and moment of inertia
-            let mut center = Vector2 { x: 0.0, y: 0.0 };
-            let mut area = 0.0;
-            let mut inertia = 0.0;
+            self.contacts_count = 1;
+            let mut normal = v1 - center;
+            normal = transform_b.multiply_vector2(normal);
+            math_normalize(&mut normal);
+            self.normal = normal;
+            v1 = transform_b.multiply_vector2(v1);
+            v1 += body_b.position;
+            self.contacts[0] = v1;
+        } else if dot2 <= 0.0 { // Closest to v2
+            if dist_sqr(center, v2) > radius_a*radius_a {
+                return;
+            }
 
-            for i in 0..vertex_data.vertex_count {
-                // Triangle vertices, third vertex implied as (0, 0)
-                let position1 = vertex_data.positions[i];
-                let next_index = next_idx(i, vertex_data.vertex_count);
-                let position2 = vertex_data.positions[next_index];
+            self.contacts_count = 1;
+            let mut normal = v2 - center;
+            v2 = transform_b.multiply_vector2(v2);
+            v2 += body_b.position;
+            self.contacts[0] = v2;
+            normal = transform_b.multiply_vector2(normal);
+            math_normalize(&mut normal);
+            self.normal = normal;
+        } else { // Closest to face
+            let mut normal = vertex_data.normals[face_normal];
 
-                let cross = math_cross_vector2(position1, position2);
-                let triangle_area = cross/2.0;
+            if (center - v1).dot(normal) > radius_a {
+                return;
+            }
 
-                area += triangle_area;
+            normal = transform_b.multiply_vector2(normal);
+            self.normal = Vector2 { x: -normal.x, y: -normal.y };
+            self.contacts[0] = Vector2 { x: self.normal.x*radius_a + body_a.position.x, y: self.normal.y*radius_a + body_a.position.y };
+            self.contacts_count = 1;
+        }
+    }
 
-                // Use area to weight the centroid average, not just vertex position
-                center.x += triangle_area*PHYSAC_K*(position1.x + position2.x);
-                center.y += triangle_area*PHYSAC_K*(position1.y + position2.y);
+    // Solves collision between two polygons shape physics bodies
+    fn solve_polygon_to_polygon(&mut self, body_a: &mut PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>, body_b: &mut PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>) {
+        self.contacts_count = 0;
 
-                let intx2 = position1.x*position1.x + position2.x*position1.x + position2.x*position2.x;
-                let inty2 = position1.y*position1.y + position2.y*position1.y + position2.y*position2.y;
-                inertia += (0.25*PHYSAC_K*cross)*(intx2 + inty2);
-            }
+        // Check for separating axis with A shape's face planes
+        let mut face_a = 0;
+        let penetration_a = find_axis_least_penetration(&mut face_a, body_a, body_b);
 
-            center.x *= 1.0/area;
-            center.y *= 1.0/area;
+        if penetration_a >= 0.0 {
+            return;
+        }
 
-            // Translate vertices to centroid (make the centroid (0, 0) for the polygon in model space)
-            // Note: this is not really necessary
-            for i in 0..vertex_data.vertex_count {
-                vertex_data.positions[i].x -= center.x;
-                vertex_data.positions[i].y -= center.y;
-            }
+        // Check for separating axis with B shape's face planes
+        let mut face_b = 0;
+        let penetration_b = find_axis_least_penetration(&mut face_b, body_b, body_a);
 
-            let mut new_body = PhysicsBodyData {
-                id: new_id,
-                enabled: true,
-                position: pos,
-                velocity: Vector2::zero(),
-                force: Vector2::zero(),
-                angular_velocity: 0.0,
-                torque: 0.0,
-                orient: 0.0,
-                shape: PHYSICS_POLYGON {
-                    vertex_data,
-                    transform: Mat2::radians(0.0),
-                },
-                mass: density*area,
-                static_friction: 0.4,
-                dynamic_friction: 0.2,
-                restitution: 0.0,
-                use_gravity: true,
-                is_grounded: false,
-                freeze_orient: false,
-                is_simulating: true,
-                ..Default::default()
-            };
-            new_body.inverse_mass = if new_body.mass != 0.0 { 1.0/new_body.mass } else { 0.0 };
-            new_body.inertia = density*inertia;
-            new_body.inverse_inertia = if new_body.inertia != 0.0 { 1.0/new_body.inertia } else { 0.0 };
+        if penetration_b >= 0.0 {
+            return;
+        }
 
-            // Add new body to bodies pointers array and update bodies count
-            self.bodies.push(Strong::new(new_body));
+        let mut reference_index;
+        let mut flip = false;  // Always point from A shape to B shape
 
-            debug_print!("[PHYSAC] created polygon physics body id {new_id}");
-            let [.., result] = &self.bodies[..] else { unreachable!("should have at least one element after pushing") };
-            Some(result)
+        let ref_body; // Reference
+        let inc_body; // Incident
+
+        // Determine which shape contains reference face
+        if bias_greater_than(penetration_a, penetration_b) {
+            ref_body = body_a;
+            inc_body = body_b;
+            reference_index = face_a;
         } else {
-            debug_print!("[PHYSAC] new physics body creation failed because there isn't any available id to use");
-            None
+            ref_body = body_b;
+            inc_body = body_a;
+            reference_index = face_b;
+            flip = true;
         }
-    }
-
-    /// Creates a new polygon physics body with generic parameters
-    ///
-    /// # Panics
-    ///
-    /// This method may panic if there are no available IDs
-    #[inline]
-    pub fn create_physics_body_polygon(&mut self, pos: Vector2, radius: f32, sides: usize, density: f32) -> &Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>> {
-        self.try_create_physics_body_polygon(pos, radius, sides, density).unwrap()
-    }
-
-    /// Shatters a polygon shape physics body to little physics bodies with explosion force
-    ///
-    /// # Panics
-    ///
-    /// This method may panic if there are not enough available IDs to create fragments
-    #[allow(
-        clippy::needless_pass_by_value,
-        reason = "this is basically another destroy method",
-    )]
-    pub fn physics_shatter(&mut self, body: Weak<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>, position: Vector2, force: f32) {
-        if let Some(phys_body) = body.upgrade() {
-            let body = phys_body.borrow_mut();
-            if let PHYSICS_POLYGON { vertex_data, transform } = body.shape {
-                let mut collision = false;
+        let PHYSICS_POLYGON { vertex_data: ref_data, transform: ref_transform } = &ref_body.shape else { unreachable!("only polygon bodies should be passed to solve_polygon_to_polygon") };
+        // let PHYSICS_POLYGON { vertex_data: inc_data, transform: inc_transform } = &inc_body.shape else { unreachable!("only polygon bodies should be passed to solve_polygon_to_polygon") };
 
-                for i in 0..vertex_data.vertex_count {
-                    let position_a = body.position;
-                    let position_b = transform.multiply_vector2(body.position + vertex_data.positions[i]);
-                    let next_index = next_idx(i, vertex_data.vertex_count);
-                    let position_c = transform.multiply_vector2(body.position + vertex_data.positions[next_index]);
+        // World space incident face
+        let (mut incident_face, incident_index) = find_incident_face(ref_body, inc_body, reference_index);
+        let reference_face = reference_index as u32;
 
-                    // Check collision between each triangle
-                    let alpha = ((position_b.y - position_c.y)*(position.x - position_c.x) + (position_c.x - position_b.x)*(position.y - position_c.y))/
-                                ((position_b.y - position_c.y)*(position_a.x - position_c.x) + (position_c.x - position_b.x)*(position_a.y - position_c.y));
+        // Setup reference face vertices
+        let mut v1 = ref_data.positions[reference_index];
+        reference_index = next_idx(reference_index, ref_data.vertex_count);
+        let mut v2 = ref_data.positions[reference_index];
 
-                    let beta = ((position_c.y - position_a.y)*(position.x - position_c.x) + (position_a.x - position_c.x)*(position.y - position_c.y))/
-                                ((position_b.y - position_c.y)*(position_a.x - position_c.x) + (position_c.x - position_b.x)*(position_a.y - position_c.y));
+        // Transform vertices to world space
+        v1 = ref_transform.multiply_vector2(v1);
+        v1 += ref_body.position;
+        v2 = ref_transform.multiply_vector2(v2);
+        v2 += ref_body.position;
 
-                    let gamma = 1.0 - alpha - beta;
+        // Calculate reference face side normal in world space
+        let mut side_plane_normal = v2 - v1;
+        math_normalize(&mut side_plane_normal);
 
-                    if (alpha > 0.0) && (beta > 0.0) && (gamma > 0.0) {
-                        collision = true;
-                        break;
-                    }
-                }
+        // Orthogonalize
+        let ref_face_normal = Vector2 { x: side_plane_normal.y, y: -side_plane_normal.x };
+        let ref_c = ref_face_normal.dot(v1);
+        let neg_side = -side_plane_normal.dot(v1);
+        let pos_side = side_plane_normal.dot(v2);
 
-                if collision {
-                    let count = vertex_data.vertex_count;
-                    let body_pos = body.position;
-                    let mut vertices = vec![Vector2::zero(); count];
-                    let trans = transform;
-
-                    vertices[..count].copy_from_slice(&vertex_data.positions[..count]);
-
-                    // Destroy shattered physics body
-                    drop(body);
-                    self.destroy_physics_body(phys_body);
-
-                    for i in 0..count {
-                        let next_index = next_idx(i, count);
-                        let mut center = triangle_barycenter(vertices[i], vertices[next_index], Vector2::zero());
-                        center = body_pos + center;
-                        let offset = center - body_pos;
-
-                        // Create polygon physics body with relevant values
-                        let new_body = self.try_create_physics_body_polygon(center, 10.0, 3, 10.0).expect("failed to create physics body"); // The original line just creates a pointer without checking if it's valid
-                        let mut new_body_data = new_body.borrow_mut();
-
-                        let mut new_data = PolygonData {
-                            vertex_count: 3,
-                            ..Default::default()
-                        };
-
-                        new_data.positions[0] = vertices[i] - offset;
-                        new_data.positions[1] = vertices[next_index] - offset;
-                        new_data.positions[2] = position - center;
-
-                        // Separate vertices to avoid unnecessary physics collisions
-                        new_data.positions[0].x *= 0.95;
-                        new_data.positions[0].y *= 0.95;
-                        new_data.positions[1].x *= 0.95;
-                        new_data.positions[1].y *= 0.95;
-                        new_data.positions[2].x *= 0.95;
-                        new_data.positions[2].y *= 0.95;
-
-                        // Calculate polygon faces normals
-                        for j in 0..new_data.vertex_count {
-                            let next_vertex = next_idx(j, new_data.vertex_count);
-                            let face = new_data.positions[next_vertex] - new_data.positions[j];
-
-                            new_data.normals[j] = Vector2 { x: face.y, y: -face.x };
-                            math_normalize(&mut new_data.normals[j]);
-                        }
-
-                        // Calculate centroid and moment of inertia
-                        center = Vector2::zero();
-                        let mut area = 0.0;
-                        let mut inertia = 0.0;
-
-                        for j in 0..new_data.vertex_count {
-                            // Triangle vertices, third vertex implied as (0, 0)
-                            let p1 = new_data.positions[j];
-                            let next_vertex = next_idx(j, new_data.vertex_count);
-                            let p2 = new_data.positions[next_vertex];
-
-                            #[allow(non_snake_case)]
-                            let D = math_cross_vector2(p1, p2);
-                            let triangle_area = D/2.0;
-
-                            area += triangle_area;
-
-                            // Use area to weight the centroid average, not just vertex position
-                            center.x += triangle_area*PHYSAC_K*(p1.x + p2.x);
-                            center.y += triangle_area*PHYSAC_K*(p1.y + p2.y);
-
-                            let intx2 = p1.x*p1.x + p2.x*p1.x + p2.x*p2.x;
-                            let inty2 = p1.y*p1.y + p2.y*p1.y + p2.y*p2.y;
-                            inertia += (0.25*PHYSAC_K*D)*(intx2 + inty2);
-                        }
-
-                        // Apply computed vertex data to new physics body shape
-                        new_body_data.shape = PHYSICS_POLYGON { vertex_data: new_data, transform: trans };
-
-                        center.x *= 1.0/area;
-                        center.y *= 1.0/area;
-
-                        new_body_data.mass = area;
-                        new_body_data.inverse_mass = if new_body_data.mass != 0.0 { 1.0/new_body_data.mass } else { 0.0 };
-                        new_body_data.inertia = inertia;
-                        new_body_data.inverse_inertia = if new_body_data.inertia != 0.0 { 1.0/new_body_data.inertia } else { 0.0 };
-
-                        // Calculate explosion force direction
-                        let point_a = new_body_data.position;
-                        let mut point_b = new_data.positions[1] - new_data.positions[0];
-                        point_b.x /= 2.0;
-                        point_b.y /= 2.0;
-                        let mut force_direction = (point_a + (new_data.positions[0] + point_b)) - new_body_data.position;
-                        math_normalize(&mut force_direction);
-                        force_direction.x *= force;
-                        force_direction.y *= force;
-
-                        drop(new_body_data);
-
-                        // Apply force to new physics body
-                        new_body.borrow_mut().add_force(force_direction);
-                    }
+        // Clip incident face to reference face side planes (due to floating point error, possible to not have required points
+        let [face_a, face_b] = &mut incident_face;
+        let mut features = [0, 1];
+        if clip(-side_plane_normal, neg_side, face_a, face_b, &mut features, 2) < 2 {
+            return;
+        }
 
-                    drop(vertices);
-                }
-            }
-        } else {
-            debug_print!("[PHYSAC] error when trying to shatter a null reference physics body");
+        if clip(side_plane_normal, pos_side, face_a, face_b, &mut features, 3) < 2 {
+            return;
         }
-    }
+        let contact_id = |feature| ContactId { parts: [0; 2], reference_face, incident_face: incident_index as u32, feature, flip };
 
-    /// Returns the current amount of created physics bodies
-    #[must_use]
-    pub fn get_physics_bodies_count(&self) -> usize {
-        self.bodies.len()
-    }
+        // Flip normal if required
+        self.normal = if flip { -ref_face_normal } else { ref_face_normal };
 
-    /// Returns a physics body of the bodies pool at a specific index
-    ///
-    /// Returns [`None`] if `index` is out of bounds
-    #[must_use]
-    pub fn try_get_physics_body(&self, index: usize) -> Option<&Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>> {
-        if index >= self.bodies.len() {
-            debug_print!("[PHYSAC] physics body index is out of bounds");
+        // Keep points behind reference face
+        let mut current_point: u32 = 0; // Clipped points behind reference face
+        let mut separation = ref_face_normal.dot(incident_face[0]) - ref_c;
+
+        if separation <= 0.0 {
+            self.contacts[current_point as usize] = incident_face[0];
+            self.contact_ids[current_point as usize] = contact_id(features[0]);
+            self.penetration = -separation;
+            current_point += 1;
+        } else {
+            self.penetration = 0.0;
         }
 
-        self.bodies.get(index)
-    }
+        separation = ref_face_normal.dot(incident_face[1]) - ref_c;
 
-    /// Returns a physics body of the bodies pool at a specific index
-    ///
-    /// # Panics
-    ///
-    /// This method may panic if `index` is out of bounds
-    #[must_use]
-    pub fn get_physics_body(&self, index: usize) -> &Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>> {
-        self.try_get_physics_body(index).unwrap()
-    }
+        if separation <= 0.0 {
+            self.contacts[current_point as usize] = incident_face[1];
+            self.contact_ids[current_point as usize] = contact_id(features[1]);
+            self.penetration += -separation;
+            current_point += 1;
 
-    /// Returns a physics body of the bodies pool at a specific index
-    ///
-    /// # Panics
-    ///
-    /// This method may panic if `index` is out of bounds
-    pub fn borrow_physics_body(&self, index: usize) -> PhysacReadGuard<'_, PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>> {
-        self.get_physics_body(index).borrow()
-    }
+            // Calculate total penetration average
+            self.penetration /= count_to_real(current_point);
+        }
 
-    /// Returns a physics body of the bodies pool at a specific index
-    ///
-    /// # Panics
-    ///
-    /// This method may panic if `index` is out of bounds
-    pub fn borrow_physics_body_mut(&self, index: usize) -> PhysacWriteGuard<'_, PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>> {
-        self.get_physics_body(index).borrow_mut()
+        self.contacts_count = current_point;
     }
 
-    /// Returns an iterator over borrows of each physics body in the simulation
-    #[must_use]
-    pub fn physics_body_iter(&self) -> impl DoubleEndedIterator<Item = PhysacReadGuard<'_, PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>> + ExactSizeIterator {
-        self.bodies.iter().map(|body| body.borrow())
-    }
+    // Solves collision between any two shapes, treating them as convex cores with a rounding radius
+    fn solve_convex_to_convex(&mut self, body_a: &mut PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>, body_b: &mut PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>) {
+        self.solve_convex_proxies(&body_a.convex_proxy(), &body_b.convex_proxy());
 
-    /// Returns an iterator over mutable borrows of each physics body in the simulation
-    #[must_use]
-    pub fn physics_body_iter_mut(&self) -> impl DoubleEndedIterator<Item = PhysacWriteGuard<'_, PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>> + ExactSizeIterator {
-        self.bodies.iter().map(|body| body.borrow_mut())
+        // Update physics body grounded state if normal direction is down
+        if self.contacts_count > 0 && !body_a.is_grounded {
+            body_a.is_grounded = self.normal.y > 0.0;
+        }
     }
 
-    /// Returns an iterator over [`Strong`] references to each physics body in the simulation
-    #[must_use]
-    pub fn strong_physics_body_iter(&self) -> impl DoubleEndedIterator<Item = &Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>> + ExactSizeIterator {
-        self.bodies.iter()
-    }
+    // Solves collision between two world space convex proxies
+    fn solve_convex_proxies(&mut self, proxy_a: &ConvexProxy, proxy_b: &ConvexProxy) {
+        self.contacts_count = 0;
 
-    /// Unitializes and destroys a physics body
-    #[allow(
-        clippy::needless_pass_by_value,
-        reason = "the user will presumably pass in an upgrade they made specifically for this, and it indicates the end of that instance's life.",
-    )]
-    pub fn destroy_physics_body(&mut self, body: Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>) {
-        let id = body.borrow().id;
+        let total_radius = proxy_a.radius + proxy_b.radius;
 
-        let index = self.bodies.iter().position(|body| body.borrow().id == id);
+        if proxy_separation(proxy_a, proxy_b) > 0.0 {
+            // Cores are not touching, so the closest features decide the contact
+            let (point_a, point_b) = closest_points(proxy_a, proxy_b);
+            let distance = dist_sqr(point_a, point_b).sqrt();
 
-        if let Some(index) = index {
-            body.borrow_mut().is_simulating = false;
-            #[cfg(debug_assertions)]
-            let weak = body.downgrade();
-            // Free body allocated memory
-            drop(body);
-            self.bodies.remove(index);
+            if distance > total_radius {
+                return;
+            }
 
-            debug_print!("[PHYSAC] destroyed physics body id {id}");
-            #[cfg(debug_assertions)] {
-                let strong_count = weak.strong_count();
-                if strong_count > 0 {
-                    debug_print!("[PHYSAC] physics body id {id} is destroyed, but still has {strong_count} strong references");
-                }
+            let mut normal = point_b - point_a;
+            math_normalize(&mut normal);
+
+            // Resting face to face (like a capsule lying on a box) needs two contacts to be stable
+            if self.solve_convex_faces(proxy_a, proxy_b, Some(normal)) {
+                return;
             }
-        } else {
-            debug_print!("[PHYSAC] Not possible to find body id {id} in pointers array");
-        }
-    }
 
-    /// Unitializes and destroys a physics body
-    #[allow(
-        clippy::needless_pass_by_value,
-        reason = "the user will presumably pass in a downgrade they made specifically for this, and it indicates the end of that instance's life.",
-    )]
-    pub fn try_destroy_physics_body(&mut self, body: Weak<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>) {
-        if let Some(body) = body.upgrade() {
-            self.destroy_physics_body(body);
-        } else {
-            debug_print!("[PHYSAC] error trying to destroy a null referenced body");
+            self.normal = normal;
+            self.penetration = total_radius - distance;
+            self.contacts[0] = (point_a + normal*proxy_a.radius + point_b - normal*proxy_b.radius)*0.5;
+            self.contact_ids[0] = ContactId::default();
+            self.contacts_count = 1;
+        } else if !self.solve_convex_faces(proxy_a, proxy_b, None) {
+            // Neither core has faces to push out of (both are points in the same place)
+            self.normal = Vector2 { x: 1.0, y: 0.0 };
+            self.penetration = total_radius;
+            self.contacts[0] = proxy_a.vertices[0];
+            self.contact_ids[0] = ContactId::default();
+            self.contacts_count = 1;
         }
     }
 
-    /// Destroy all physics bodies that meet a condition
-    ///
-    /// This method operates in place, visiting each element exactly once in the original order, and preserves the order of the retained elements
-    pub fn destroy_physics_bodies<P>(&mut self, mut predicate: P)
-    where
-        P: FnMut(&PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>) -> bool,
-    {
-        self.destroy_physics_bodies_mut(|body| predicate(body));
-    }
+    // Clips the incident face of one proxy against the reference face of the other
+    //
+    // If `aligned_with` is provided, only a reference face facing that direction (from A to B) is considered
+    //
+    // Returns false if no contacts could be generated this way
+    fn solve_convex_faces(&mut self, proxy_a: &ConvexProxy, proxy_b: &ConvexProxy, aligned_with: Option<Vector2>) -> bool {
+        const ALIGNMENT_TOLERANCE: Real = 0.999;
+
+        let total_radius = proxy_a.radius + proxy_b.radius;
+
+        // Find the reference face: the face of either shape with the least penetration
+        let (separation_a, face_a) = max_face_separation(proxy_a, proxy_b);
+        let (separation_b, face_b) = max_face_separation(proxy_b, proxy_a);
+
+        let (ref_proxy, inc_proxy, reference_index, flip) = match (face_a, face_b) {
+            (None, None) => return false,
+            (Some(face_a), None) => (proxy_a, proxy_b, face_a, false),
+            (None, Some(face_b)) => (proxy_b, proxy_a, face_b, true),
+            (Some(face_a), Some(face_b)) => {
+                if let Some(direction) = aligned_with {
+                    if proxy_a.normals[face_a].dot(direction) >= proxy_b.normals[face_b].dot(-direction) {
+                        (proxy_a, proxy_b, face_a, false)
+                    } else {
+                        (proxy_b, proxy_a, face_b, true)
+                    }
+                } else if bias_greater_than(separation_a, separation_b) {
+                    (proxy_a, proxy_b, face_a, false)
+                } else {
+                    (proxy_b, proxy_a, face_b, true)
+                }
+            }
+        };
 
-    /// Destroy all physics bodies that meet a condition
-    ///
-    /// This method operates in place, visiting each element exactly once in the original order, and preserves the order of the retained elements
-    pub fn destroy_physics_bodies_mut<P>(&mut self, mut predicate: P)
-    where
-        P: FnMut(&mut PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>) -> bool,
-    {
-        self.bodies.retain_mut(|body| !predicate(&mut *body.borrow_mut()));
-    }
+        let ref_face_normal = ref_proxy.normals[reference_index];
 
-    /// Removes all physics bodies
-    pub fn clear_physics_bodies(&mut self) {
-        self.bodies.clear();
-    }
-}
+        if let Some(direction) = aligned_with {
+            let direction = if flip { -direction } else { direction };
+            if ref_face_normal.dot(direction) < ALIGNMENT_TOLERANCE {
+                return false;
+            }
+        }
 
-impl<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize> PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES> {
-    /// Returns true if the body is still being simulated, returns false if it has been destroyed
-    #[must_use]
-    pub fn is_simulating(&self) -> bool {
-        self.is_simulating
-    }
+        // Find most anti-normal face on the incident shape
+        let mut incident_face = [inc_proxy.vertices[0]; 2];
+        let mut incident_index = 0;
+        let mut min_dot = Real::MAX;
+        for i in 0..inc_proxy.normals.len() {
+            let dot = ref_face_normal.dot(inc_proxy.normals[i]);
+
+            if dot < min_dot {
+                min_dot = dot;
+                incident_face = [inc_proxy.vertices[i], inc_proxy.vertices[next_idx(i, inc_proxy.vertices.len())]];
+                incident_index = i;
+            }
+        }
 
-    /// Adds a force to a physics body
-    pub fn add_force(&mut self, force: Vector2) {
-        self.force += force;
-    }
+        // Setup reference face vertices in world space
+        let v1 = ref_proxy.vertices[reference_index];
+        let v2 = ref_proxy.vertices[next_idx(reference_index, ref_proxy.vertices.len())];
 
-    /// Adds an angular force to a physics body
-    pub fn add_torque(&mut self, amount: f32) {
-        self.torque += amount;
-    }
+        let mut side_plane_normal = v2 - v1;
+        math_normalize(&mut side_plane_normal);
 
-    /// Sets physics body shape transform based on radians parameter
-    pub fn set_rotation(&mut self, radians: f32) {
-        self.orient = radians;
+        let ref_c = ref_face_normal.dot(v1);
+        let neg_side = -side_plane_normal.dot(v1);
+        let pos_side = side_plane_normal.dot(v2);
 
-        if let PHYSICS_POLYGON { transform, .. } = &mut self.shape {
-            *transform = Mat2::radians(radians);
+        // Clip incident face to reference face side planes
+        let [face_a, face_b] = &mut incident_face;
+        let mut features = [0, 1];
+        if clip(-side_plane_normal, neg_side, face_a, face_b, &mut features, 2) < 2 {
+            return false;
         }
-    }
 
-    /// Returns the number of vertices of a physics body shape
-    #[must_use]
-    pub fn get_physics_shape_vertices_count(&self) -> usize {
-        match self.shape {
-            PHYSICS_CIRCLE { .. } => CIRCLE_VERTICES,
-            PHYSICS_POLYGON { vertex_data, .. } => vertex_data.vertex_count,
+        if clip(side_plane_normal, pos_side, face_a, face_b, &mut features, 3) < 2 {
+            return false;
         }
-    }
 
-    /// Returns transformed position of a body shape (body position + vertex transformed position)
-    ///
-    /// Returns [`None`] if `vertex` index is out of bounds
-    #[must_use]
-    pub fn try_get_physics_body_shape_vertex(&self, vertex: usize) -> Option<Vector2> {
-        match self.shape {
-            PHYSICS_CIRCLE { radius } => {
-                Some(Vector2 {
-                    x: self.position.x + (360.0/CIRCLE_VERTICES as f32*vertex as f32*DEG2RAD as f32).cos()*radius,
-                    y: self.position.y + (360.0/CIRCLE_VERTICES as f32*vertex as f32*DEG2RAD as f32).sin()*radius,
-                })
-            }
-            PHYSICS_POLYGON { vertex_data, transform } => {
-                if let Some(&p) = vertex_data.positions.get(vertex) {
-                    Some(self.position + transform.multiply_vector2(p))
-                } else {
-                    debug_print!("[PHYSAC] physics shape vertex index is out of bounds");
-                    None
+        // Keep points within reach of the reference face
+        let mut current_point: u32 = 0;
+        let mut penetration = 0.0;
+
+        for (point, feature) in incident_face.into_iter().zip(features) {
+            let separation = ref_face_normal.dot(point) - ref_c;
+
+            if separation <= total_radius {
+                // Halfway between the surfaces of both shapes
+                let contact = point - ref_face_normal*(0.5*(separation - ref_proxy.radius + inc_proxy.radius));
+
+                if current_point == 0 || dist_sqr(contact, self.contacts[0]) > Real::EPSILON {
+                    self.contacts[current_point as usize] = contact;
+                    self.contact_ids[current_point as usize] = ContactId {
+                        parts: [0; 2],
+                        reference_face: reference_index as u32,
+                        incident_face: incident_index as u32,
+                        feature,
+                        flip,
+                    };
+                    current_point += 1;
                 }
+                penetration += total_radius - separation;
             }
         }
-    }
 
-    /// Returns transformed position of a body shape (body position + vertex transformed position)
-    ///
-    /// # Panics
-    ///
-    /// This method may panic if `vertex` index is out of bounds
-    #[must_use]
-    pub fn get_physics_shape_vertex(&self, vertex: usize) -> Vector2 {
-        self.try_get_physics_body_shape_vertex(vertex).unwrap()
-    }
+        if current_point == 0 {
+            return false;
+        }
 
-    /// Returns an iterator over the transformed positions of a body shape (body position + vertex transformed position)
-    #[must_use]
-    pub fn vertices_iter(&self) -> impl DoubleEndedIterator<Item = Vector2> + ExactSizeIterator {
-        (0..self.get_physics_shape_vertices_count())
-            .map(|i| self.get_physics_shape_vertex(i))
-    }
+        self.normal = if flip { -ref_face_normal } else { ref_face_normal };
+        self.penetration = penetration/count_to_real(current_point);
+        self.contacts_count = current_point;
 
-    /// Returns an iterator over the transformed positions of a body shape (body position + vertex transformed position), repeating the first vertex one more time after the final vertex
-    #[must_use]
-    pub fn vertices_iter_closed(&self) -> impl DoubleEndedIterator<Item = Vector2> {
-        self.vertices_iter()
-            .chain(std::iter::once_with(|| self.get_physics_shape_vertex(0)))
+        true
     }
+}
 
-    /// Returns the physics body shape type ([`PHYSICS_CIRCLE`] or [`PHYSICS_POLYGON`])
-    #[must_use]
-    pub fn get_physics_shape_type(&self) -> std::mem::Discriminant<PhysicsShape<MAX_VERTICES, CIRCLE_VERTICES>> {
-        std::mem::discriminant(&self.shape)
-    }
+/// The convex core of a physics body shape in world space, expanded outward by a radius
+///
+/// Circles are a single vertex and capsules are a segment (two vertices) with their radius as the expansion,
+/// while polygons are their own core with no expansion
+#[derive(Debug, Clone)]
+struct ConvexProxy {
+    /// Core vertex positions in world space
+    vertices: Vec<Vector2>,
+    /// Core face normals in world space (empty if there are fewer than two vertices)
+    normals: Vec<Vector2>,
+    /// Distance of the shape surface from its core
+    radius: Real,
+    /// Normals of the previous and next segments if this is a one-sided chain segment ([`None`] at the open ends of the chain)
+    neighbor_normals: Option<[Option<Vector2>; 2]>,
+    /// Index of the part of the shape this proxy was made from (such as a chain segment), which stays the same between steps
+    part: u32,
 }
 
-//----------------------------------------------------------------------------------
-// Module Internal Functions Definition
-//----------------------------------------------------------------------------------
-impl<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize> PolygonData<MAX_VERTICES, CIRCLE_VERTICES> {
-    /// Creates a random polygon shape with max vertex distance from polygon pivot
-    fn create_random_polygon(radius: f32, sides: usize) -> PolygonData<MAX_VERTICES, CIRCLE_VERTICES> {
-        let mut data = PolygonData {
-            vertex_count: sides,
-            ..Default::default()
-        };
+impl ConvexProxy {
+    /// Returns true if a contact pushing out of this proxy along `normal`, against a shape centered at `other_center`, should be kept
+    ///
+    /// Contacts are always kept unless this is a one-sided chain segment, in which case contacts from behind the segment
+    /// and contacts at a joint which the neighboring segment should handle are dropped
+    fn accepts_contact(&self, normal: Vector2, other_center: Vector2) -> bool {
+        const ALIGNMENT_TOLERANCE: Real = 0.999;
+
+        let Some([prev_normal, next_normal]) = self.neighbor_normals else { return true };
+        let face_normal = self.normals[0];
+        let [v1, v2] = [self.vertices[0], self.vertices[1]];
+
+        // One-sided: shapes behind the segment pass through it
+        if face_normal.dot(other_center - v1) < 0.0 {
+            return false;
+        }
 
-        // Calculate polygon vertices positions
-        for i in 0..data.vertex_count {
-            data.positions[i].x = (360.0/sides as f32*i as f32*DEG2RAD as f32).cos()*radius;
-            data.positions[i].y = (360.0/sides as f32*i as f32*DEG2RAD as f32).sin()*radius;
+        if normal.dot(face_normal) >= ALIGNMENT_TOLERANCE {
+            return true;
         }
 
-        // Calculate polygon faces normals
-        for i in 0..data.vertex_count {
-            let next_index = next_idx(i, sides);
-            let face = data.positions[next_index] - data.positions[i];
+        // The contact is at whichever end the normal leans towards
+        let (from, to) = if normal.dot(v2 - v1) < 0.0 {
+            let Some(prev_normal) = prev_normal else { return true };
+            (prev_normal, face_normal)
+        } else {
+            let Some(next_normal) = next_normal else { return true };
+            (face_normal, next_normal)
+        };
 
-            data.normals[i] = Vector2 { x: face.y, y: -face.x };
-            math_normalize(&mut data.normals[i]);
+        // Only convex joints have a corner that can be touched, and only from between the normals of both segments
+        math_cross_vector2(from, to) > Real::EPSILON &&
+        math_cross_vector2(from, normal) >= -Real::EPSILON &&
+        math_cross_vector2(normal, to) >= -Real::EPSILON
+    }
+
+    /// Returns true if the surfaces of both proxies overlap
+    fn overlaps(&self, other: &ConvexProxy) -> bool {
+        if proxy_separation(self, other) <= 0.0 {
+            return true;
         }
 
-        data
+        let (point_a, point_b) = closest_points(self, other);
+        let total_radius = self.radius + other.radius;
+        dist_sqr(point_a, point_b) < total_radius*total_radius
     }
 
-    /// Creates a rectangle polygon shape based on a min and max positions
-    fn create_rectangle_polygon(pos: Vector2, size: Vector2) -> PolygonData<MAX_VERTICES, CIRCLE_VERTICES> {
-        let mut data = PolygonData {
-            vertex_count: 4,
-            ..Default::default()
-        };
+    /// Returns true if `point` is inside the core or within the radius of it
+    fn contains_point(&self, point: Vector2) -> bool {
+        let count = self.vertices.len();
+        let is_inside_core = count > 2 && self.normals.iter().zip(&self.vertices).all(|(&normal, &vertex)| normal.dot(point - vertex) <= 0.0);
 
-        // Calculate polygon vertices positions
-        data.positions[0] = Vector2 { x: pos.x + size.x/2.0, y: pos.y - size.y/2.0 };
-        data.positions[1] = Vector2 { x: pos.x + size.x/2.0, y: pos.y + size.y/2.0 };
-        data.positions[2] = Vector2 { x: pos.x - size.x/2.0, y: pos.y + size.y/2.0 };
-        data.positions[3] = Vector2 { x: pos.x - size.x/2.0, y: pos.y - size.y/2.0 };
+        is_inside_core || (0..count).any(|i| {
+            let closest = closest_point_on_segment(point, self.vertices[i], self.vertices[next_idx(i, count)]);
+            dist_sqr(point, closest) <= self.radius*self.radius
+        })
+    }
 
-        // Calculate polygon faces normals
-        for i in 0..data.vertex_count {
-            let next_index = next_idx(i, data.vertex_count);
-            let face = data.positions[next_index] - data.positions[i];
+    /// Returns the direction and distance to push a circle out of the surface of the proxy by, or [`None`] if they don't overlap
+    fn circle_penetration(&self, center: Vector2, radius: Real) -> Option<(Vector2, Real)> {
+        let count = self.vertices.len();
+        let total_radius = self.radius + radius;
 
-            data.normals[i] = Vector2 { x: face.y, y: -face.x };
-            math_normalize(&mut data.normals[i]);
+        // Circles inside the core leave through the nearest face
+        if count > 2 && self.normals.iter().zip(&self.vertices).all(|(&normal, &vertex)| normal.dot(center - vertex) <= 0.0) {
+            let (normal, separation) = self.normals.iter().zip(&self.vertices)
+                .map(|(&normal, &vertex)| (normal, normal.dot(center - vertex)))
+                .max_by(|(_, a), (_, b)| a.total_cmp(b))?;
+            return Some((normal, total_radius - separation));
         }
 
-        data
+        let closest = (0..count)
+            .map(|i| closest_point_on_segment(center, self.vertices[i], self.vertices[next_idx(i, count)]))
+            .min_by(|&a, &b| dist_sqr(center, a).total_cmp(&dist_sqr(center, b)))?;
+        let distance = dist_sqr(center, closest).sqrt();
+        if distance >= total_radius {
+            return None;
+        }
+
+        let normal = if distance > Real::EPSILON {
+            (center - closest)*(1.0/distance)
+        } else {
+            self.normals.first().copied().unwrap_or(Vector2 { x: 0.0, y: -1.0 })
+        };
+        Some((normal, total_radius - distance))
     }
-}
 
-/// An error that occurs during [`Physac::run_physics_step`]
-#[derive(Debug)]
-pub enum PhysicsStepError {
-    /// [`Physac`] is poisoned
-    PhysacPoison,
-    /// A [`Strong<PhysicsBodyData>`] is poisoned
-    PhysicsBodyPoison,
-    /// [`Physac`] is out of available IDs to satisfy the demand for physics bodies and manifolds
-    OutOfIDs,
-    /// An index is out of bounds
-    OutOfBounds,
-    /// A denominator is zero
-    DivByZero,
-}
-impl std::fmt::Display for PhysicsStepError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            Self::PhysacPoison => write!(f, "a panic occurred while Physac was borrowed mutably"),
-            Self::PhysicsBodyPoison => write!(f, "a panic occurred while a physics body was borrowed mutably"),
-            Self::OutOfIDs => write!(f, "insufficient IDs are available"),
-            Self::OutOfBounds => write!(f, "an out of bounds error occurred"),
-            Self::DivByZero => write!(f, "tried to divide by zero"),
-        }
+    /// Returns the average of the core vertices
+    fn center(&self) -> Vector2 {
+        let sum = self.vertices.iter().fold(Vector2::zero(), |sum, &v| sum + v);
+        sum*(1.0/self.vertices.len() as Real)
     }
-}
-impl std::error::Error for PhysicsStepError {}
-impl<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize> From<std::sync::PoisonError<PhysacReadGuard<'_, PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>>> for PhysicsStepError {
-    fn from(_: std::sync::PoisonError<PhysacReadGuard<'_, PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>>) -> Self {
-        Self::PhysicsBodyPoison
+
+    /// Returns the outline of the proxy as a convex polygon, rounding off its corners with a few segments each
+    ///
+    /// Proxies without area (such as chain segments) have an empty outline
+    fn outline(&self) -> Vec<Vector2> {
+        const ROUND_SEGMENTS: usize = 32;
+
+        if self.radius <= 0.0 {
+            return if self.vertices.len() > 2 { self.vertices.clone() } else { Vec::new() };
+        }
+
+        let points: Vec<Vector2> = self.vertices.iter()
+            .flat_map(|&vertex| (0..ROUND_SEGMENTS).map(move |i| {
+                let angle = 2.0*PI as Real*i as Real/ROUND_SEGMENTS as Real;
+                vertex + Vector2 { x: math_cos(angle), y: math_sin(angle) }*self.radius
+            }))
+            .collect();
+        convex_hull(points)
     }
 }
-impl<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize> From<std::sync::PoisonError<PhysacWriteGuard<'_, PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>>> for PhysicsStepError {
-    fn from(_: std::sync::PoisonError<PhysacWriteGuard<'_, PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>>) -> Self {
-        Self::PhysicsBodyPoison
+
+impl<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize> PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES> {
+    /// Returns true if the body doesn't move by itself, because it is asleep, static or disabled
+    fn is_inert(&self) -> bool {
+        self.is_sleeping || self.inverse_mass == 0.0 || !self.enabled
     }
-}
 
-/// Physics loop thread function
-#[cfg(feature = "phys_thread")]
-#[allow(
-    clippy::needless_pass_by_value,
-    reason = "replacing these with references would cause those references to be dropped when the caller finishes, but we want this function to outlive that.",
-)]
-fn physics_loop<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize>(phys: Arc<RwLock<Physac<MAX_VERTICES, CIRCLE_VERTICES>>>, is_physics_thread_enabled: Arc<AtomicBool>) {
-    debug_print!("[PHYSAC] physics thread created successfully");
+    /// Returns true if the body moves the bodies touching it by itself, being kinematic with a velocity to move by or having a moving surface
+    fn is_driving(&self) -> bool {
+        (self.is_kinematic && !self.enabled && (self.velocity != Vector2::zero() || self.angular_velocity != 0.0)) ||
+        self.surface_velocity != 0.0
+    }
 
-    // Initialize physics loop thread values
-    is_physics_thread_enabled.store(true, Relaxed);
+    /// Returns the convex core of the body shape in world space
+    fn convex_proxy(&self) -> ConvexProxy {
+        self.convex_proxies(None).into_iter().next().expect("only single part shapes should be passed to convex_proxy")
+    }
 
-    // Physics update loop
-    while is_physics_thread_enabled.load(Relaxed) {
-        let fixed_time = match phys.write().map_err(|_| PhysicsStepError::PhysacPoison).and_then(|mut lock| lock.run_physics_step().map(|()| lock.fixed_time)) {
-            Ok(fixed_time) => fixed_time,
-            Err(e) => {
-                debug_print!("[PHYSAC] {e}; physics thread will now close");
-                is_physics_thread_enabled.store(false, Release);
-                return;
-            }
-        };
+    /// Returns the convex cores of every part of the body shape in world space
+    ///
+    /// If `within` is provided, parts of chains and heightfields outside of it are skipped
+    fn convex_proxies(&self, within: Option<Aabb>) -> Vec<ConvexProxy> {
+        let mut proxies = Vec::new();
+        push_shape_proxies(&self.shape, self.position, self.shape_transform(), within, &mut proxies);
+        proxies
+    }
 
-        let req = Duration::from_secs_f64(fixed_time);
+    /// Returns true if the body shape is collided as several separate parts
+    fn is_multi_part(&self) -> bool {
+        matches!(self.shape, PHYSICS_COMPOUND { .. } | PHYSICS_CHAIN { .. } | PHYSICS_HEIGHTFIELD { .. })
+    }
 
-        std::thread::sleep(req);
+    /// Returns the world space bounding box of the body shape
+    fn bounds(&self) -> Aabb {
+        shape_bounds(&self.shape, self.position, self.shape_transform())
     }
 }
 
-impl<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize> Physac<MAX_VERTICES, CIRCLE_VERTICES> {
-    /// Physics steps calculations (dynamics, collisions and position corrections)
-    fn physics_step(&mut self) -> Result<(), PhysicsStepError> {
-        // Update current steps count
-        self.steps_count += 1;
+/// Returns the greatest separation of any face of `proxy_a` from the core of `proxy_b`, along with that face
+fn max_face_separation(proxy_a: &ConvexProxy, proxy_b: &ConvexProxy) -> (Real, Option<usize>) {
+    let mut best_distance = Real::MIN;
+    let mut best_index = None;
 
-        // Clear previous generated collisions information
-        self.contacts.clear();
+    for (i, &normal) in proxy_a.normals.iter().enumerate() {
+        let vertex = proxy_a.vertices[i];
+        let distance = proxy_b.vertices.iter()
+            .map(|&support| normal.dot(support - vertex))
+            .fold(Real::MAX, Real::min);
 
-        // Reset physics bodies grounded state
-        for body in &self.bodies {
-            body.write()?.is_grounded = false;
+        if distance > best_distance {
+            best_distance = distance;
+            best_index = Some(i);
         }
+    }
 
-        // Generate new collision information
-        for i in 0..self.bodies.len() {
-            for j in (i + 1)..self.bodies.len() {
-                let body_a = self.bodies[i].clone();
-                let body_b = self.bodies[j].clone();
-                if (body_a.read()?.inverse_mass == 0.0) &&
-                   (body_b.read()?.inverse_mass == 0.0) {
-                    continue;
-                }
+    (best_distance, best_index)
+}
 
-                let manifold = self.create_physics_manifold(body_a.clone(), body_b.clone()).ok_or(PhysicsStepError::OutOfIDs)?;
-                manifold.solve(&mut *(body_a.write()?), &mut *(body_b.write()?));
+/// Returns the greatest separation between the cores of two proxies along any potential separating axis
+///
+/// The result is positive if and only if the cores do not overlap
+fn proxy_separation(proxy_a: &ConvexProxy, proxy_b: &ConvexProxy) -> Real {
+    /// Segments are flat, so their direction can also separate them from other shapes
+    fn axes(proxy: &ConvexProxy) -> impl Iterator<Item = Vector2> + '_ {
+        let direction = if let [v1, v2] = proxy.vertices[..] {
+            let mut direction = v2 - v1;
+            math_normalize(&mut direction);
+            Some(direction)
+        } else { None };
+        proxy.normals.iter().copied().chain(direction)
+    }
+
+    fn project(proxy: &ConvexProxy, axis: Vector2) -> (Real, Real) {
+        proxy.vertices.iter()
+            .map(|&v| axis.dot(v))
+            .fold((Real::MAX, Real::MIN), |(min, max), x| (min.min(x), max.max(x)))
+    }
+
+    if proxy_a.vertices.len() == 1 && proxy_b.vertices.len() == 1 {
+        return dist_sqr(proxy_a.vertices[0], proxy_b.vertices[0]).sqrt();
+    }
+
+    axes(proxy_a).chain(axes(proxy_b))
+        .map(|axis| {
+            let (min_a, max_a) = project(proxy_a, axis);
+            let (min_b, max_b) = project(proxy_b, axis);
+            (min_b - max_a).max(min_a - max_b)
+        })
+        .fold(Real::MIN, Real::max)
+}
 
-                if manifold.contacts_count > 0 {
-                    let manifold = manifold.clone();
-                    // Create a new manifold with same information as previously solved manifold and add it to the manifolds pool last slot
-                    let new_manifold = self.create_physics_manifold(body_a, body_b).ok_or(PhysicsStepError::OutOfIDs)?;
-                    new_manifold.penetration = manifold.penetration;
-                    new_manifold.normal = manifold.normal;
-                    new_manifold.contacts[0] = manifold.contacts[0];
-                    new_manifold.contacts[1] = manifold.contacts[1];
-                    new_manifold.contacts_count = manifold.contacts_count;
-                    new_manifold.restitution = manifold.restitution;
-                    new_manifold.dynamic_friction = manifold.dynamic_friction;
-                    new_manifold.static_friction = manifold.static_friction;
+/// Returns the closest points between the cores of two non-overlapping proxies (on a, on b)
+fn closest_points(proxy_a: &ConvexProxy, proxy_b: &ConvexProxy) -> (Vector2, Vector2) {
+    let mut best = (proxy_a.vertices[0], proxy_b.vertices[0]);
+    let mut best_dist = Real::MAX;
+
+    for (vertices, edges, swap) in [(proxy_a, proxy_b, false), (proxy_b, proxy_a, true)] {
+        let count = edges.vertices.len();
+        for &point in &vertices.vertices {
+            for i in 0..count {
+                let on_edge = closest_point_on_segment(point, edges.vertices[i], edges.vertices[next_idx(i, count)]);
+                let dist = dist_sqr(point, on_edge);
+
+                if dist < best_dist {
+                    best_dist = dist;
+                    best = if swap { (on_edge, point) } else { (point, on_edge) };
                 }
             }
         }
+    }
 
-        // Integrate forces to physics bodies
-        for body in &self.bodies {
-            Self::integrate_physics_forces(&mut *body.write()?, self.delta_time, self.gravity_force);
-        }
+    best
+}
 
-        // Initialize physics manifolds to solve collisions
-        for manifold in &mut self.contacts {
-            Self::initialize_physics_manifolds(manifold, self.delta_time, self.gravity_force)?;
+/// Returns the fraction of `translation` that `cast` can move before touching `target`, along with the hit point and normal on `target`
+///
+/// Uses conservative advancement: the proxy repeatedly moves as far as the current gap allows without passing through
+fn cast_proxy(cast: &ConvexProxy, target: &ConvexProxy, translation: Vector2) -> Option<(Real, Vector2, Vector2)> {
+    const TOLERANCE: Real = 0.01;
+    const MAX_ITERATIONS: usize = 32;
+
+    let total_radius = cast.radius + target.radius;
+    let mut moved = cast.clone();
+    let mut fraction = 0.0;
+
+    for _ in 0..MAX_ITERATIONS {
+        if proxy_separation(&moved, target) <= 0.0 {
+            // The cores overlap, so there is no closest direction to report
+            let mut normal = -translation;
+            math_normalize(&mut normal);
+            return target.accepts_contact(normal, moved.center()).then_some((fraction, moved.center(), normal));
         }
 
-        // Integrate physics collisions impulses to solve collisions
-        for _ in 0..self.collision_iterations {
-            for manifold in &mut self.contacts {
-                Self::integrate_physics_impulses(manifold)?;
-            }
+        let (point_cast, point_target) = closest_points(&moved, target);
+        let gap = dist_sqr(point_cast, point_target).sqrt() - total_radius;
+        let mut normal = point_cast - point_target;
+        math_normalize(&mut normal);
+
+        if gap <= TOLERANCE {
+            return target.accepts_contact(normal, moved.center()).then_some((fraction, point_target + normal*target.radius, normal));
         }
 
-        // Integrate velocity to physics bodies
-        for body in &self.bodies {
-            Self::integrate_physics_velocity(&mut *body.write()?, self.delta_time, self.gravity_force);
+        // Moving along the normal, or parallel to the surface, never closes the gap
+        let closing_speed = -translation.dot(normal);
+        if closing_speed <= 0.0 {
+            return None;
         }
 
-        // Correct physics bodies positions based on manifolds collision information
-        for manifold in &mut self.contacts {
-            Self::correct_physics_positions(manifold, self.penetration_allowance, self.penetration_correction)?;
+        fraction += (gap - 0.5*TOLERANCE)/closing_speed;
+        if fraction > 1.0 {
+            return None;
         }
 
-        // Clear physics bodies forces
-        for body in &self.bodies {
-            let mut body = body.write()?;
-            body.force = Vector2::zero();
-            body.torque = 0.0;
+        for (vertex, &start) in moved.vertices.iter_mut().zip(&cast.vertices) {
+            *vertex = start + translation*fraction;
         }
+    }
 
-        Ok(())
+    None
+}
+
+/// Returns the point on the segment from `v1` to `v2` that is closest to `point`
+fn closest_point_on_segment(point: Vector2, v1: Vector2, v2: Vector2) -> Vector2 {
+    let edge = v2 - v1;
+    let length_sqr = edge.length_sqr();
+
+    if length_sqr <= Real::EPSILON {
+        return v1;
     }
 
-    /// Wrapper to ensure `physics_step` is run with at a fixed time step
-    ///
-    /// # Errors
-    ///
-    /// This method may return a [`PhysicsStepError`] if an error occurs at some point during the physics step.
-    ///
-    /// See [`PhysicsStepError`] for information about the specific errors that can occur.
-    pub fn run_physics_step(&mut self) -> Result<(), PhysicsStepError> {
-        // Calculate current time
-        self.current_time = self.get_curr_time();
+    let t = ((point - v1).dot(edge)/length_sqr).clamp(0.0, 1.0);
+    v1 + edge*t
+}
 
-        // Calculate current delta time
-        let delta: f64 = self.current_time - self.start_time;
+/// Returns the extreme point along a direction within a polygon
+fn get_support<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize>(vertex_data: &PolygonData<MAX_VERTICES, CIRCLE_VERTICES>, dir: Vector2) -> Vector2 {
+    let mut best_projection = -Real::MIN_POSITIVE;
+    let mut best_vertex = Vector2 { x: 0.0, y: 0.0 };
 
-        // Store the time elapsed since the last frame began
-        self.accumulator += delta;
+    for i in 0..vertex_data.vertex_count {
+        let vertex = vertex_data.positions[i];
+        let projection = vertex.dot(dir);
 
-        // Fixed time stepping loop
-        while self.accumulator >= self.delta_time {
-            self.physics_step()?;
-            self.accumulator -= self.delta_time;
+        if projection > best_projection {
+            best_vertex = vertex;
+            best_projection = projection;
         }
+    }
 
-        // Record the starting of this frame
-        self.start_time = self.current_time;
+    best_vertex
+}
 
-        Ok(())
-    }
+/// Finds polygon shapes axis least penetration
+fn find_axis_least_penetration<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize>(
+    face_index: &mut usize,
+    body_a: &PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>,
+    body_b: &PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>,
+) -> Real {
+    let shape_a = &body_a.shape;
+    let shape_b = &body_b.shape;
 
-    /// Sets the time step of the physics simulation
-    pub fn set_physics_time_step(&mut self, delta: f64) {
-        self.delta_time = delta;
-    }
+    let PHYSICS_POLYGON { vertex_data: data_a, transform: transform_a } = &shape_a else { unreachable!("only polygons should be passed to find_axis_least_penetration") };
+    let PHYSICS_POLYGON { vertex_data: data_b, transform: transform_b } = &shape_b else { unreachable!("only polygons should be passed to find_axis_least_penetration") };
 
-    /// Finds a valid index for a new physics body initialization
-    fn find_available_body_index(&self) -> Option<u32> {
-        let mut index = None;
-        for i in 0..self.bodies.capacity() as u32 {
-            let mut current_id = i;
+    let mut best_distance = Real::MIN;
+    let mut best_index = 0;
 
-            // Check if current id already exist in other physics body
-            for body in &self.bodies {
-                if body.borrow().id == current_id {
-                    current_id += 1;
-                    break;
-                }
-            }
+    for i in 0..data_a.vertex_count {
+        // Retrieve a face normal from A shape
+        let mut normal = data_a.normals[i];
+        let trans_normal = transform_a.multiply_vector2(normal);
 
-            // If it is not used, use it as new physics body id
-            if current_id == i {
-                index = Some(i);
-                break;
-            }
-        }
+        // Transform face normal into B shape's model space
+        let bu_t = transform_b.transpose();
+        normal = bu_t.multiply_vector2(trans_normal);
 
-        index
-    }
+        // Retrieve support point from B shape along -n
+        let support = get_support(data_b, Vector2 { x: -normal.x, y: -normal.y });
 
-    /// Finds a valid index for a new manifold initialization
-    fn find_available_manifold_index(&self) -> Option<u32> {
-        (self.contacts.len() as u32).checked_add(1)
-            .filter(|id| *id < self.contacts.capacity() as u32)
+        // Retrieve vertex on face from A shape, transform into B shape's model space
+        let mut vertex = data_a.positions[i];
+        vertex = transform_a.multiply_vector2(vertex);
+        vertex += body_a.position;
+        vertex -= body_b.position;
+        vertex = bu_t.multiply_vector2(vertex);
+
+        // Compute penetration distance in B shape's model space
+        let distance = normal.dot(support - vertex);
+
+        // Store greatest distance
+        if distance > best_distance {
+            best_distance = distance;
+            best_index = i;
+        }
     }
 
-    /// Creates a new physics manifold to solve collision
-    fn create_physics_manifold(&mut self, a: Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>, b: Strong<PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>>) -> Option<&mut PhysicsManifoldData<MAX_VERTICES, CIRCLE_VERTICES>> {
-        if let Some(new_id) = self.find_available_manifold_index() {
-            let mut new_manifold = PhysicsManifoldData::new(a, b);
+    *face_index = best_index;
+    best_distance
+}
+
+/// Finds two polygon shapes incident face
+fn find_incident_face<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize>(ref_body: &PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>, inc_body: &PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>, index: usize) -> ([Vector2; 2], usize) {
+    let ref_shape = &ref_body.shape;
+    let inc_shape = &inc_body.shape;
+
+    let PHYSICS_POLYGON { vertex_data: ref_data, transform: ref_transform } = &ref_shape else { unreachable!("only polygons should be passed to find_incident_face") };
+    let PHYSICS_POLYGON { vertex_data: inc_data, transform: inc_transform } = &inc_shape else { unreachable!("only polygons should be passed to find_incident_face") };
+
+    let mut reference_normal = ref_data.normals[index];
+
+    // Calculate normal in incident's frame of reference
+    reference_normal = ref_transform.multiply_vector2(reference_normal); // To world space
+    reference_normal = inc_transform.transpose().multiply_vector2(reference_normal); // To incident's model space
+
+    // Find most anti-normal face on polygon
+    let mut incident_face = 0;
+    let mut min_dot = Real::MAX;
 
-            // Initialize new manifold with generic values
-            new_manifold.id = new_id;
-            new_manifold.penetration = 0.0;
-            new_manifold.normal = Vector2::zero();
-            new_manifold.contacts[0] = Vector2::zero();
-            new_manifold.contacts[1] = Vector2::zero();
-            new_manifold.contacts_count = 0;
-            new_manifold.restitution = 0.0;
-            new_manifold.dynamic_friction = 0.0;
-            new_manifold.static_friction = 0.0;
+    for i in 0..inc_data.vertex_count {
+        let dot = reference_normal.dot(inc_data.normals[i]);
 
-            // Add new body to bodies pointers array and update bodies count
-            self.contacts.push(new_manifold);
-            Some(&mut *self.contacts.last_mut().expect("should have at least one element after push"))
-        } else {
-            debug_print!("[PHYSAC] new physics manifold creation failed because there isn't any available id to use");
-            None
+        if dot < min_dot {
+            min_dot = dot;
+            incident_face = i;
         }
     }
 
-    /// Integrates physics forces into velocity
-    fn integrate_physics_forces(body: &mut PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>, delta_time: f64, gravity_force: Vector2) {
-        if (body.inverse_mass == 0.0) || !body.enabled {
-            return;
-        }
+    // Assign face vertices for incident face
+    let v0 = inc_transform.multiply_vector2(inc_data.positions[incident_face]) + inc_body.position;
+    let v1 = inc_transform.multiply_vector2(inc_data.positions[next_idx(incident_face, inc_data.vertex_count)]) + inc_body.position;
+    ([v0, v1], incident_face)
+}
 
-        body.velocity.x += (f64::from(body.force.x*body.inverse_mass)*(delta_time/2.0)) as f32;
-        body.velocity.y += (f64::from(body.force.y*body.inverse_mass)*(delta_time/2.0)) as f32;
+/// Returns the velocity of the contact point on `body_b` relative to the one on `body_a`, where `radius_a` and `radius_b` point from each body to the contact
+fn contact_relative_velocity<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize>(body_a: &PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>, body_b: &PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>, radius_a: Vector2, radius_b: Vector2) -> Vector2 {
+    body_b.velocity + math_cross(body_b.angular_velocity, radius_b) - body_a.velocity - math_cross(body_a.angular_velocity, radius_a)
+}
 
-        if body.use_gravity {
-            body.velocity.x += (f64::from(gravity_force.x)*(delta_time/1000.0/2.0)) as f32;
-            body.velocity.y += (f64::from(gravity_force.y)*(delta_time/1000.0/2.0)) as f32;
+/// Returns the inverse of the mass the bodies of a contact have along `direction` at the contact point
+///
+/// Kinematic bodies can't be pushed, so the whole impulse goes into matching the other body to their velocity
+fn contact_inverse_mass<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize>(body_a: &PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>, body_b: &PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>, radius_a: Vector2, radius_b: Vector2, direction: Vector2) -> Real {
+    let inverse_mass = |body: &PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>, radius: Vector2| {
+        if body.is_kinematic && !body.enabled {
+            return 0.0;
         }
+        let r_cross_d = math_cross_vector2(radius, direction);
+        body.inverse_mass + (r_cross_d*r_cross_d)*body.inverse_inertia
+    };
+    inverse_mass(body_a, radius_a) + inverse_mass(body_b, radius_b)
+}
 
-        if !body.freeze_orient {
-            body.angular_velocity += (f64::from(body.torque)*f64::from(body.inverse_inertia)*(delta_time/2.0)) as f32;
+/// Applies `impulse` to `body_b` and the opposite impulse to `body_a` at the contact point
+fn apply_contact_impulse<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize>(body_a: &mut PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>, body_b: &mut PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>, radius_a: Vector2, radius_b: Vector2, impulse: Vector2) {
+    if body_a.enabled {
+        body_a.velocity -= impulse*body_a.inverse_mass;
+
+        if !body_a.freeze_orient {
+            body_a.angular_velocity += body_a.inverse_inertia*math_cross_vector2(radius_a, -impulse);
         }
     }
 
-    /// Initializes physics manifolds to solve collisions
-    fn initialize_physics_manifolds(manifold: &mut PhysicsManifoldData<MAX_VERTICES, CIRCLE_VERTICES>, delta_time: f64, gravity_force: Vector2) -> Result<(), PhysicsStepError> {
-        let body_a = manifold.body_a.read()?;
-        let body_b = manifold.body_b.read()?;
+    if body_b.enabled {
+        body_b.velocity += impulse*body_b.inverse_mass;
 
-        // Calculate average restitution, static and dynamic friction
-        manifold.restitution = (body_a.restitution*body_b.restitution).sqrt();
-        manifold.static_friction = (body_a.static_friction*body_b.static_friction).sqrt();
-        manifold.dynamic_friction = (body_a.dynamic_friction*body_b.dynamic_friction).sqrt();
+        if !body_b.freeze_orient {
+            body_b.angular_velocity += body_b.inverse_inertia*math_cross_vector2(radius_b, impulse);
+        }
+    }
+}
 
-        for i in 0..manifold.contacts_count {
-            // Caculate radius from center of mass to contact
-            let radius_a = manifold.contacts[i as usize] - body_a.position;
-            let radius_b = manifold.contacts[i as usize] - body_b.position;
+/// Calculates clipping based on a normal and two faces
+///
+/// `features` follow the points of the faces, and points created by the clip are given `clip_feature`
+fn clip(normal: Vector2, clip: Real, face_a: &mut Vector2, face_b: &mut Vector2, features: &mut [u32; 2], clip_feature: u32) -> usize {
+    let mut sp = 0;
+    let mut out = [*face_a, *face_b];
+    let mut out_features = *features;
 
-            let cross_a = math_cross(body_a.angular_velocity, radius_a);
-            let cross_b = math_cross(body_b.angular_velocity, radius_b);
+    // Retrieve distances from each endpoint to the line
+    let distance_a = normal.dot(*face_a) - clip;
+    let distance_b = normal.dot(*face_b) - clip;
 
-            let radius_v = Vector2 {
-                x: body_b.velocity.x + cross_b.x - body_a.velocity.x - cross_a.x,
-                y: body_b.velocity.y + cross_b.y - body_a.velocity.y - cross_a.y,
-            };
+    // If negative (behind plane)
+    if distance_a <= 0.0 {
+        out[sp] = *face_a;
+        out_features[sp] = features[0];
+        sp += 1;
+    }
 
-            // Determine if we should perform a resting collision or not;
-            // The idea is if the only thing moving this object is gravity, then the collision should be performed without any restitution
-            if radius_v.length_sqr() < ((Vector2 { x: gravity_force.x*delta_time as f32/1000.0, y: gravity_force.y*delta_time as f32/1000.0, }).length_sqr() + f32::EPSILON) {
-                manifold.restitution = 0.0;
-            }
-        }
+    if distance_b <= 0.0 {
+        out[sp] = *face_b;
+        out_features[sp] = features[1];
+        sp += 1;
+    }
 
-        Ok(())
+    // If the points are on different sides of the plane
+    if (distance_a*distance_b) < 0.0 {
+        // Push intersection point
+        let alpha = distance_a/(distance_a - distance_b);
+        out[sp] = *face_a;
+        let mut delta = *face_b - *face_a;
+        delta.x *= alpha;
+        delta.y *= alpha;
+        out[sp] += delta;
+        out_features[sp] = clip_feature;
+        sp += 1;
     }
 
-    /// Integrates physics collisions impulses to solve collisions
-    fn integrate_physics_impulses(manifold: &mut PhysicsManifoldData<MAX_VERTICES, CIRCLE_VERTICES>) -> Result<(), PhysicsStepError> {
-        let mut body_a = manifold.body_a.write()?;
-        let mut body_b = manifold.body_b.write()?;
+    // Assign the new converted values
+    *face_a = out[0];
+    *face_b = out[1];
+    *features = out_features;
 
-        // Early out and positional correct if both objects have infinite mass
-        if (body_a.inverse_mass + body_b.inverse_mass).abs() <= f32::EPSILON {
-            body_a.velocity = Vector2::zero();
-            body_b.velocity = Vector2::zero();
-            return Ok(());
-        }
+    sp
+}
 
-        for i in 0..manifold.contacts_count {
-            // Calculate radius from center of mass to contact
-            let radius_a = manifold.contacts[i as usize] - body_a.position;
-            let radius_b = manifold.contacts[i as usize] - body_b.position;
+/// Check if values are between bias range
+fn bias_greater_than(value_a: Real, value_b: Real) -> bool {
+    value_a >= (value_b*0.95 + value_a*0.01)
+}
 
-            // Calculate relative velocity
-            let mut radius_v = Vector2::zero();
-            radius_v.x = body_b.velocity.x + math_cross(body_b.angular_velocity, radius_b).x - body_a.velocity.x - math_cross(body_a.angular_velocity, radius_a).x;
-            radius_v.y = body_b.velocity.y + math_cross(body_b.angular_velocity, radius_b).y - body_a.velocity.y - math_cross(body_a.angular_velocity, radius_a).y;
+/// Returns the signed area of a polygon (positive if it winds clockwise on screen) and its centroid
+fn polygon_area_centroid(points: &[Vector2]) -> (Real, Vector2) {
+    let mut area = 0.0;
+    let mut center = Vector2::zero();
+    for i in 0..points.len() {
+        let (p1, p2) = (points[i], points[next_idx(i, points.len())]);
+        let cross = math_cross_vector2(p1, p2);
+        area += cross/2.0;
+        center += (p1 + p2)*(cross/6.0);
+    }
 
-            // Relative velocity along the normal
-            let contact_velocity = radius_v.dot(manifold.normal);
+    if area != 0.0 {
+        center *= 1.0/area;
+    }
 
-            // Do not resolve if velocities are separating
-            if contact_velocity > 0.0 {
-                return Ok(());
+    (area, center)
+}
+
+/// Returns the convex hull of a set of points (Andrew's monotone chain)
+fn convex_hull(mut points: Vec<Vector2>) -> Vec<Vector2> {
+    points.sort_by(|a, b| a.x.total_cmp(&b.x).then(a.y.total_cmp(&b.y)));
+    points.dedup();
+    if points.len() < 3 {
+        return points;
+    }
+
+    let mut hull: Vec<Vector2> = Vec::with_capacity(points.len() + 1);
+    for pass in 0..2 {
+        let start = hull.len();
+        let half: Box<dyn Iterator<Item = &Vector2>> = if pass == 0 { Box::new(points.iter()) } else { Box::new(points.iter().rev()) };
+        for &point in half {
+            while hull.len() >= start + 2 && math_cross_vector2(hull[hull.len() - 1] - hull[hull.len() - 2], point - hull[hull.len() - 2]) <= 0.0 {
+                hull.pop();
             }
+            hull.push(point);
+        }
+        // The last point of each half is the first point of the other
+        hull.pop();
+    }
 
-            let ra_cross_n = math_cross_vector2(radius_a, manifold.normal);
-            let rb_cross_n = math_cross_vector2(radius_b, manifold.normal);
+    hull
+}
 
-            let inverse_mass_sum = body_a.inverse_mass + body_b.inverse_mass + (ra_cross_n*ra_cross_n)*body_a.inverse_inertia + (rb_cross_n*rb_cross_n)*body_b.inverse_inertia;
+/// Clips a convex polygon to the side of a line where `side` is not negative (Sutherland-Hodgman)
+fn clip_polygon<F: Fn(Vector2) -> Real>(points: &[Vector2], side: F) -> Vec<Vector2> {
+    let mut result = Vec::with_capacity(points.len() + 1);
 
-            // Calculate impulse scalar value
-            let mut impulse = -(1.0 + manifold.restitution)*contact_velocity;
-            impulse /= inverse_mass_sum;
-            impulse /= manifold.contacts_count as f32;
+    for i in 0..points.len() {
+        let (p1, p2) = (points[i], points[next_idx(i, points.len())]);
+        let (d1, d2) = (side(p1), side(p2));
 
-            // Apply impulse to each physics body
-            let impulse_v = manifold.normal*impulse;
+        if d1 >= 0.0 {
+            result.push(p1);
+        }
+        if (d1 >= 0.0) != (d2 >= 0.0) {
+            result.push(p1 + (p2 - p1)*(d1/(d1 - d2)));
+        }
+    }
 
-            if body_a.enabled {
-                body_a.velocity.x += body_a.inverse_mass*(-impulse_v.x);
-                body_a.velocity.y += body_a.inverse_mass*(-impulse_v.y);
+    result
+}
 
-                if !body_a.freeze_orient {
-                    body_a.angular_velocity += body_a.inverse_inertia*math_cross_vector2(radius_a, -impulse_v);
-                }
-            }
+/// Subtracts the convex polygon `hole` from the convex polygon `points`, both in the winding order used by Physac
+///
+/// Returns the convex pieces that are left (dropping slivers with less than half a square unit of area),
+/// or [`None`] if the polygons don't overlap
+fn subtract_convex(points: &[Vector2], hole: &[Vector2]) -> Option<Vec<Vec<Vector2>>> {
+    let inside_edge = |k: usize| {
+        let (q1, q2) = (hole[k], hole[next_idx(k, hole.len())]);
+        move |p: Vector2| math_cross_vector2(q2 - q1, p - q1)
+    };
+    let area = |piece: &[Vector2]| {
+        (0..piece.len()).map(|i| math_cross_vector2(piece[i], piece[next_idx(i, piece.len())])).sum::<Real>().abs()/2.0
+    };
 
-            if body_b.enabled {
-                body_b.velocity.x += body_b.inverse_mass*(impulse_v.x);
-                body_b.velocity.y += body_b.inverse_mass*(impulse_v.y);
+    let overlap = (0..hole.len()).fold(points.to_vec(), |piece, k| clip_polygon(&piece, inside_edge(k)));
+    if area(&overlap) < 0.5 {
+        return None;
+    }
 
-                if !body_b.freeze_orient {
-                    body_b.angular_velocity += body_b.inverse_inertia*math_cross_vector2(radius_b, impulse_v);
-                }
-            }
+    // Every piece is outside of one edge of the hole, and inside of all of the edges before it, so that no pieces overlap
+    let mut pieces = Vec::new();
+    let mut remaining = points.to_vec();
+    for k in 0..hole.len() {
+        let edge = inside_edge(k);
+        let piece = clip_polygon(&remaining, |p| -edge(p));
+        if piece.len() >= 3 && area(&piece) >= 0.5 {
+            pieces.push(piece);
+        }
+        remaining = clip_polygon(&remaining, edge);
+        if remaining.len() < 3 {
+            break;
+        }
+    }
 
-            // Apply friction impulse to each physics body
-            radius_v.x = body_b.velocity.x + math_cross(body_b.angular_velocity, radius_b).x - body_a.velocity.x - math_cross(body_a.angular_velocity, radius_a).x;
-            radius_v.y = body_b.velocity.y + math_cross(body_b.angular_velocity, radius_b).y - body_a.velocity.y - math_cross(body_a.angular_velocity, radius_a).y;
+    Some(pieces)
+}
 
-            let mut tangent = Vector2 {
-                x: radius_v.x - (manifold.normal.x*radius_v.dot(manifold.normal)),
-                y: radius_v.y - (manifold.normal.y*radius_v.dot(manifold.normal)),
-            };
-            math_normalize(&mut tangent);
+/// A small, fast pseudorandom number generator, so that randomness can be seeded without any dependencies
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+struct SplitMix64 {
+    /// Current state
+    state: u64,
+}
+impl SplitMix64 {
+    /// Construct a new generator from a seed
+    const fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
 
-            // Calculate impulse tangent magnitude
-            let mut impulse_tangent = -radius_v.dot(tangent);
-            impulse_tangent /= inverse_mass_sum;
-            impulse_tangent /= manifold.contacts_count as f32;
+    /// Returns the next pseudorandom number
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
 
-            let abs_impulse_tangent = impulse_tangent.abs();
+    /// Returns the next pseudorandom number between 0 and 1
+    fn next_real(&mut self) -> Real {
+        (self.next_u64() >> 40) as Real/(1u64 << 24) as Real
+    }
+}
 
-            // Don't apply tiny friction impulses
-            if abs_impulse_tangent <= f32::EPSILON {
-                return Ok(());
-            }
+/// Drawing the world with raylib while debugging, see [`Physac::debug_draw`]
+#[cfg(feature = "raylib")]
+pub mod debug_draw {
+    use raylib::prelude::{Color, RaylibDraw};
+    use super::{
+        Physac, PhysicsShape, Mat2, Real, Vector2, shape_vertices_count, shape_vertex, fixture_placement,
+        PHYSICS_COMPOUND, PHYSICS_CHAIN, PHYSICS_HEIGHTFIELD,
+    };
 
-            // Apply coulumb's law
-            let tangent_impulse = if abs_impulse_tangent < impulse*manifold.static_friction {
-                Vector2 { x: tangent.x*impulse_tangent, y: tangent.y*impulse_tangent }
-            } else {
-                Vector2 { x: tangent.x*-impulse*manifold.dynamic_friction, y: tangent.y*-impulse*manifold.dynamic_friction }
-            };
+    /// Milliseconds of motion drawn for each body velocity
+    const VELOCITY_LOOKAHEAD: Real = 100.0;
+    /// Length of the collision normal drawn at each contact point
+    const NORMAL_LENGTH: Real = 10.0;
+    /// Radius of the dots drawn at contact points and joint anchors
+    const POINT_RADIUS: Real = 3.0;
 
-            // Apply friction impulse
-            if body_a.enabled {
-                body_a.velocity.x += body_a.inverse_mass*(-tangent_impulse.x);
-                body_a.velocity.y += body_a.inverse_mass*(-tangent_impulse.y);
+    /// Parts of the world drawn by [`Physac::debug_draw`]
+    ///
+    /// The default flags draw the shapes, the sleep state and the joints
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    #[allow(
+        clippy::struct_excessive_bools,
+        reason = "these are flags, not states",
+    )]
+    pub struct DebugDrawFlags {
+        /// Outlines of the bodies, in green for moving bodies, blue for static and kinematic bodies and yellow for sensors
+        pub shapes: bool,
+        /// Bounding boxes of the bodies, in dark gray
+        pub aabbs: bool,
+        /// Contact points from the last step, in red, with their collision normals in orange
+        pub contacts: bool,
+        /// Joint anchors and the lines between them, in purple
+        pub joints: bool,
+        /// Outlines of sleeping bodies in gray instead of their usual color
+        pub sleep_state: bool,
+        /// Velocities of the bodies, in magenta, as lines to where each body will be in 100 milliseconds
+        pub velocities: bool,
+    }
+    impl DebugDrawFlags {
+        /// Draw everything
+        pub const ALL: Self = Self {
+            shapes: true,
+            aabbs: true,
+            contacts: true,
+            joints: true,
+            sleep_state: true,
+            velocities: true,
+        };
 
-                if !body_a.freeze_orient {
-                    body_a.angular_velocity += body_a.inverse_inertia*math_cross_vector2(radius_a, -tangent_impulse);
+        /// Draw nothing, for turning on single parts
+        pub const NONE: Self = Self {
+            shapes: false,
+            aabbs: false,
+            contacts: false,
+            joints: false,
+            sleep_state: false,
+            velocities: false,
+        };
+    }
+    impl Default for DebugDrawFlags {
+        fn default() -> Self {
+            Self {
+                shapes: true,
+                joints: true,
+                sleep_state: true,
+                ..Self::NONE
+            }
+        }
+    }
+
+    impl<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize> Physac<MAX_VERTICES, CIRCLE_VERTICES> {
+        /// Draws the parts of the world chosen by `flags`, to see what the physics is doing
+        ///
+        /// Draw inside a 2D mode to follow a camera around the world
+        ///
+        /// # Examples
+        ///
+        /// ```no_run
+        /// use raylib::prelude::*;
+        /// use physac::prelude::*;
+        ///
+        /// let (mut rl, thread) = raylib::init().size(640, 480).title("Debug draw").build();
+        /// let mut ph = init_physics::<24, 24>().build();
+        ///
+        /// ph.borrow_mut().create_physics_body_circle(Vector2 { x: 320.0, y: 100.0 }, 20.0, 1.0);
+        /// ph.borrow_mut()
+        ///     .create_physics_body_rectangle(Vector2 { x: 320.0, y: 450.0 }, 600.0, 40.0, 1.0)
+        ///     .borrow_mut()
+        ///     .enabled = false;
+        ///
+        /// while !rl.window_should_close() {
+        ///     let mut d = rl.begin_drawing(&thread);
+        ///     d.clear_background(Color::BLACK);
+        ///     ph.borrow().debug_draw(&mut d, DebugDrawFlags { contacts: true, velocities: true, ..Default::default() });
+        /// }
+        /// ```
+        pub fn debug_draw(&self, d: &mut impl RaylibDraw, flags: DebugDrawFlags) {
+            for body in &self.bodies {
+                let body = body.borrow();
+                if flags.aabbs {
+                    draw_polyline(d, body.bounds().corners(), true, Color::DARKGRAY);
+                }
+                if flags.shapes {
+                    let color = if flags.sleep_state && body.is_sleeping {
+                        Color::GRAY
+                    } else if body.is_sensor {
+                        Color::YELLOW
+                    } else if !body.enabled || body.is_kinematic {
+                        Color::SKYBLUE
+                    } else {
+                        Color::GREEN
+                    };
+                    draw_shape(d, &body.shape, body.position, body.shape_transform(), color);
+                }
+                if flags.velocities && body.velocity != Vector2::zero() {
+                    d.draw_line_v(body.position, body.position + body.velocity*VELOCITY_LOOKAHEAD, Color::MAGENTA);
                 }
             }
 
-            if body_b.enabled {
-                body_b.velocity.x += body_b.inverse_mass*(tangent_impulse.x);
-                body_b.velocity.y += body_b.inverse_mass*(tangent_impulse.y);
+            if flags.contacts {
+                for contact in &self.contacts {
+                    for &point in &contact.contacts[..contact.contacts_count as usize] {
+                        d.draw_line_v(point, point + contact.normal*NORMAL_LENGTH, Color::ORANGE);
+                        d.draw_circle_v(point, POINT_RADIUS, Color::RED);
+                    }
+                }
+            }
 
-                if !body_b.freeze_orient {
-                    body_b.angular_velocity += body_b.inverse_inertia*math_cross_vector2(radius_b, tangent_impulse);
+            if flags.joints {
+                for joint in &self.joints {
+                    let joint = joint.borrow();
+                    let (anchor_a, anchor_b) = (joint.anchor_a(), joint.anchor_b());
+                    d.draw_line_v(anchor_a, anchor_b, Color::PURPLE);
+                    d.draw_circle_v(anchor_a, POINT_RADIUS, Color::PURPLE);
+                    d.draw_circle_v(anchor_b, POINT_RADIUS, Color::PURPLE);
                 }
             }
         }
-
-        Ok(())
     }
 
-    /// Integrates physics velocity into position and forces
-    fn integrate_physics_velocity(body: &mut PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>, delta_time: f64, gravity_force: Vector2) {
-        if !body.enabled {
-            return;
+    impl<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize> Physac<MAX_VERTICES, CIRCLE_VERTICES> {
+        /// Draws the [`Physac::stats`] of the last step as lines of text, starting from the top left corner at `x`, `y`
+        pub fn debug_draw_stats(&self, d: &mut impl RaylibDraw, x: i32, mut y: i32, font_size: i32) {
+            for line in self.stats().to_string().lines() {
+                d.draw_text(line, x, y, font_size, Color::LIME);
+                y += font_size;
+            }
         }
+    }
 
-        body.position.x += (f64::from(body.velocity.x)*delta_time) as f32;
-        body.position.y += (f64::from(body.velocity.y)*delta_time) as f32;
-
-        if !body.freeze_orient {
-            body.orient += (f64::from(body.angular_velocity)*delta_time) as f32;
+    /// Draws the outline of a shape placed at `position` and rotated by `transform`, with each fixture of a compound shape drawn on its own
+    fn draw_shape<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize>(
+        d: &mut impl RaylibDraw,
+        shape: &PhysicsShape<MAX_VERTICES, CIRCLE_VERTICES>,
+        position: Vector2,
+        transform: Mat2,
+        color: Color,
+    ) {
+        let vertices = (0..shape_vertices_count(shape)).filter_map(|vertex| shape_vertex(shape, position, transform, vertex));
+        match shape {
+            PHYSICS_COMPOUND { fixtures } => {
+                for fixture in fixtures {
+                    let (fixture_position, fixture_transform) = fixture_placement(fixture, position, transform);
+                    draw_shape(d, &fixture.shape, fixture_position, fixture_transform, color);
+                }
+            }
+            // Chains and heightfields are open lines rather than loops
+            PHYSICS_CHAIN { .. } | PHYSICS_HEIGHTFIELD { .. } => draw_polyline(d, vertices, false, color),
+            _ => draw_polyline(d, vertices, true, color),
         }
+    }
 
-        let orient = body.orient;
-        if let PHYSICS_POLYGON { transform, .. } = &mut body.shape {
-            transform.set(orient);
+    /// Draws lines between consecutive points, and from the last point back to the first if `closed`
+    fn draw_polyline(d: &mut impl RaylibDraw, points: impl IntoIterator<Item = Vector2>, closed: bool, color: Color) {
+        let mut points = points.into_iter();
+        let Some(first) = points.next() else { return };
+        let last = points.fold(first, |previous, point| {
+            d.draw_line_v(previous, point, color);
+            point
+        });
+        if closed {
+            d.draw_line_v(last, first, color);
         }
-
-        Self::integrate_physics_forces(body, delta_time, gravity_force);
     }
+}
+#[cfg(feature = "raylib")]
+pub use self::debug_draw::DebugDrawFlags;
 
-    /// Corrects physics bodies positions based on manifolds collision information
-    fn correct_physics_positions(manifold: &mut PhysicsManifoldData<MAX_VERTICES, CIRCLE_VERTICES>, penetration_allowance: f32, penetration_correction: f32) -> Result<(), PhysicsStepError> {
-        let mut body_a = manifold.body_a.write()?;
-        let mut body_b = manifold.body_b.write()?;
+/// Looking at and tuning physics bodies while the simulation runs, with an egui panel (see [`inspector::Inspector`])
+#[cfg(feature = "inspector")]
+pub mod inspector {
+    use egui::{DragValue, Grid, ScrollArea, Ui};
+    use super::{Physac, PhysicsBodyData, BodyHandle, Real, Vector2};
 
-        let inverse_mass_sum = body_a.inverse_mass + body_b.inverse_mass;
-        if inverse_mass_sum == 0.0 {
-            return Err(PhysicsStepError::DivByZero);
+    /// An egui panel that lists the physics bodies of a world and live-edits the selected one
+    ///
+    /// Keep the same inspector from frame to frame, so that it remembers which body is selected.
+    /// Mass, friction and restitution are edited through the material of the body (see [`PhysicsBodyData::set_material`])
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use physac::prelude::*;
+    /// let mut ph = init_physics::<24, 24>().build();
+    /// ph.borrow_mut().create_physics_body_circle(Vector2 { x: 100.0, y: 100.0 }, 10.0, 1.0);
+    ///
+    /// let mut inspector = Inspector::new();
+    ///
+    /// // Select the body the mouse clicked on, converted to world space
+    /// let clicked = Vector2 { x: 104.0, y: 98.0 };
+    /// assert!(inspector.select_at(&ph.borrow(), clicked).is_some());
+    ///
+    /// // Every frame
+    /// let ctx = egui::Context::default();
+    /// let output = ctx.run_ui(egui::RawInput::default(), |ui| inspector.ui(ui, &ph.borrow()));
+    /// # output.drop_without_applying_deltas();
+    /// ```
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+    pub struct Inspector {
+        /// Body being edited
+        selected: Option<BodyHandle>,
+    }
+    impl Inspector {
+        /// Construct an inspector with no body selected
+        #[must_use]
+        pub const fn new() -> Self {
+            Self { selected: None }
+        }
+
+        /// Returns the selected body, which may have been destroyed since it was selected
+        #[must_use]
+        pub const fn selected(&self) -> Option<BodyHandle> {
+            self.selected
         }
 
-        let correction = Vector2 {
-            x: ((manifold.penetration - penetration_allowance).max(0.0)/inverse_mass_sum)*manifold.normal.x*penetration_correction,
-            y: ((manifold.penetration - penetration_allowance).max(0.0)/inverse_mass_sum)*manifold.normal.y*penetration_correction,
-        };
-
-        if body_a.enabled {
-            body_a.position.x -= correction.x*body_a.inverse_mass;
-            body_a.position.y -= correction.y*body_a.inverse_mass;
+        /// Selects a body to edit, or clears the selection
+        pub const fn select(&mut self, body: Option<BodyHandle>) {
+            self.selected = body;
         }
 
-        if body_b.enabled {
-            body_b.position.x += correction.x*body_b.inverse_mass;
-            body_b.position.y += correction.y*body_b.inverse_mass;
+        /// Selects the body under `point` in the world (such as where the mouse was clicked), or clears the selection if there isn't one
+        ///
+        /// Returns the newly selected body
+        pub fn select_at<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize>(&mut self, ph: &Physac<MAX_VERTICES, CIRCLE_VERTICES>, point: impl Into<Vector2>) -> Option<BodyHandle> {
+            let point = point.into();
+            self.selected = ph.query_point(point).first().and_then(|body| ph.body_handle(body));
+            self.selected
         }
 
-        Ok(())
-    }
+        /// Shows the list of bodies and the editor of the selected body
+        pub fn ui<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize>(&mut self, ui: &mut Ui, ph: &Physac<MAX_VERTICES, CIRCLE_VERTICES>) {
+            ui.label(format!("{} bodies", ph.get_physics_bodies_count()));
+            ScrollArea::vertical().id_salt("physac_inspector_bodies").max_height(160.0).show(ui, |ui| {
+                for body in ph.strong_physics_body_iter() {
+                    let handle = ph.body_handle(body);
+                    let label = body.borrowed(|body| {
+                        if body.name().is_empty() { format!("#{}", body.id) } else { format!("#{} {}", body.id, body.name()) }
+                    });
+                    if ui.selectable_label(handle.is_some() && handle == self.selected, label).clicked() {
+                        self.selected = handle;
+                    }
+                }
+            });
+            ui.separator();
 
-    /// Initializes hi-resolution MONOTONIC timer
-    fn init_timer(&mut self) {
-        self.base_time = Instant::now(); // Get MONOTONIC clock time offset
-        self.start_time = self.get_curr_time(); // Get current time
+            if let Some(body) = self.selected.and_then(|handle| ph.get_body(handle)) {
+                body.borrowed_mut(|body| body_ui(ui, body));
+            } else {
+                self.selected = None;
+                ui.label("Click a body to inspect it");
+            }
+        }
     }
 
-    /// Get current time in milliseconds
-    fn get_curr_time(&self) -> f64 {
-        let duration = self.base_time.elapsed();
-        duration.as_secs_f64() * 1_000.0
+    /// Shows the properties of a body, applying any edits
+    fn body_ui<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize>(ui: &mut Ui, body: &mut PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>) {
+        Grid::new("physac_inspector_body").num_columns(2).show(ui, |ui| {
+            ui.label("Position");
+            ui.label(format!("{:.1}, {:.1}", body.position.x, body.position.y));
+            ui.end_row();
+
+            ui.label("State");
+            ui.label(if body.is_sleeping() { "asleep" } else { "awake" });
+            ui.end_row();
+
+            // The material scales the mass and inertia together, so that the body keeps the same shape of mass
+            let mut material = body.material();
+            let mut mass = body.mass;
+            let mut changed = false;
+            ui.label("Mass");
+            if ui.add(DragValue::new(&mut mass).speed(0.1).range(0.001..=Real::MAX)).changed() && body.mass > 0.0 {
+                material.density *= mass/body.mass;
+                changed = true;
+            }
+            ui.end_row();
+            ui.label("Static friction");
+            changed |= ui.add(DragValue::new(&mut material.static_friction).speed(0.01).range(0.0..=Real::MAX)).changed();
+            ui.end_row();
+            ui.label("Dynamic friction");
+            changed |= ui.add(DragValue::new(&mut material.dynamic_friction).speed(0.01).range(0.0..=Real::MAX)).changed();
+            ui.end_row();
+            ui.label("Restitution");
+            changed |= ui.add(DragValue::new(&mut material.restitution).speed(0.01).range(0.0..=1.0)).changed();
+            ui.end_row();
+            if changed {
+                body.set_material(&material);
+            }
+
+            let mut velocity = body.velocity;
+            ui.label("Velocity");
+            let velocity_changed = ui.horizontal(|ui| {
+                ui.add(DragValue::new(&mut velocity.x).speed(0.01)).changed() |
+                ui.add(DragValue::new(&mut velocity.y).speed(0.01)).changed()
+            }).inner;
+            if velocity_changed {
+                body.set_linear_velocity(velocity);
+            }
+            ui.end_row();
+
+            let mut angular_velocity = body.angular_velocity;
+            ui.label("Angular velocity");
+            if ui.add(DragValue::new(&mut angular_velocity).speed(0.001)).changed() {
+                body.set_angular_velocity(angular_velocity);
+            }
+            ui.end_row();
+
+            ui.label("Gravity scale");
+            let mut flags_changed = ui.add(DragValue::new(&mut body.gravity_scale).speed(0.01)).changed();
+            ui.end_row();
+
+            ui.label("Flags");
+            ui.vertical(|ui| {
+                flags_changed |= ui.checkbox(&mut body.enabled, "Dynamic").changed();
+                flags_changed |= ui.checkbox(&mut body.is_kinematic, "Kinematic").changed();
+                flags_changed |= ui.checkbox(&mut body.use_gravity, "Gravity").changed();
+                flags_changed |= ui.checkbox(&mut body.is_sensor, "Sensor").changed();
+                flags_changed |= ui.checkbox(&mut body.freeze_orient, "Fixed rotation").changed();
+                flags_changed |= ui.checkbox(&mut body.can_sleep, "Can sleep").changed();
+            });
+            ui.end_row();
+
+            // Sleeping bodies wouldn't notice the change until something else woke them up
+            if flags_changed {
+                body.wake_up();
+            }
+        });
     }
 }
+#[cfg(feature = "inspector")]
+pub use self::inspector::Inspector;
+
+/// Running physics in a Bevy app, with bodies moving the [`Transform`](bevy::transform::components::Transform) of their entities (see [`bevy_plugin::PhysacPlugin`])
+#[cfg(feature = "bevy")]
+#[allow(clippy::needless_pass_by_value, reason = "Bevy systems take their parameters by value")]
+pub mod bevy_plugin {
+    use bevy::{
+        app::{App, FixedPostUpdate, Plugin},
+        ecs::{component::Component, message::{Message, MessageWriter}, resource::Resource, system::{Query, Res, ResMut}},
+        math::Quat,
+        transform::components::Transform,
+    };
+    #[cfg(not(feature = "phys_thread"))]
+    use bevy::{app::FixedUpdate, ecs::error::Result, time::Time};
+    use super::{init_physics, BodyHandle, CollisionEvent, Physac, PhysacHandle};
 
-impl<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize> PhysicsManifoldData<MAX_VERTICES, CIRCLE_VERTICES> {
-    /// Solves a created physics manifold between two physics bodies
-    fn solve(&mut self, body_a: &mut PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>, body_b: &mut PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>) {
-        match body_a.shape {
-            PHYSICS_CIRCLE { .. } => match body_b.shape {
-                PHYSICS_CIRCLE { .. } => self.solve_circle_to_circle(body_a, body_b),
-                PHYSICS_POLYGON { .. } => self.solve_circle_to_polygon(body_a, body_b),
-            }
-            PHYSICS_POLYGON { .. } => match body_b.shape {
-                PHYSICS_CIRCLE { .. } => self.solve_polygon_to_circle(body_a, body_b),
-                PHYSICS_POLYGON { .. } => self.solve_polygon_to_polygon(body_a, body_b),
+    /// Adds a [`PhysacWorld`] (unless the app already has one), keeps the [`Transform`] of every entity with a [`PhysacBody`]
+    /// in line with its body, and sends the [`CollisionEvent`]s of the world as Bevy messages
+    ///
+    /// The world is stepped in `FixedUpdate` with the fixed time of the app. With the `phys_thread` feature the physics thread
+    /// already steps the world, so the plugin only keeps transforms and messages up to date.
+    /// Positions are copied as they are, so with the y axis of Bevy pointing up, gravity should point towards negative y
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::time::Duration;
+    /// use bevy::prelude::*;
+    /// use bevy::time::{TimePlugin, TimeUpdateStrategy};
+    /// use physac::prelude::*;
+    ///
+    /// let mut app = App::new();
+    /// app.add_plugins((TimePlugin, PhysacPlugin::<24, 24>))
+    ///     .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(50)));
+    ///
+    /// let platform = {
+    ///     let mut world = app.world_mut().resource_mut::<PhysacWorld>();
+    ///     let mut ph = world.borrow_mut();
+    ///     let platform = ph.create_physics_body_rectangle(Vector2 { x: 100.0, y: -50.0 }, 200.0, 20.0, 1.0).clone();
+    ///     platform.borrow_mut().enabled = false;
+    ///     ph.body_handle(&platform).unwrap()
+    /// };
+    /// let entity = app.world_mut().spawn((PhysacBody(platform), Transform::default())).id();
+    ///
+    /// app.update();
+    /// app.update();
+    ///
+    /// let transform = app.world().get::<Transform>(entity).unwrap();
+    /// assert_eq!(transform.translation, Vec3::new(100.0, -50.0, 0.0));
+    /// ```
+    #[derive(Debug, Clone, Copy, Default)]
+    pub struct PhysacPlugin<const MAX_VERTICES: usize = 24, const CIRCLE_VERTICES: usize = MAX_VERTICES>;
+    impl<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize> Plugin for PhysacPlugin<MAX_VERTICES, CIRCLE_VERTICES> {
+        fn build(&self, app: &mut App) {
+            if !app.world().contains_resource::<PhysacWorld<MAX_VERTICES, CIRCLE_VERTICES>>() {
+                app.insert_resource(PhysacWorld(init_physics::<MAX_VERTICES, CIRCLE_VERTICES>().build()));
             }
+            app.add_message::<CollisionEvent<MAX_VERTICES, CIRCLE_VERTICES>>();
+
+            #[cfg(not(feature = "phys_thread"))]
+            app.add_systems(FixedUpdate, step_physics::<MAX_VERTICES, CIRCLE_VERTICES>);
+            app.add_systems(FixedPostUpdate, (
+                write_transforms::<MAX_VERTICES, CIRCLE_VERTICES>,
+                send_collision_events::<MAX_VERTICES, CIRCLE_VERTICES>,
+            ));
         }
+    }
 
-        // Update physics body grounded state if normal direction is down and grounded state is not set yet in previous manifolds
-        if !body_b.is_grounded {
-            body_b.is_grounded = self.normal.y < 0.0;
+    /// The physics world of the app, as a Bevy resource
+    ///
+    /// Insert one built with [`init_physics()`] before adding the [`PhysacPlugin`] to configure it, otherwise the plugin adds one with the default settings
+    #[derive(Resource)]
+    pub struct PhysacWorld<const MAX_VERTICES: usize = 24, const CIRCLE_VERTICES: usize = MAX_VERTICES>(pub PhysacHandle<Physac<MAX_VERTICES, CIRCLE_VERTICES>>);
+    impl<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize> std::ops::Deref for PhysacWorld<MAX_VERTICES, CIRCLE_VERTICES> {
+        type Target = PhysacHandle<Physac<MAX_VERTICES, CIRCLE_VERTICES>>;
+
+        fn deref(&self) -> &Self::Target {
+            &self.0
         }
     }
-
-    // Solves collision between two circle shape physics bodies
-    fn solve_circle_to_circle(&mut self, body_a: &mut PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>, body_b: &mut PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>) {
-        let PHYSICS_CIRCLE { radius: radus_a } = body_a.shape else { unreachable!("only circle bodies should be passed to solve_circle_to_circle") };
-        let PHYSICS_CIRCLE { radius: radus_b } = body_b.shape else { unreachable!("only circle bodies should be passed to solve_circle_to_circle") };
-
-        // Calculate translational vector, which is normal
-        let normal = body_b.position - body_a.position;
-
-        let dist_sqr = normal.length_sqr();
-        let radius = radus_a + radus_b;
-
-        // Check if circles are not in contact
-        if dist_sqr >= radius*radius {
-            self.contacts_count = 0;
-            return;
+    impl<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize> std::ops::DerefMut for PhysacWorld<MAX_VERTICES, CIRCLE_VERTICES> {
+        fn deref_mut(&mut self) -> &mut Self::Target {
+            &mut self.0
         }
+    }
 
-        let distance = dist_sqr.sqrt();
-        self.contacts_count = 1;
+    /// Links an entity to a physics body, so that its [`Transform`] follows the body
+    ///
+    /// The translation is set to the position of the body (keeping z), and the rotation to the orientation of the body around the z axis.
+    /// Entities whose body has been destroyed are left where they are
+    #[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct PhysacBody(pub BodyHandle);
 
-        if distance == 0.0 {
-            self.penetration = radus_a;
-            self.normal = Vector2 { x: 1.0, y: 0.0 };
-            self.contacts[0] = body_a.position;
-        } else {
-            self.penetration = radius - distance;
-            self.normal = Vector2 { x: normal.x/distance, y: normal.y/distance }; // Faster than using math_normalize() due to sqrt is already performed
-            self.contacts[0] = Vector2 { x: self.normal.x*radus_a + body_a.position.x, y: self.normal.y*radus_a + body_a.position.y };
-        }
+    impl<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize> Message for CollisionEvent<MAX_VERTICES, CIRCLE_VERTICES> {}
 
-        // Update physics body grounded state if normal direction is down
-        if !body_a.is_grounded {
-            body_a.is_grounded = self.normal.y < 0.0;
-        }
+    /// Steps the world by the fixed time of the app
+    #[cfg(not(feature = "phys_thread"))]
+    fn step_physics<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize>(mut world: ResMut<PhysacWorld<MAX_VERTICES, CIRCLE_VERTICES>>, time: Res<Time>) -> Result {
+        world.borrow_mut().update(time.delta_secs_f64())?;
+        Ok(())
     }
 
-    // Solves collision between a circle to a polygon shape physics bodies
-    fn solve_circle_to_polygon(&mut self, body_a: &mut PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>, body_b: &mut PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>) {
-        self.solve_different_shapes(body_a, body_b);
+    /// Copies the position and orientation of every linked body to the transform of its entity
+    #[allow(clippy::unnecessary_cast, reason = "narrows to the `f32` of Bevy with the `f64` feature")]
+    fn write_transforms<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize>(world: Res<PhysacWorld<MAX_VERTICES, CIRCLE_VERTICES>>, mut entities: Query<(&PhysacBody, &mut Transform)>) {
+        let entities = entities.iter_mut().map(|(PhysacBody(handle), transform)| (transform, *handle));
+        world.borrow().sync_entities(entities, |mut transform, position, orient| {
+            let z = transform.translation.z;
+            transform.translation = (position.x as f32, position.y as f32, z).into();
+            transform.rotation = Quat::from_rotation_z(orient as f32);
+        });
     }
 
-    // Solves collision between a circle to a polygon shape physics bodies
-    fn solve_polygon_to_circle(&mut self, body_a: &mut PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>, body_b: &mut PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>) {
-        self.solve_different_shapes(body_b, body_a);
+    /// Sends the collision events of the world since the last time
+    fn send_collision_events<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize>(mut world: ResMut<PhysacWorld<MAX_VERTICES, CIRCLE_VERTICES>>, mut messages: MessageWriter<CollisionEvent<MAX_VERTICES, CIRCLE_VERTICES>>) {
+        messages.write_batch(world.borrow_mut().drain_collision_events());
+    }
+}
+#[cfg(feature = "bevy")]
+pub use self::bevy_plugin::{PhysacBody, PhysacPlugin, PhysacWorld};
 
-        self.normal.x *= -1.0;
-        self.normal.y *= -1.0;
+/// (De)serializes fixed size arrays of any length as sequences, which serde only supports up to a length of 32
+#[cfg(feature = "serde")]
+mod serde_array {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer, de::Error};
+
+    pub fn serialize<S: Serializer, T: Serialize, const N: usize>(array: &[T; N], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(array)
     }
 
-    // Solve collision between two different types of shapes
-    fn solve_different_shapes(&mut self, body_a: &mut PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>, body_b: &mut PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>) {
-        let PHYSICS_CIRCLE { radius: radius_a } = body_a.shape else { unreachable!("only circle bodies should be passed to solve_different_shapes body_a") };
-        let PHYSICS_POLYGON { vertex_data: vertex_data_b, transform: transform_b } = &body_b.shape else { unreachable!("only circle bodies should be passed to solve_different_shapes body_b") };
+    pub fn deserialize<'de, D: Deserializer<'de>, T: Deserialize<'de>, const N: usize>(deserializer: D) -> Result<[T; N], D::Error> {
+        let items = Vec::<T>::deserialize(deserializer)?;
+        let len = items.len();
+        items.try_into().map_err(|_| D::Error::invalid_length(len, &format!("an array of length {N}").as_str()))
+    }
+}
 
-        self.contacts_count = 0;
+/// The binary format of [`WorldState::to_bytes`]
+mod world_file {
+    use super::{
+        NonZeroUsize, Real, Vector2, Mat2, JointLimits, JointMotor, ContactId, SplitMix64, JointState, JointSolverData, SoftBodyState, Particle, ParticleConstraintState, FluidParticle, Fluid, WorldState,
+        PhysicsFixture, PhysicsBodyData, PolygonData, PhysicsShape, PhysicsJointKind, CombineMode, GravityState, ForceField, ForceFieldKind, ForceFieldRegion, BuoyancyZone,
+        PHYSICS_CIRCLE, PHYSICS_POLYGON, PHYSICS_CAPSULE, PHYSICS_COMPOUND, PHYSICS_CHAIN, PHYSICS_ELLIPSE, PHYSICS_HEIGHTFIELD,
+    };
 
-        // Transform circle center to polygon transform space
-        let mut center = body_a.position;
-        center = transform_b.transpose().multiply_vector2(center - body_b.position);
+    /// Bytes every encoded world state starts with, which differ with the `f64` feature since every number is twice as long
+    pub(super) const MAGIC: [u8; 4] = if cfg!(feature = "f64") { *b"PH64" } else { *b"PHYS" };
 
-        // Find edge with minimum penetration
-        // It is the same concept as using support points in SolvePolygonToPolygon
-        let mut separation = f32::MIN;
-        let mut face_normal = 0;
-        let vertex_data = vertex_data_b;
+    /// A value that can be written to and read back from the binary format
+    pub(super) trait Encode: Sized {
+        fn encode(&self, out: &mut Vec<u8>);
+        /// Reads a value from the start of `input` and advances past it, or returns [`None`] if it isn't valid
+        fn decode(input: &mut &[u8]) -> Option<Self>;
+    }
 
-        for i in 0..vertex_data.vertex_count {
-            let current_separation = vertex_data.normals[i].dot(center - vertex_data.positions[i]);
 
-            if current_separation > radius_a {
-                return;
+    macro_rules! impl_encode_number {
+        ($($ty:ty),*) => {$(
+            impl Encode for $ty {
+                fn encode(&self, out: &mut Vec<u8>) {
+                    out.extend_from_slice(&self.to_le_bytes());
+                }
+                fn decode(input: &mut &[u8]) -> Option<Self> {
+                    let (bytes, rest) = input.split_first_chunk::<{ size_of::<$ty>() }>()?;
+                    *input = rest;
+                    Some(Self::from_le_bytes(*bytes))
+                }
             }
+        )*};
+    }
+    impl_encode_number!(u8, u32, u64, u128, i32, f32, f64);
 
-            if current_separation > separation {
-                separation = current_separation;
-                face_normal = i;
+    macro_rules! impl_encode_tuple {
+        ($($name:ident)*) => {
+            #[allow(non_snake_case)]
+            impl<$($name: Encode),*> Encode for ($($name,)*) {
+                fn encode(&self, out: &mut Vec<u8>) {
+                    let ($($name,)*) = self;
+                    $($name.encode(out);)*
+                }
+                fn decode(input: &mut &[u8]) -> Option<Self> {
+                    Some(($($name::decode(input)?,)*))
+                }
             }
-        }
+        };
+    }
+    impl_encode_tuple!(A B);
+    impl_encode_tuple!(A B C);
+    impl_encode_tuple!(A B C D);
+    impl_encode_tuple!(A B C D E);
 
-        // Grab face's vertices
-        let mut v1 = vertex_data.positions[face_normal];
-        let next_index = next_idx(face_normal, vertex_data.vertex_count);
-        let mut v2 = vertex_data.positions[next_index];
+    /// Implements [`Encode`] for a struct by encoding each of its fields in order
+    macro_rules! encode_fields {
+        ($($field:ident),* $(,)?) => {
+            encode_fields!($($field),*;);
+        };
+        // Fields after the semicolon aren't written, and are left at their defaults when decoding
+        ($($field:ident),* ; $($skipped:ident),* $(,)?) => {
+            fn encode(&self, out: &mut Vec<u8>) {
+                $(self.$field.encode(out);)*
+            }
+            fn decode(input: &mut &[u8]) -> Option<Self> {
+                Some(Self { $($field: Encode::decode(input)?,)* $($skipped: Default::default(),)* })
+            }
+        };
+    }
 
-        // Check to see if center is within polygon
-        if separation < f32::EPSILON {
-            self.contacts_count = 1;
-            let normal = transform_b.multiply_vector2(vertex_data.normals[face_normal]);
-            self.normal = -normal;
-            self.contacts[0] = Vector2 { x: self.normal.x*radius_a + body_a.position.x, y: self.normal.y*radius_a + body_a.position.y };
-            self.penetration = radius_a;
-            return;
+    impl Encode for bool {
+        fn encode(&self, out: &mut Vec<u8>) {
+            u8::from(*self).encode(out);
         }
-
-        // Determine which voronoi region of the edge center of circle lies within
-        let dot1 = (center - v1).dot(v2 - v1);
-        let dot2 = (center - v2).dot(v1 - v2);
-        self.penetration = radius_a - separation;
-
-        if dot1 <= 0.0 { // Closest to v1
-            if dist_sqr(center, v1) > radius_a*radius_a {
-                return;
+        fn decode(input: &mut &[u8]) -> Option<Self> {
+            match u8::decode(input)? {
+                0 => Some(false),
+                1 => Some(true),
+                _ => None,
             }
-
-            self.contacts_count = 1;
-            let mut normal = v1 - center;
-            normal = transform_b.multiply_vector2(normal);
-            math_normalize(&mut normal);
-            self.normal = normal;
-            v1 = transform_b.multiply_vector2(v1);
-            v1 += body_b.position;
-            self.contacts[0] = v1;
-        } else if dot2 <= 0.0 { // Closest to v2
-            if dist_sqr(center, v2) > radius_a*radius_a {
-                return;
+        }
+    }
+    impl Encode for usize {
+        fn encode(&self, out: &mut Vec<u8>) {
+            (*self as u64).encode(out);
+        }
+        fn decode(input: &mut &[u8]) -> Option<Self> {
+            u64::decode(input)?.try_into().ok()
+        }
+    }
+    impl Encode for NonZeroUsize {
+        fn encode(&self, out: &mut Vec<u8>) {
+            self.get().encode(out);
+        }
+        fn decode(input: &mut &[u8]) -> Option<Self> {
+            Self::new(usize::decode(input)?)
+        }
+    }
+    impl<T: Encode> Encode for Option<T> {
+        fn encode(&self, out: &mut Vec<u8>) {
+            self.is_some().encode(out);
+            if let Some(value) = self {
+                value.encode(out);
             }
-
-            self.contacts_count = 1;
-            let mut normal = v2 - center;
-            v2 = transform_b.multiply_vector2(v2);
-            v2 += body_b.position;
-            self.contacts[0] = v2;
-            normal = transform_b.multiply_vector2(normal);
-            math_normalize(&mut normal);
-            self.normal = normal;
-        } else { // Closest to face
-            let mut normal = vertex_data.normals[face_normal];
-
-            if (center - v1).dot(normal) > radius_a {
-                return;
+        }
+        fn decode(input: &mut &[u8]) -> Option<Self> {
+            bool::decode(input)?.then(|| T::decode(input)).map_or(Some(None), |value| value.map(Some))
+        }
+    }
+    impl<T: Encode> Encode for Vec<T> {
+        fn encode(&self, out: &mut Vec<u8>) {
+            (self.len() as u32).encode(out);
+            for item in self {
+                item.encode(out);
+            }
+        }
+        fn decode(input: &mut &[u8]) -> Option<Self> {
+            let len = u32::decode(input)? as usize;
+            // Every item takes at least a byte, so a corrupt length can't allocate more than the input
+            let mut items = Vec::with_capacity(len.min(input.len()));
+            for _ in 0..len {
+                items.push(T::decode(input)?);
             }
+            Some(items)
+        }
+    }
+    impl Encode for String {
+        fn encode(&self, out: &mut Vec<u8>) {
+            (self.len() as u32).encode(out);
+            out.extend_from_slice(self.as_bytes());
+        }
+        fn decode(input: &mut &[u8]) -> Option<Self> {
+            let len = u32::decode(input)? as usize;
+            let (bytes, rest) = input.split_at_checked(len)?;
+            *input = rest;
+            Self::from_utf8(bytes.to_vec()).ok()
+        }
+    }
+    impl<T: Encode, const N: usize> Encode for [T; N] {
+        fn encode(&self, out: &mut Vec<u8>) {
+            for item in self {
+                item.encode(out);
+            }
+        }
+        fn decode(input: &mut &[u8]) -> Option<Self> {
+            let mut items = Vec::with_capacity(N);
+            for _ in 0..N {
+                items.push(T::decode(input)?);
+            }
+            items.try_into().ok()
+        }
+    }
 
-            normal = transform_b.multiply_vector2(normal);
-            self.normal = Vector2 { x: -normal.x, y: -normal.y };
-            self.contacts[0] = Vector2 { x: self.normal.x*radius_a + body_a.position.x, y: self.normal.y*radius_a + body_a.position.y };
-            self.contacts_count = 1;
+    impl Encode for Vector2 {
+        encode_fields!(x, y);
+    }
+    impl Encode for Mat2 {
+        encode_fields!(m00, m01, m10, m11);
+    }
+    impl Encode for JointLimits {
+        encode_fields!(lower, upper);
+    }
+    impl Encode for JointMotor {
+        encode_fields!(speed, max_force);
+    }
+    impl Encode for ContactId {
+        encode_fields!(parts, reference_face, incident_face, feature, flip);
+    }
+    impl Encode for SplitMix64 {
+        encode_fields!(state);
+    }
+    impl Encode for JointState {
+        encode_fields!(
+            id, body_a, body_b, local_anchor_a, local_anchor_b, kind, break_force, break_torque, linked_joints, solver, user_data,
+        );
+    }
+    impl Encode for JointSolverData {
+        encode_fields!(
+            radius_a, radius_b, axis, axis_b, axial_mass, axial_bias, gamma, axial_impulse, point_mass, point_bias, point_impulse,
+            angular_mass, angle, translation, lever_a, perpendicular_mass, perpendicular_bias, angular_bias, angular_impulse,
+            motor_impulse, lower_impulse, upper_impulse, max_impulse, frame_speed,
+        );
+    }
+    impl<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize> Encode for PhysicsFixture<MAX_VERTICES, CIRCLE_VERTICES> {
+        encode_fields!(shape, offset, rotation);
+    }
+    impl<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize> Encode for PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES> {
+        encode_fields!(
+            id, enabled, position, velocity, force, angular_velocity, torque, orient, inertia, inverse_inertia, mass, inverse_mass,
+            static_friction, dynamic_friction, restitution, use_gravity, is_grounded, freeze_orient, is_sensor,
+            collision_category, collision_mask, collision_group, can_sleep, rotation_limits, is_sleeping, resting_steps,
+            previous_transform, shape, shape_offset, is_simulating, is_kinematic, gravity_scale, linear_damping, angular_damping,
+            max_linear_velocity, max_angular_velocity, friction_combine, restitution_combine, surface_velocity, user_data, name;
+            // Stale impulses are left out of the state instead, and loaded bodies start out undisturbed
+            has_stale_contacts, settled_transform, has_new_shape,
+        );
+    }
+    impl<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize> Encode for WorldState<MAX_VERTICES, CIRCLE_VERTICES> {
+        encode_fields!(
+            gravity, delta_time, time_scale, collision_iterations, position_iterations, substeps, penetration_allowance,
+            penetration_correction, sleep_steps, sleep_velocity, sleep_angular_velocity, warm_starting, friction_combine,
+            restitution_combine, bodies, joints, ignored_pairs, contact_impulses, accumulator, steps_count, rng, body_generations,
+            force_fields, next_force_field_id, buoyancy_zones, next_buoyancy_zone_id, soft_bodies, next_soft_body_id, particles,
+            next_particle_id, particle_constraints, next_particle_constraint_id, fluids, next_fluid_id;
+            // The version is written ahead of the state by `WorldState::to_bytes`, and read back by `WorldState::from_bytes`
+            version,
+        );
+    }
+
+    impl<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize> Encode for PolygonData<MAX_VERTICES, CIRCLE_VERTICES> {
+        // Only the vertices in use are written
+        fn encode(&self, out: &mut Vec<u8>) {
+            self.vertex_count.encode(out);
+            for i in 0..self.vertex_count {
+                self.positions[i].encode(out);
+                self.normals[i].encode(out);
+            }
+            self.radius.encode(out);
+        }
+        fn decode(input: &mut &[u8]) -> Option<Self> {
+            let mut data = Self::new();
+            data.vertex_count = usize::decode(input).filter(|&count| count <= MAX_VERTICES)?;
+            for i in 0..data.vertex_count {
+                data.positions[i] = Vector2::decode(input)?;
+                data.normals[i] = Vector2::decode(input)?;
+            }
+            data.radius = Real::decode(input)?;
+            Some(data)
+        }
+    }
+    impl<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize> Encode for PhysicsShape<MAX_VERTICES, CIRCLE_VERTICES> {
+        fn encode(&self, out: &mut Vec<u8>) {
+            match self {
+                PHYSICS_CIRCLE { radius } => {
+                    0u8.encode(out);
+                    radius.encode(out);
+                }
+                PHYSICS_POLYGON { vertex_data, transform } => {
+                    1u8.encode(out);
+                    vertex_data.encode(out);
+                    transform.encode(out);
+                }
+                PHYSICS_CAPSULE { radius, height } => {
+                    2u8.encode(out);
+                    radius.encode(out);
+                    height.encode(out);
+                }
+                PHYSICS_COMPOUND { fixtures } => {
+                    3u8.encode(out);
+                    fixtures.encode(out);
+                }
+                PHYSICS_CHAIN { vertices } => {
+                    4u8.encode(out);
+                    vertices.encode(out);
+                }
+                PHYSICS_ELLIPSE { radius_x, radius_y, .. } => {
+                    5u8.encode(out);
+                    radius_x.encode(out);
+                    radius_y.encode(out);
+                }
+                PHYSICS_HEIGHTFIELD { heights, cell_width } => {
+                    6u8.encode(out);
+                    heights.encode(out);
+                    cell_width.encode(out);
+                }
+            }
+        }
+        fn decode(input: &mut &[u8]) -> Option<Self> {
+            Some(match u8::decode(input)? {
+                0 => PHYSICS_CIRCLE { radius: Encode::decode(input)? },
+                1 => PHYSICS_POLYGON { vertex_data: Encode::decode(input)?, transform: Encode::decode(input)? },
+                2 => PHYSICS_CAPSULE { radius: Encode::decode(input)?, height: Encode::decode(input)? },
+                3 => PHYSICS_COMPOUND { fixtures: Encode::decode(input)? },
+                4 => PHYSICS_CHAIN { vertices: Encode::decode(input)? },
+                5 => PhysicsShape::ellipse(Encode::decode(input)?, Encode::decode(input)?),
+                6 => PHYSICS_HEIGHTFIELD { heights: Encode::decode(input)?, cell_width: Encode::decode(input)? },
+                _ => return None,
+            })
+        }
+    }
+    impl Encode for CombineMode {
+        fn encode(&self, out: &mut Vec<u8>) {
+            (*self as u8).encode(out);
+        }
+        fn decode(input: &mut &[u8]) -> Option<Self> {
+            Some(match u8::decode(input)? {
+                0 => Self::GeometricMean,
+                1 => Self::Average,
+                2 => Self::Min,
+                3 => Self::Multiply,
+                4 => Self::Max,
+                _ => return None,
+            })
+        }
+    }
+    impl Encode for ForceField {
+        encode_fields!(kind, region, mask);
+    }
+    impl Encode for BuoyancyZone {
+        encode_fields!(region, density, linear_drag, angular_drag, flow, mask);
+    }
+    impl Encode for SoftBodyState {
+        encode_fields!(id, points, rest_area, pressure);
+    }
+    impl Encode for Particle {
+        encode_fields!(position, previous_position, radius, gravity_scale, damping, friction, mask);
+    }
+    impl Encode for FluidParticle {
+        encode_fields!(position, velocity);
+    }
+    impl Encode for Fluid {
+        encode_fields!(particles, interaction_radius, rest_density, stiffness, near_stiffness, viscosity, particle_radius, gravity_scale, mask);
+    }
+    impl Encode for ParticleConstraintState {
+        fn encode(&self, out: &mut Vec<u8>) {
+            match *self {
+                Self::Distance { particle_a, particle_b, length, stiffness } => (0u8, particle_a, particle_b, length, stiffness).encode(out),
+                Self::Pin { particle, point } => (1u8, particle, point).encode(out),
+                Self::BodyPin { particle, body, local_anchor } => (2u8, particle, body, local_anchor).encode(out),
+            }
+        }
+        fn decode(input: &mut &[u8]) -> Option<Self> {
+            Some(match u8::decode(input)? {
+                0 => Self::Distance { particle_a: Encode::decode(input)?, particle_b: Encode::decode(input)?, length: Encode::decode(input)?, stiffness: Encode::decode(input)? },
+                1 => Self::Pin { particle: Encode::decode(input)?, point: Encode::decode(input)? },
+                2 => Self::BodyPin { particle: Encode::decode(input)?, body: Encode::decode(input)?, local_anchor: Encode::decode(input)? },
+                _ => return None,
+            })
+        }
+    }
+    impl Encode for GravityState {
+        fn encode(&self, out: &mut Vec<u8>) {
+            match *self {
+                Self::Directional(acceleration) => (0u8, acceleration).encode(out),
+                Self::Radial { center, strength } => (1u8, center, strength).encode(out),
+                Self::Custom => 2u8.encode(out),
+            }
+        }
+        fn decode(input: &mut &[u8]) -> Option<Self> {
+            Some(match u8::decode(input)? {
+                0 => Self::Directional(Encode::decode(input)?),
+                1 => Self::Radial { center: Encode::decode(input)?, strength: Encode::decode(input)? },
+                2 => Self::Custom,
+                _ => return None,
+            })
+        }
+    }
+    impl Encode for ForceFieldKind {
+        fn encode(&self, out: &mut Vec<u8>) {
+            match *self {
+                Self::Directional { force } => (0u8, force).encode(out),
+                Self::Radial { center, strength } => (1u8, center, strength).encode(out),
+                Self::Vortex { center, strength } => (2u8, center, strength).encode(out),
+            }
+        }
+        fn decode(input: &mut &[u8]) -> Option<Self> {
+            Some(match u8::decode(input)? {
+                0 => Self::Directional { force: Encode::decode(input)? },
+                1 => Self::Radial { center: Encode::decode(input)?, strength: Encode::decode(input)? },
+                2 => Self::Vortex { center: Encode::decode(input)?, strength: Encode::decode(input)? },
+                _ => return None,
+            })
+        }
+    }
+    impl Encode for ForceFieldRegion {
+        fn encode(&self, out: &mut Vec<u8>) {
+            match self {
+                Self::Aabb { min, max } => (0u8, *min, *max).encode(out),
+                Self::Circle { center, radius } => (1u8, *center, *radius).encode(out),
+                Self::Polygon { vertices } => {
+                    2u8.encode(out);
+                    vertices.encode(out);
+                },
+            }
+        }
+        fn decode(input: &mut &[u8]) -> Option<Self> {
+            Some(match u8::decode(input)? {
+                0 => Self::Aabb { min: Encode::decode(input)?, max: Encode::decode(input)? },
+                1 => Self::Circle { center: Encode::decode(input)?, radius: Encode::decode(input)? },
+                2 => Self::Polygon { vertices: Encode::decode(input)? },
+                _ => return None,
+            })
+        }
+    }
+    impl Encode for PhysicsJointKind {
+        fn encode(&self, out: &mut Vec<u8>) {
+            match *self {
+                Self::Distance { length, stiffness } => (0u8, length, stiffness).encode(out),
+                Self::Rope { max_length } => (1u8, max_length).encode(out),
+                Self::Pulley { ground_anchor_a, ground_anchor_b, length, ratio } => (2u8, ground_anchor_a, ground_anchor_b, length, ratio).encode(out),
+                Self::Spring { rest_length, stiffness, damping } => (3u8, rest_length, stiffness, damping).encode(out),
+                Self::Revolute { reference_angle, limits, motor } => (4u8, reference_angle, limits, motor).encode(out),
+                Self::Prismatic { local_axis, reference_angle, limits, motor } => (5u8, local_axis, reference_angle, limits, motor).encode(out),
+                Self::Weld { reference_angle } => (6u8, reference_angle).encode(out),
+                Self::Gear { ratio, reference } => (7u8, ratio, reference).encode(out),
+                Self::Target { max_force, frequency, damping_ratio } => (8u8, max_force, frequency, damping_ratio).encode(out),
+            }
+        }
+        fn decode(input: &mut &[u8]) -> Option<Self> {
+            Some(match u8::decode(input)? {
+                0 => Self::Distance { length: Encode::decode(input)?, stiffness: Encode::decode(input)? },
+                1 => Self::Rope { max_length: Encode::decode(input)? },
+                2 => Self::Pulley { ground_anchor_a: Encode::decode(input)?, ground_anchor_b: Encode::decode(input)?, length: Encode::decode(input)?, ratio: Encode::decode(input)? },
+                3 => Self::Spring { rest_length: Encode::decode(input)?, stiffness: Encode::decode(input)?, damping: Encode::decode(input)? },
+                4 => Self::Revolute { reference_angle: Encode::decode(input)?, limits: Encode::decode(input)?, motor: Encode::decode(input)? },
+                5 => Self::Prismatic { local_axis: Encode::decode(input)?, reference_angle: Encode::decode(input)?, limits: Encode::decode(input)?, motor: Encode::decode(input)? },
+                6 => Self::Weld { reference_angle: Encode::decode(input)? },
+                7 => Self::Gear { ratio: Encode::decode(input)?, reference: Encode::decode(input)? },
+                8 => Self::Target { max_force: Encode::decode(input)?, frequency: Encode::decode(input)?, damping_ratio: Encode::decode(input)? },
+                _ => return None,
+            })
         }
     }
+}
 
-    // Solves collision between two polygons shape physics bodies
-    fn solve_polygon_to_polygon(&mut self, body_a: &mut PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>, body_b: &mut PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>) {
-        self.contacts_count = 0;
-
-        // Check for separating axis with A shape's face planes
-        let mut face_a = 0;
-        let penetration_a = find_axis_least_penetration(&mut face_a, body_a, body_b);
-
-        if penetration_a >= 0.0 {
-            return;
-        }
-
-        // Check for separating axis with B shape's face planes
-        let mut face_b = 0;
-        let penetration_b = find_axis_least_penetration(&mut face_b, body_b, body_a);
-
-        if penetration_b >= 0.0 {
-            return;
-        }
+/// Returns true if no two edges of a polygon cross or touch, other than neighboring edges at their shared vertex
+fn is_simple_polygon(positions: &[Vector2]) -> bool {
+    let count = positions.len();
 
-        let mut reference_index;
-        let mut flip = false;  // Always point from A shape to B shape
+    for i in 0..count {
+        let (a1, a2) = (positions[i], positions[next_idx(i, count)]);
 
-        let ref_body; // Reference
-        let inc_body; // Incident
+        for j in (i + 1)..count {
+            // Neighboring edges always share a vertex
+            if j == i + 1 || next_idx(j, count) == i {
+                continue;
+            }
 
-        // Determine which shape contains reference face
-        if bias_greater_than(penetration_a, penetration_b) {
-            ref_body = body_a;
-            inc_body = body_b;
-            reference_index = face_a;
-        } else {
-            ref_body = body_b;
-            inc_body = body_a;
-            reference_index = face_b;
-            flip = true;
+            let (b1, b2) = (positions[j], positions[next_idx(j, count)]);
+            if segments_intersect(a1, a2, b1, b2) {
+                return false;
+            }
         }
-        let PHYSICS_POLYGON { vertex_data: ref_data, transform: ref_transform } = &ref_body.shape else { unreachable!("only polygon bodies should be passed to solve_polygon_to_polygon") };
-        // let PHYSICS_POLYGON { vertex_data: inc_data, transform: inc_transform } = &inc_body.shape else { unreachable!("only polygon bodies should be passed to solve_polygon_to_polygon") };
+    }
 
-        // World space incident face
-        let mut incident_face = find_incident_face(ref_body, inc_body, reference_index);
+    true
+}
 
-        // Setup reference face vertices
-        let mut v1 = ref_data.positions[reference_index];
-        reference_index = next_idx(reference_index, ref_data.vertex_count);
-        let mut v2 = ref_data.positions[reference_index];
+/// Returns true if the segment from `a1` to `a2` crosses or touches the segment from `b1` to `b2`
+fn segments_intersect(a1: Vector2, a2: Vector2, b1: Vector2, b2: Vector2) -> bool {
+    let d1 = math_cross_vector2(b2 - b1, a1 - b1);
+    let d2 = math_cross_vector2(b2 - b1, a2 - b1);
+    let d3 = math_cross_vector2(a2 - a1, b1 - a1);
+    let d4 = math_cross_vector2(a2 - a1, b2 - a1);
 
-        // Transform vertices to world space
-        v1 = ref_transform.multiply_vector2(v1);
-        v1 += ref_body.position;
-        v2 = ref_transform.multiply_vector2(v2);
-        v2 += ref_body.position;
+    if ((d1 > 0.0 && d2 < 0.0) || (d1 < 0.0 && d2 > 0.0)) && ((d3 > 0.0 && d4 < 0.0) || (d3 < 0.0 && d4 > 0.0)) {
+        return true;
+    }
 
-        // Calculate reference face side normal in world space
-        let mut side_plane_normal = v2 - v1;
-        math_normalize(&mut side_plane_normal);
+    // Collinear touching
+    (d1 == 0.0 && closest_point_on_segment(a1, b1, b2) == a1) ||
+    (d2 == 0.0 && closest_point_on_segment(a2, b1, b2) == a2) ||
+    (d3 == 0.0 && closest_point_on_segment(b1, a1, a2) == b1) ||
+    (d4 == 0.0 && closest_point_on_segment(b2, a1, a2) == b2)
+}
 
-        // Orthogonalize
-        let ref_face_normal = Vector2 { x: side_plane_normal.y, y: -side_plane_normal.x };
-        let ref_c = ref_face_normal.dot(v1);
-        let neg_side = side_plane_normal.dot(v1)*-1.0;
-        let pos_side = side_plane_normal.dot(v2);
+/// Returns true if `point` is inside or on the edges of the counter-clockwise triangle `v1`, `v2`, `v3`
+fn triangle_contains(v1: Vector2, v2: Vector2, v3: Vector2, point: Vector2) -> bool {
+    math_cross_vector2(v2 - v1, point - v1) >= 0.0 &&
+    math_cross_vector2(v3 - v2, point - v2) >= 0.0 &&
+    math_cross_vector2(v1 - v3, point - v3) >= 0.0
+}
 
-        // Clip incident face to reference face side planes (due to floating point error, possible to not have required points
-        let [face_a, face_b] = &mut incident_face;
-        if clip(-side_plane_normal, neg_side, face_a, face_b) < 2 {
-            return;
-        }
+/// Triangulates a simple polygon (in Physac's winding order) by ear clipping
+///
+/// Returns the triangles as indices into `positions`, or [`None`] if no ear could be found
+fn ear_clip(positions: &[Vector2]) -> Option<Vec<Vec<usize>>> {
+    let mut remaining: Vec<usize> = (0..positions.len()).collect();
+    let mut triangles = Vec::with_capacity(positions.len() - 2);
+
+    while remaining.len() > 3 {
+        let count = remaining.len();
+
+        let ear = (0..count).find(|&i| {
+            let prev = remaining[(i + count - 1)%count];
+            let curr = remaining[i];
+            let next = remaining[next_idx(i, count)];
+            let (v1, v2, v3) = (positions[prev], positions[curr], positions[next]);
+
+            // Reflex and straight corners can't be clipped
+            if math_cross_vector2(v2 - v1, v3 - v2) <= 0.0 {
+                return false;
+            }
 
-        if clip(side_plane_normal, pos_side, face_a, face_b) < 2 {
-            return;
-        }
+            // No other vertex may be inside of the ear
+            remaining.iter()
+                .filter(|&&j| j != prev && j != curr && j != next)
+                .all(|&j| positions[j] == v1 || positions[j] == v3 || !triangle_contains(v1, v2, v3, positions[j]))
+        })?;
 
-        // Flip normal if required
-        self.normal = if flip { -ref_face_normal } else { ref_face_normal };
+        triangles.push(vec![remaining[(ear + count - 1)%count], remaining[ear], remaining[next_idx(ear, count)]]);
+        remaining.remove(ear);
+    }
 
-        // Keep points behind reference face
-        let mut current_point: u32 = 0; // Clipped points behind reference face
-        let mut separation = ref_face_normal.dot(incident_face[0]) - ref_c;
+    triangles.push(remaining);
+    Some(triangles)
+}
 
-        if separation <= 0.0 {
-            self.contacts[current_point as usize] = incident_face[0];
-            self.penetration = -separation;
-            current_point += 1;
-        } else {
-            self.penetration = 0.0;
-        }
+/// Merges neighboring convex pieces of a polygon (as indices into `positions`) for as long as the result stays convex and has at most `max_vertices` vertices
+fn merge_convex_pieces(positions: &[Vector2], pieces: &mut Vec<Vec<usize>>, max_vertices: usize) {
+    /// Joins two pieces along the edge `a` to `b` of `piece_p`, which is the edge `b` to `a` of `piece_q`
+    fn join(piece_p: &[usize], piece_q: &[usize], edge_p: usize, edge_q: usize) -> Vec<usize> {
+        let count_p = piece_p.len();
+        let count_q = piece_q.len();
+
+        // All of P starting after `a` (at `b`) and ending at `a`, then Q between `a` and `b`
+        let mut merged: Vec<usize> = (1..=count_p).map(|k| piece_p[(edge_p + k)%count_p]).collect();
+        merged.extend((2..count_q).map(|k| piece_q[(edge_q + k)%count_q]));
+        merged
+    }
+
+    fn is_convex(positions: &[Vector2], piece: &[usize]) -> bool {
+        let count = piece.len();
+        (0..count).all(|i| {
+            let v1 = positions[piece[i]];
+            let v2 = positions[piece[next_idx(i, count)]];
+            let v3 = positions[piece[next_idx(next_idx(i, count), count)]];
+            math_cross_vector2(v2 - v1, v3 - v2) >= 0.0
+        })
+    }
+
+    'search: loop {
+        for p in 0..pieces.len() {
+            for q in (p + 1)..pieces.len() {
+                if pieces[p].len() + pieces[q].len() - 2 > max_vertices {
+                    continue;
+                }
 
-        separation = ref_face_normal.dot(incident_face[1]) - ref_c;
+                for edge_p in 0..pieces[p].len() {
+                    let a = pieces[p][edge_p];
+                    let b = pieces[p][next_idx(edge_p, pieces[p].len())];
 
-        if separation <= 0.0 {
-            self.contacts[current_point as usize] = incident_face[1];
-            self.penetration += -separation;
-            current_point += 1;
+                    let Some(edge_q) = (0..pieces[q].len())
+                        .find(|&k| pieces[q][k] == b && pieces[q][next_idx(k, pieces[q].len())] == a) else { continue };
 
-            // Calculate total penetration average
-            self.penetration /= current_point as f32;
+                    let merged = join(&pieces[p], &pieces[q], edge_p, edge_q);
+                    if is_convex(positions, &merged) {
+                        pieces[p] = merged;
+                        pieces.swap_remove(q);
+                        continue 'search;
+                    }
+                }
+            }
         }
 
-        self.contacts_count = current_point;
+        break;
     }
 }
 
-/// Returns the extreme point along a direction within a polygon
-fn get_support<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize>(vertex_data: &PolygonData<MAX_VERTICES, CIRCLE_VERTICES>, dir: Vector2) -> Vector2 {
-    let mut best_projection = -f32::MIN_POSITIVE;
-    let mut best_vertex = Vector2 { x: 0.0, y: 0.0 };
+/// Sets up a constraint on the angle of a joint from the inverse inertia it acts against and how far the angle is off
+fn initialize_joint_angle(solver: &mut JointSolverData, inverse_inertia_sum: Real, angle: Real, delta_time: f64) {
+    solver.angular_mass = if inverse_inertia_sum > 0.0 { 1.0/inverse_inertia_sum } else { 0.0 };
+    solver.angle = angle;
+    solver.angular_bias = JOINT_BAUMGARTE*angle/delta_time as Real;
+}
 
-    for i in 0..vertex_data.vertex_count {
-        let vertex = vertex_data.positions[i];
-        let projection = vertex.dot(dir);
+/// Points the axis of one side of a pulley joint from its ground anchor to the body anchor,
+/// and returns the length of that side and the inverse mass the body resists being pulled along it with
+fn pulley_side<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize>(
+    solver: &mut JointSolverData,
+    body: &PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>,
+    ground_anchor: Vector2,
+    is_side_b: bool,
+) -> (Real, Real) {
+    let (radius, axis) = if is_side_b { (solver.radius_b, &mut solver.axis_b) } else { (solver.radius_a, &mut solver.axis) };
+    let delta = body.position + radius - ground_anchor;
+    let length = delta.length_sqr().sqrt();
+
+    // The rope can't pull a body that is right at its ground anchor in any direction
+    *axis = if length > Real::EPSILON { delta*(1.0/length) } else { Vector2::zero() };
+    (length, joint_axis_inverse_mass(joint_inverse_mass(body), (0.0, 0.0), radius, Vector2::zero(), *axis))
+}
 
-        if projection > best_projection {
-            best_vertex = vertex;
-            best_projection = projection;
-        }
+/// Returns the inverse mass and inverse inertia a body responds to joint impulses with
+fn joint_inverse_mass<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize>(body: &PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>) -> (Real, Real) {
+    if !body.enabled {
+        return (0.0, 0.0);
     }
 
-    best_vertex
+    (body.inverse_mass, if body.freeze_orient { 0.0 } else { body.inverse_inertia })
 }
 
-/// Finds polygon shapes axis least penetration
-fn find_axis_least_penetration<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize>(
-    face_index: &mut usize,
+/// Returns the velocity of the second anchor relative to the first
+fn anchor_relative_velocity<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize>(
     body_a: &PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>,
     body_b: &PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>,
-) -> f32 {
-    let shape_a = &body_a.shape;
-    let shape_b = &body_b.shape;
-
-    let PHYSICS_POLYGON { vertex_data: data_a, transform: transform_a } = &shape_a else { unreachable!("only polygons should be passed to find_axis_least_penetration") };
-    let PHYSICS_POLYGON { vertex_data: data_b, transform: transform_b } = &shape_b else { unreachable!("only polygons should be passed to find_axis_least_penetration") };
-
-    let mut best_distance = f32::MIN;
-    let mut best_index = 0;
+    radius_a: Vector2,
+    radius_b: Vector2,
+) -> Vector2 {
+    (body_b.velocity + math_cross(body_b.angular_velocity, radius_b)) - (body_a.velocity + math_cross(body_a.angular_velocity, radius_a))
+}
 
-    for i in 0..data_a.vertex_count {
-        // Retrieve a face normal from A shape
-        let mut normal = data_a.normals[i];
-        let trans_normal = transform_a.multiply_vector2(normal);
+/// Applies an angular impulse to the second body and the opposite to the first
+fn apply_joint_angular_impulse<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize>(
+    body_a: &mut PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>,
+    body_b: &mut PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>,
+    impulse: Real,
+) {
+    let (_, inverse_inertia_a) = joint_inverse_mass(body_a);
+    let (_, inverse_inertia_b) = joint_inverse_mass(body_b);
+    body_a.angular_velocity -= inverse_inertia_a*impulse;
+    body_b.angular_velocity += inverse_inertia_b*impulse;
+}
 
-        // Transform face normal into B shape's model space
-        let bu_t = transform_b.transpose();
-        normal = bu_t.multiply_vector2(trans_normal);
+/// Pulls the body of a target joint towards its target, without exceeding the maximum force
+fn solve_target_joint<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize>(
+    solver: &mut JointSolverData,
+    body: &mut PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>,
+) {
+    let velocity = body.velocity + math_cross(body.angular_velocity, solver.radius_a);
+    let impulse = -solver.point_mass.multiply_vector2(velocity + solver.point_bias + solver.point_impulse*solver.gamma);
 
-        // Retrieve support point from B shape along -n
-        let support = get_support(data_b, Vector2 { x: -normal.x, y: -normal.y });
+    let old_impulse = solver.point_impulse;
+    solver.point_impulse += impulse;
+    if solver.point_impulse.length_sqr() > solver.max_impulse*solver.max_impulse {
+        solver.point_impulse *= solver.max_impulse/solver.point_impulse.length_sqr().sqrt();
+    }
 
-        // Retrieve vertex on face from A shape, transform into B shape's model space
-        let mut vertex = data_a.positions[i];
-        vertex = transform_a.multiply_vector2(vertex);
-        vertex += body_a.position;
-        vertex -= body_b.position;
-        vertex = bu_t.multiply_vector2(vertex);
+    apply_joint_impulse(body, solver.point_impulse - old_impulse, solver.radius_a);
+}
 
-        // Compute penetration distance in B shape's model space
-        let distance = normal.dot(support - vertex);
+/// Applies the motor, limits and point constraint of a revolute joint
+fn solve_revolute_joint<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize>(
+    solver: &mut JointSolverData,
+    limits: Option<JointLimits>,
+    motor: Option<JointMotor>,
+    body_a: &mut PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>,
+    body_b: &mut PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>,
+    delta_time: Real,
+) {
+    if solver.angular_mass > 0.0 {
+        // Drive the joint towards the motor speed without exceeding its torque
+        if let Some(motor) = motor {
+            let relative_speed = body_b.angular_velocity - body_a.angular_velocity - motor.speed;
+            let max_impulse = motor.max_force*delta_time;
+            let old_impulse = solver.motor_impulse;
+            solver.motor_impulse = (old_impulse - solver.angular_mass*relative_speed).clamp(-max_impulse, max_impulse);
+            apply_joint_angular_impulse(body_a, body_b, solver.motor_impulse - old_impulse);
+        }
 
-        // Store greatest distance
-        if distance > best_distance {
-            best_distance = distance;
-            best_index = i;
+        // Keep the joint angle within its limits, only ever pushing away from them
+        if let Some(limits) = limits {
+            let relative_speed = body_b.angular_velocity - body_a.angular_velocity;
+            let old_impulse = solver.lower_impulse;
+            solver.lower_impulse = limit_impulse(old_impulse, solver.angular_mass, solver.angle - limits.lower, relative_speed, delta_time);
+            apply_joint_angular_impulse(body_a, body_b, solver.lower_impulse - old_impulse);
+
+            let relative_speed = body_b.angular_velocity - body_a.angular_velocity;
+            let old_impulse = solver.upper_impulse;
+            solver.upper_impulse = limit_impulse(old_impulse, solver.angular_mass, limits.upper - solver.angle, -relative_speed, delta_time);
+            apply_joint_angular_impulse(body_a, body_b, old_impulse - solver.upper_impulse);
         }
     }
 
-    *face_index = best_index;
-    best_distance
+    // Keep the anchors together
+    solve_joint_point(solver, body_a, body_b);
 }
 
-/// Finds two polygon shapes incident face
-fn find_incident_face<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize>(ref_body: &PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>, inc_body: &PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>, index: usize) -> [Vector2; 2] {
-    let ref_shape = &ref_body.shape;
-    let inc_shape = &inc_body.shape;
-
-    let PHYSICS_POLYGON { vertex_data: ref_data, transform: ref_transform } = &ref_shape else { unreachable!("only polygons should be passed to find_incident_face") };
-    let PHYSICS_POLYGON { vertex_data: inc_data, transform: inc_transform } = &inc_shape else { unreachable!("only polygons should be passed to find_incident_face") };
+/// Applies the motor, limits, angle lock and perpendicular constraint of a prismatic joint
+fn solve_prismatic_joint<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize>(
+    solver: &mut JointSolverData,
+    limits: Option<JointLimits>,
+    motor: Option<JointMotor>,
+    body_a: &mut PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>,
+    body_b: &mut PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>,
+    delta_time: Real,
+) {
+    let (lever_a, radius_b, axis) = (solver.lever_a, solver.radius_b, solver.axis);
+
+    if solver.axial_mass > 0.0 {
+        // Drive the joint towards the motor speed without exceeding its force
+        if let Some(motor) = motor {
+            let relative_speed = anchor_relative_velocity(body_a, body_b, lever_a, radius_b).dot(axis) - motor.speed;
+            let max_impulse = motor.max_force*delta_time;
+            let old_impulse = solver.motor_impulse;
+            solver.motor_impulse = (old_impulse - solver.axial_mass*relative_speed).clamp(-max_impulse, max_impulse);
+            apply_joint_impulse_pair(body_a, body_b, axis*(solver.motor_impulse - old_impulse), lever_a, radius_b);
+        }
 
-    let mut reference_normal = ref_data.normals[index];
+        // Keep the joint translation within its limits, only ever pushing away from them
+        if let Some(limits) = limits {
+            let relative_speed = anchor_relative_velocity(body_a, body_b, lever_a, radius_b).dot(axis);
+            let old_impulse = solver.lower_impulse;
+            solver.lower_impulse = limit_impulse(old_impulse, solver.axial_mass, solver.translation - limits.lower, relative_speed, delta_time);
+            apply_joint_impulse_pair(body_a, body_b, axis*(solver.lower_impulse - old_impulse), lever_a, radius_b);
+
+            let relative_speed = anchor_relative_velocity(body_a, body_b, lever_a, radius_b).dot(axis);
+            let old_impulse = solver.upper_impulse;
+            solver.upper_impulse = limit_impulse(old_impulse, solver.axial_mass, limits.upper - solver.translation, -relative_speed, delta_time);
+            apply_joint_impulse_pair(body_a, body_b, axis*(old_impulse - solver.upper_impulse), lever_a, radius_b);
+        }
+    }
 
-    // Calculate normal in incident's frame of reference
-    reference_normal = ref_transform.multiply_vector2(reference_normal); // To world space
-    reference_normal = inc_transform.transpose().multiply_vector2(reference_normal); // To incident's model space
+    // Keep the relative angle fixed
+    solve_joint_angle_lock(solver, body_a, body_b);
 
-    // Find most anti-normal face on polygon
-    let mut incident_face = 0;
-    let mut min_dot = f32::MAX;
+    // Keep the second anchor on the axis
+    if solver.perpendicular_mass > 0.0 {
+        let perpendicular = Vector2 { x: -axis.y, y: axis.x };
+        let relative_speed = anchor_relative_velocity(body_a, body_b, lever_a, radius_b).dot(perpendicular);
+        let impulse = perpendicular*(-solver.perpendicular_mass*(relative_speed + solver.perpendicular_bias));
+        solver.point_impulse += impulse;
+        apply_joint_impulse_pair(body_a, body_b, impulse, lever_a, radius_b);
+    }
+}
 
-    for i in 0..inc_data.vertex_count {
-        let dot = reference_normal.dot(inc_data.normals[i]);
+/// Applies the impulse needed to keep the anchors of a joint together
+fn solve_joint_point<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize>(
+    solver: &mut JointSolverData,
+    body_a: &mut PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>,
+    body_b: &mut PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>,
+) {
+    let relative_velocity = anchor_relative_velocity(body_a, body_b, solver.radius_a, solver.radius_b);
+    let impulse = -solver.point_mass.multiply_vector2(relative_velocity + solver.point_bias);
+    solver.point_impulse += impulse;
+
+    apply_joint_impulse_pair(body_a, body_b, impulse, solver.radius_a, solver.radius_b);
+}
 
-        if dot < min_dot {
-            min_dot = dot;
-            incident_face = i;
-        }
+/// Applies the angular impulse needed to keep the relative angle of a joint's bodies fixed
+fn solve_joint_angle_lock<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize>(
+    solver: &mut JointSolverData,
+    body_a: &mut PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>,
+    body_b: &mut PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>,
+) {
+    if solver.angular_mass > 0.0 {
+        let relative_speed = body_b.angular_velocity - body_a.angular_velocity;
+        let impulse = -solver.angular_mass*(relative_speed + solver.angular_bias);
+        solver.angular_impulse += impulse;
+        apply_joint_angular_impulse(body_a, body_b, impulse);
     }
+}
 
-    // Assign face vertices for incident face
-    let v0 = inc_transform.multiply_vector2(inc_data.positions[incident_face]) + inc_body.position;
-    incident_face = next_idx(incident_face, inc_data.vertex_count);
-    let v1 = inc_transform.multiply_vector2(inc_data.positions[incident_face]) + inc_body.position;
-    [v0, v1]
+/// Returns the inverse effective mass of two bodies along an axis, for anchors at the given lever arms
+fn joint_axis_inverse_mass(inverse_mass_a: (Real, Real), inverse_mass_b: (Real, Real), lever_a: Vector2, lever_b: Vector2, axis: Vector2) -> Real {
+    let cross_a = math_cross_vector2(lever_a, axis);
+    let cross_b = math_cross_vector2(lever_b, axis);
+    inverse_mass_a.0 + inverse_mass_b.0 + inverse_mass_a.1*cross_a*cross_a + inverse_mass_b.1*cross_b*cross_b
 }
 
-/// Calculates clipping based on a normal and two faces
-fn clip(normal: Vector2, clip: f32, face_a: &mut Vector2, face_b: &mut Vector2) -> usize {
-    let mut sp = 0;
-    let mut out = [*face_a, *face_b];
+/// Returns the new accumulated impulse of a one-sided limit, given how far the joint is from it and how fast it approaches
+///
+/// Speculatively allows the joint to close the remaining gap within a step, and corrects part of the error once the limit has been passed
+fn limit_impulse(accumulated: Real, mass: Real, error: Real, relative_speed: Real, delta_time: Real) -> Real {
+    let bias = if error > 0.0 { error/delta_time } else { JOINT_BAUMGARTE*error/delta_time };
+    (accumulated - mass*(relative_speed + bias)).max(0.0)
+}
 
-    // Retrieve distances from each endpoint to the line
-    let distance_a = normal.dot(*face_a) - clip;
-    let distance_b = normal.dot(*face_b) - clip;
+/// Returns the inverse of a matrix 2x2, or a zero matrix if it can't be inverted
+fn mat2_inverse(matrix: Mat2) -> Mat2 {
+    let det = matrix.m00*matrix.m11 - matrix.m01*matrix.m10;
+    if det == 0.0 {
+        return Mat2::default();
+    }
 
-    // If negative (behind plane)
-    if distance_a <= 0.0 {
-        out[sp] = *face_a;
-        sp += 1;
+    let inverse_det = 1.0/det;
+    Mat2 {
+        m00: matrix.m11*inverse_det, m01: -matrix.m01*inverse_det,
+        m10: -matrix.m10*inverse_det, m11: matrix.m00*inverse_det,
     }
+}
 
-    if distance_b <= 0.0 {
-        out[sp] = *face_b;
-        sp += 1;
+/// Applies an impulse to the second body and the opposite to the first, at points relative to their positions
+fn apply_joint_impulse_pair<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize>(
+    body_a: &mut PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>,
+    body_b: &mut PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>,
+    impulse: Vector2,
+    radius_a: Vector2,
+    radius_b: Vector2,
+) {
+    apply_joint_impulse(body_a, -impulse, radius_a);
+    apply_joint_impulse(body_b, impulse, radius_b);
+}
+
+/// Applies an impulse to a body at a point relative to its position
+fn apply_joint_impulse<const MAX_VERTICES: usize, const CIRCLE_VERTICES: usize>(body: &mut PhysicsBodyData<MAX_VERTICES, CIRCLE_VERTICES>, impulse: Vector2, radius: Vector2) {
+    if !body.enabled {
+        return;
     }
 
-    // If the points are on different sides of the plane
-    if (distance_a*distance_b) < 0.0 {
-        // Push intersection point
-        let alpha = distance_a/(distance_a - distance_b);
-        out[sp] = *face_a;
-        let mut delta = *face_b - *face_a;
-        delta.x *= alpha;
-        delta.y *= alpha;
-        out[sp] += delta;
-        sp += 1;
+    body.velocity += impulse*body.inverse_mass;
+
+    if !body.freeze_orient {
+        body.angular_velocity += body.inverse_inertia*math_cross_vector2(radius, impulse);
     }
+}
 
-    // Assign the new converted values
-    *face_a = out[0];
-    *face_b = out[1];
+// Returns the cross product of a vector and a value
+#[inline(always)]
+fn math_cross(value: Real, vector: Vector2) -> Vector2 {
+    Vector2 { x: -value*vector.y, y: value*vector.x }
+}
 
-    sp
+/// Widens a [`Real`] for the calculations that are always done in double precision
+#[inline(always)]
+#[allow(clippy::useless_conversion, reason = "does nothing with the `f64` feature")]
+fn real_to_f64(value: Real) -> f64 {
+    f64::from(value)
 }
 
-/// Check if values are between bias range
-fn bias_greater_than(value_a: f32, value_b: f32) -> bool {
-    value_a >= (value_b*0.95 + value_a*0.01)
+/// Returns the sine of an angle in radians, which is the same on every platform with the `deterministic` feature
+#[inline(always)]
+fn math_sin(radians: Real) -> Real {
+    #[cfg(feature = "deterministic")] {
+        libm::Libm::<Real>::sin(radians)
+    } #[cfg(not(feature = "deterministic"))] {
+        radians.sin()
+    }
 }
 
-/// Returns the barycenter of a triangle given by 3 points
-fn triangle_barycenter(v1: Vector2, v2: Vector2, v3: Vector2) -> Vector2 {
-    Vector2 {
-        x: (v1.x + v2.x + v3.x)/3.0,
-        y: (v1.y + v2.y + v3.y)/3.0,
+/// Returns the cosine of an angle in radians, which is the same on every platform with the `deterministic` feature
+#[inline(always)]
+fn math_cos(radians: Real) -> Real {
+    #[cfg(feature = "deterministic")] {
+        libm::Libm::<Real>::cos(radians)
+    } #[cfg(not(feature = "deterministic"))] {
+        radians.cos()
     }
 }
 
-// Returns the cross product of a vector and a value
+/// Returns the sine and cosine of an angle in radians, which are the same on every platform with the `deterministic` feature
 #[inline(always)]
-fn math_cross(value: f32, vector: Vector2) -> Vector2 {
-    Vector2 { x: -value*vector.y, y: value*vector.x }
+fn math_sin_cos(radians: Real) -> (Real, Real) {
+    (math_sin(radians), math_cos(radians))
+}
+
+/// Returns the four quadrant arctangent of `y` and `x` in radians, which is the same on every platform with the `deterministic` feature
+#[inline(always)]
+fn math_atan2(y: Real, x: Real) -> Real {
+    #[cfg(feature = "deterministic")] {
+        libm::Libm::<Real>::atan2(y, x)
+    } #[cfg(not(feature = "deterministic"))] {
+        y.atan2(x)
+    }
+}
+
+/// Converts a small count (such as of contact points) to a [`Real`]
+#[inline(always)]
+#[allow(clippy::cast_lossless, reason = "`f32` can't be made from a `u32` with `From`")]
+const fn count_to_real(count: u32) -> Real {
+    count as Real
 }
 
 // Returns the cross product of two vectors
 #[inline(always)]
-fn math_cross_vector2(v1: Vector2, v2: Vector2) -> f32 {
+fn math_cross_vector2(v1: Vector2, v2: Vector2) -> Real {
     v1.x*v2.y - v1.y*v2.x
 }
 
@@ -3014,7 +16070,7 @@ impl std::ops::Neg for Vector2 {
     fn neg(self) -> Self::Output {
         Self {
             x: -self.x,
-            y: -self.x,
+            y: -self.y,
         }
     }
 }
@@ -3025,7 +16081,7 @@ impl std::ops::Add for Vector2 {
     fn add(self, rhs: Self) -> Self::Output {
         Self {
             x: self.x + rhs.x,
-            y: self.x + rhs.y,
+            y: self.y + rhs.y,
         }
     }
 }
@@ -3036,7 +16092,7 @@ impl std::ops::Sub for Vector2 {
     fn sub(self, rhs: Self) -> Self::Output {
         Self {
             x: self.x - rhs.x,
-            y: self.x - rhs.y,
+            y: self.y - rhs.y,
         }
     }
 }
@@ -3047,18 +16103,18 @@ impl std::ops::Mul for Vector2 {
     fn mul(self, rhs: Self) -> Self::Output {
         Self {
             x: self.x * rhs.x,
-            y: self.x * rhs.y,
+            y: self.y * rhs.y,
         }
     }
 }
 #[cfg(not(feature = "raylib"))]
-impl std::ops::Mul<f32> for Vector2 {
+impl std::ops::Mul<Real> for Vector2 {
     type Output = Self;
     #[inline(always)]
-    fn mul(self, rhs: f32) -> Self::Output {
+    fn mul(self, rhs: Real) -> Self::Output {
         Self {
             x: self.x * rhs,
-            y: self.x * rhs,
+            y: self.y * rhs,
         }
     }
 }
@@ -3084,9 +16140,9 @@ impl std::ops::MulAssign for Vector2 {
     }
 }
 #[cfg(not(feature = "raylib"))]
-impl std::ops::MulAssign<f32> for Vector2 {
+impl std::ops::MulAssign<Real> for Vector2 {
     #[inline(always)]
-    fn mul_assign(&mut self, rhs: f32) {
+    fn mul_assign(&mut self, rhs: Real) {
         *self = *self * rhs;
     }
 }
@@ -3095,28 +16151,91 @@ impl Vector2 {
     /// Returns the dot product of the two vectors
     #[inline(always)]
     #[must_use]
-    pub const fn dot(self, other: Vector2) -> f32 {
+    pub const fn dot(self, other: Vector2) -> Real {
         self.x*other.x + self.y*other.y
     }
 
     /// Returns the square of the magnitude of a vector
     #[inline(always)]
     #[must_use]
-    pub const fn length_sqr(self) -> f32 {
+    pub const fn length_sqr(self) -> Real {
         self.dot(self)
     }
 }
 
+#[cfg(all(feature = "glam", not(feature = "f64")))]
+use glam::Vec2 as GlamVec2;
+#[cfg(all(feature = "glam", feature = "f64"))]
+use glam::DVec2 as GlamVec2;
+
+/// # Examples
+///
+/// ```
+/// # use physac::prelude::*;
+/// # #[cfg(not(feature = "f64"))] use glam::Vec2;
+/// # #[cfg(feature = "f64")] use glam::DVec2 as Vec2;
+/// let mut ph = init_physics::<24, 24>().build();
+///
+/// let ball = ph.borrow_mut()
+///     .create_physics_body_circle(Vec2::new(10.0, 20.0), 5.0, 1.0)
+///     .clone();
+///
+/// let position: Vec2 = ball.borrow().position.into();
+/// assert_eq!(position, Vec2::new(10.0, 20.0));
+/// ```
+#[cfg(all(feature = "glam", not(feature = "raylib")))]
+impl From<GlamVec2> for Vector2 {
+    #[inline]
+    fn from(v: GlamVec2) -> Self {
+        Self { x: v.x, y: v.y }
+    }
+}
+#[cfg(all(feature = "glam", not(feature = "raylib")))]
+impl From<Vector2> for GlamVec2 {
+    #[inline]
+    fn from(v: Vector2) -> Self {
+        Self::new(v.x, v.y)
+    }
+}
+
+/// # Examples
+///
+/// ```
+/// # use physac::prelude::*;
+/// let mut ph = init_physics::<24, 24>().build();
+///
+/// let ball = ph.borrow_mut()
+///     .create_physics_body_circle(nalgebra::Vector2::new(10.0, 20.0), 5.0, 1.0)
+///     .clone();
+///
+/// let position: nalgebra::Vector2<Real> = ball.borrow().position.into();
+/// assert_eq!(position, nalgebra::Vector2::new(10.0, 20.0));
+/// ```
+#[cfg(all(feature = "nalgebra", not(feature = "raylib")))]
+impl From<nalgebra::Vector2<Real>> for Vector2 {
+    #[inline]
+    fn from(v: nalgebra::Vector2<Real>) -> Self {
+        Self { x: v.x, y: v.y }
+    }
+}
+#[cfg(all(feature = "nalgebra", not(feature = "raylib")))]
+impl From<Vector2> for nalgebra::Vector2<Real> {
+    #[inline]
+    fn from(v: Vector2) -> Self {
+        Self::new(v.x, v.y)
+    }
+}
+
 // Returns the square of distance between two vectors
 #[inline(always)]
-fn dist_sqr(v1: Vector2, v2: Vector2) -> f32 {
+fn dist_sqr(v1: Vector2, v2: Vector2) -> Real {
     let dir = v1 - v2;
     dir.dot(dir)
 }
 
 /// Returns the normalized values of a vector
 fn math_normalize(vector: &mut Vector2) {
-    let (mut length, ilength): (f32, f32);
+    let (mut length, ilength): (Real, Real);
 
     let aux = *vector;
     length = (aux.x*aux.x + aux.y*aux.y).sqrt();